@@ -0,0 +1,19 @@
+-- Migration: secondary-market transfers of prediction positions
+--
+-- Records every `prediction_transferred` contract event. The `predictions`
+-- table always reflects current ownership (the sender's rows are reduced and
+-- a row is added for the recipient); this table keeps the ownership history.
+
+CREATE TABLE IF NOT EXISTS prediction_transfers (
+    id            BIGSERIAL       PRIMARY KEY,
+    pool_id       BIGINT          NOT NULL REFERENCES pools (pool_id) ON DELETE RESTRICT,
+    from_address  VARCHAR(56)     NOT NULL,
+    to_address    VARCHAR(56)     NOT NULL,
+    outcome       INTEGER         NOT NULL,
+    amount        NUMERIC(32, 7)  NOT NULL,
+    created_at    TIMESTAMPTZ     NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_prediction_transfers_pool_id ON prediction_transfers (pool_id);
+CREATE INDEX IF NOT EXISTS idx_prediction_transfers_from    ON prediction_transfers (from_address);
+CREATE INDEX IF NOT EXISTS idx_prediction_transfers_to      ON prediction_transfers (to_address);
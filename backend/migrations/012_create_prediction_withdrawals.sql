@@ -0,0 +1,19 @@
+-- Migration: early withdrawals from prediction positions
+--
+-- Records every `prediction_withdrawn` contract event. The `predictions`
+-- table is reduced by the withdrawn amount; `fee` is the exit fee kept in the
+-- pool and `refund` the amount paid back to the user.
+
+CREATE TABLE IF NOT EXISTS prediction_withdrawals (
+    id            BIGSERIAL       PRIMARY KEY,
+    pool_id       BIGINT          NOT NULL REFERENCES pools (pool_id) ON DELETE RESTRICT,
+    user_address  VARCHAR(56)     NOT NULL,
+    outcome       INTEGER         NOT NULL,
+    amount        NUMERIC(32, 7)  NOT NULL,
+    fee           NUMERIC(32, 7)  NOT NULL,
+    refund        NUMERIC(32, 7)  NOT NULL,
+    created_at    TIMESTAMPTZ     NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_prediction_withdrawals_pool_id ON prediction_withdrawals (pool_id);
+CREATE INDEX IF NOT EXISTS idx_prediction_withdrawals_user    ON prediction_withdrawals (user_address);
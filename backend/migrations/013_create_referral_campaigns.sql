@@ -0,0 +1,48 @@
+-- Migration: referral campaigns and their attribution
+--
+-- Indexes the contract's referral campaigns. `referral_campaigns` mirrors
+-- `referral_campaign_created` / `referral_campaign_ended` events,
+-- `referral_campaign_attributions` records each referral made under a
+-- campaign (`referral_attributed`), and `referral_campaign_payouts` each
+-- reward paid from a campaign budget (`campaign_referral_paid`). Level 1 is
+-- the referrer, level 2 the referrer's own referrer.
+
+CREATE TABLE IF NOT EXISTS referral_campaigns (
+    campaign_id           BIGINT          PRIMARY KEY,
+    token                 VARCHAR(56)     NOT NULL,
+    cut_bps               INTEGER         NOT NULL,
+    second_level_cut_bps  INTEGER         NOT NULL,
+    start_time            TIMESTAMPTZ     NOT NULL,
+    end_time              TIMESTAMPTZ     NOT NULL,
+    budget                NUMERIC(32, 7)  NOT NULL,
+    spent                 NUMERIC(32, 7)  NOT NULL DEFAULT 0,
+    ended_at              TIMESTAMPTZ,
+    created_at            TIMESTAMPTZ     NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS referral_campaign_attributions (
+    campaign_id   BIGINT       NOT NULL REFERENCES referral_campaigns (campaign_id) ON DELETE CASCADE,
+    pool_id       BIGINT       NOT NULL,
+    user_address  VARCHAR(56)  NOT NULL,
+    referrer      VARCHAR(56)  NOT NULL,
+    created_at    TIMESTAMPTZ  NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (campaign_id, pool_id, user_address)
+);
+
+CREATE TABLE IF NOT EXISTS referral_campaign_payouts (
+    id             BIGSERIAL       PRIMARY KEY,
+    campaign_id    BIGINT          NOT NULL REFERENCES referral_campaigns (campaign_id) ON DELETE CASCADE,
+    pool_id        BIGINT          NOT NULL,
+    referrer       VARCHAR(56)     NOT NULL,
+    referred_user  VARCHAR(56)     NOT NULL,
+    amount         NUMERIC(32, 7)  NOT NULL,
+    level          SMALLINT        NOT NULL,
+    created_at     TIMESTAMPTZ     NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_referral_campaign_attributions_referrer
+    ON referral_campaign_attributions (referrer);
+CREATE INDEX IF NOT EXISTS idx_referral_campaign_payouts_campaign
+    ON referral_campaign_payouts (campaign_id);
+CREATE INDEX IF NOT EXISTS idx_referral_campaign_payouts_referrer
+    ON referral_campaign_payouts (referrer);
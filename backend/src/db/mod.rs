@@ -58,6 +58,8 @@ pub use predictions::{
     MarketPredictionRow,
     PredictionHistoryRow,
     PredictionPlacedEvent,
+    PredictionTransferredEvent,
+    PredictionWithdrawnEvent,
     ProtocolStats,
     UserBettingVolume,
     UserPrediction,
@@ -73,16 +75,29 @@ pub use predictions::{
     get_users_by_winnings,
     insert_prediction_from_event,
     insert_prediction_from_event_with_pool,
+    transfer_prediction_from_event,
+    transfer_prediction_from_event_with_pool,
+    withdraw_prediction_from_event,
+    withdraw_prediction_from_event_with_pool,
 };
 
 pub use referrals::{
     // Types
+    CampaignReferralPaidEvent,
+    ReferralAttributedEvent,
+    ReferralCampaignCreatedEvent,
+    ReferralCampaignRow,
     ReferralEarningRow,
     ReferralPaidEvent,
     // Referral queries
+    end_referral_campaign_from_event,
+    get_referral_campaign,
     get_referral_earnings,
+    insert_referral_attribution_from_event,
+    insert_referral_campaign_from_event,
     insert_referral_from_event,
     insert_referrals_bulk,
+    record_campaign_referral_payout,
 };
 
 use std::time::Duration;
@@ -100,6 +100,27 @@ pub struct PredictionPlacedEvent {
     pub amount: i64,
 }
 
+/// Decoded data from a `prediction_transferred` contract event.
+#[derive(Debug)]
+pub struct PredictionTransferredEvent {
+    pub pool_id: u64,
+    pub from_address: String,
+    pub to_address: String,
+    pub outcome: i32,
+    pub amount: i64,
+}
+
+/// Decoded data from a `prediction_withdrawn` contract event.
+#[derive(Debug)]
+pub struct PredictionWithdrawnEvent {
+    pub pool_id: u64,
+    pub user_address: String,
+    pub outcome: i32,
+    pub amount: i64,
+    pub fee: i64,
+    pub refund: i64,
+}
+
 // ── Private row types ─────────────────────────────────────────────────────────
 
 #[derive(sqlx::FromRow)]
@@ -610,6 +631,172 @@ pub async fn insert_prediction_from_event_with_pool(
     Ok(())
 }
 
+/// Move `amount` of a position from `from_address` to `to_address`.
+///
+/// The sender's rows for the pool and outcome are reduced newest-first (rows
+/// that reach zero are deleted), a row is inserted for the recipient, and the
+/// transfer is recorded in `prediction_transfers`. `pools.total_stake` is
+/// unchanged. Must be called inside an open transaction.
+#[instrument(skip(tx), name = "db.transfer_prediction_from_event",
+    fields(pool_id = event.pool_id, from = %event.from_address, to = %event.to_address))]
+pub async fn transfer_prediction_from_event(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    event: &PredictionTransferredEvent,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        WITH ordered AS (
+            SELECT id, amount,
+                   SUM(amount) OVER (ORDER BY id DESC) AS running
+            FROM predictions
+            WHERE pool_id = $1 AND user_address = $2 AND outcome = $3
+        )
+        UPDATE predictions p
+        SET amount = GREATEST(o.running - $4, 0)
+        FROM ordered o
+        WHERE p.id = o.id
+          AND o.running - o.amount < $4
+        "#,
+    )
+    .bind(event.pool_id as i64)
+    .bind(&event.from_address)
+    .bind(event.outcome)
+    .bind(event.amount)
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query(
+        "DELETE FROM predictions WHERE pool_id = $1 AND user_address = $2 AND amount = 0",
+    )
+    .bind(event.pool_id as i64)
+    .bind(&event.from_address)
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO predictions (pool_id, user_address, outcome, amount)
+        VALUES ($1, $2, $3, $4)
+        "#,
+    )
+    .bind(event.pool_id as i64)
+    .bind(&event.to_address)
+    .bind(event.outcome)
+    .bind(event.amount)
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO prediction_transfers (pool_id, from_address, to_address, outcome, amount)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+    )
+    .bind(event.pool_id as i64)
+    .bind(&event.from_address)
+    .bind(&event.to_address)
+    .bind(event.outcome)
+    .bind(event.amount)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Convenience wrapper: open a transaction, apply the transfer, commit.
+#[instrument(skip(pool), name = "db.transfer_prediction_from_event_with_pool",
+    fields(pool_id = event.pool_id, from = %event.from_address, to = %event.to_address))]
+pub async fn transfer_prediction_from_event_with_pool(
+    pool: &PgPool,
+    event: &PredictionTransferredEvent,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    transfer_prediction_from_event(&mut tx, event).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Reduce a position by `amount` after an early withdrawal.
+///
+/// The user's rows for the pool and outcome are reduced newest-first (rows
+/// that reach zero are deleted), `pools.total_stake` drops by the refund only
+/// since the exit fee stays in the pool, and the withdrawal is recorded in
+/// `prediction_withdrawals`. Must be called inside an open transaction.
+#[instrument(skip(tx), name = "db.withdraw_prediction_from_event",
+    fields(pool_id = event.pool_id, user_address = %event.user_address))]
+pub async fn withdraw_prediction_from_event(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    event: &PredictionWithdrawnEvent,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        WITH ordered AS (
+            SELECT id, amount,
+                   SUM(amount) OVER (ORDER BY id DESC) AS running
+            FROM predictions
+            WHERE pool_id = $1 AND user_address = $2 AND outcome = $3
+        )
+        UPDATE predictions p
+        SET amount = GREATEST(o.running - $4, 0)
+        FROM ordered o
+        WHERE p.id = o.id
+          AND o.running - o.amount < $4
+        "#,
+    )
+    .bind(event.pool_id as i64)
+    .bind(&event.user_address)
+    .bind(event.outcome)
+    .bind(event.amount)
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query(
+        "DELETE FROM predictions WHERE pool_id = $1 AND user_address = $2 AND amount = 0",
+    )
+    .bind(event.pool_id as i64)
+    .bind(&event.user_address)
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query(
+        "UPDATE pools SET total_stake = total_stake - $1 WHERE pool_id = $2",
+    )
+    .bind(event.refund)
+    .bind(event.pool_id as i64)
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO prediction_withdrawals (pool_id, user_address, outcome, amount, fee, refund)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(event.pool_id as i64)
+    .bind(&event.user_address)
+    .bind(event.outcome)
+    .bind(event.amount)
+    .bind(event.fee)
+    .bind(event.refund)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Convenience wrapper: open a transaction, apply the withdrawal, commit.
+#[instrument(skip(pool), name = "db.withdraw_prediction_from_event_with_pool",
+    fields(pool_id = event.pool_id, user_address = %event.user_address))]
+pub async fn withdraw_prediction_from_event_with_pool(
+    pool: &PgPool,
+    event: &PredictionWithdrawnEvent,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    withdraw_prediction_from_event(&mut tx, event).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
 // ── Unit tests ────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
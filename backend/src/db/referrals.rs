@@ -1,6 +1,7 @@
-//! Referral repository — queries for the `referrals` and
-//! `referrer_pool_stats` tables.
+//! Referral repository — queries for the `referrals`,
+//! `referrer_pool_stats` and `referral_campaign*` tables.
 
+use chrono::{DateTime, Utc};
 use sqlx::{Executor, PgPool, Postgres};
 
 // ── Row / DTO types ───────────────────────────────────────────────────────────
@@ -23,6 +24,57 @@ pub struct ReferralPaidEvent {
     pub referral_amount: i64,
 }
 
+/// A referral campaign with its attribution and payout totals.
+#[derive(Debug, serde::Serialize, sqlx::FromRow)]
+pub struct ReferralCampaignRow {
+    pub campaign_id: i64,
+    pub token: String,
+    pub cut_bps: i32,
+    pub second_level_cut_bps: i32,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub budget: i64,
+    pub spent: i64,
+    pub ended_at: Option<DateTime<Utc>>,
+    /// Referrals attributed to the campaign.
+    pub attributed_referrals: i64,
+    /// Distinct referrers with a referral attributed to the campaign.
+    pub referrers: i64,
+}
+
+/// Decoded data from a `referral_campaign_created` contract event.
+#[derive(Debug)]
+pub struct ReferralCampaignCreatedEvent {
+    pub campaign_id: u64,
+    pub token: String,
+    pub cut_bps: i32,
+    pub second_level_cut_bps: i32,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub budget: i64,
+}
+
+/// Decoded data from a `referral_attributed` contract event.
+#[derive(Debug)]
+pub struct ReferralAttributedEvent {
+    pub campaign_id: u64,
+    pub pool_id: u64,
+    pub user_address: String,
+    pub referrer: String,
+}
+
+/// Decoded data from a `campaign_referral_paid` contract event.
+#[derive(Debug)]
+pub struct CampaignReferralPaidEvent {
+    pub campaign_id: u64,
+    pub pool_id: u64,
+    pub referrer: String,
+    pub referred_user: String,
+    pub amount: i64,
+    /// 1 for the referrer, 2 for the referrer's own referrer.
+    pub level: i16,
+}
+
 // ── Read queries ──────────────────────────────────────────────────────────────
 
 /// Referral earnings grouped by pool for a given referrer address.
@@ -48,6 +100,36 @@ pub async fn get_referral_earnings(
     .await
 }
 
+/// A referral campaign with its attribution totals, if it has been indexed.
+pub async fn get_referral_campaign(
+    pool: &PgPool,
+    campaign_id: i64,
+) -> Result<Option<ReferralCampaignRow>, sqlx::Error> {
+    sqlx::query_as::<_, ReferralCampaignRow>(
+        r#"
+        SELECT
+            c.campaign_id,
+            c.token,
+            c.cut_bps,
+            c.second_level_cut_bps,
+            c.start_time,
+            c.end_time,
+            c.budget::BIGINT AS budget,
+            c.spent::BIGINT  AS spent,
+            c.ended_at,
+            COUNT(a.user_address)        AS attributed_referrals,
+            COUNT(DISTINCT a.referrer)   AS referrers
+        FROM referral_campaigns c
+        LEFT JOIN referral_campaign_attributions a ON a.campaign_id = c.campaign_id
+        WHERE c.campaign_id = $1
+        GROUP BY c.campaign_id
+        "#,
+    )
+    .bind(campaign_id)
+    .fetch_optional(pool)
+    .await
+}
+
 // ── Write queries ─────────────────────────────────────────────────────────────
 
 /// Insert a batch of referral events using a single multi-row INSERT.
@@ -95,6 +177,117 @@ where
     Ok(())
 }
 
+/// Insert a newly created campaign, ignoring duplicate deliveries.
+pub async fn insert_referral_campaign_from_event<'e, E>(
+    executor: E,
+    event: &ReferralCampaignCreatedEvent,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    sqlx::query(
+        r#"
+        INSERT INTO referral_campaigns
+            (campaign_id, token, cut_bps, second_level_cut_bps, start_time, end_time, budget)
+        VALUES ($1, $2, $3, $4, to_timestamp($5), to_timestamp($6), $7)
+        ON CONFLICT (campaign_id) DO NOTHING
+        "#,
+    )
+    .bind(event.campaign_id as i64)
+    .bind(&event.token)
+    .bind(event.cut_bps)
+    .bind(event.second_level_cut_bps)
+    .bind(event.start_time as f64)
+    .bind(event.end_time as f64)
+    .bind(event.budget)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Mark a campaign ended: it takes no new referrals from now on.
+pub async fn end_referral_campaign_from_event<'e, E>(
+    executor: E,
+    campaign_id: u64,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    sqlx::query(
+        r#"
+        UPDATE referral_campaigns
+        SET ended_at = COALESCE(ended_at, NOW()),
+            end_time = LEAST(end_time, NOW())
+        WHERE campaign_id = $1
+        "#,
+    )
+    .bind(campaign_id as i64)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Record a referral made under a campaign, ignoring duplicate deliveries.
+pub async fn insert_referral_attribution_from_event<'e, E>(
+    executor: E,
+    event: &ReferralAttributedEvent,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    sqlx::query(
+        r#"
+        INSERT INTO referral_campaign_attributions (campaign_id, pool_id, user_address, referrer)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT DO NOTHING
+        "#,
+    )
+    .bind(event.campaign_id as i64)
+    .bind(event.pool_id as i64)
+    .bind(&event.user_address)
+    .bind(&event.referrer)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Record a reward paid from a campaign budget and add it to the campaign's
+/// `spent`, in one transaction.
+pub async fn record_campaign_referral_payout(
+    pool: &PgPool,
+    event: &CampaignReferralPaidEvent,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO referral_campaign_payouts
+            (campaign_id, pool_id, referrer, referred_user, amount, level)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(event.campaign_id as i64)
+    .bind(event.pool_id as i64)
+    .bind(&event.referrer)
+    .bind(&event.referred_user)
+    .bind(event.amount)
+    .bind(event.level)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query("UPDATE referral_campaigns SET spent = spent + $1 WHERE campaign_id = $2")
+        .bind(event.amount)
+        .bind(event.campaign_id as i64)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
 // ── Private helpers ───────────────────────────────────────────────────────────
 
 /// Build and execute a single multi-row INSERT for one chunk.
@@ -41,7 +41,7 @@ use axum::{
 use serde::Serialize;
 use sqlx::PgPool;
 
-use crate::db::ReferralEarningRow;
+use crate::db::{ReferralCampaignRow, ReferralEarningRow};
 use crate::response::error_codes;
 use crate::response::ApiResponse;
 
@@ -211,6 +211,29 @@ pub async fn get_user_referral_earnings(
     }
 }
 
+/// `GET /api/v1/referral-campaigns/:campaign_id`
+///
+/// Returns a referral campaign's terms, how much of its budget has been paid
+/// out and how many referrals were made under it. Responds with 404 if the
+/// campaign has not been indexed.
+pub async fn get_referral_campaign(
+    Path(campaign_id): Path<i64>,
+    State(pool): State<PgPool>,
+) -> Result<(StatusCode, Json<ApiResponse<ReferralCampaignRow>>), AppError> {
+    match crate::db::get_referral_campaign(&pool, campaign_id).await {
+        Ok(Some(row)) => Ok(ApiResponse::success(row)),
+        Ok(None) => Ok(ApiResponse::error(
+            StatusCode::NOT_FOUND,
+            error_codes::NOT_FOUND,
+            format!("referral campaign {campaign_id} not found"),
+        )),
+        Err(err) => {
+            tracing::error!(error = %err, "referral campaign query failed");
+            Err(AppError::from(err))
+        }
+    }
+}
+
 #[cfg(test)]
 mod estimation_tests {
     use super::estimate_referral_reward;
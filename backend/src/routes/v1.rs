@@ -1398,6 +1398,10 @@ pub fn router(
                 "/referrals/:address/estimate",
                 get(referral_estimate_handler),
             )
+            .route(
+                "/referral-campaigns/:campaign_id",
+                get(referral_campaign_handler),
+            )
             .route("/markets/:id/predictions", get(get_market_predictions))
             .with_state(state.clone()),
         RateLimitTier::Read,
@@ -1558,6 +1562,41 @@ async fn referral_estimate_handler(
     }
 }
 
+/// `GET /api/v1/referral-campaigns/:campaign_id` — a referral campaign's
+/// terms, spend and attribution counts.
+async fn referral_campaign_handler(
+    axum::extract::Path(campaign_id): axum::extract::Path<i64>,
+    State(state): State<AppState>,
+) -> axum::response::Response {
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+
+    match state.db {
+        Some(pool) => {
+            match crate::referrals::get_referral_campaign(
+                axum::extract::Path(campaign_id),
+                State(pool),
+            )
+            .await
+            {
+                Ok((status, body)) => (status, body).into_response(),
+                Err(e) => ApiResponse::<()>::error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    error_codes::INTERNAL_ERROR,
+                    e.to_string(),
+                )
+                .into_response(),
+            }
+        }
+        None => ApiResponse::<()>::error(
+            StatusCode::SERVICE_UNAVAILABLE,
+            error_codes::DATABASE_UNAVAILABLE,
+            "database not configured",
+        )
+        .into_response(),
+    }
+}
+
 /// `GET /api/v1/users/:address/referrals` — per-pool referral earnings for a user.
 async fn user_referral_earnings_handler(
     axum::extract::Path(address): axum::extract::Path<StellarAddress>,
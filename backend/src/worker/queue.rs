@@ -413,9 +413,15 @@ pub fn job_kind_from_topics(topics: Option<&Vec<String>>) -> String {
                         s.as_str(),
                         "pool_created"
                             | "prediction_placed"
+                            | "prediction_transferred"
+                            | "prediction_withdrawn"
                             | "pool_resolved"
                             | "pool_canceled"
                             | "referral_paid"
+                            | "referral_campaign_created"
+                            | "referral_campaign_ended"
+                            | "referral_attributed"
+                            | "campaign_referral_paid"
                     )
                 })
                 .cloned()
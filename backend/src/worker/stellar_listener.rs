@@ -325,6 +325,22 @@ async fn process_event_batch(
                         queue.record_failure(job, e);
                     }
                 }
+            } else if topic_matches("prediction_transferred") {
+                match handle_prediction_transferred_event(db, event, event_bus).await {
+                    Ok(()) => queue.record_success(&job),
+                    Err(e) => {
+                        error!(id = %event.id, ledger = event.ledger, error = %e, "failed to process prediction_transferred event");
+                        queue.record_failure(job, e);
+                    }
+                }
+            } else if topic_matches("prediction_withdrawn") {
+                match handle_prediction_withdrawn_event(db, event, event_bus).await {
+                    Ok(()) => queue.record_success(&job),
+                    Err(e) => {
+                        error!(id = %event.id, ledger = event.ledger, error = %e, "failed to process prediction_withdrawn event");
+                        queue.record_failure(job, e);
+                    }
+                }
             } else if topic_matches("pool_resolved") {
                 match handle_pool_resolved_event(db, event).await {
                     Ok(()) => queue.record_success(&job),
@@ -341,6 +357,18 @@ async fn process_event_batch(
                         queue.record_failure(job, e);
                     }
                 }
+            } else if topic_matches("referral_campaign_created")
+                || topic_matches("referral_campaign_ended")
+                || topic_matches("referral_attributed")
+                || topic_matches("campaign_referral_paid")
+            {
+                match handle_referral_campaign_event(db, event, &kind).await {
+                    Ok(()) => queue.record_success(&job),
+                    Err(e) => {
+                        error!(id = %event.id, ledger = event.ledger, error = %e, kind = %kind, "failed to process referral campaign event");
+                        queue.record_failure(job, e);
+                    }
+                }
             } else if topic_matches("referral_paid") {
                 match parse_referral_paid_event(event) {
                     Ok(ev) => {
@@ -469,6 +497,114 @@ async fn handle_prediction_placed_event(
     Ok(())
 }
 
+async fn handle_prediction_transferred_event(
+    db: &PgPool,
+    event: &StellarEvent,
+    event_bus: &crate::ws::EventBus,
+) -> Result<(), String> {
+    let ev = parse_prediction_transferred_event(event)?;
+
+    crate::db::transfer_prediction_from_event_with_pool(db, &ev)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    event_bus.send(&serde_json::json!({
+        "type": "prediction_transferred",
+        "pool_id": ev.pool_id,
+        "from_address": ev.from_address,
+        "to_address": ev.to_address,
+        "outcome": ev.outcome,
+        "amount": ev.amount,
+    }));
+
+    Ok(())
+}
+
+/// Parse a `prediction_transferred` event (emitted by both `transfer_prediction`
+/// and filled secondary-market listings) into a [`PredictionTransferredEvent`].
+fn parse_prediction_transferred_event(
+    event: &StellarEvent,
+) -> Result<crate::db::PredictionTransferredEvent, String> {
+    let data = event
+        .data
+        .as_ref()
+        .ok_or_else(|| "missing event data".to_string())?;
+
+    let pool_id =
+        extract_u64(data, "pool_id").ok_or_else(|| "missing or invalid pool_id".to_string())?;
+    let from_address =
+        extract_string(data, "from").ok_or_else(|| "missing or invalid from".to_string())?;
+    let to_address =
+        extract_string(data, "to").ok_or_else(|| "missing or invalid to".to_string())?;
+    let outcome =
+        extract_i32(data, "outcome").ok_or_else(|| "missing or invalid outcome".to_string())?;
+    let amount =
+        extract_i64(data, "amount").ok_or_else(|| "missing or invalid amount".to_string())?;
+
+    Ok(crate::db::PredictionTransferredEvent {
+        pool_id,
+        from_address,
+        to_address,
+        outcome,
+        amount,
+    })
+}
+
+async fn handle_prediction_withdrawn_event(
+    db: &PgPool,
+    event: &StellarEvent,
+    event_bus: &crate::ws::EventBus,
+) -> Result<(), String> {
+    let ev = parse_prediction_withdrawn_event(event)?;
+
+    crate::db::withdraw_prediction_from_event_with_pool(db, &ev)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    event_bus.send(&serde_json::json!({
+        "type": "prediction_withdrawn",
+        "pool_id": ev.pool_id,
+        "user_address": ev.user_address,
+        "outcome": ev.outcome,
+        "amount": ev.amount,
+        "fee": ev.fee,
+        "refund": ev.refund,
+    }));
+
+    Ok(())
+}
+
+/// Parse a `prediction_withdrawn` event into a [`PredictionWithdrawnEvent`].
+fn parse_prediction_withdrawn_event(
+    event: &StellarEvent,
+) -> Result<crate::db::PredictionWithdrawnEvent, String> {
+    let data = event
+        .data
+        .as_ref()
+        .ok_or_else(|| "missing event data".to_string())?;
+
+    let pool_id =
+        extract_u64(data, "pool_id").ok_or_else(|| "missing or invalid pool_id".to_string())?;
+    let user_address =
+        extract_string(data, "user").ok_or_else(|| "missing or invalid user".to_string())?;
+    let outcome =
+        extract_i32(data, "outcome").ok_or_else(|| "missing or invalid outcome".to_string())?;
+    let amount =
+        extract_i64(data, "amount").ok_or_else(|| "missing or invalid amount".to_string())?;
+    let fee = extract_i64(data, "fee").ok_or_else(|| "missing or invalid fee".to_string())?;
+    let refund =
+        extract_i64(data, "refund").ok_or_else(|| "missing or invalid refund".to_string())?;
+
+    Ok(crate::db::PredictionWithdrawnEvent {
+        pool_id,
+        user_address,
+        outcome,
+        amount,
+        fee,
+        refund,
+    })
+}
+
 async fn handle_pool_resolved_event(db: &PgPool, event: &StellarEvent) -> Result<(), String> {
     let data = event
         .data
@@ -499,6 +635,132 @@ async fn handle_pool_canceled_event(db: &PgPool, event: &StellarEvent) -> Result
         .map_err(|e| e.to_string())
 }
 
+/// Index a referral campaign event: campaign creation and end, the
+/// attribution of a referral to a campaign, or a reward paid from its budget.
+async fn handle_referral_campaign_event(
+    db: &PgPool,
+    event: &StellarEvent,
+    kind: &str,
+) -> Result<(), String> {
+    match kind {
+        "referral_campaign_created" => {
+            let ev = parse_referral_campaign_created_event(event)?;
+            crate::db::insert_referral_campaign_from_event(db, &ev).await
+        }
+        "referral_campaign_ended" => {
+            let campaign_id = event
+                .data
+                .as_ref()
+                .and_then(|data| extract_u64(data, "campaign_id"))
+                .ok_or_else(|| "missing or invalid campaign_id".to_string())?;
+            crate::db::end_referral_campaign_from_event(db, campaign_id).await
+        }
+        "referral_attributed" => {
+            let ev = parse_referral_attributed_event(event)?;
+            crate::db::insert_referral_attribution_from_event(db, &ev).await
+        }
+        _ => {
+            let ev = parse_campaign_referral_paid_event(event)?;
+            crate::db::record_campaign_referral_payout(db, &ev).await
+        }
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Parse a `referral_campaign_created` event into a [`ReferralCampaignCreatedEvent`].
+fn parse_referral_campaign_created_event(
+    event: &StellarEvent,
+) -> Result<crate::db::ReferralCampaignCreatedEvent, String> {
+    let data = event
+        .data
+        .as_ref()
+        .ok_or_else(|| "missing event data".to_string())?;
+
+    let campaign_id = extract_u64(data, "campaign_id")
+        .ok_or_else(|| "missing or invalid campaign_id".to_string())?;
+    let token =
+        extract_string(data, "token").ok_or_else(|| "missing or invalid token".to_string())?;
+    let cut_bps =
+        extract_i32(data, "cut_bps").ok_or_else(|| "missing or invalid cut_bps".to_string())?;
+    let second_level_cut_bps = extract_i32(data, "second_level_cut_bps")
+        .ok_or_else(|| "missing or invalid second_level_cut_bps".to_string())?;
+    let start_time = extract_u64(data, "start_time")
+        .ok_or_else(|| "missing or invalid start_time".to_string())?;
+    let end_time =
+        extract_u64(data, "end_time").ok_or_else(|| "missing or invalid end_time".to_string())?;
+    let budget =
+        extract_i64(data, "budget").ok_or_else(|| "missing or invalid budget".to_string())?;
+
+    Ok(crate::db::ReferralCampaignCreatedEvent {
+        campaign_id,
+        token,
+        cut_bps,
+        second_level_cut_bps,
+        start_time,
+        end_time,
+        budget,
+    })
+}
+
+/// Parse a `referral_attributed` event into a [`ReferralAttributedEvent`].
+fn parse_referral_attributed_event(
+    event: &StellarEvent,
+) -> Result<crate::db::ReferralAttributedEvent, String> {
+    let data = event
+        .data
+        .as_ref()
+        .ok_or_else(|| "missing event data".to_string())?;
+
+    let campaign_id = extract_u64(data, "campaign_id")
+        .ok_or_else(|| "missing or invalid campaign_id".to_string())?;
+    let pool_id =
+        extract_u64(data, "pool_id").ok_or_else(|| "missing or invalid pool_id".to_string())?;
+    let user_address =
+        extract_string(data, "user").ok_or_else(|| "missing or invalid user".to_string())?;
+    let referrer = extract_string(data, "referrer")
+        .ok_or_else(|| "missing or invalid referrer".to_string())?;
+
+    Ok(crate::db::ReferralAttributedEvent {
+        campaign_id,
+        pool_id,
+        user_address,
+        referrer,
+    })
+}
+
+/// Parse a `campaign_referral_paid` event into a [`CampaignReferralPaidEvent`].
+fn parse_campaign_referral_paid_event(
+    event: &StellarEvent,
+) -> Result<crate::db::CampaignReferralPaidEvent, String> {
+    let data = event
+        .data
+        .as_ref()
+        .ok_or_else(|| "missing event data".to_string())?;
+
+    let campaign_id = extract_u64(data, "campaign_id")
+        .ok_or_else(|| "missing or invalid campaign_id".to_string())?;
+    let pool_id =
+        extract_u64(data, "pool_id").ok_or_else(|| "missing or invalid pool_id".to_string())?;
+    let referrer = extract_string(data, "referrer")
+        .ok_or_else(|| "missing or invalid referrer".to_string())?;
+    let referred_user = extract_string(data, "referred_user")
+        .ok_or_else(|| "missing or invalid referred_user".to_string())?;
+    let amount =
+        extract_i64(data, "amount").ok_or_else(|| "missing or invalid amount".to_string())?;
+    let level = extract_i32(data, "level")
+        .and_then(|level| i16::try_from(level).ok())
+        .ok_or_else(|| "missing or invalid level".to_string())?;
+
+    Ok(crate::db::CampaignReferralPaidEvent {
+        campaign_id,
+        pool_id,
+        referrer,
+        referred_user,
+        amount,
+        level,
+    })
+}
+
 /// Parse a `referral_paid` event into a [`ReferralPaidEvent`] without touching the database.
 ///
 /// This is used in conjunction with `insert_referrals_bulk` so that multiple referral
@@ -827,6 +1089,154 @@ mod tests {
         assert!(extract_u64(&data, "end_time").is_none());
     }
 
+    /// A prediction_transferred event is parsed into sender, recipient and stake.
+    #[test]
+    fn parse_prediction_transferred_event_fields() {
+        let event: StellarEvent = serde_json::from_value(serde_json::json!({
+            "type": "contract",
+            "ledger": 50,
+            "id": "evt-transfer",
+            "topics": ["prediction_transferred"],
+            "data": {
+                "pool_id": 3,
+                "from": { "address": "GSELLER" },
+                "to": "GBUYER",
+                "outcome": 1,
+                "amount": "250"
+            }
+        }))
+        .unwrap();
+
+        let ev = parse_prediction_transferred_event(&event).unwrap();
+        assert_eq!(ev.pool_id, 3);
+        assert_eq!(ev.from_address, "GSELLER");
+        assert_eq!(ev.to_address, "GBUYER");
+        assert_eq!(ev.outcome, 1);
+        assert_eq!(ev.amount, 250);
+    }
+
+    #[test]
+    fn parse_prediction_transferred_event_missing_recipient_fails() {
+        let event: StellarEvent = serde_json::from_value(serde_json::json!({
+            "type": "contract",
+            "ledger": 50,
+            "id": "evt-transfer",
+            "data": { "pool_id": 3, "from": "GSELLER", "outcome": 1, "amount": 250 }
+        }))
+        .unwrap();
+
+        assert!(parse_prediction_transferred_event(&event).is_err());
+    }
+
+    /// A prediction_withdrawn event is parsed into the stake, fee and refund.
+    #[test]
+    fn parse_prediction_withdrawn_event_fields() {
+        let event: StellarEvent = serde_json::from_value(serde_json::json!({
+            "type": "contract",
+            "ledger": 60,
+            "id": "evt-withdraw",
+            "topics": ["prediction_withdrawn"],
+            "data": {
+                "pool_id": 4,
+                "user": { "address": "GUSER" },
+                "outcome": 0,
+                "amount": "500",
+                "fee": 10,
+                "refund": "490"
+            }
+        }))
+        .unwrap();
+
+        let ev = parse_prediction_withdrawn_event(&event).unwrap();
+        assert_eq!(ev.pool_id, 4);
+        assert_eq!(ev.user_address, "GUSER");
+        assert_eq!(ev.outcome, 0);
+        assert_eq!(ev.amount, 500);
+        assert_eq!(ev.fee, 10);
+        assert_eq!(ev.refund, 490);
+    }
+
+    #[test]
+    fn parse_prediction_withdrawn_event_missing_refund_fails() {
+        let event: StellarEvent = serde_json::from_value(serde_json::json!({
+            "type": "contract",
+            "ledger": 60,
+            "id": "evt-withdraw",
+            "data": { "pool_id": 4, "user": "GUSER", "outcome": 0, "amount": 500, "fee": 10 }
+        }))
+        .unwrap();
+
+        assert!(parse_prediction_withdrawn_event(&event).is_err());
+    }
+
+    /// A referral_campaign_created event is parsed into the campaign terms.
+    #[test]
+    fn parse_referral_campaign_created_event_fields() {
+        let event: StellarEvent = serde_json::from_value(serde_json::json!({
+            "type": "contract",
+            "ledger": 70,
+            "id": "evt-campaign",
+            "topics": ["referral_campaign_created"],
+            "data": {
+                "admin": "GADMIN",
+                "campaign_id": 3,
+                "token": { "address": "CTOKEN" },
+                "cut_bps": 5000,
+                "second_level_cut_bps": 2000,
+                "start_time": 1000,
+                "end_time": 10000,
+                "budget": "1000000"
+            }
+        }))
+        .unwrap();
+
+        let ev = parse_referral_campaign_created_event(&event).unwrap();
+        assert_eq!(ev.campaign_id, 3);
+        assert_eq!(ev.token, "CTOKEN");
+        assert_eq!((ev.cut_bps, ev.second_level_cut_bps), (5000, 2000));
+        assert_eq!((ev.start_time, ev.end_time), (1000, 10000));
+        assert_eq!(ev.budget, 1_000_000);
+    }
+
+    /// A campaign_referral_paid event keeps the reward level.
+    #[test]
+    fn parse_campaign_referral_paid_event_fields() {
+        let event: StellarEvent = serde_json::from_value(serde_json::json!({
+            "type": "contract",
+            "ledger": 71,
+            "id": "evt-campaign-paid",
+            "topics": ["campaign_referral_paid"],
+            "data": {
+                "campaign_id": 3,
+                "pool_id": 9,
+                "referrer": "GUPLINE",
+                "referred_user": "GUSER",
+                "amount": "20",
+                "level": 2
+            }
+        }))
+        .unwrap();
+
+        let ev = parse_campaign_referral_paid_event(&event).unwrap();
+        assert_eq!((ev.campaign_id, ev.pool_id), (3, 9));
+        assert_eq!(ev.referrer, "GUPLINE");
+        assert_eq!(ev.referred_user, "GUSER");
+        assert_eq!((ev.amount, ev.level), (20, 2));
+    }
+
+    #[test]
+    fn parse_referral_attributed_event_missing_referrer_fails() {
+        let event: StellarEvent = serde_json::from_value(serde_json::json!({
+            "type": "contract",
+            "ledger": 72,
+            "id": "evt-attributed",
+            "data": { "campaign_id": 3, "pool_id": 9, "user": "GUSER" }
+        }))
+        .unwrap();
+
+        assert!(parse_referral_attributed_event(&event).is_err());
+    }
+
     #[test]
     fn reconnect_delay_is_exponential_and_capped() {
         assert_eq!(reconnect_delay_secs(1), 1);
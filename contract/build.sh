@@ -55,6 +55,10 @@ wasm-opt -Oz --enable-bulk-memory \
     target/wasm32-unknown-unknown/release/access_control.wasm \
     -o target/wasm32-unknown-unknown/release/access_control_optimized.wasm
 
+wasm-opt -Oz --enable-bulk-memory \
+    target/wasm32-unknown-unknown/release/outcome_token.wasm \
+    -o target/wasm32-unknown-unknown/release/outcome_token_optimized.wasm
+
 echo ""
 echo "Build complete!"
 echo ""
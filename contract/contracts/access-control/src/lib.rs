@@ -83,6 +83,18 @@
 // 4. Any contract can check if a user has a role by calling `has_role(user, role)`.
 // 5. The `has_any_role` function allows checking if a user has any of a set of roles.
 //
+// EXPIRY, SCOPES AND ENUMERATION
+// ──────────────────────────────
+// - `assign_role_until` grants a role that lapses at a ledger timestamp;
+//   `has_role` treats an expired grant as absent until it is re-assigned or
+//   revoked.
+// - `assign_scoped_role` grants a role for a single pool or pool category
+//   only (e.g. Operator for `Sports`). Scoped grants never satisfy
+//   `has_role`; predifi-contract checks them with `has_role_for_pool` when an
+//   operator resolves a pool.
+// - Every grant, global or scoped, is listed under its role and can be paged
+//   through with `get_role_members` / `get_role_member_count`.
+//
 // SECURITY CONSIDERATIONS
 // ───────────────────────
 // - Only the admin can assign or revoke roles
@@ -93,7 +105,7 @@
 // ═══════════════════════════════════════════════════════════════════════════
 
 use predifi_errors::PrediFiError;
-use soroban_sdk::{contract, contractevent, contractimpl, contracttype, Address, Env};
+use soroban_sdk::{contract, contractevent, contractimpl, contracttype, Address, Env, Symbol, Vec};
 
 /// Role-based access control enumeration.
 ///
@@ -115,10 +127,8 @@ pub enum Role {
     Operator = 1,
     /// Moderator can handle disputes and moderate content.
     ///
-    /// **Reserved for future use (#595).** This role is defined for planned
-    /// dispute-resolution functionality but is not currently enforced by any
-    /// function in `predifi-contract`. Assigning this role has no effect on
-    /// protocol behaviour until dispute handling is implemented.
+    /// In `predifi-contract` it opens disputes (`flag_disputed_pool`) and
+    /// rejects spam pools (`reject_pool`).
     Moderator = 2,
     /// Oracle can resolve pools based on external data and price feeds.
     Oracle = 3,
@@ -140,6 +150,7 @@ pub struct RoleAssignedEvent {
     pub admin: Address,
     pub user: Address,
     pub role: Role,
+    pub expires_at: Option<u64>,
 }
 
 #[contractevent(topics = ["role_revoked"])]
@@ -185,6 +196,27 @@ pub struct AllRolesRevokedEvent {
     pub user: Address,
 }
 
+#[contractevent(topics = ["scoped_role_assigned"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScopedRoleAssignedEvent {
+    pub admin: Address,
+    pub user: Address,
+    pub role: Role,
+    pub scope: RoleScope,
+    pub expires_at: Option<u64>,
+}
+
+#[contractevent(topics = ["scoped_role_revoked"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScopedRoleRevokedEvent {
+    pub admin: Address,
+    pub user: Address,
+    pub role: Role,
+    pub scope: RoleScope,
+}
+
 /// Status of a prediction pool in the access control system.
 ///
 /// This enum tracks the lifecycle state of a pool for permission management.
@@ -221,6 +253,38 @@ pub enum PoolCategory {
     Other,
 }
 
+/// The part of the protocol a scoped grant applies to.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RoleScope {
+    /// The whole protocol: a plain `assign_role` grant.
+    Global,
+    /// A single pool, by id.
+    Pool(u64),
+    /// Every pool in a category, matching the pool's `category` symbol
+    /// (e.g. `Sports`).
+    Category(Symbol),
+}
+
+/// A single grant of `role` to `user` within `scope`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleGrant {
+    pub user: Address,
+    pub role: Role,
+    pub scope: RoleScope,
+}
+
+/// A role member as returned by `get_role_members`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleMember {
+    pub user: Address,
+    pub scope: RoleScope,
+    /// Ledger timestamp the grant lapses at; `None` if it never does.
+    pub expires_at: Option<u64>,
+}
+
 /// Storage keys for access control data.
 ///
 /// This enum defines all storage keys used by the access control contract.
@@ -238,6 +302,18 @@ pub enum DataKey {
     PoolCount,
     /// Count of addresses currently holding the Operator role: `OperatorCount` -> u32
     OperatorCount,
+    /// Scoped role assignment: `ScopedRole(user_address, role, scope)` -> ()
+    ScopedRole(Address, Role, RoleScope),
+    /// Expiry of a grant that lapses: `RoleExpiry(grant)` -> u64
+    RoleExpiry(RoleGrant),
+    /// Number of grants listed for a role: `RoleMemberCount(role)` -> u32
+    RoleMemberCount(Role),
+    /// Grant at a position in a role's list: `RoleMember(role, index)` -> RoleGrant
+    RoleMember(Role, u32),
+    /// Position of a grant in its role's list: `RoleMemberIndex(grant)` -> u32
+    RoleMemberIndex(RoleGrant),
+    /// Scoped grants held by a user: `ScopedGrants(user_address)` -> Vec<RoleGrant>
+    ScopedGrants(Address),
 }
 
 #[contract]
@@ -265,9 +341,7 @@ impl AccessControl {
             soroban_sdk::panic_with_error!(&env, PrediFiError::AlreadyInitializedOrConfigNotSet);
         }
         env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage()
-            .persistent()
-            .set(&DataKey::Role(admin.clone(), Role::Admin), &());
+        Self::store_grant(env, &Self::global_grant(&admin, Role::Admin), None);
 
         AdminInitEvent { admin }.publish(env);
     }
@@ -292,6 +366,9 @@ impl AccessControl {
 
     /// Assigns a role to a user.
     ///
+    /// Re-assigning a role the user already holds clears any expiry set by
+    /// `assign_role_until`.
+    ///
     /// # Errors
     /// Returns `Unauthorized` if the caller is not the current admin.
     pub fn assign_role(
@@ -299,6 +376,44 @@ impl AccessControl {
         admin_caller: Address,
         user: Address,
         role: Role,
+    ) -> Result<(), PrediFiError> {
+        Self::assign_global_role(env, admin_caller, user, role, None)
+    }
+
+    /// Assigns a role to a user until the ledger timestamp `expires_at`.
+    ///
+    /// From `expires_at` on, `has_role` returns `false` for the grant. It stays
+    /// listed by `get_role_members` until it is revoked or re-assigned.
+    ///
+    /// # Errors
+    /// Returns `Unauthorized` if the caller is not the current admin.
+    /// Returns `InvalidTimestamp` if `expires_at` is not in the future.
+    pub fn assign_role_until(
+        env: &Env,
+        admin_caller: Address,
+        user: Address,
+        role: Role,
+        expires_at: u64,
+    ) -> Result<(), PrediFiError> {
+        Self::assign_global_role(env, admin_caller, user, role, Some(expires_at))
+    }
+
+    /// Assigns a role to a user for one pool or pool category only.
+    ///
+    /// A scoped grant never satisfies `has_role`; it is checked by
+    /// `has_role_for_pool`. `expires_at` works as in `assign_role_until`.
+    ///
+    /// # Errors
+    /// Returns `Unauthorized` if the caller is not the current admin.
+    /// Returns `InvalidData` if `scope` is `Global`; use `assign_role`.
+    /// Returns `InvalidTimestamp` if `expires_at` is not in the future.
+    pub fn assign_scoped_role(
+        env: &Env,
+        admin_caller: Address,
+        user: Address,
+        role: Role,
+        scope: RoleScope,
+        expires_at: Option<u64>,
     ) -> Result<(), PrediFiError> {
         admin_caller.require_auth();
 
@@ -306,28 +421,67 @@ impl AccessControl {
         if admin_caller != current_admin {
             return Err(PrediFiError::Unauthorized);
         }
+        if scope == RoleScope::Global {
+            return Err(PrediFiError::InvalidData);
+        }
+        Self::validate_expiry(env, expires_at)?;
 
-        // Only increment operator count if the role is newly assigned
-        let role_key = DataKey::Role(user.clone(), role.clone());
-        let is_new = !env.storage().persistent().has(&role_key);
+        let grant = RoleGrant {
+            user: user.clone(),
+            role: role.clone(),
+            scope: scope.clone(),
+        };
+        Self::store_grant(env, &grant, expires_at);
 
-        env.storage().persistent().set(&role_key, &());
+        ScopedRoleAssignedEvent {
+            admin: admin_caller,
+            user,
+            role,
+            scope,
+            expires_at,
+        }
+        .publish(env);
+        Ok(())
+    }
 
-        if matches!(role, Role::Operator) && is_new {
-            let count: u32 = env
-                .storage()
-                .instance()
-                .get(&DataKey::OperatorCount)
-                .unwrap_or(0);
-            env.storage()
-                .instance()
-                .set(&DataKey::OperatorCount, &(count + 1));
+    /// Revokes a scoped grant made by `assign_scoped_role`.
+    ///
+    /// # Errors
+    /// Returns `Unauthorized` if the caller is not the current admin.
+    /// Returns `InvalidData` if `scope` is `Global`; use `revoke_role`.
+    /// Returns `InsufficientPermissions` if the user has no such scoped grant.
+    pub fn revoke_scoped_role(
+        env: &Env,
+        admin_caller: Address,
+        user: Address,
+        role: Role,
+        scope: RoleScope,
+    ) -> Result<(), PrediFiError> {
+        admin_caller.require_auth();
+
+        let current_admin = Self::get_admin(env);
+        if admin_caller != current_admin {
+            return Err(PrediFiError::Unauthorized);
         }
 
-        RoleAssignedEvent {
+        if scope == RoleScope::Global {
+            return Err(PrediFiError::InvalidData);
+        }
+
+        let grant = RoleGrant {
+            user: user.clone(),
+            role: role.clone(),
+            scope: scope.clone(),
+        };
+        if !Self::clear_grant(env, &grant) {
+            return Err(PrediFiError::InsufficientPermissions);
+        }
+
+        ScopedRoleRevokedEvent {
             admin: admin_caller,
             user,
             role,
+            scope,
         }
         .publish(env);
         Ok(())
@@ -351,18 +505,10 @@ impl AccessControl {
             return Err(PrediFiError::Unauthorized);
         }
 
-        if !env
-            .storage()
-            .persistent()
-            .has(&DataKey::Role(user.clone(), role.clone()))
-        {
+        if !Self::clear_grant(env, &Self::global_grant(&user, role.clone())) {
             return Err(PrediFiError::InsufficientPermissions);
         }
 
-        env.storage()
-            .persistent()
-            .remove(&DataKey::Role(user.clone(), role.clone()));
-
         if matches!(role, Role::Operator) {
             let count: u32 = env
                 .storage()
@@ -385,10 +531,40 @@ impl AccessControl {
         Ok(())
     }
 
-    /// Checks if a user has a specific role.
+    /// Checks if a user holds a specific role globally and the grant has not
+    /// expired.
     #[must_use]
     pub fn has_role(env: &Env, user: Address, role: Role) -> bool {
-        env.storage().persistent().has(&DataKey::Role(user, role))
+        Self::is_grant_live(env, &Self::global_grant(&user, role))
+    }
+
+    /// Checks if a user may act with `role` on the pool `pool_id` in
+    /// `category`: through a global grant, a grant scoped to the pool, or a
+    /// grant scoped to its category. Expired grants are ignored.
+    #[must_use]
+    pub fn has_role_for_pool(
+        env: &Env,
+        user: Address,
+        role: Role,
+        pool_id: u64,
+        category: Symbol,
+    ) -> bool {
+        [
+            RoleScope::Global,
+            RoleScope::Pool(pool_id),
+            RoleScope::Category(category),
+        ]
+        .into_iter()
+        .any(|scope| {
+            Self::is_grant_live(
+                env,
+                &RoleGrant {
+                    user: user.clone(),
+                    role: role.clone(),
+                    scope,
+                },
+            )
+        })
     }
 
     /// Transfers a role from one user to another.
@@ -410,22 +586,22 @@ impl AccessControl {
             return Err(PrediFiError::Unauthorized);
         }
 
-        if !env
+        let from_grant = Self::global_grant(&from, role.clone());
+        let expires_at: Option<u64> = env
             .storage()
             .persistent()
-            .has(&DataKey::Role(from.clone(), role.clone()))
-        {
+            .get(&DataKey::RoleExpiry(from_grant.clone()));
+        if !Self::clear_grant(env, &from_grant) {
             return Err(PrediFiError::InsufficientPermissions);
         }
 
-        env.storage()
-            .persistent()
-            .remove(&DataKey::Role(from.clone(), role.clone()));
-
-        // Only increment if `to` doesn't already hold the role
-        let to_key = DataKey::Role(to.clone(), role.clone());
-        let to_is_new = !env.storage().persistent().has(&to_key);
-        env.storage().persistent().set(&to_key, &());
+        // Only increment if `to` doesn't already hold the role; an existing
+        // grant keeps its own expiry.
+        let to_grant = Self::global_grant(&to, role.clone());
+        let to_is_new = !env.storage().persistent().has(&Self::grant_key(&to_grant));
+        if to_is_new {
+            Self::store_grant(env, &to_grant, expires_at);
+        }
 
         // Operator count: removed from `from` (always), added to `to` only if new
         if matches!(role, Role::Operator) {
@@ -569,9 +745,7 @@ impl AccessControl {
             Role::Oracle,
             Role::User,
         ] {
-            let key = DataKey::Role(user.clone(), role.clone());
-            if env.storage().persistent().has(&key) {
-                env.storage().persistent().remove(&key);
+            if Self::clear_grant(env, &Self::global_grant(&user, role.clone())) {
                 // Decrement operator count if the Operator role is being removed
                 if matches!(role, Role::Operator) {
                     let count: u32 = env
@@ -588,6 +762,15 @@ impl AccessControl {
             }
         }
 
+        let scoped: Vec<RoleGrant> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ScopedGrants(user.clone()))
+            .unwrap_or_else(|| Vec::new(env));
+        for grant in scoped.iter() {
+            Self::clear_grant(env, &grant);
+        }
+
         AllRolesRevokedEvent {
             admin: admin_caller,
             user,
@@ -600,16 +783,9 @@ impl AccessControl {
     /// Checks if a user has any of the specified roles.
     #[must_use]
     pub fn has_any_role(env: &Env, user: &Address, roles: &soroban_sdk::Vec<Role>) -> bool {
-        for role in roles.iter() {
-            if env
-                .storage()
-                .persistent()
-                .has(&DataKey::Role(user.clone(), role))
-            {
-                return true;
-            }
-        }
-        false
+        roles
+            .iter()
+            .any(|role| Self::is_grant_live(env, &Self::global_grant(user, role)))
     }
 
     /// Returns the number of addresses currently holding the Operator role.
@@ -625,16 +801,212 @@ impl AccessControl {
             .unwrap_or(0)
     }
 
+    /// Returns the number of grants listed for `role`, scoped and expired
+    /// ones included.
+    #[must_use]
+    pub fn get_role_member_count(env: &Env, role: Role) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RoleMemberCount(role))
+            .unwrap_or(0)
+    }
+
+    /// Returns up to `limit` grants of `role`, starting at `offset`.
+    ///
+    /// Revoking a grant moves the last one listed into its place, so the
+    /// order is not stable across revocations.
+    ///
+    /// # Errors
+    /// Returns `InvalidPagination` if `offset + limit` overflows `u32`.
+    pub fn get_role_members(
+        env: &Env,
+        role: Role,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<RoleMember>, PrediFiError> {
+        let end_check = offset
+            .checked_add(limit)
+            .ok_or(PrediFiError::InvalidPagination)?;
+
+        let count = Self::get_role_member_count(env, role.clone());
+        let mut members = Vec::new(env);
+        if offset >= count || limit == 0 {
+            return Ok(members);
+        }
+
+        for i in offset..core::cmp::min(end_check, count) {
+            let grant: RoleGrant = env
+                .storage()
+                .persistent()
+                .get(&DataKey::RoleMember(role.clone(), i))
+                .expect("role member not found");
+            let expires_at = env
+                .storage()
+                .persistent()
+                .get(&DataKey::RoleExpiry(grant.clone()));
+            members.push_back(RoleMember {
+                user: grant.user,
+                scope: grant.scope,
+                expires_at,
+            });
+        }
+        Ok(members)
+    }
+
     fn apply_admin_transfer(env: &Env, current_admin: Address, new_admin: &Address) {
         env.storage().instance().set(&DataKey::Admin, new_admin);
         env.storage().instance().remove(&DataKey::ProposedAdmin);
 
-        env.storage()
-            .persistent()
-            .remove(&DataKey::Role(current_admin, Role::Admin));
-        env.storage()
+        Self::clear_grant(env, &Self::global_grant(&current_admin, Role::Admin));
+        Self::store_grant(env, &Self::global_grant(new_admin, Role::Admin), None);
+    }
+
+    fn assign_global_role(
+        env: &Env,
+        admin_caller: Address,
+        user: Address,
+        role: Role,
+        expires_at: Option<u64>,
+    ) -> Result<(), PrediFiError> {
+        admin_caller.require_auth();
+
+        let current_admin = Self::get_admin(env);
+        if admin_caller != current_admin {
+            return Err(PrediFiError::Unauthorized);
+        }
+        Self::validate_expiry(env, expires_at)?;
+
+        // Only increment operator count if the role is newly assigned
+        let is_new = Self::store_grant(env, &Self::global_grant(&user, role.clone()), expires_at);
+
+        if matches!(role, Role::Operator) && is_new {
+            let count: u32 = env
+                .storage()
+                .instance()
+                .get(&DataKey::OperatorCount)
+                .unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&DataKey::OperatorCount, &(count + 1));
+        }
+
+        RoleAssignedEvent {
+            admin: admin_caller,
+            user,
+            role,
+            expires_at,
+        }
+        .publish(env);
+        Ok(())
+    }
+
+    fn global_grant(user: &Address, role: Role) -> RoleGrant {
+        RoleGrant {
+            user: user.clone(),
+            role,
+            scope: RoleScope::Global,
+        }
+    }
+
+    fn grant_key(grant: &RoleGrant) -> DataKey {
+        match &grant.scope {
+            RoleScope::Global => DataKey::Role(grant.user.clone(), grant.role.clone()),
+            scope => DataKey::ScopedRole(grant.user.clone(), grant.role.clone(), scope.clone()),
+        }
+    }
+
+    fn validate_expiry(env: &Env, expires_at: Option<u64>) -> Result<(), PrediFiError> {
+        match expires_at {
+            Some(expires_at) if expires_at <= env.ledger().timestamp() => {
+                Err(PrediFiError::InvalidTimestamp)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn is_grant_live(env: &Env, grant: &RoleGrant) -> bool {
+        if !env.storage().persistent().has(&Self::grant_key(grant)) {
+            return false;
+        }
+        let expires_at: Option<u64> = env
+            .storage()
             .persistent()
-            .set(&DataKey::Role(new_admin.clone(), Role::Admin), &());
+            .get(&DataKey::RoleExpiry(grant.clone()));
+        expires_at.is_none_or(|expires_at| env.ledger().timestamp() < expires_at)
+    }
+
+    /// Stores `grant` with `expires_at`, replacing any previous expiry, and
+    /// lists it under its role. Returns whether the grant is new.
+    fn store_grant(env: &Env, grant: &RoleGrant, expires_at: Option<u64>) -> bool {
+        let storage = env.storage().persistent();
+        let key = Self::grant_key(grant);
+        let is_new = !storage.has(&key);
+        storage.set(&key, &());
+
+        let expiry_key = DataKey::RoleExpiry(grant.clone());
+        match expires_at {
+            Some(expires_at) => storage.set(&expiry_key, &expires_at),
+            None => storage.remove(&expiry_key),
+        }
+
+        if is_new {
+            let count_key = DataKey::RoleMemberCount(grant.role.clone());
+            let count: u32 = storage.get(&count_key).unwrap_or(0);
+            storage.set(&DataKey::RoleMember(grant.role.clone(), count), grant);
+            storage.set(&DataKey::RoleMemberIndex(grant.clone()), &count);
+            storage.set(&count_key, &(count + 1));
+
+            if grant.scope != RoleScope::Global {
+                let scoped_key = DataKey::ScopedGrants(grant.user.clone());
+                let mut scoped: Vec<RoleGrant> =
+                    storage.get(&scoped_key).unwrap_or_else(|| Vec::new(env));
+                scoped.push_back(grant.clone());
+                storage.set(&scoped_key, &scoped);
+            }
+        }
+        is_new
+    }
+
+    /// Removes `grant` and its listing. Returns whether it existed.
+    fn clear_grant(env: &Env, grant: &RoleGrant) -> bool {
+        let storage = env.storage().persistent();
+        let key = Self::grant_key(grant);
+        if !storage.has(&key) {
+            return false;
+        }
+        storage.remove(&key);
+        storage.remove(&DataKey::RoleExpiry(grant.clone()));
+
+        // Grants made before membership was tracked have no listing.
+        let index_key = DataKey::RoleMemberIndex(grant.clone());
+        if let Some(index) = storage.get::<_, u32>(&index_key) {
+            let count_key = DataKey::RoleMemberCount(grant.role.clone());
+            let last = storage.get::<_, u32>(&count_key).unwrap_or(1) - 1;
+            let last_key = DataKey::RoleMember(grant.role.clone(), last);
+            if index != last {
+                let moved: RoleGrant = storage.get(&last_key).expect("role member not found");
+                storage.set(&DataKey::RoleMember(grant.role.clone(), index), &moved);
+                storage.set(&DataKey::RoleMemberIndex(moved), &index);
+            }
+            storage.remove(&last_key);
+            storage.remove(&index_key);
+            storage.set(&count_key, &last);
+        }
+
+        if grant.scope != RoleScope::Global {
+            let scoped_key = DataKey::ScopedGrants(grant.user.clone());
+            let mut scoped: Vec<RoleGrant> =
+                storage.get(&scoped_key).unwrap_or_else(|| Vec::new(env));
+            if let Some(i) = scoped.first_index_of(grant) {
+                scoped.remove(i);
+            }
+            if scoped.is_empty() {
+                storage.remove(&scoped_key);
+            } else {
+                storage.set(&scoped_key, &scoped);
+            }
+        }
+        true
     }
 }
 
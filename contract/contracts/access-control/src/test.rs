@@ -1,8 +1,8 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::testutils::Address as _;
-use soroban_sdk::{Address, Env};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{symbol_short, vec, Address, Env};
 
 #[test]
 fn test_initialization() {
@@ -973,3 +973,147 @@ fn test_removed_admin_cannot_assign_roles() {
         "An address whose Admin role was revoked must not be able to assign roles"
     );
 }
+
+/// Every grant is listed under its role; revoking one moves the last grant
+/// into its place.
+#[test]
+fn test_role_members_paginate_and_track_revocations() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let op1 = Address::generate(&env);
+    let op2 = Address::generate(&env);
+    let op3 = Address::generate(&env);
+    client.init(&admin);
+    assert_eq!(client.get_role_member_count(&Role::Admin), 1);
+
+    client.assign_role(&admin, &op1, &Role::Operator);
+    client.assign_role(&admin, &op2, &Role::Operator);
+    client.assign_role(&admin, &op3, &Role::Operator);
+    client.assign_role(&admin, &op1, &Role::Operator);
+    assert_eq!(client.get_role_member_count(&Role::Operator), 3);
+
+    let page = client.get_role_members(&Role::Operator, &1, &5);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().user, op2);
+    assert_eq!(page.get(1).unwrap().user, op3);
+
+    client.revoke_role(&admin, &op1, &Role::Operator);
+    let members = client.get_role_members(&Role::Operator, &0, &5);
+    assert_eq!(members.len(), 2);
+    assert_eq!(members.get(0).unwrap().user, op3);
+    assert_eq!(members.get(1).unwrap().user, op2);
+
+    let new_admin = Address::generate(&env);
+    client.transfer_admin(&admin, &new_admin);
+    let admins = client.get_role_members(&Role::Admin, &0, &5);
+    assert_eq!(admins.len(), 1);
+    assert_eq!(admins.get(0).unwrap().user, new_admin);
+
+    let overflow = client.try_get_role_members(&Role::Operator, &1, &u32::MAX);
+    assert_eq!(overflow, Err(Ok(PrediFiError::InvalidPagination)));
+}
+
+/// A grant made with `assign_role_until` lapses at its expiry, and
+/// re-assigning the role makes it permanent again.
+#[test]
+fn test_role_expiry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    client.init(&admin);
+
+    let past = client.try_assign_role_until(&admin, &user, &Role::Operator, &1_000);
+    assert_eq!(past, Err(Ok(PrediFiError::InvalidTimestamp)));
+
+    client.assign_role_until(&admin, &user, &Role::Operator, &2_000);
+    assert!(client.has_role(&user, &Role::Operator));
+    let member = client
+        .get_role_members(&Role::Operator, &0, &1)
+        .get(0)
+        .unwrap();
+    assert_eq!(member.expires_at, Some(2_000));
+
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert!(!client.has_role(&user, &Role::Operator));
+    assert!(!client.has_any_role(&user, &vec![&env, Role::Operator]));
+    assert_eq!(client.get_role_member_count(&Role::Operator), 1);
+
+    client.assign_role(&admin, &user, &Role::Operator);
+    assert!(client.has_role(&user, &Role::Operator));
+    let member = client
+        .get_role_members(&Role::Operator, &0, &1)
+        .get(0)
+        .unwrap();
+    assert_eq!(member.expires_at, None);
+}
+
+/// A scoped grant covers only its pool or category, never satisfies
+/// `has_role`, and is cleared by `revoke_all_roles`.
+#[test]
+fn test_scoped_roles() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let desk = Address::generate(&env);
+    client.init(&admin);
+
+    let sports = RoleScope::Category(symbol_short!("Sports"));
+    client.assign_scoped_role(&admin, &desk, &Role::Operator, &sports, &None);
+    client.assign_scoped_role(&admin, &desk, &Role::Operator, &RoleScope::Pool(7), &None);
+
+    assert!(!client.has_role(&desk, &Role::Operator));
+    assert!(client.has_role_for_pool(&desk, &Role::Operator, &1, &symbol_short!("Sports")));
+    assert!(client.has_role_for_pool(&desk, &Role::Operator, &7, &symbol_short!("Crypto")));
+    assert!(!client.has_role_for_pool(&desk, &Role::Operator, &1, &symbol_short!("Crypto")));
+    assert!(!client.has_role_for_pool(&desk, &Role::Oracle, &1, &symbol_short!("Sports")));
+    assert_eq!(client.get_operator_count(), 0);
+
+    let members = client.get_role_members(&Role::Operator, &0, &5);
+    assert_eq!(members.get(0).unwrap().scope, sports);
+
+    client.revoke_scoped_role(&admin, &desk, &Role::Operator, &RoleScope::Pool(7));
+    assert!(!client.has_role_for_pool(&desk, &Role::Operator, &7, &symbol_short!("Crypto")));
+    let missing =
+        client.try_revoke_scoped_role(&admin, &desk, &Role::Operator, &RoleScope::Pool(7));
+    assert_eq!(missing, Err(Ok(PrediFiError::InsufficientPermissions)));
+
+    client.revoke_all_roles(&admin, &desk);
+    assert!(!client.has_role_for_pool(&desk, &Role::Operator, &1, &symbol_short!("Sports")));
+    assert_eq!(client.get_role_member_count(&Role::Operator), 0);
+}
+
+/// Scoped grants are admin-only and must name a pool or category.
+#[test]
+fn test_scoped_role_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let desk = Address::generate(&env);
+    client.init(&admin);
+
+    let global =
+        client.try_assign_scoped_role(&admin, &desk, &Role::Operator, &RoleScope::Global, &None);
+    assert_eq!(global, Err(Ok(PrediFiError::InvalidData)));
+    let not_admin =
+        client.try_assign_scoped_role(&desk, &desk, &Role::Operator, &RoleScope::Pool(1), &None);
+    assert_eq!(not_admin, Err(Ok(PrediFiError::Unauthorized)));
+}
@@ -0,0 +1,246 @@
+#![no_std]
+#![allow(clippy::too_many_arguments)]
+
+// ═══════════════════════════════════════════════════════════════════════════
+// OUTCOME TOKEN — SEP-41 VIEW OF A POOL OUTCOME
+// ═══════════════════════════════════════════════════════════════════════════
+//
+// One instance per (pool, outcome), deployed by predifi-contract's
+// `deploy_outcome_tokens`. The token keeps no balances of its own: the
+// prediction market stays the ledger (one share per staked unit) and every
+// call is forwarded to it.
+//
+// ┌────────────────┬───────────────────────────────────────────────────────┐
+// │ SEP-41         │ predifi-contract                                      │
+// ├────────────────┼───────────────────────────────────────────────────────┤
+// │ balance        │ share_balance                                         │
+// │ transfer       │ transfer_prediction                                   │
+// │ approve        │ approve_shares                                        │
+// │ allowance      │ share_allowance                                       │
+// │ transfer_from  │ transfer_shares_from                                  │
+// │ burn/burn_from │ unsupported; shares are burned by claim_winnings       │
+// └────────────────┴───────────────────────────────────────────────────────┘
+//
+// The owner (or spender) signs the token call, which covers the nested
+// market call. The market checks that authorization again itself, so a rogue
+// copy of this contract cannot move anyone's shares. Transfers follow the
+// market's rules and fail once the pool has stopped taking stakes. SEP-41
+// calls carry no invite, so in a private pool the recipient must already be
+// whitelisted (or be the creator).
+
+use predifi_errors::PrediFiError;
+use soroban_sdk::{
+    contract, contractclient, contractevent, contractimpl, contracttype, panic_with_error,
+    token::TokenInterface, Address, Env, MuxedAddress, String, Val,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// `Share` -> `ShareInfo`
+    Share,
+}
+
+/// The pool outcome a token serves and its SEP-41 metadata.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShareInfo {
+    pub market: Address,
+    pub pool_id: u64,
+    pub outcome: u32,
+    pub decimals: u32,
+    pub name: String,
+    pub symbol: String,
+}
+
+/// SEP-41 `transfer` event.
+#[contractevent(data_format = "single-value")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Transfer {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub to: Address,
+    pub amount: i128,
+}
+
+/// SEP-41 `approve` event.
+#[contractevent(data_format = "vec")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Approve {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub spender: Address,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+/// The share functions of predifi-contract this token forwards to. `invite`
+/// is the market's `Option<Invite>`; the token always passes `None`.
+#[contractclient(name = "MarketClient")]
+pub trait Market {
+    fn share_balance(env: Env, pool_id: u64, outcome: u32, owner: Address) -> i128;
+    fn share_allowance(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        pool_id: u64,
+        outcome: u32,
+    ) -> i128;
+    fn approve_shares(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        pool_id: u64,
+        outcome: u32,
+        amount: i128,
+        expiration_ledger: u32,
+    );
+    fn transfer_prediction(
+        env: Env,
+        from: Address,
+        to: Address,
+        pool_id: u64,
+        outcome: u32,
+        amount: i128,
+        invite: Option<Val>,
+    );
+    fn transfer_shares_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        pool_id: u64,
+        outcome: u32,
+        amount: i128,
+        invite: Option<Val>,
+    );
+}
+
+#[contract]
+pub struct OutcomeToken;
+
+#[contractimpl]
+impl OutcomeToken {
+    pub fn __constructor(
+        env: Env,
+        market: Address,
+        pool_id: u64,
+        outcome: u32,
+        decimals: u32,
+        name: String,
+        symbol: String,
+    ) {
+        let info = ShareInfo {
+            market,
+            pool_id,
+            outcome,
+            decimals,
+            name,
+            symbol,
+        };
+        env.storage().instance().set(&DataKey::Share, &info);
+    }
+
+    /// The market, pool and outcome this token serves.
+    pub fn share_info(env: Env) -> ShareInfo {
+        Self::info(&env)
+    }
+
+    fn info(env: &Env) -> ShareInfo {
+        env.storage()
+            .instance()
+            .get(&DataKey::Share)
+            .unwrap_or_else(|| panic_with_error!(env, PrediFiError::NotInitialized))
+    }
+
+    fn market(env: &Env, info: &ShareInfo) -> MarketClient<'static> {
+        MarketClient::new(env, &info.market)
+    }
+}
+
+#[contractimpl]
+impl TokenInterface for OutcomeToken {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        let info = Self::info(&env);
+        Self::market(&env, &info).share_allowance(&from, &spender, &info.pool_id, &info.outcome)
+    }
+
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        from.require_auth();
+        let info = Self::info(&env);
+        Self::market(&env, &info).approve_shares(
+            &from,
+            &spender,
+            &info.pool_id,
+            &info.outcome,
+            &amount,
+            &expiration_ledger,
+        );
+        Approve {
+            from,
+            spender,
+            amount,
+            expiration_ledger,
+        }
+        .publish(&env);
+    }
+
+    fn balance(env: Env, id: Address) -> i128 {
+        let info = Self::info(&env);
+        Self::market(&env, &info).share_balance(&info.pool_id, &info.outcome, &id)
+    }
+
+    fn transfer(env: Env, from: Address, to: MuxedAddress, amount: i128) {
+        from.require_auth();
+        let info = Self::info(&env);
+        let to = to.address();
+        Self::market(&env, &info).transfer_prediction(
+            &from,
+            &to,
+            &info.pool_id,
+            &info.outcome,
+            &amount,
+            &None,
+        );
+        Transfer { from, to, amount }.publish(&env);
+    }
+
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        let info = Self::info(&env);
+        Self::market(&env, &info).transfer_shares_from(
+            &spender,
+            &from,
+            &to,
+            &info.pool_id,
+            &info.outcome,
+            &amount,
+            &None,
+        );
+        Transfer { from, to, amount }.publish(&env);
+    }
+
+    fn burn(env: Env, _from: Address, _amount: i128) {
+        panic_with_error!(&env, PrediFiError::TokenError)
+    }
+
+    fn burn_from(env: Env, _spender: Address, _from: Address, _amount: i128) {
+        panic_with_error!(&env, PrediFiError::TokenError)
+    }
+
+    fn decimals(env: Env) -> u32 {
+        Self::info(&env).decimals
+    }
+
+    fn name(env: Env) -> String {
+        Self::info(&env).name
+    }
+
+    fn symbol(env: Env) -> String {
+        Self::info(&env).symbol
+    }
+}
+
+mod test;
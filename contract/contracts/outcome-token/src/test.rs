@@ -0,0 +1,148 @@
+#![cfg(test)]
+
+use super::*;
+use access_control::{AccessControl, AccessControlClient};
+use predifi_contract::{PoolConfig, PredifiContract, PredifiContractClient, PricingMode};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{symbol_short, token, vec, Address, Env, String};
+
+const END_TIME: u64 = 10_000;
+
+/// A two-outcome pool where `staker` holds 1_000 on outcome 1, and the
+/// token serving that outcome.
+fn setup(
+    env: &Env,
+) -> (
+    PredifiContractClient<'_>,
+    token::TokenClient<'_>,
+    Address,
+    u64,
+) {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let admin = Address::generate(env);
+    let ac_id = env.register(AccessControl, ());
+    AccessControlClient::new(env, &ac_id).init(&admin);
+
+    let market_id = env.register(PredifiContract, ());
+    let market = PredifiContractClient::new(env, &market_id);
+    market.init(
+        &ac_id,
+        &Address::generate(env),
+        &0u32,
+        &0u64,
+        &3600u64,
+        &0u32,
+    );
+
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(env));
+    market.add_token_to_whitelist(&admin, &asset.address());
+    let config = PoolConfig {
+        start_time: 0,
+        description: String::from_str(env, "Match winner"),
+        metadata_url: String::from_str(env, "ipfs://outcome-token"),
+        min_stake: 1i128,
+        max_stake: 0i128,
+        max_total_stake: 0i128,
+        min_total_stake: 1i128,
+        initial_liquidity: 0i128,
+        required_resolutions: 1u32,
+        private: false,
+        whitelist_key: None,
+        outcome_descriptions: vec![
+            env,
+            String::from_str(env, "Home"),
+            String::from_str(env, "Away"),
+        ],
+        optimistic_liveness: 0,
+        optimistic_bond: 0,
+        pricing_mode: PricingMode::Parimutuel,
+        creator_fee_bps: 0,
+    };
+    let pool_id = market.create_pool(
+        &Address::generate(env),
+        &END_TIME,
+        &asset.address(),
+        &2u32,
+        &symbol_short!("Sports"),
+        &config,
+    );
+
+    let staker = Address::generate(env);
+    token::StellarAssetClient::new(env, &asset.address()).mint(&staker, &1_000);
+    market.place_prediction(&staker, &pool_id, &1_000, &1u32, &None, &None);
+
+    let token_id = env.register(
+        OutcomeToken,
+        (
+            market_id,
+            pool_id,
+            1u32,
+            7u32,
+            String::from_str(env, "Away"),
+            String::from_str(env, "P0O1"),
+        ),
+    );
+    (
+        market,
+        token::TokenClient::new(env, &token_id),
+        staker,
+        pool_id,
+    )
+}
+
+#[test]
+fn test_metadata_and_balance() {
+    let env = Env::default();
+    let (_, share, staker, _) = setup(&env);
+
+    assert_eq!(share.decimals(), 7);
+    assert_eq!(share.name(), String::from_str(&env, "Away"));
+    assert_eq!(share.symbol(), String::from_str(&env, "P0O1"));
+    assert_eq!(share.balance(&staker), 1_000);
+    assert_eq!(share.balance(&Address::generate(&env)), 0);
+}
+
+#[test]
+fn test_transfer_moves_position() {
+    let env = Env::default();
+    let (market, share, staker, pool_id) = setup(&env);
+    let buyer = Address::generate(&env);
+
+    share.transfer(&staker, &buyer, &400);
+    assert_eq!(share.balance(&staker), 600);
+    assert_eq!(market.share_balance(&pool_id, &1u32, &buyer), 400);
+    assert_eq!(market.get_pool(&pool_id).participants_count, 2);
+}
+
+#[test]
+fn test_approve_and_transfer_from() {
+    let env = Env::default();
+    let (_, share, staker, _) = setup(&env);
+    let spender = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    share.approve(&staker, &spender, &500, &(env.ledger().sequence() + 100));
+    assert_eq!(share.allowance(&staker, &spender), 500);
+    share.transfer_from(&spender, &staker, &buyer, &300);
+    assert_eq!(share.allowance(&staker, &spender), 200);
+    assert_eq!(share.balance(&buyer), 300);
+
+    assert!(share
+        .try_transfer_from(&spender, &staker, &buyer, &201)
+        .is_err());
+}
+
+#[test]
+fn test_transfers_close_with_the_pool_and_burn_is_unsupported() {
+    let env = Env::default();
+    let (_, share, staker, _) = setup(&env);
+
+    assert!(share.try_burn(&staker, &1).is_err());
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME);
+    let buyer = Address::generate(&env);
+    assert!(share.try_transfer(&staker, &buyer, &1).is_err());
+    assert_eq!(share.balance(&staker), 1_000);
+}
@@ -1,16 +1,15 @@
 #![cfg(test)]
 
+extern crate std;
+
 use crate::test::ROLE_ADMIN;
-use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    token, vec, Address, BytesN, Env, String, Symbol,
-};
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env};
 
 #[test]
 fn test_set_fee_bps_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let result = client.try_set_fee_bps(&attacker, &500);
@@ -21,7 +20,7 @@ fn test_set_fee_bps_rejects_unauthorized() {
 fn test_set_treasury_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let result = client.try_set_treasury(&attacker, &Address::generate(&env));
@@ -32,7 +31,7 @@ fn test_set_treasury_rejects_unauthorized() {
 fn test_add_oracle_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let result = client.try_add_oracle(&attacker, &Address::generate(&env));
@@ -45,9 +44,11 @@ fn test_remove_oracle_rejects_unauthorized() {
     env.mock_all_auths();
     let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
     let attacker = Address::generate(&env);
     let oracle = Address::generate(&env);
-    client.add_oracle(&Address::generate(&env), &oracle);
+    client.add_oracle(&admin, &oracle);
     let result = client.try_remove_oracle(&attacker, &oracle);
     assert!(result.is_err());
 }
@@ -56,7 +57,7 @@ fn test_remove_oracle_rejects_unauthorized() {
 fn test_upgrade_contract_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let result = client.try_upgrade_contract(&attacker, &BytesN::from_array(&env, &[0u8; 32]));
@@ -67,7 +68,7 @@ fn test_upgrade_contract_rejects_unauthorized() {
 fn test_withdraw_treasury_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let result = client.try_withdraw_treasury(
@@ -83,7 +84,7 @@ fn test_withdraw_treasury_rejects_unauthorized() {
 fn test_pause_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
@@ -96,7 +97,7 @@ fn test_pause_rejects_unauthorized() {
 fn test_unpause_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
@@ -109,7 +110,7 @@ fn test_unpause_rejects_unauthorized() {
 fn test_set_resolution_delay_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let result = client.try_set_resolution_delay(&attacker, &3600);
@@ -120,7 +121,7 @@ fn test_set_resolution_delay_rejects_unauthorized() {
 fn test_set_min_stake_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let result = client.try_set_min_stake(&attacker, &100);
@@ -131,7 +132,7 @@ fn test_set_min_stake_rejects_unauthorized() {
 fn test_add_token_to_whitelist_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let result = client.try_add_token_to_whitelist(&attacker, &Address::generate(&env));
@@ -142,7 +143,7 @@ fn test_add_token_to_whitelist_rejects_unauthorized() {
 fn test_remove_token_from_whitelist_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let result = client.try_remove_token_from_whitelist(&attacker, &Address::generate(&env));
@@ -153,7 +154,7 @@ fn test_remove_token_from_whitelist_rejects_unauthorized() {
 fn test_set_prediction_cooldown_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let result = client.try_set_prediction_cooldown(&attacker, &60);
@@ -164,7 +165,7 @@ fn test_set_prediction_cooldown_rejects_unauthorized() {
 fn test_set_max_predictions_per_user_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let result = client.try_set_max_predictions_per_user(&attacker, &10);
@@ -175,7 +176,7 @@ fn test_set_max_predictions_per_user_rejects_unauthorized() {
 fn test_set_claim_window_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let result = client.try_set_claim_window(&attacker, &86400);
@@ -186,7 +187,7 @@ fn test_set_claim_window_rejects_unauthorized() {
 fn test_set_min_pool_duration_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let result = client.try_set_min_pool_duration(&attacker, &7200);
@@ -197,7 +198,7 @@ fn test_set_min_pool_duration_rejects_unauthorized() {
 fn test_init_oracle_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let result = client.try_init_oracle(&attacker, &Address::generate(&env), &300, &100);
@@ -208,7 +209,7 @@ fn test_init_oracle_rejects_unauthorized() {
 fn test_set_fee_tiers_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let tier = crate::FeeTier {
@@ -223,7 +224,7 @@ fn test_set_fee_tiers_rejects_unauthorized() {
 fn test_set_referral_rate_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let result = client.try_set_referral_rate(&attacker, &500);
@@ -234,7 +235,7 @@ fn test_set_referral_rate_rejects_unauthorized() {
 fn test_set_referral_volume_threshold_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let result = client.try_set_referral_volume_threshold(&attacker, &1000);
@@ -245,7 +246,7 @@ fn test_set_referral_volume_threshold_rejects_unauthorized() {
 fn test_emergency_withdraw_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
     let result = client.try_emergency_withdraw(
@@ -261,9 +262,9 @@ fn test_emergency_withdraw_rejects_unauthorized() {
 fn test_migrate_state_rejects_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
-    let (ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
+    let (_ac_client, client, _token_address, _token, _token_admin, _treasury, _operator, _creator) =
         crate::test::setup(&env);
     let attacker = Address::generate(&env);
-    let result = client.try_migrate_state(&attacker);
+    let result = client.try_migrate_state(&attacker, &10u32);
     assert!(result.is_err());
 }
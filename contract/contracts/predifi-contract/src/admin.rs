@@ -5,14 +5,17 @@ use soroban_sdk::{contractimpl, Address, BytesN, Env, Symbol, Vec};
 
 use crate::{
     AddedToWhitelistEvent, ClaimWindowUpdateEvent, Config, ContractInfo, ContractMetadata,
-    ContractPausedAlertEvent, ContractUpgradedEvent, DataKey, FeeChangeCancelEvent,
-    FeeChangeProposeEvent, FeeInfo, FeeTier, FeeTiersUpdateEvent, FeeUpdateEvent, InitEvent,
-    MaxPredictionsUpdateEvent, MinPoolDurationUpdateEvent, MinStakeUpdateEvent, PauseEvent,
-    PendingFeeChange, Pool, PredictionCooldownUpdateEvent, PredifiContract, PredifiContractArgs,
+    ContractPausedAlertEvent, DataKey, FeeChangeCancelEvent, FeeChangeProposeEvent, FeeInfo,
+    FeeTier, FeeTiersUpdateEvent, FeeUpdateEvent, InitEvent, MaxPredictionsUpdateEvent,
+    MigrationKey, MinPoolDurationUpdateEvent, MinStakeUpdateEvent, PauseEvent, PendingFeeChange,
+    PendingUpgrade, Pool, PredictionCooldownUpdateEvent, PredifiContract, PredifiContractArgs,
     PredifiContractClient, PredifiError, RemovedFromWhitelistEvent, ResolutionDelayUpdateEvent,
-    StorageTtlRenewedEvent, TokenWhitelistAddedEvent, TokenWhitelistRemovedEvent, UnpauseEvent,
-    UpgradeEvent, CONTRACT_VERSION, DEFAULT_GLOBAL_MIN_STAKE, DEFAULT_PREDICTION_COOLDOWN_SECONDS,
-    FEE_CHANGE_TIMELOCK_SECONDS, MAX_CLAIM_WINDOW, MAX_RESOLUTION_DELAY, MIN_CLAIM_WINDOW,
+    SensitiveAction, StorageTtlRenewedEvent, TokenWhitelistAddedEvent, TokenWhitelistRemovedEvent,
+    UnpauseEvent, UpgradeApprovedEvent, UpgradeCanceledEvent, UpgradeEvent, UpgradeKey,
+    UpgradePolicy, UpgradePolicyUpdatedEvent, UpgradeProposedEvent, CONTRACT_VERSION,
+    DEFAULT_GLOBAL_MIN_STAKE, DEFAULT_PREDICTION_COOLDOWN_SECONDS, DEFAULT_UPGRADE_APPROVALS,
+    DEFAULT_UPGRADE_TIMELOCK_SECONDS, FEE_CHANGE_TIMELOCK_SECONDS, MAX_CLAIM_WINDOW,
+    MAX_RESOLUTION_DELAY, MIN_CLAIM_WINDOW, MIN_UPGRADE_TIMELOCK_SECONDS,
 };
 
 #[contractimpl]
@@ -62,6 +65,9 @@ impl PredifiContract {
         env.storage()
             .instance()
             .set(&DataKey::Version, &CONTRACT_VERSION);
+        env.storage()
+            .instance()
+            .set(&MigrationKey::SchemaVersion, &CONTRACT_VERSION);
         Self::extend_instance(&env);
 
         InitEvent {
@@ -75,15 +81,17 @@ impl PredifiContract {
         .publish(&env);
     }
 
-    /// Pause the contract.
+    /// Pause the contract, or approve a pending pause.
     ///
     /// # Authorization
-    /// Requires authentication from a caller with the Admin role.
+    /// Requires authentication from a caller with the Admin role. The pause
+    /// takes effect once the `SensitiveActionKind::Pause` approval policy's
+    /// threshold of distinct admins has called this (two by default).
     ///
     /// # Effects
-    /// - Marks the contract as paused.
-    /// - Emits `ContractPausedAlertEvent`.
-    /// - Emits `PauseEvent`.
+    /// - Records the caller's approval (see `get_approval_request`).
+    /// - Once approved: marks the contract as paused and emits
+    ///   `ContractPausedAlertEvent` and `PauseEvent`.
     ///
     /// While paused, administrative checks continue to work, but
     /// state-changing operations guarded by the pause flag are rejected.
@@ -92,6 +100,11 @@ impl PredifiContract {
         if Self::require_admin_role(&env, &admin, "pause").is_err() {
             panic!("Unauthorized: missing required role");
         }
+        match Self::record_approval(&env, &admin, &SensitiveAction::Pause) {
+            Ok(None) => {}
+            Ok(Some(_)) => return,
+            Err(e) => soroban_sdk::panic_with_error!(&env, e),
+        }
         env.storage().instance().set(&DataKey::Paused, &true);
         Self::extend_instance(&env);
 
@@ -575,7 +588,7 @@ impl PredifiContract {
     /// # Errors
     /// * `Unauthorized` - If caller does not hold the Admin role.
     /// * `InvalidData` - If `tokens` is empty or exceeds `MAX_BATCH_SIZE` (100).
-    pub fn batch_remove_tokens_from_whitelist(
+    pub fn batch_remove_tokens_from_wl(
         env: Env,
         admin: Address,
         tokens: Vec<Address>,
@@ -584,7 +597,7 @@ impl PredifiContract {
 
         Self::require_not_paused(&env)?;
         admin.require_auth();
-        Self::require_admin_role(&env, &admin, "batch_remove_tokens_from_whitelist")?;
+        Self::require_admin_role(&env, &admin, "batch_remove_tokens_from_wl")?;
 
         let batch_size = tokens.len();
         if batch_size == 0 || batch_size > MAX_BATCH_SIZE {
@@ -648,61 +661,248 @@ impl PredifiContract {
         whitelist
     }
 
-    /// Upgrade the contract Wasm code. Only callable by Admin (role 0).
-    pub fn upgrade_contract(
+    /// Set the delay and approval threshold for contract upgrades, or approve
+    /// a pending change to exactly these values. Caller must have Admin role
+    /// (0).
+    ///
+    /// The change takes effect once as many distinct admins as the current
+    /// `UpgradePolicy.required_approvals` have called this with the same
+    /// arguments, so a single admin cannot lower the threshold an upgrade
+    /// needs. Returns without changing the policy while approvals are short.
+    ///
+    /// # Errors
+    /// - [`PredifiError::Unauthorized`]     – caller lacks Admin role (0).
+    /// - [`PredifiError::EmergencyCancelAlreadyApproved`] – caller already
+    ///   approved this change.
+    /// - [`PredifiError::InvalidData`]      – `delay` is below
+    ///   [`MIN_UPGRADE_TIMELOCK_SECONDS`] or `required_approvals` is zero.
+    /// - [`PredifiError::FeeChangePending`] – an upgrade is queued; the policy
+    ///   it was proposed under must not change underneath it.
+    pub fn set_upgrade_policy(
         env: Env,
         admin: Address,
-        new_wasm_hash: BytesN<32>,
+        delay: u64,
+        required_approvals: u32,
     ) -> Result<(), PredifiError> {
         admin.require_auth();
-        Self::require_admin_role(&env, &admin, "upgrade_contract")?;
+        Self::require_admin_role(&env, &admin, "set_upgrade_policy")?;
+        if delay < MIN_UPGRADE_TIMELOCK_SECONDS || required_approvals == 0 {
+            return Err(PredifiError::InvalidData);
+        }
+        if env.storage().instance().has(&UpgradeKey::PendingUpgrade) {
+            return Err(PredifiError::FeeChangePending);
+        }
+        let action = SensitiveAction::SetUpgradePolicy(delay, required_approvals);
+        if Self::record_approval(&env, &admin, &action)?.is_some() {
+            return Ok(());
+        }
 
-        let old_version: u32 = env
-            .storage()
+        env.storage().instance().set(
+            &UpgradeKey::UpgradePolicy,
+            &UpgradePolicy {
+                delay,
+                required_approvals,
+            },
+        );
+        Self::extend_instance(&env);
+
+        UpgradePolicyUpdatedEvent {
+            admin,
+            delay,
+            required_approvals,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Current upgrade policy, or the defaults
+    /// ([`DEFAULT_UPGRADE_TIMELOCK_SECONDS`], [`DEFAULT_UPGRADE_APPROVALS`])
+    /// if none was set.
+    pub fn get_upgrade_policy(env: Env) -> UpgradePolicy {
+        env.storage()
             .instance()
-            .get(&DataKey::Version)
-            .unwrap_or(0u32);
-        let new_version = old_version + 1;
+            .get(&UpgradeKey::UpgradePolicy)
+            .unwrap_or(UpgradePolicy {
+                delay: DEFAULT_UPGRADE_TIMELOCK_SECONDS,
+                required_approvals: DEFAULT_UPGRADE_APPROVALS,
+            })
+    }
 
-        env.deployer()
-            .update_current_contract_wasm(new_wasm_hash.clone());
+    /// Queue an upgrade to `new_wasm_hash`, counting as its first approval.
+    /// It can execute through [`Self::upgrade_contract`] once the policy delay
+    /// has elapsed and enough admins have called [`Self::approve_upgrade`].
+    ///
+    /// Upgrades are deliberately not blocked by `pause`, so a fix can still
+    /// ship while the contract is halted.
+    ///
+    /// # Errors
+    /// - [`PredifiError::Unauthorized`]     – caller lacks Admin role (0).
+    /// - [`PredifiError::FeeChangePending`] – an upgrade is already queued;
+    ///   call `cancel_upgrade` first.
+    ///
+    /// PRE:  admin has role 0; no upgrade is queued.
+    /// POST: A [`PendingUpgrade`] is stored with
+    ///       `executable_at = now + UpgradePolicy.delay`.
+    pub fn propose_upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<(), PredifiError> {
+        admin.require_auth();
+        Self::require_admin_role(&env, &admin, "propose_upgrade")?;
+        if env.storage().instance().has(&UpgradeKey::PendingUpgrade) {
+            return Err(PredifiError::FeeChangePending);
+        }
 
+        let policy = Self::get_upgrade_policy(env.clone());
+        let executable_at = env
+            .ledger()
+            .timestamp()
+            .checked_add(policy.delay)
+            .ok_or(PredifiError::ArithmeticError)?;
+        let pending = PendingUpgrade {
+            new_wasm_hash: new_wasm_hash.clone(),
+            proposed_by: admin.clone(),
+            executable_at,
+            approvals: Vec::from_array(&env, [admin.clone()]),
+        };
         env.storage()
             .instance()
-            .set(&DataKey::Version, &new_version);
+            .set(&UpgradeKey::PendingUpgrade, &pending);
         Self::extend_instance(&env);
 
-        UpgradeEvent {
-            admin: admin.clone(),
+        UpgradeProposedEvent {
+            admin,
             new_wasm_hash,
+            executable_at,
+            required_approvals: policy.required_approvals,
         }
         .publish(&env);
+        Ok(())
+    }
+
+    /// Add the caller's approval to the queued upgrade.
+    ///
+    /// # Errors
+    /// - [`PredifiError::Unauthorized`]                   – caller lacks Admin role (0).
+    /// - [`PredifiError::NoFeeChangePending`]             – no upgrade is queued.
+    /// - [`PredifiError::EmergencyCancelAlreadyApproved`] – caller already approved.
+    pub fn approve_upgrade(env: Env, admin: Address) -> Result<(), PredifiError> {
+        admin.require_auth();
+        Self::require_admin_role(&env, &admin, "approve_upgrade")?;
 
-        ContractUpgradedEvent {
-            old_version,
-            new_version,
-            upgraded_by: admin,
+        let mut pending: PendingUpgrade = env
+            .storage()
+            .instance()
+            .get(&UpgradeKey::PendingUpgrade)
+            .ok_or(PredifiError::NoFeeChangePending)?;
+        if pending.approvals.contains(&admin) {
+            return Err(PredifiError::EmergencyCancelAlreadyApproved);
+        }
+        pending.approvals.push_back(admin.clone());
+        env.storage()
+            .instance()
+            .set(&UpgradeKey::PendingUpgrade, &pending);
+        Self::extend_instance(&env);
+
+        UpgradeApprovedEvent {
+            admin,
+            new_wasm_hash: pending.new_wasm_hash,
+            approvals: pending.approvals.len(),
         }
         .publish(&env);
+        Ok(())
+    }
 
+    /// Withdraw the queued upgrade. Any single admin may cancel, so one
+    /// honest key is enough to stop an upgrade the others approved.
+    ///
+    /// # Errors
+    /// - [`PredifiError::Unauthorized`]       – caller lacks Admin role (0).
+    /// - [`PredifiError::NoFeeChangePending`] – no upgrade is queued.
+    pub fn cancel_upgrade(env: Env, admin: Address) -> Result<(), PredifiError> {
+        admin.require_auth();
+        Self::require_admin_role(&env, &admin, "cancel_upgrade")?;
+
+        let pending: PendingUpgrade = env
+            .storage()
+            .instance()
+            .get(&UpgradeKey::PendingUpgrade)
+            .ok_or(PredifiError::NoFeeChangePending)?;
+        env.storage().instance().remove(&UpgradeKey::PendingUpgrade);
+        Self::extend_instance(&env);
+
+        UpgradeCanceledEvent {
+            admin,
+            new_wasm_hash: pending.new_wasm_hash,
+        }
+        .publish(&env);
         Ok(())
     }
 
-    /// Post-upgrade migration logic.
+    /// Return the queued upgrade, or `None` if no upgrade is pending.
+    pub fn get_pending_upgrade(env: Env) -> Option<PendingUpgrade> {
+        env.storage().instance().get(&UpgradeKey::PendingUpgrade)
+    }
+
+    /// Execute the queued upgrade. Only callable by Admin (role 0).
     ///
-    /// v2 migration: the deprecated `resolved` and `canceled` boolean fields have been
-    /// removed from the `Pool` struct. All state is now represented exclusively by the
-    /// `state: MarketState` field. Existing pools stored with the old schema are
-    /// automatically handled by Soroban's XDR codec — the removed fields are simply
-    /// ignored on read, so no explicit data rewrite is required.
-    pub fn migrate_state(env: Env, admin: Address) -> Result<(), PredifiError> {
-        Self::require_not_paused(&env)?;
+    /// `new_wasm_hash` must match the queued hash, the policy delay must have
+    /// elapsed since `propose_upgrade`, and at least
+    /// `UpgradePolicy.required_approvals` of the approvers must still hold the
+    /// Admin role.
+    ///
+    /// If the new code expects a newer state layout, user entry points stay
+    /// blocked until `migrate_state` has run to completion.
+    ///
+    /// # Errors
+    /// - [`PredifiError::Unauthorized`]           – caller lacks Admin role (0).
+    /// - [`PredifiError::NoFeeChangePending`]     – no upgrade is queued.
+    /// - [`PredifiError::InvalidData`]            – `new_wasm_hash` is not the queued hash.
+    /// - [`PredifiError::TimelockNotExpired`]     – the delay has not yet elapsed.
+    /// - [`PredifiError::EmergencyCancelPending`] – not enough approvals.
+    pub fn upgrade_contract(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<(), PredifiError> {
         admin.require_auth();
-        Self::require_admin_role(&env, &admin, "migrate_state")?;
+        Self::require_admin_role(&env, &admin, "upgrade_contract")?;
 
-        // v2 migration: Add any state migration logic here.
-        // Use Self::validate_pool_invariants(&pool) to ensure pool data consistency
-        // during migrations.
+        let pending: PendingUpgrade = env
+            .storage()
+            .instance()
+            .get(&UpgradeKey::PendingUpgrade)
+            .ok_or(PredifiError::NoFeeChangePending)?;
+        if pending.new_wasm_hash != new_wasm_hash {
+            return Err(PredifiError::InvalidData);
+        }
+        if env.ledger().timestamp() < pending.executable_at {
+            return Err(PredifiError::TimelockNotExpired);
+        }
+        // Approvals from admins who have since lost the role no longer count.
+        let required = Self::get_upgrade_policy(env.clone()).required_approvals;
+        let approvals = pending
+            .approvals
+            .iter()
+            .filter(|approver| Self::require_role(&env, approver, 0).is_ok())
+            .count() as u32;
+        if approvals < required {
+            return Err(PredifiError::EmergencyCancelPending);
+        }
+        env.storage().instance().remove(&UpgradeKey::PendingUpgrade);
+
+        // Stored state keeps its schema version until `migrate_state` has
+        // brought it up to the new code's `CONTRACT_VERSION`.
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+        Self::extend_instance(&env);
+
+        UpgradeEvent {
+            admin,
+            new_wasm_hash,
+        }
+        .publish(&env);
 
         Ok(())
     }
@@ -0,0 +1,209 @@
+//! Aggregated price resolution: the per-pair observation buffer, TWAP and
+//! median-of-oracles settlement, and outlier rejection.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{AggregationMode, PredifiError, PriceAggregation, MAX_PRICE_OBSERVATIONS};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, Symbol,
+};
+
+const END_TIME: u64 = 10_000;
+const WINDOW: u64 = 1_000;
+
+fn feed(env: &Env) -> Symbol {
+    Symbol::new(env, "ETH_USD")
+}
+
+/// Configure the oracle settings and open a first pool.
+fn aggregation_pool(ctx: &TestContext) -> u64 {
+    ctx.client.init_oracle(
+        &ctx.admin,
+        &Address::generate(&ctx.client.env),
+        &100_000u64,
+        &500u32,
+    );
+    new_pool(ctx)
+}
+
+/// A binary pool where Yes wins above 3000.
+fn new_pool(ctx: &TestContext) -> u64 {
+    let env = &ctx.client.env;
+    let pool_id = ctx.create_pool(END_TIME, &default_pool_config(env));
+    ctx.client
+        .set_price_condition(&ctx.operator, &pool_id, &feed(env), &3_000, &1, &0);
+    pool_id
+}
+
+fn new_oracle(ctx: &TestContext) -> Address {
+    let oracle = Address::generate(&ctx.client.env);
+    ctx.client.add_oracle(&ctx.admin, &oracle);
+    oracle
+}
+
+/// Push `price` observed at `timestamp`, advancing the ledger past it.
+fn push(ctx: &TestContext, oracle: &Address, price: i128, timestamp: u64) {
+    let env = &ctx.client.env;
+    if env.ledger().timestamp() <= timestamp {
+        env.ledger().with_mut(|li| li.timestamp = timestamp + 1);
+    }
+    ctx.client.update_price_feed(
+        oracle,
+        &feed(env),
+        &price,
+        &1,
+        &timestamp,
+        &(timestamp + 100_000),
+    );
+}
+
+fn twap() -> PriceAggregation {
+    PriceAggregation {
+        mode: AggregationMode::Twap,
+        window: WINDOW,
+        max_deviation_bps: 0,
+        min_sources: 0,
+    }
+}
+
+fn median(min_sources: u32) -> PriceAggregation {
+    PriceAggregation {
+        mode: AggregationMode::Median,
+        window: WINDOW,
+        max_deviation_bps: 1_000,
+        min_sources,
+    }
+}
+
+/// The buffer stays sorted by observation time and keeps only the newest
+/// `MAX_PRICE_OBSERVATIONS` entries.
+#[test]
+fn test_observation_buffer_is_sorted_and_bounded() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    aggregation_pool(&ctx);
+    let oracle = new_oracle(&ctx);
+
+    for i in 0..40u64 {
+        push(&ctx, &oracle, 3_000, 2_000 + i * 10);
+    }
+    let late = new_oracle(&ctx);
+    push(&ctx, &late, 3_100, 2_305);
+
+    let observations = ctx.client.get_price_observations(&feed(&env));
+    assert_eq!(observations.len(), MAX_PRICE_OBSERVATIONS);
+    let mut previous = 0u64;
+    for observation in observations.iter() {
+        assert!(observation.timestamp >= previous);
+        previous = observation.timestamp;
+    }
+    assert_eq!(observations.last().unwrap().timestamp, 2_390);
+    assert!(observations.iter().any(|o| o.oracle == late));
+}
+
+/// A spike pushed just before close barely moves the TWAP, and pushes after
+/// close are ignored; the spot price of an identical pool follows the spike.
+#[test]
+fn test_twap_resists_last_minute_spike() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = aggregation_pool(&ctx);
+    let spot_pool = new_pool(&ctx);
+    let oracle = new_oracle(&ctx);
+    ctx.client
+        .set_price_aggregation(&ctx.operator, &pool_id, &Some(twap()));
+
+    push(&ctx, &oracle, 2_900, 8_500);
+    push(&ctx, &oracle, 2_900, 9_500);
+    push(&ctx, &oracle, 6_000, 9_990);
+    push(&ctx, &oracle, 6_000, 10_050);
+
+    ctx.client.resolve_pool_from_price(&pool_id);
+    ctx.client.resolve_pool_from_price(&spot_pool);
+
+    assert_eq!(ctx.client.get_pool(&pool_id).outcome, 0);
+    assert_eq!(ctx.client.get_pool(&spot_pool).outcome, 1);
+}
+
+/// A TWAP window without any observation cannot resolve.
+#[test]
+fn test_twap_requires_observation_in_window() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = aggregation_pool(&ctx);
+    let oracle = new_oracle(&ctx);
+    ctx.client
+        .set_price_aggregation(&ctx.operator, &pool_id, &Some(twap()));
+
+    push(&ctx, &oracle, 3_500, 5_000);
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+
+    let result = ctx.client.try_resolve_pool_from_price(&pool_id);
+    assert_eq!(result, Err(Ok(PredifiError::PriceDataInvalid)));
+}
+
+/// The median drops a manipulated oracle and uses each oracle's latest
+/// price; too few agreeing oracles leave the pool unresolved.
+#[test]
+fn test_median_rejects_outlier_oracle() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = aggregation_pool(&ctx);
+    let quorum_pool = new_pool(&ctx);
+    let a = new_oracle(&ctx);
+    let b = new_oracle(&ctx);
+    let c = new_oracle(&ctx);
+    ctx.client
+        .set_price_aggregation(&ctx.operator, &pool_id, &Some(median(2)));
+    ctx.client
+        .set_price_aggregation(&ctx.operator, &quorum_pool, &Some(median(3)));
+
+    push(&ctx, &a, 3_400, 9_100);
+    push(&ctx, &a, 2_900, 9_200);
+    push(&ctx, &b, 2_950, 9_300);
+    push(&ctx, &c, 9_000, 9_900);
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+
+    ctx.client.resolve_pool_from_price(&pool_id);
+    assert_eq!(ctx.client.get_pool(&pool_id).outcome, 0);
+
+    let result = ctx.client.try_resolve_pool_from_price(&quorum_pool);
+    assert_eq!(result, Err(Ok(PredifiError::PriceDataInvalid)));
+}
+
+/// Settings are validated, and `None` returns the pool to spot resolution.
+#[test]
+fn test_set_price_aggregation_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = aggregation_pool(&ctx);
+
+    let no_window = ctx.client.try_set_price_aggregation(
+        &ctx.operator,
+        &pool_id,
+        &Some(PriceAggregation {
+            window: 0,
+            ..twap()
+        }),
+    );
+    assert_eq!(no_window, Err(Ok(PredifiError::InvalidData)));
+
+    let no_quorum = ctx
+        .client
+        .try_set_price_aggregation(&ctx.operator, &pool_id, &Some(median(0)));
+    assert_eq!(no_quorum, Err(Ok(PredifiError::InvalidData)));
+
+    let not_operator = ctx
+        .client
+        .try_set_price_aggregation(&ctx.creator, &pool_id, &Some(twap()));
+    assert_eq!(not_operator, Err(Ok(PredifiError::Unauthorized)));
+
+    ctx.client
+        .set_price_aggregation(&ctx.operator, &pool_id, &Some(twap()));
+    assert_eq!(ctx.client.get_price_aggregation(&pool_id), Some(twap()));
+    ctx.client
+        .set_price_aggregation(&ctx.operator, &pool_id, &None);
+    assert_eq!(ctx.client.get_price_aggregation(&pool_id), None);
+}
@@ -0,0 +1,500 @@
+//! LMSR pool domain: quoting, buying and selling outcome shares against the
+//! market maker of a `PricingMode::Lmsr` pool, redeeming them once the pool
+//! is settled, and returning the market maker's left-over subsidy to the
+//! creator. Pricing math lives in [`crate::lmsr`].
+
+use soroban_sdk::{contractimpl, token, Address, Env, Vec};
+
+use crate::lmsr::{self, MAX_LMSR_OUTCOMES};
+use crate::{
+    AmmPosition, AmmSettlement, AmmState, AmmSurplusClaimedEvent, AmmTradeEvent, DataKey, Invite,
+    MarketState, Pool, PoolKey, PredifiContract, PredifiContractArgs, PredifiContractClient,
+    PredifiError, PricingMode, WinningsClaimedEvent,
+};
+
+#[contractimpl]
+impl PredifiContract {
+    /// Quote the cost, in the pool token, of buying `shares` of `outcome`.
+    ///
+    /// # Errors
+    /// - `InvalidPoolState` – not an `Lmsr` pool.
+    /// - `InvalidOutcome` – outcome is out of range.
+    /// - `InvalidAmount` – `shares` is not positive.
+    pub fn quote_buy_shares(
+        env: Env,
+        pool_id: u64,
+        outcome: u32,
+        shares: i128,
+    ) -> Result<i128, PredifiError> {
+        let (pool, state) = Self::load_amm(&env, pool_id)?;
+        Self::amm_buy_cost(&pool, &state, outcome, shares)
+    }
+
+    /// Quote the tokens received for selling `shares` of `outcome`.
+    ///
+    /// # Errors
+    /// - `InvalidPoolState` – not an `Lmsr` pool.
+    /// - `InvalidOutcome` – outcome is out of range.
+    /// - `InvalidAmount` – `shares` is not positive or exceeds the outstanding shares.
+    pub fn quote_sell_shares(
+        env: Env,
+        pool_id: u64,
+        outcome: u32,
+        shares: i128,
+    ) -> Result<i128, PredifiError> {
+        let (pool, state) = Self::load_amm(&env, pool_id)?;
+        Self::amm_sell_proceeds(&pool, &state, outcome, shares)
+    }
+
+    /// Current price of every outcome in basis points (implied probability).
+    pub fn get_amm_prices(env: Env, pool_id: u64) -> Result<Vec<u32>, PredifiError> {
+        let (_, state) = Self::load_amm(&env, pool_id)?;
+        let (buf, len) = Self::amm_shares_buf(&state);
+        let mut prices = Vec::new(&env);
+        for i in 0..len {
+            let price = lmsr::price_bps(&buf[..len], state.liquidity, i)
+                .map_err(|_| PredifiError::ArithmeticError)?;
+            prices.push_back(price);
+        }
+        Ok(prices)
+    }
+
+    /// Return a user's share holdings in an `Lmsr` pool, if any.
+    pub fn get_amm_position(env: Env, user: Address, pool_id: u64) -> Option<AmmPosition> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AmmPos(user, pool_id))
+    }
+
+    /// Buy `shares` of `outcome` at the market maker's current price.
+    /// Returns the tokens paid.
+    ///
+    /// Each share pays out one token base unit if `outcome` wins. The trade
+    /// fails instead of paying more than `max_cost`. `invite` grants access to
+    /// a private pool, as in [`Self::place_prediction`].
+    ///
+    /// # Errors
+    /// - `InvalidPoolState` – not an `Lmsr` pool, or the pool is not `Active`.
+    /// - `TimeConstraintError` – the pool's `end_time` has passed.
+    /// - `TokenNotWhitelisted` – the pool token was delisted.
+    /// - `Unauthorized` – the pool is private and `user` has no access.
+    /// - `InvalidOutcome` – outcome is out of range.
+    /// - `InvalidAmount` – `shares` is not positive, or the cost exceeds `max_cost`.
+    /// - `MaxTotalStakeExceeded` – the pool's `max_total_stake` would be exceeded.
+    pub fn buy_shares(
+        env: Env,
+        user: Address,
+        pool_id: u64,
+        outcome: u32,
+        shares: i128,
+        max_cost: i128,
+        invite: Option<Invite>,
+    ) -> Result<i128, PredifiError> {
+        Self::require_not_paused(&env)?;
+        user.require_auth();
+
+        let (mut pool, mut state) = Self::load_amm(&env, pool_id)?;
+        Self::require_amm_trading(&env, &pool)?;
+        if !Self::is_token_whitelisted(&env, &pool.token) {
+            return Err(PredifiError::TokenNotWhitelisted);
+        }
+        if pool.private {
+            Self::require_private_access(&env, pool_id, &pool, &user, &invite)?;
+        }
+
+        let cost = Self::amm_buy_cost(&pool, &state, outcome, shares)?;
+        if cost > max_cost {
+            return Err(PredifiError::InvalidAmount);
+        }
+        pool.total_stake = pool
+            .total_stake
+            .checked_add(cost)
+            .ok_or(PredifiError::ArithmeticError)?;
+        if pool.max_total_stake > 0 && pool.total_stake > pool.max_total_stake {
+            return Err(PredifiError::MaxTotalStakeExceeded);
+        }
+
+        let outstanding = state.shares.get(outcome).unwrap_or(0);
+        state.shares.set(outcome, outstanding + shares);
+
+        let pos_key = DataKey::AmmPos(user.clone(), pool_id);
+        let mut position = match env.storage().persistent().get::<_, AmmPosition>(&pos_key) {
+            Some(position) => position,
+            None => {
+                pool.participants_count = pool.participants_count.saturating_add(1);
+                let mut empty = Vec::new(&env);
+                for _ in 0..pool.options_count {
+                    empty.push_back(0i128);
+                }
+                AmmPosition {
+                    shares: empty,
+                    cost_basis: 0,
+                }
+            }
+        };
+        let held = position.shares.get(outcome).unwrap_or(0);
+        position.shares.set(outcome, held + shares);
+        position.cost_basis += cost;
+
+        Self::save_amm_trade(&env, pool_id, &pool, &state, &pos_key, &position);
+
+        Self::enter_reentrancy_guard(&env);
+        let token_client = token::Client::new(&env, &pool.token);
+        token_client.transfer(&user, env.current_contract_address(), &cost);
+        Self::exit_reentrancy_guard(&env);
+
+        Self::publish_amm_trade(&env, pool_id, &state, user, outcome, shares, cost)?;
+
+        Ok(cost)
+    }
+
+    /// Sell `shares` of `outcome` back to the market maker. Returns the
+    /// tokens received. The trade fails instead of paying less than
+    /// `min_proceeds`.
+    ///
+    /// # Errors
+    /// - `InvalidPoolState` – not an `Lmsr` pool, or the pool is not `Active`.
+    /// - `TimeConstraintError` – the pool's `end_time` has passed.
+    /// - `InvalidOutcome` – outcome is out of range.
+    /// - `InsufficientBalance` – `user` holds fewer than `shares`.
+    /// - `InvalidAmount` – `shares` is not positive, or the proceeds are
+    ///   below `min_proceeds`.
+    pub fn sell_shares(
+        env: Env,
+        user: Address,
+        pool_id: u64,
+        outcome: u32,
+        shares: i128,
+        min_proceeds: i128,
+    ) -> Result<i128, PredifiError> {
+        Self::require_not_paused(&env)?;
+        user.require_auth();
+
+        let (mut pool, mut state) = Self::load_amm(&env, pool_id)?;
+        Self::require_amm_trading(&env, &pool)?;
+
+        let pos_key = DataKey::AmmPos(user.clone(), pool_id);
+        let mut position: AmmPosition = env
+            .storage()
+            .persistent()
+            .get(&pos_key)
+            .ok_or(PredifiError::InsufficientBalance)?;
+        let held = position.shares.get(outcome).unwrap_or(0);
+        if shares > held {
+            return Err(PredifiError::InsufficientBalance);
+        }
+
+        let proceeds = Self::amm_sell_proceeds(&pool, &state, outcome, shares)?;
+        if proceeds < min_proceeds {
+            return Err(PredifiError::InvalidAmount);
+        }
+
+        let outstanding = state.shares.get(outcome).unwrap_or(0);
+        state.shares.set(outcome, outstanding - shares);
+        position.shares.set(outcome, held - shares);
+        position.cost_basis -= proceeds;
+        pool.total_stake -= proceeds;
+
+        Self::save_amm_trade(&env, pool_id, &pool, &state, &pos_key, &position);
+
+        if proceeds > 0 {
+            Self::enter_reentrancy_guard(&env);
+            let token_client = token::Client::new(&env, &pool.token);
+            token_client.transfer(&env.current_contract_address(), &user, &proceeds);
+            Self::exit_reentrancy_guard(&env);
+        }
+
+        Self::publish_amm_trade(&env, pool_id, &state, user, outcome, -shares, proceeds)?;
+
+        Ok(proceeds)
+    }
+
+    /// Redeem a user's shares once an `Lmsr` pool is settled. Returns the
+    /// amount paid out.
+    ///
+    /// - `Resolved`: one token unit per share of the winning outcome.
+    /// - `Canceled`: the user's net cost basis is refunded, capped at what
+    ///   their shares are worth at the pool's final prices.
+    ///
+    /// No payout exceeds what the pool still holds. Users without a position
+    /// receive `0`.
+    ///
+    /// # Errors
+    /// - `InvalidPoolState` – not an `Lmsr` pool.
+    /// - `PoolNotResolved` – the pool is still `Active`.
+    /// - `PoolDisputed` / `ChallengeWindowOpen` – see `claim_winnings`.
+    /// - `AlreadyClaimed` – the user already redeemed this pool.
+    pub fn claim_amm_payout(env: Env, user: Address, pool_id: u64) -> Result<i128, PredifiError> {
+        Self::require_not_paused(&env)?;
+        user.require_auth();
+
+        let (pool, state) = Self::load_amm(&env, pool_id)?;
+        Self::require_amm_settled(&env, pool_id, &pool)?;
+
+        let claimed_key = DataKey::Claimed(user.clone(), pool_id);
+        if env.storage().persistent().has(&claimed_key) {
+            return Err(PredifiError::AlreadyClaimed);
+        }
+
+        let Some(position) = env
+            .storage()
+            .persistent()
+            .get::<_, AmmPosition>(&DataKey::AmmPos(user.clone(), pool_id))
+        else {
+            return Ok(0);
+        };
+        let mut settlement = Self::load_amm_settlement(&env, pool_id, &pool, &state)?;
+        let value = Self::amm_position_value(&pool, &state, &position.shares)?;
+        let entitled = if pool.state == MarketState::Resolved {
+            value
+        } else {
+            position.cost_basis.clamp(0, value)
+        };
+        let payout = entitled.min(settlement.balance);
+        settlement.balance -= payout;
+        settlement.reserved = (settlement.reserved - value).max(0);
+        Self::save_amm_settlement(&env, pool_id, &settlement);
+
+        env.storage().persistent().set(&claimed_key, &true);
+        Self::extend_persistent(&env, &claimed_key);
+
+        if payout > 0 {
+            Self::enter_reentrancy_guard(&env);
+            let token_client = token::Client::new(&env, &pool.token);
+            token_client.transfer(&env.current_contract_address(), &user, &payout);
+            Self::exit_reentrancy_guard(&env);
+        }
+
+        WinningsClaimedEvent {
+            pool_id,
+            user,
+            amount: payout,
+        }
+        .publish(&env);
+
+        Ok(payout)
+    }
+
+    /// Send the creator of a settled `Lmsr` pool what the pool holds beyond
+    /// what unredeemed positions can still claim: the market maker's left-over
+    /// subsidy and trading surplus. Requires the creator's authorization and
+    /// can be called again as positions are redeemed. Returns the amount sent.
+    ///
+    /// # Errors
+    /// - `InvalidPoolState` – not an `Lmsr` pool.
+    /// - `PoolNotResolved` – the pool is still `Active`.
+    /// - `PoolDisputed` / `ChallengeWindowOpen` – see `claim_winnings`.
+    /// - `InsufficientBalance` – nothing is left to reclaim.
+    pub fn claim_amm_surplus(env: Env, pool_id: u64) -> Result<i128, PredifiError> {
+        Self::require_not_paused(&env)?;
+
+        let (pool, state) = Self::load_amm(&env, pool_id)?;
+        pool.creator.require_auth();
+        Self::require_amm_settled(&env, pool_id, &pool)?;
+
+        let mut settlement = Self::load_amm_settlement(&env, pool_id, &pool, &state)?;
+        let surplus = settlement.balance - settlement.reserved;
+        if surplus <= 0 {
+            return Err(PredifiError::InsufficientBalance);
+        }
+        settlement.balance = settlement.reserved;
+        Self::save_amm_settlement(&env, pool_id, &settlement);
+
+        Self::enter_reentrancy_guard(&env);
+        let token_client = token::Client::new(&env, &pool.token);
+        token_client.transfer(&env.current_contract_address(), &pool.creator, &surplus);
+        Self::exit_reentrancy_guard(&env);
+
+        AmmSurplusClaimedEvent {
+            pool_id,
+            creator: pool.creator,
+            amount: surplus,
+        }
+        .publish(&env);
+
+        Ok(surplus)
+    }
+
+    /// Load an `Lmsr` pool together with its market maker state.
+    fn load_amm(env: &Env, pool_id: u64) -> Result<(Pool, AmmState), PredifiError> {
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.pricing_mode != PricingMode::Lmsr {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        let state: AmmState = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AmmState(pool_id))
+            .ok_or(PredifiError::InvalidPoolState)?;
+        Ok((pool, state))
+    }
+
+    /// Redemptions open once the pool is canceled, or resolved and past its
+    /// challenge window.
+    fn require_amm_settled(env: &Env, pool_id: u64, pool: &Pool) -> Result<(), PredifiError> {
+        match pool.state {
+            MarketState::Resolved => Self::require_challenge_window_elapsed(env, pool_id, pool),
+            MarketState::Canceled => Ok(()),
+            MarketState::Disputed => Err(PredifiError::PoolDisputed),
+            MarketState::Active => Err(PredifiError::PoolNotResolved),
+        }
+    }
+
+    /// What `shares` are worth once the pool is settled: the winning
+    /// outcome's shares if resolved, or their value at the final prices if
+    /// canceled (rounded down).
+    fn amm_position_value(
+        pool: &Pool,
+        state: &AmmState,
+        shares: &Vec<i128>,
+    ) -> Result<i128, PredifiError> {
+        if pool.state == MarketState::Resolved {
+            return Ok(shares.get(pool.outcome).unwrap_or(0));
+        }
+        let (buf, len) = Self::amm_shares_buf(state);
+        let mut value_bps = 0i128;
+        for (i, held) in shares.iter().enumerate().take(len) {
+            let price = lmsr::price_bps(&buf[..len], state.liquidity, i)
+                .map_err(|_| PredifiError::ArithmeticError)?;
+            value_bps = held
+                .checked_mul(price as i128)
+                .and_then(|v| value_bps.checked_add(v))
+                .ok_or(PredifiError::ArithmeticError)?;
+        }
+        Ok(value_bps / 10_000)
+    }
+
+    /// The pool's settlement record, created on first use from the pool's
+    /// holdings and the value of every outstanding share.
+    ///
+    /// Prices are rounded down, so the outstanding shares are never worth
+    /// more than `C(q)`, which the pool always holds.
+    fn load_amm_settlement(
+        env: &Env,
+        pool_id: u64,
+        pool: &Pool,
+        state: &AmmState,
+    ) -> Result<AmmSettlement, PredifiError> {
+        if let Some(settlement) = env
+            .storage()
+            .persistent()
+            .get(&PoolKey::AmmSettlement(pool_id))
+        {
+            return Ok(settlement);
+        }
+        let reserved = Self::amm_position_value(pool, state, &state.shares)?;
+        Ok(AmmSettlement {
+            balance: pool.total_stake,
+            reserved: reserved.min(pool.total_stake),
+        })
+    }
+
+    fn save_amm_settlement(env: &Env, pool_id: u64, settlement: &AmmSettlement) {
+        let key = PoolKey::AmmSettlement(pool_id);
+        env.storage().persistent().set(&key, settlement);
+        Self::extend_persistent(env, &key);
+    }
+
+    /// Trading is open while the pool is `Active` and before `end_time`.
+    fn require_amm_trading(env: &Env, pool: &Pool) -> Result<(), PredifiError> {
+        if !Self::is_pool_active(pool) {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if env.ledger().timestamp() >= pool.end_time {
+            return Err(PredifiError::TimeConstraintError);
+        }
+        Ok(())
+    }
+
+    /// Copy outstanding shares into a fixed buffer for the `lmsr` math.
+    fn amm_shares_buf(state: &AmmState) -> ([i128; MAX_LMSR_OUTCOMES], usize) {
+        let mut buf = [0i128; MAX_LMSR_OUTCOMES];
+        let len = (state.shares.len() as usize).min(MAX_LMSR_OUTCOMES);
+        for (i, slot) in buf.iter_mut().enumerate().take(len) {
+            *slot = state.shares.get(i as u32).unwrap_or(0);
+        }
+        (buf, len)
+    }
+
+    fn amm_buy_cost(
+        pool: &Pool,
+        state: &AmmState,
+        outcome: u32,
+        shares: i128,
+    ) -> Result<i128, PredifiError> {
+        if outcome >= pool.options_count {
+            return Err(PredifiError::InvalidOutcome);
+        }
+        if shares <= 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+        let (buf, len) = Self::amm_shares_buf(state);
+        lmsr::buy_cost(&buf[..len], state.liquidity, outcome as usize, shares)
+            .map_err(|_| PredifiError::ArithmeticError)
+    }
+
+    fn amm_sell_proceeds(
+        pool: &Pool,
+        state: &AmmState,
+        outcome: u32,
+        shares: i128,
+    ) -> Result<i128, PredifiError> {
+        if outcome >= pool.options_count {
+            return Err(PredifiError::InvalidOutcome);
+        }
+        if shares <= 0 || shares > state.shares.get(outcome).unwrap_or(0) {
+            return Err(PredifiError::InvalidAmount);
+        }
+        let (buf, len) = Self::amm_shares_buf(state);
+        lmsr::sell_proceeds(&buf[..len], state.liquidity, outcome as usize, shares)
+            .map_err(|_| PredifiError::ArithmeticError)
+    }
+
+    fn save_amm_trade(
+        env: &Env,
+        pool_id: u64,
+        pool: &Pool,
+        state: &AmmState,
+        pos_key: &DataKey,
+        position: &AmmPosition,
+    ) {
+        let pool_key = DataKey::Pool(pool_id);
+        env.storage().persistent().set(&pool_key, pool);
+        Self::bump_ttl(env, &pool_key);
+
+        let amm_key = DataKey::AmmState(pool_id);
+        env.storage().persistent().set(&amm_key, state);
+        Self::extend_persistent(env, &amm_key);
+
+        env.storage().persistent().set(pos_key, position);
+        Self::extend_persistent(env, pos_key);
+    }
+
+    fn publish_amm_trade(
+        env: &Env,
+        pool_id: u64,
+        state: &AmmState,
+        trader: Address,
+        outcome: u32,
+        shares: i128,
+        amount: i128,
+    ) -> Result<(), PredifiError> {
+        let (buf, len) = Self::amm_shares_buf(state);
+        let price_bps = lmsr::price_bps(&buf[..len], state.liquidity, outcome as usize)
+            .map_err(|_| PredifiError::ArithmeticError)?;
+        AmmTradeEvent {
+            pool_id,
+            trader,
+            outcome,
+            shares,
+            amount,
+            price_bps,
+        }
+        .publish(env);
+        Ok(())
+    }
+}
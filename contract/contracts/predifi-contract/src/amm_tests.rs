@@ -0,0 +1,265 @@
+//! LMSR pools: share trading against the market maker, slippage limits and
+//! redemption once the pool resolves or is canceled.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{Invite, PoolConfig, PredifiError, PricingMode};
+use soroban_sdk::{symbol_short, testutils::Ledger, vec, Env, String, Symbol};
+
+const END_TIME: u64 = 10_000;
+const LIQUIDITY: i128 = 100_000;
+
+fn lmsr_config(env: &Env, initial_liquidity: i128) -> PoolConfig {
+    PoolConfig {
+        initial_liquidity,
+        pricing_mode: PricingMode::Lmsr,
+        ..default_pool_config(env)
+    }
+}
+
+/// Fund the creator with `config.initial_liquidity` and create the pool.
+fn seeded_pool(ctx: &TestContext, config: &PoolConfig) -> u64 {
+    ctx.token_admin_client
+        .mint(&ctx.creator, &config.initial_liquidity);
+    ctx.create_pool(END_TIME, config)
+}
+
+fn lmsr_pool(ctx: &TestContext) -> u64 {
+    seeded_pool(ctx, &lmsr_config(&ctx.client.env, LIQUIDITY))
+}
+
+/// Buying at the quoted price moves the price; selling back refunds no
+/// more than was paid.
+#[test]
+fn test_buy_and_sell_shares() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = lmsr_pool(&ctx);
+    let alice = ctx.funded_user(1_000_000);
+
+    let prices = ctx.client.get_amm_prices(&pool_id);
+    assert_eq!(prices, vec![&env, 5_000u32, 5_000u32]);
+
+    let quote = ctx.client.quote_buy_shares(&pool_id, &1u32, &50_000);
+    let paid = ctx
+        .client
+        .buy_shares(&alice, &pool_id, &1u32, &50_000, &quote, &None);
+    assert_eq!(paid, quote);
+    assert!(paid > 25_000 && paid < 50_000);
+    assert_eq!(ctx.token.balance(&alice), 1_000_000 - paid);
+    assert!(ctx.client.get_amm_prices(&pool_id).get(1).unwrap() > 5_000);
+
+    let pool = ctx.client.get_pool(&pool_id);
+    assert_eq!(pool.total_stake, LIQUIDITY + paid);
+    assert_eq!(pool.participants_count, 1);
+
+    let received = ctx.client.sell_shares(&alice, &pool_id, &1u32, &50_000, &0);
+    assert!(received <= paid);
+    assert_eq!(ctx.token.balance(&alice), 1_000_000 - paid + received);
+    let position = ctx.client.get_amm_position(&alice, &pool_id).unwrap();
+    assert_eq!(position.shares, vec![&env, 0i128, 0i128]);
+}
+
+/// Trades that would breach the caller's price limits are rejected.
+#[test]
+fn test_slippage_limits() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = lmsr_pool(&ctx);
+    let alice = ctx.funded_user(1_000_000);
+
+    let quote = ctx.client.quote_buy_shares(&pool_id, &0u32, &10_000);
+    let too_cheap =
+        ctx.client
+            .try_buy_shares(&alice, &pool_id, &0u32, &10_000, &(quote - 1), &None);
+    assert_eq!(too_cheap, Err(Ok(PredifiError::InvalidAmount)));
+
+    ctx.client
+        .buy_shares(&alice, &pool_id, &0u32, &10_000, &quote, &None);
+    let greedy = ctx
+        .client
+        .try_sell_shares(&alice, &pool_id, &0u32, &10_000, &quote);
+    assert_eq!(greedy, Err(Ok(PredifiError::InvalidAmount)));
+
+    let oversell = ctx
+        .client
+        .try_sell_shares(&alice, &pool_id, &0u32, &10_001, &0);
+    assert_eq!(oversell, Err(Ok(PredifiError::InsufficientBalance)));
+}
+
+/// Winning shares redeem one token unit each after resolution.
+#[test]
+fn test_resolved_pool_redeems_winning_shares() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = lmsr_pool(&ctx);
+    let alice = ctx.funded_user(1_000_000);
+    let bob = ctx.funded_user(1_000_000);
+
+    ctx.client
+        .buy_shares(&alice, &pool_id, &1u32, &80_000, &i128::MAX, &None);
+    ctx.client
+        .buy_shares(&bob, &pool_id, &0u32, &30_000, &i128::MAX, &None);
+    let alice_balance = ctx.token.balance(&alice);
+
+    let early = ctx.client.try_claim_amm_payout(&alice, &pool_id);
+    assert_eq!(early, Err(Ok(PredifiError::PoolNotResolved)));
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &1u32);
+
+    assert_eq!(ctx.client.claim_amm_payout(&alice, &pool_id), 80_000);
+    assert_eq!(ctx.token.balance(&alice), alice_balance + 80_000);
+    assert_eq!(ctx.client.claim_amm_payout(&bob, &pool_id), 0);
+
+    let twice = ctx.client.try_claim_amm_payout(&alice, &pool_id);
+    assert_eq!(twice, Err(Ok(PredifiError::AlreadyClaimed)));
+}
+
+/// A canceled pool refunds each trader's net cost.
+#[test]
+fn test_canceled_pool_refunds_cost_basis() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = lmsr_pool(&ctx);
+    let alice = ctx.funded_user(1_000_000);
+
+    let paid = ctx
+        .client
+        .buy_shares(&alice, &pool_id, &1u32, &20_000, &i128::MAX, &None);
+    ctx.client
+        .cancel_pool(&ctx.operator, &pool_id, &String::from_str(&env, "void"));
+
+    assert_eq!(ctx.client.claim_amm_payout(&alice, &pool_id), paid);
+    assert_eq!(ctx.token.balance(&alice), 1_000_000);
+}
+
+/// Wash trading cannot inflate a canceled pool's refunds: one trader keeps
+/// the profits of selling into the other's buys, and the other's cost basis
+/// grows with every cycle, yet refunds stay within what the pool holds and
+/// never touch other pools' funds.
+#[test]
+fn test_wash_trading_cannot_drain_canceled_pool() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = lmsr_pool(&ctx);
+    let alice = ctx.funded_user(1_000_000);
+    let bob = ctx.funded_user(1_000_000);
+
+    // Another pool's stake sits in the same contract.
+    let bystander = ctx.funded_user(1_000_000);
+    let other_pool = lmsr_pool(&ctx);
+    let bystander_cost =
+        ctx.client
+            .buy_shares(&bystander, &other_pool, &0u32, &10_000, &i128::MAX, &None);
+
+    for _ in 0..10 {
+        ctx.client
+            .buy_shares(&alice, &pool_id, &1u32, &50_000, &i128::MAX, &None);
+        ctx.client
+            .buy_shares(&bob, &pool_id, &1u32, &200_000, &i128::MAX, &None);
+        ctx.client.sell_shares(&alice, &pool_id, &1u32, &50_000, &0);
+        ctx.client.sell_shares(&bob, &pool_id, &1u32, &200_000, &0);
+    }
+    let held = ctx.client.get_pool(&pool_id).total_stake;
+    let bob_basis = ctx
+        .client
+        .get_amm_position(&bob, &pool_id)
+        .unwrap()
+        .cost_basis;
+    assert!(bob_basis > held);
+
+    ctx.client
+        .cancel_pool(&ctx.operator, &pool_id, &String::from_str(&env, "void"));
+    let refunds =
+        ctx.client.claim_amm_payout(&bob, &pool_id) + ctx.client.claim_amm_payout(&alice, &pool_id);
+    let surplus = ctx.client.claim_amm_surplus(&pool_id);
+    assert!(refunds <= held);
+    assert_eq!(refunds + surplus, held);
+    assert_eq!(
+        ctx.token.balance(&ctx.client.address),
+        LIQUIDITY + bystander_cost
+    );
+}
+
+/// The creator reclaims the left-over subsidy once winners are covered, and
+/// again as positions are redeemed.
+#[test]
+fn test_creator_reclaims_surplus() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = lmsr_pool(&ctx);
+    let alice = ctx.funded_user(1_000_000);
+
+    let paid = ctx
+        .client
+        .buy_shares(&alice, &pool_id, &1u32, &30_000, &i128::MAX, &None);
+    let early = ctx.client.try_claim_amm_surplus(&pool_id);
+    assert_eq!(early, Err(Ok(PredifiError::PoolNotResolved)));
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &1u32);
+    let surplus = ctx.client.claim_amm_surplus(&pool_id);
+    assert_eq!(surplus, LIQUIDITY + paid - 30_000);
+    let nothing_left = ctx.client.try_claim_amm_surplus(&pool_id);
+    assert_eq!(nothing_left, Err(Ok(PredifiError::InsufficientBalance)));
+
+    assert_eq!(ctx.client.claim_amm_payout(&alice, &pool_id), 30_000);
+    assert_eq!(ctx.token.balance(&ctx.client.address), 0);
+}
+
+/// LMSR pools need seed liquidity and do not accept parimutuel stakes.
+#[test]
+fn test_lmsr_pool_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = lmsr_pool(&ctx);
+    let alice = ctx.funded_user(1_000_000);
+
+    let unfunded = ctx.client.try_create_pool(
+        &ctx.creator,
+        &END_TIME,
+        &ctx.token_address,
+        &2u32,
+        &symbol_short!("Tech"),
+        &lmsr_config(&env, 0),
+    );
+    assert_eq!(unfunded, Err(Ok(PredifiError::InvalidData)));
+
+    let stake = ctx
+        .client
+        .try_place_prediction(&alice, &pool_id, &100, &1u32, &None, &None);
+    assert!(stake.is_err());
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME);
+    let closed = ctx
+        .client
+        .try_buy_shares(&alice, &pool_id, &1u32, &100, &i128::MAX, &None);
+    assert_eq!(closed, Err(Ok(PredifiError::TimeConstraintError)));
+}
+
+/// Buying into a private LMSR pool needs a whitelist entry or an invite.
+#[test]
+fn test_private_pool_buy_needs_access() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let alice = ctx.funded_user(1_000_000);
+    let key = Symbol::new(&env, "TRADEDESK");
+    let config = PoolConfig {
+        private: true,
+        whitelist_key: Some(key.clone()),
+        ..lmsr_config(&env, LIQUIDITY)
+    };
+    let pool_id = seeded_pool(&ctx, &config);
+
+    let uninvited = ctx
+        .client
+        .try_buy_shares(&alice, &pool_id, &1u32, &1_000, &i128::MAX, &None);
+    assert_eq!(uninvited, Err(Ok(PredifiError::Unauthorized)));
+    let invite = Some(Invite::Key(key));
+    ctx.client
+        .buy_shares(&alice, &pool_id, &1u32, &1_000, &i128::MAX, &invite);
+    let position = ctx.client.get_amm_position(&alice, &pool_id).unwrap();
+    assert_eq!(position.shares, vec![&env, 0i128, 1_000i128]);
+}
@@ -0,0 +1,240 @@
+//! N-of-M approval of sensitive admin actions: thresholds, expiry of stale
+//! proposals, and changes to the approval policy itself.
+
+#![cfg(test)]
+
+use crate::test::ROLE_ADMIN;
+use crate::test_utils::{default_pool_config, TestContext, START_TIME};
+use crate::{
+    ApprovalPolicy, MarketState, PoolConfig, PredifiError, SensitiveAction, SensitiveActionKind,
+    DEFAULT_APPROVAL_EXPIRY_SECONDS, EMERGENCY_CANCEL_MULTISIG_THRESHOLD,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, String,
+};
+
+const EXPIRY: u64 = 3_600;
+
+/// Three admins, with `pause` and `emergency_withdraw` proposals expiring
+/// after `EXPIRY`; both keep the default 2-of-3 threshold. Returns `admins`.
+fn approval_admins(ctx: &TestContext) -> [Address; 3] {
+    let admins = [
+        ctx.admin.clone(),
+        ctx.grant(ROLE_ADMIN),
+        ctx.grant(ROLE_ADMIN),
+    ];
+    for kind in [
+        SensitiveActionKind::Pause,
+        SensitiveActionKind::EmergencyWithdraw,
+    ] {
+        ctx.client
+            .set_approval_policy(&admins[0], &kind, &2u32, &EXPIRY);
+        ctx.client
+            .set_approval_policy(&admins[1], &kind, &2u32, &EXPIRY);
+    }
+    admins
+}
+
+/// A second admin's call completes the pause; the same admin cannot approve
+/// twice.
+#[test]
+fn test_pause_waits_for_threshold() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let admins = approval_admins(&ctx);
+    let [a, b, _] = &admins;
+
+    ctx.client.pause(a);
+    assert!(!ctx.client.is_contract_paused());
+    let request = ctx
+        .client
+        .get_approval_request(&SensitiveAction::Pause)
+        .unwrap();
+    assert_eq!(request.approvals, vec![&env, a.clone()]);
+    assert_eq!(request.expires_at, START_TIME + EXPIRY);
+
+    let twice = ctx.client.try_pause(a);
+    assert_eq!(
+        twice,
+        Err(Ok(PredifiError::EmergencyCancelAlreadyApproved.into()))
+    );
+
+    ctx.client.pause(b);
+    assert!(ctx.client.is_contract_paused());
+    assert_eq!(
+        ctx.client.get_approval_request(&SensitiveAction::Pause),
+        None
+    );
+}
+
+/// Approvals count towards one exact withdrawal; different arguments start a
+/// separate proposal.
+#[test]
+fn test_emergency_withdraw_needs_matching_approvals() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let admins = approval_admins(&ctx);
+    let [a, b, _] = &admins;
+    let destination = Address::generate(&env);
+    ctx.token_admin_client.mint(&ctx.client.address, &1_000);
+
+    ctx.client
+        .emergency_withdraw(a, &ctx.token_address, &destination, &600);
+    ctx.client
+        .emergency_withdraw(b, &ctx.token_address, &destination, &500);
+    assert_eq!(ctx.token.balance(&destination), 0);
+
+    ctx.client
+        .emergency_withdraw(b, &ctx.token_address, &destination, &600);
+    assert_eq!(ctx.token.balance(&destination), 600);
+
+    let other = SensitiveAction::EmergencyWithdraw(ctx.token_address.clone(), destination, 500);
+    assert_eq!(
+        ctx.client
+            .get_approval_request(&other)
+            .unwrap()
+            .approvals
+            .len(),
+        1
+    );
+}
+
+/// A proposal short of its threshold goes stale; the next approval starts
+/// over, and an approver who lost the Admin role no longer counts.
+#[test]
+fn test_stale_and_revoked_approvals() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let admins = approval_admins(&ctx);
+    let [a, b, c] = &admins;
+
+    ctx.client.pause(a);
+    env.ledger()
+        .with_mut(|li| li.timestamp = START_TIME + EXPIRY);
+    assert_eq!(
+        ctx.client.get_approval_request(&SensitiveAction::Pause),
+        None
+    );
+
+    ctx.client.pause(b);
+    assert!(!ctx.client.is_contract_paused());
+    let request = ctx
+        .client
+        .get_approval_request(&SensitiveAction::Pause)
+        .unwrap();
+    assert_eq!(request.approvals, vec![&env, b.clone()]);
+
+    ctx.ac_client.revoke_role(b, &ROLE_ADMIN);
+    ctx.client.pause(c);
+    assert!(!ctx.client.is_contract_paused());
+    ctx.client.pause(a);
+    assert!(ctx.client.is_contract_paused());
+}
+
+/// Emergency cancellation runs on the same queue, with operators eligible.
+#[test]
+fn test_emergency_cancel_uses_approval_queue() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let operator2 = ctx.grant(1u32);
+
+    let config = PoolConfig {
+        description: String::from_str(&env, "Match winner"),
+        metadata_url: String::from_str(&env, "ipfs://approvals"),
+        outcome_descriptions: vec![
+            &env,
+            String::from_str(&env, "Home"),
+            String::from_str(&env, "Away"),
+        ],
+        ..default_pool_config(&env)
+    };
+    let pool_id = ctx.create_pool(10_000, &config);
+
+    let reason = String::from_str(&env, "oracle outage");
+    ctx.client
+        .emergency_cancel_pool(&ctx.operator, &pool_id, &reason);
+    assert_eq!(
+        ctx.client.get_emergency_cancel_approvals(&pool_id),
+        vec![&env, ctx.operator.clone()]
+    );
+    assert_eq!(ctx.client.get_pool(&pool_id).state, MarketState::Active);
+
+    ctx.client
+        .emergency_cancel_pool(&operator2, &pool_id, &reason);
+    assert_eq!(ctx.client.get_pool(&pool_id).state, MarketState::Canceled);
+    assert_eq!(ctx.client.get_emergency_cancel_approvals(&pool_id).len(), 0);
+}
+
+/// Lowering a threshold takes the current threshold of admins agreeing on
+/// the same policy.
+#[test]
+fn test_policy_change_needs_current_threshold() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let admins = approval_admins(&ctx);
+    let [a, b, _] = &admins;
+    let kind = SensitiveActionKind::EmergencyWithdraw;
+    let two_of_three = ctx.client.get_approval_policy(&kind);
+
+    ctx.client.set_approval_policy(a, &kind, &1u32, &EXPIRY);
+    assert_eq!(ctx.client.get_approval_policy(&kind), two_of_three);
+    let twice = ctx.client.try_set_approval_policy(a, &kind, &1u32, &EXPIRY);
+    assert_eq!(twice, Err(Ok(PredifiError::EmergencyCancelAlreadyApproved)));
+
+    ctx.client.set_approval_policy(b, &kind, &1u32, &EXPIRY);
+    assert_eq!(
+        ctx.client.get_approval_policy(&kind),
+        ApprovalPolicy {
+            threshold: 1,
+            expiry: EXPIRY,
+        }
+    );
+}
+
+/// Policies need a positive threshold and expiry, emergency cancellation
+/// keeps its multisig floor, changes are admin-only, and kinds without a
+/// stored policy fall back to the defaults.
+#[test]
+fn test_approval_policy_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let admins = approval_admins(&ctx);
+    let [a, _, _] = &admins;
+    let kind = SensitiveActionKind::EmergencyCancelPool;
+
+    assert_eq!(
+        ctx.client.get_approval_policy(&kind),
+        ApprovalPolicy {
+            threshold: EMERGENCY_CANCEL_MULTISIG_THRESHOLD,
+            expiry: DEFAULT_APPROVAL_EXPIRY_SECONDS,
+        }
+    );
+    assert_eq!(
+        ctx.client.get_approval_policy(&SensitiveActionKind::Pause),
+        ApprovalPolicy {
+            threshold: 2,
+            expiry: EXPIRY,
+        }
+    );
+
+    let zero = ctx.client.try_set_approval_policy(a, &kind, &0u32, &EXPIRY);
+    assert_eq!(zero, Err(Ok(PredifiError::InvalidData)));
+    let no_expiry = ctx.client.try_set_approval_policy(a, &kind, &2u32, &0u64);
+    assert_eq!(no_expiry, Err(Ok(PredifiError::InvalidData)));
+    let below_floor = ctx.client.try_set_approval_policy(
+        a,
+        &kind,
+        &(EMERGENCY_CANCEL_MULTISIG_THRESHOLD - 1),
+        &EXPIRY,
+    );
+    assert_eq!(below_floor, Err(Ok(PredifiError::InvalidData)));
+    let upgrade =
+        ctx.client
+            .try_set_approval_policy(a, &SensitiveActionKind::UpgradePolicy, &2u32, &EXPIRY);
+    assert_eq!(upgrade, Err(Ok(PredifiError::InvalidData)));
+    let not_admin = ctx
+        .client
+        .try_set_approval_policy(&ctx.operator, &kind, &1u32, &EXPIRY);
+    assert_eq!(not_admin, Err(Ok(PredifiError::Unauthorized)));
+}
@@ -0,0 +1,89 @@
+//! Approvals domain: N-of-M sign-off for high-risk admin actions.
+//!
+//! `pause`, `emergency_withdraw` and `emergency_cancel_pool` each record the
+//! caller's approval of the exact action requested and only take effect once
+//! the action's [`ApprovalPolicy`] threshold is met. A proposal that stays
+//! short of its threshold past `expires_at` is discarded by the next
+//! approval, which starts a fresh one. Changes to a policy go through the
+//! same queue under the policy being changed.
+
+use soroban_sdk::{contractimpl, Address, Env};
+
+use crate::{
+    ApprovalKey, ApprovalPolicy, ApprovalPolicyUpdatedEvent, ApprovalRequest, PredifiContract,
+    PredifiContractArgs, PredifiContractClient, PredifiError, SensitiveAction, SensitiveActionKind,
+    EMERGENCY_CANCEL_MULTISIG_THRESHOLD,
+};
+
+#[contractimpl]
+impl PredifiContract {
+    /// Set the approval threshold and proposal lifetime for one kind of
+    /// sensitive action, or approve a pending change to exactly these values.
+    /// Only callable by Admin (role 0).
+    ///
+    /// The change goes through the approval queue under the current policy of
+    /// `kind`, so lowering a threshold takes as many admins as the action
+    /// itself. Returns without changing the policy while approvals are short.
+    /// Once applied it governs approvals recorded from then on, including
+    /// those for proposals already pending.
+    ///
+    /// # Errors
+    /// - [`PredifiError::Unauthorized`] – caller lacks Admin role (0).
+    /// - [`PredifiError::InvalidData`]  – `threshold` or `expiry` is zero,
+    ///   `kind` is `UpgradePolicy`, whose threshold follows the upgrade policy,
+    ///   or an `EmergencyCancelPool` threshold is below
+    ///   [`EMERGENCY_CANCEL_MULTISIG_THRESHOLD`].
+    /// - [`PredifiError::EmergencyCancelAlreadyApproved`] – caller already
+    ///   approved this change.
+    pub fn set_approval_policy(
+        env: Env,
+        admin: Address,
+        kind: SensitiveActionKind,
+        threshold: u32,
+        expiry: u64,
+    ) -> Result<(), PredifiError> {
+        admin.require_auth();
+        Self::require_admin_role(&env, &admin, "set_approval_policy")?;
+        if threshold == 0 || expiry == 0 || kind == SensitiveActionKind::UpgradePolicy {
+            return Err(PredifiError::InvalidData);
+        }
+        if kind == SensitiveActionKind::EmergencyCancelPool
+            && threshold < EMERGENCY_CANCEL_MULTISIG_THRESHOLD
+        {
+            return Err(PredifiError::InvalidData);
+        }
+        let action = SensitiveAction::SetApprovalPolicy(kind, threshold, expiry);
+        if Self::record_approval(&env, &admin, &action)?.is_some() {
+            return Ok(());
+        }
+
+        env.storage().instance().set(
+            &ApprovalKey::ApprovalPolicy(kind),
+            &ApprovalPolicy { threshold, expiry },
+        );
+        Self::extend_instance(&env);
+
+        ApprovalPolicyUpdatedEvent {
+            admin,
+            kind,
+            threshold,
+            expiry,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Return the approval policy for `kind`, or its defaults if none is set.
+    pub fn get_approval_policy(env: Env, kind: SensitiveActionKind) -> ApprovalPolicy {
+        Self::read_approval_policy(&env, kind)
+    }
+
+    /// Return the pending request for `action`, or `None` if nothing is
+    /// pending or the proposal has gone stale.
+    pub fn get_approval_request(env: Env, action: SensitiveAction) -> Option<ApprovalRequest> {
+        env.storage()
+            .persistent()
+            .get::<_, ApprovalRequest>(&ApprovalKey::ApprovalRequest(action))
+            .filter(|request| env.ledger().timestamp() < request.expires_at)
+    }
+}
@@ -9,7 +9,7 @@ mod benchmark_tests {
 
     extern crate std;
 
-    use crate::{PoolConfig, PredifiContract, PredifiContractClient};
+    use crate::{PoolConfig, PredifiContract, PredifiContractClient, PricingMode};
     use soroban_sdk::{
         symbol_short,
         testutils::{Address as _, Ledger},
@@ -117,6 +117,10 @@ mod benchmark_tests {
                 private: false,
                 whitelist_key: None,
                 outcome_descriptions,
+                optimistic_liveness: 0,
+                optimistic_bond: 0,
+                pricing_mode: PricingMode::Parimutuel,
+                creator_fee_bps: 0,
             },
         );
         let budget_create = env.cost_estimate().budget().cpu_instruction_cost();
@@ -185,6 +189,10 @@ mod benchmark_tests {
                 private: false,
                 whitelist_key: None,
                 outcome_descriptions,
+                optimistic_liveness: 0,
+                optimistic_bond: 0,
+                pricing_mode: PricingMode::Parimutuel,
+                creator_fee_bps: 0,
             },
         );
         let create_cpu = env.cost_estimate().budget().cpu_instruction_cost();
@@ -246,6 +254,10 @@ mod benchmark_tests {
                     private: false,
                     whitelist_key: None,
                     outcome_descriptions: outcomes,
+                    optimistic_liveness: 0,
+                    optimistic_bond: 0,
+                    pricing_mode: PricingMode::Parimutuel,
+                    creator_fee_bps: 0,
                 },
             );
         }
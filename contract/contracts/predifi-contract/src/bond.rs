@@ -0,0 +1,184 @@
+//! Creation bond domain: an admin-configured, per-token bond that
+//! `create_pool` locks from the creator to make junk pools costly. The bond is
+//! returned once the pool resolves or is canceled, and slashed to the treasury
+//! when a moderator rejects the pool as spam or invalid.
+
+use soroban_sdk::{contractimpl, token, Address, Env, String};
+
+use crate::{
+    CreationBondReturnedEvent, CreationBondSetEvent, DataKey, MarketState, Pool, PoolCanceledEvent,
+    PoolKey, PoolRejectedEvent, PredifiContract, PredifiContractArgs, PredifiContractClient,
+    PredifiError, TreasuryKey,
+};
+
+#[contractimpl]
+impl PredifiContract {
+    /// Set the bond `create_pool` locks from creators of pools in `token`.
+    /// An `amount` of 0 disables the bond. Pools created earlier keep the
+    /// bond they locked. Caller must have Admin role (0).
+    ///
+    /// # Errors
+    /// - `Unauthorized` – caller lacks Admin role (0).
+    /// - `InvalidAmount` – `amount` is negative.
+    pub fn set_creation_bond(
+        env: Env,
+        admin: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        admin.require_auth();
+        Self::require_admin_role(&env, &admin, "set_creation_bond")?;
+        if amount < 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+
+        let key = TreasuryKey::CreationBond(token.clone());
+        if amount == 0 {
+            env.storage().instance().remove(&key);
+        } else {
+            env.storage().instance().set(&key, &amount);
+        }
+        Self::extend_instance(&env);
+
+        CreationBondSetEvent {
+            admin,
+            token,
+            amount,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Bond currently required to create a pool in `token` (0 if none).
+    pub fn get_creation_bond(env: Env, token: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&TreasuryKey::CreationBond(token))
+            .unwrap_or(0)
+    }
+
+    /// Bond still held for `pool_id` (0 once returned or slashed).
+    pub fn get_pool_creation_bond(env: Env, pool_id: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&PoolKey::CreationBond(pool_id))
+            .unwrap_or(0)
+    }
+
+    /// Reject an active pool as spam or invalid. Only callable by a
+    /// Moderator (role 2).
+    ///
+    /// The pool is canceled exactly like [`Self::cancel_pool`], so stakers
+    /// recover their stakes through `claim_refund`, and the creator's bond is
+    /// credited to the treasury's accrued fees for the pool token.
+    ///
+    /// # Errors
+    /// - `Unauthorized` – caller does not hold the Moderator role.
+    /// - `PoolNotFound` – `pool_id` does not exist.
+    /// - `InvalidPoolState` – the pool is not `Active`.
+    pub fn reject_pool(
+        env: Env,
+        moderator: Address,
+        pool_id: u64,
+        reason: String,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        moderator.require_auth();
+        Self::require_role(&env, &moderator, 2)?;
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .ok_or(PredifiError::PoolNotFound)?;
+        if !Self::is_pool_active(&pool) {
+            return Err(PredifiError::InvalidPoolState);
+        }
+
+        pool.state = MarketState::Canceled;
+        env.storage().persistent().set(&pool_key, &pool);
+        Self::bump_ttl(&env, &pool_key);
+        Self::remove_from_active_index(&env, pool_id);
+        Self::enter_reentrancy_guard(&env);
+        Self::settle_outcome_proposal(&env, pool_id, &pool)?;
+        Self::exit_reentrancy_guard(&env);
+        Self::credit_retained_exit_fees(&env, pool_id, &pool)?;
+
+        let bond_key = PoolKey::CreationBond(pool_id);
+        let bond_slashed: i128 = env.storage().persistent().get(&bond_key).unwrap_or(0);
+        env.storage().persistent().remove(&bond_key);
+        Self::credit_accrued_fees(&env, &pool.token, bond_slashed)?;
+
+        PoolCanceledEvent {
+            pool_id,
+            caller: moderator.clone(),
+            reason: reason.clone(),
+            operator: moderator.clone(),
+        }
+        .publish(&env);
+        PoolRejectedEvent {
+            pool_id,
+            moderator,
+            reason,
+            bond_slashed,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Return the creation bond of `pool_id` to its creator once the pool
+    /// has resolved or been canceled. Requires the creator's authorization.
+    ///
+    /// Returns the amount transferred.
+    ///
+    /// # Errors
+    /// - `PoolNotFound` – `pool_id` does not exist.
+    /// - `PoolNotResolved` – the pool is neither `Resolved` nor `Canceled`.
+    /// - `InsufficientBalance` – no bond is held for the pool: none was
+    ///   locked, it was already returned, or it was slashed by `reject_pool`.
+    pub fn reclaim_creation_bond(env: Env, pool_id: u64) -> Result<i128, PredifiError> {
+        Self::require_not_paused(&env)?;
+
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        pool.creator.require_auth();
+        if !matches!(pool.state, MarketState::Resolved | MarketState::Canceled) {
+            return Err(PredifiError::PoolNotResolved);
+        }
+
+        let bond_key = PoolKey::CreationBond(pool_id);
+        let amount: i128 = env.storage().persistent().get(&bond_key).unwrap_or(0);
+        if amount <= 0 {
+            return Err(PredifiError::InsufficientBalance);
+        }
+        env.storage().persistent().remove(&bond_key);
+
+        Self::validate_token_transfer(
+            &env,
+            &pool.token,
+            &env.current_contract_address(),
+            &pool.creator,
+            amount,
+        )?;
+        Self::enter_reentrancy_guard(&env);
+        token::Client::new(&env, &pool.token).transfer(
+            &env.current_contract_address(),
+            &pool.creator,
+            &amount,
+        );
+        Self::exit_reentrancy_guard(&env);
+
+        CreationBondReturnedEvent {
+            pool_id,
+            creator: pool.creator,
+            amount,
+        }
+        .publish(&env);
+        Ok(amount)
+    }
+}
@@ -0,0 +1,135 @@
+//! Creation bonds: locked by `create_pool`, reclaimed after resolution or
+//! cancellation, and slashed to the treasury when a moderator rejects the pool.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{PoolConfig, PredifiError};
+use soroban_sdk::{testutils::Ledger, vec, Env, String};
+
+const END_TIME: u64 = 10_000;
+const BOND: i128 = 500;
+const ROLE_MODERATOR: u32 = 2;
+
+/// A bond of `BOND` on the test token and a creator holding 10_000.
+fn require_bond(ctx: &TestContext) {
+    ctx.client
+        .set_creation_bond(&ctx.admin, &ctx.token_address, &BOND);
+    ctx.token_admin_client.mint(&ctx.creator, &10_000);
+}
+
+fn create_pool(ctx: &TestContext) -> u64 {
+    let env = &ctx.client.env;
+    let config = PoolConfig {
+        description: String::from_str(env, "Cup final"),
+        metadata_url: String::from_str(env, "ipfs://bond"),
+        outcome_descriptions: vec![
+            env,
+            String::from_str(env, "Home"),
+            String::from_str(env, "Away"),
+        ],
+        ..default_pool_config(env)
+    };
+    ctx.create_pool(END_TIME, &config)
+}
+
+/// The bond is locked at creation and returned once after resolution.
+#[test]
+fn test_bond_locked_and_returned_after_resolution() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    require_bond(&ctx);
+
+    let pool_id = create_pool(&ctx);
+    assert_eq!(ctx.token.balance(&ctx.creator), 10_000 - BOND);
+    assert_eq!(ctx.client.get_pool_creation_bond(&pool_id), BOND);
+
+    let early = ctx.client.try_reclaim_creation_bond(&pool_id);
+    assert_eq!(early, Err(Ok(PredifiError::PoolNotResolved)));
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &0u32);
+    assert_eq!(ctx.client.reclaim_creation_bond(&pool_id), BOND);
+    assert_eq!(ctx.token.balance(&ctx.creator), 10_000);
+    assert_eq!(ctx.client.get_pool_creation_bond(&pool_id), 0);
+
+    let again = ctx.client.try_reclaim_creation_bond(&pool_id);
+    assert_eq!(again, Err(Ok(PredifiError::InsufficientBalance)));
+}
+
+/// A normal cancellation returns the bond too; pools created after the bond
+/// is disabled lock nothing.
+#[test]
+fn test_bond_returned_after_cancel_and_disabled() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    require_bond(&ctx);
+
+    let pool_id = create_pool(&ctx);
+    ctx.client
+        .cancel_pool(&ctx.creator, &pool_id, &String::from_str(&env, "Postponed"));
+    assert_eq!(ctx.client.reclaim_creation_bond(&pool_id), BOND);
+
+    ctx.client
+        .set_creation_bond(&ctx.admin, &ctx.token_address, &0);
+    assert_eq!(ctx.client.get_creation_bond(&ctx.token_address), 0);
+    let free_pool = create_pool(&ctx);
+    assert_eq!(ctx.token.balance(&ctx.creator), 10_000);
+    assert_eq!(ctx.client.get_pool_creation_bond(&free_pool), 0);
+}
+
+/// Rejecting a pool cancels it, refunds stakers and slashes the bond to the
+/// treasury.
+#[test]
+fn test_reject_pool_slashes_bond_and_refunds() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    require_bond(&ctx);
+    let moderator = ctx.grant(ROLE_MODERATOR);
+
+    let pool_id = create_pool(&ctx);
+    let staker = ctx.staker(pool_id, 300, 1);
+    assert_eq!(ctx.client.get_active_pools_count(), 1);
+
+    ctx.client
+        .reject_pool(&moderator, &pool_id, &String::from_str(&env, "Spam"));
+    assert_eq!(ctx.client.get_active_pools_count(), 0);
+    assert_eq!(ctx.client.get_accrued_fees(&ctx.token_address), BOND);
+    assert_eq!(ctx.client.get_pool_creation_bond(&pool_id), 0);
+
+    assert_eq!(ctx.client.claim_refund(&staker, &pool_id), 300);
+    assert_eq!(ctx.token.balance(&staker), 300);
+    let reclaim = ctx.client.try_reclaim_creation_bond(&pool_id);
+    assert_eq!(reclaim, Err(Ok(PredifiError::InsufficientBalance)));
+}
+
+/// Only moderators may reject pools, only while active; only admins may set
+/// the bond.
+#[test]
+fn test_creation_bond_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    require_bond(&ctx);
+    let moderator = ctx.grant(ROLE_MODERATOR);
+    let pool_id = create_pool(&ctx);
+    let reason = String::from_str(&env, "Invalid");
+
+    let not_moderator = ctx.client.try_reject_pool(&ctx.operator, &pool_id, &reason);
+    assert_eq!(not_moderator, Err(Ok(PredifiError::Unauthorized)));
+    let missing = ctx
+        .client
+        .try_reject_pool(&moderator, &(pool_id + 1), &reason);
+    assert_eq!(missing, Err(Ok(PredifiError::PoolNotFound)));
+    ctx.client.reject_pool(&moderator, &pool_id, &reason);
+    let twice = ctx.client.try_reject_pool(&moderator, &pool_id, &reason);
+    assert_eq!(twice, Err(Ok(PredifiError::InvalidPoolState)));
+
+    let not_admin = ctx
+        .client
+        .try_set_creation_bond(&ctx.operator, &ctx.token_address, &BOND);
+    assert_eq!(not_admin, Err(Ok(PredifiError::Unauthorized)));
+    let negative = ctx
+        .client
+        .try_set_creation_bond(&ctx.admin, &ctx.token_address, &-1);
+    assert_eq!(negative, Err(Ok(PredifiError::InvalidAmount)));
+}
@@ -3,7 +3,7 @@
 //! | Issue  | Function              | Coverage highlights                                      |
 //! |--------|-----------------------|----------------------------------------------------------|
 //! | #1324  | `update_referrer`     | self-referrer, None/clear, post-prediction change, cycle |
-//! | #1323  | `withdraw_treasury`   | exceed bal, zero, non-admin, non-whitelisted, consistency|
+//! | #1323  | `withdraw_treasury`   | exceed bal, zero, non-admin, non-whitelisted, no fees    |
 //! | #1316  | `set_fee_bps`         | min/max boundaries, overflow, timelock, concurrent pools |
 //! | #1315  | `emergency_cancel_pool` | multi-sig quorum, duplicates, resolved/cancelled state |
 //! | #1314  | `cancel_pool`         | auth, double-cancel, resolved pool, state invariants     |
@@ -15,12 +15,12 @@ extern crate std;
 
 use crate::{
     FEE_CHANGE_TIMELOCK_SECONDS,
-    MarketState, PoolConfig, PredifiContract, PredifiContractClient, PredifiError,
+    MarketState, PoolConfig, PredifiContract, PredifiContractClient, PredifiError, PricingMode,
 };
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Ledger},
-    token, vec, Address, Env, String,
+    token, vec, Address, Env, String, Symbol,
 };
 
 // ─── Shared dummy access-control stub ────────────────────────────────────────
@@ -120,6 +120,8 @@ impl<'a> TestEnv<'a> {
         let token_admin = token::StellarAssetClient::new(env, &token_address);
 
         client.add_token_to_whitelist(&admin, &token_address);
+        // Let tests stake repeatedly without advancing past the cooldown.
+        client.set_prediction_cooldown(&admin, &0u64);
 
         Self {
             env: env.clone(),
@@ -153,7 +155,7 @@ impl<'a> TestEnv<'a> {
                 min_stake: 1i128,
                 max_stake: 0i128,
                 max_total_stake: 0i128,
-                min_total_stake: 0i128,
+                min_total_stake: 1i128,
                 initial_liquidity: 0i128,
                 required_resolutions: 1u32,
                 private: false,
@@ -163,6 +165,10 @@ impl<'a> TestEnv<'a> {
                     String::from_str(&self.env, "No"),
                     String::from_str(&self.env, "Yes"),
                 ],
+                optimistic_liveness: 0,
+                optimistic_bond: 0,
+                pricing_mode: PricingMode::Parimutuel,
+                creator_fee_bps: 0,
             },
         )
     }
@@ -184,7 +190,7 @@ impl<'a> TestEnv<'a> {
                 min_stake: 1i128,
                 max_stake: 0i128,
                 max_total_stake: 0i128,
-                min_total_stake: 0i128,
+                min_total_stake: 1i128,
                 initial_liquidity: 0i128,
                 required_resolutions: required,
                 private: false,
@@ -194,6 +200,10 @@ impl<'a> TestEnv<'a> {
                     String::from_str(&self.env, "No"),
                     String::from_str(&self.env, "Yes"),
                 ],
+                optimistic_liveness: 0,
+                optimistic_bond: 0,
+                pricing_mode: PricingMode::Parimutuel,
+                creator_fee_bps: 0,
             },
         )
     }
@@ -971,6 +981,10 @@ fn test_create_pool_zero_duration_rejected() {
                 String::from_str(&ctx.env, "No"),
                 String::from_str(&ctx.env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
     assert_eq!(
@@ -1010,6 +1024,10 @@ fn test_create_pool_max_u64_timestamp_rejected() {
                 String::from_str(&ctx.env, "No"),
                 String::from_str(&ctx.env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
     assert_eq!(
@@ -1049,6 +1067,10 @@ fn test_create_pool_empty_description_rejected() {
                 String::from_str(&ctx.env, "No"),
                 String::from_str(&ctx.env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
     // Empty description should be rejected (assert! in code will panic)
@@ -1085,6 +1107,10 @@ fn test_create_pool_description_too_long_rejected() {
                 String::from_str(&ctx.env, "No"),
                 String::from_str(&ctx.env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
     assert!(result.is_err(), "description > 256 bytes must be rejected");
@@ -1120,6 +1146,10 @@ fn test_create_pool_description_at_limit_succeeds() {
                 String::from_str(&ctx.env, "No"),
                 String::from_str(&ctx.env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
     assert!(result.is_ok(), "256-byte description should be accepted");
@@ -1158,6 +1188,10 @@ fn test_create_pool_invalid_token_address_rejected() {
                 String::from_str(&ctx.env, "No"),
                 String::from_str(&ctx.env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
     assert_eq!(
@@ -1203,9 +1237,12 @@ fn test_create_pool_duplicate_descriptions_allowed() {
                     String::from_str(&ctx.env, "No"),
                     String::from_str(&ctx.env, "Yes"),
                 ],
+                optimistic_liveness: 0,
+                optimistic_bond: 0,
+                pricing_mode: PricingMode::Parimutuel,
+                creator_fee_bps: 0,
             },
-        )
-        .unwrap();
+        );
 
     // Create second pool with same description
     let pool_id2 = ctx
@@ -1233,9 +1270,12 @@ fn test_create_pool_duplicate_descriptions_allowed() {
                     String::from_str(&ctx.env, "No"),
                     String::from_str(&ctx.env, "Yes"),
                 ],
+                optimistic_liveness: 0,
+                optimistic_bond: 0,
+                pricing_mode: PricingMode::Parimutuel,
+                creator_fee_bps: 0,
             },
-        )
-        .unwrap();
+        );
 
     // Both pools should exist with different IDs
     assert_ne!(pool_id1, pool_id2, "pools must have different IDs");
@@ -1270,6 +1310,10 @@ fn test_create_pool_single_option_rejected() {
             private: false,
             whitelist_key: None,
             outcome_descriptions: vec![&ctx.env, String::from_str(&ctx.env, "Only")],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
     assert_eq!(
@@ -1304,6 +1348,10 @@ fn test_create_pool_zero_options_rejected() {
             private: false,
             whitelist_key: None,
             outcome_descriptions: vec![&ctx.env],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
     assert_eq!(
@@ -1342,6 +1390,10 @@ fn test_create_pool_negative_liquidity_rejected() {
                 String::from_str(&ctx.env, "No"),
                 String::from_str(&ctx.env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
     assert!(result.is_err(), "negative initial_liquidity must be rejected");
@@ -1376,6 +1428,10 @@ fn test_create_pool_zero_required_resolutions_rejected() {
                 String::from_str(&ctx.env, "No"),
                 String::from_str(&ctx.env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
     assert!(result.is_err(), "zero required_resolutions must be rejected");
@@ -1410,6 +1466,10 @@ fn test_create_pool_zero_min_stake_rejected() {
                 String::from_str(&ctx.env, "No"),
                 String::from_str(&ctx.env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
     assert!(result.is_err(), "zero min_stake must be rejected");
@@ -1444,6 +1504,10 @@ fn test_create_pool_max_stake_less_than_min_rejected() {
                 String::from_str(&ctx.env, "No"),
                 String::from_str(&ctx.env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
     assert!(result.is_err(), "max_stake < min_stake must be rejected");
@@ -1478,6 +1542,10 @@ fn test_create_pool_zero_min_total_stake_rejected() {
                 String::from_str(&ctx.env, "No"),
                 String::from_str(&ctx.env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
     assert!(result.is_err(), "zero min_total_stake must be rejected");
@@ -1512,6 +1580,10 @@ fn test_create_pool_negative_max_total_stake_rejected() {
                 String::from_str(&ctx.env, "No"),
                 String::from_str(&ctx.env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
     assert!(result.is_err(), "negative max_total_stake must be rejected");
@@ -1547,6 +1619,10 @@ fn test_create_pool_metadata_url_too_long_rejected() {
                 String::from_str(&ctx.env, "No"),
                 String::from_str(&ctx.env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
     assert_eq!(
@@ -1586,6 +1662,10 @@ fn test_create_pool_metadata_url_at_limit_succeeds() {
                 String::from_str(&ctx.env, "No"),
                 String::from_str(&ctx.env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
     assert!(result.is_ok(), "512-byte metadata_url should be accepted");
@@ -1602,7 +1682,7 @@ fn test_create_pool_invalid_category_rejected() {
         &100_000u64,
         &ctx.token_address,
         &2u32,
-        &symbol_short!("INVALID_CATEGORY"), // Not in allowed list
+        &Symbol::new(&ctx.env, "INVALID_CATEGORY"), // Not in allowed list
         &PoolConfig {
             start_time: 0,
             description: String::from_str(&ctx.env, "Invalid category pool"),
@@ -1620,6 +1700,10 @@ fn test_create_pool_invalid_category_rejected() {
                 String::from_str(&ctx.env, "No"),
                 String::from_str(&ctx.env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
     assert!(result.is_err(), "invalid category must be rejected");
@@ -1633,7 +1717,7 @@ fn test_create_pool_failure_does_not_increment_pool_id() {
     let ctx = TestEnv::new(&env);
 
     // Get initial pool_id counter
-    let initial_counter = ctx.client.get_pool_id_counter();
+    let initial_counter = ctx.client.get_contract_info().total_pools;
 
     // Attempt to create a pool with invalid token (will fail)
     let invalid_token = Address::generate(&ctx.env);
@@ -1660,12 +1744,16 @@ fn test_create_pool_failure_does_not_increment_pool_id() {
                 String::from_str(&ctx.env, "No"),
                 String::from_str(&ctx.env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
     assert!(result.is_err(), "pool creation should fail");
 
     // Verify counter did not increment
-    let final_counter = ctx.client.get_pool_id_counter();
+    let final_counter = ctx.client.get_contract_info().total_pools;
     assert_eq!(
         initial_counter, final_counter,
         "pool_id counter must not increment on failed creation"
@@ -1678,12 +1766,12 @@ fn test_create_pool_success_increments_pool_id() {
     let env = Env::default();
     let ctx = TestEnv::new(&env);
 
-    let initial_counter = ctx.client.get_pool_id_counter();
+    let initial_counter = ctx.client.get_contract_info().total_pools;
 
     // Create a valid pool
     let _pool_id = ctx.create_pool(100_000);
 
-    let final_counter = ctx.client.get_pool_id_counter();
+    let final_counter = ctx.client.get_contract_info().total_pools;
     assert_eq!(
         initial_counter + 1, final_counter,
         "pool_id counter must increment by 1 on successful creation"
@@ -1842,7 +1930,7 @@ fn test_1313_resolve_one_second_before_delay_expires_fails() {
             min_stake: 1i128,
             max_stake: 0i128,
             max_total_stake: 0i128,
-            min_total_stake: 0i128,
+            min_total_stake: 1i128,
             initial_liquidity: 0i128,
             required_resolutions: 1u32,
             private: false,
@@ -1852,6 +1940,10 @@ fn test_1313_resolve_one_second_before_delay_expires_fails() {
                 String::from_str(&env, "No"),
                 String::from_str(&env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 
@@ -1904,7 +1996,7 @@ fn test_1313_resolve_at_exact_delay_boundary_succeeds() {
             min_stake: 1i128,
             max_stake: 0i128,
             max_total_stake: 0i128,
-            min_total_stake: 0i128,
+            min_total_stake: 1i128,
             initial_liquidity: 0i128,
             required_resolutions: 1u32,
             private: false,
@@ -1914,6 +2006,10 @@ fn test_1313_resolve_at_exact_delay_boundary_succeeds() {
                 String::from_str(&env, "No"),
                 String::from_str(&env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 
@@ -2360,9 +2456,10 @@ fn test_1323_withdraw_treasury_non_admin_fails() {
     assert_eq!(ctx.token.balance(&ctx.treasury), 0);
 }
 
-/// Test 4: Withdrawal of a non-whitelisted token by Admin succeeds.
+/// Test 4: A non-whitelisted token never accrues fees, so stray balances can
+/// only be recovered through `emergency_withdraw`.
 #[test]
-fn test_1323_withdraw_treasury_non_whitelisted_token_succeeds() {
+fn test_1323_withdraw_treasury_non_whitelisted_token_has_no_fees() {
     let env = Env::default();
     let ctx = TestEnv::new(&env);
 
@@ -2378,48 +2475,58 @@ fn test_1323_withdraw_treasury_non_whitelisted_token_succeeds() {
     // Mint 1000 non-whitelisted tokens to contract
     non_wl_token_admin.mint(&contract_id, &1000);
 
-    // Admin withdraws 400 of non-whitelisted tokens
-    ctx.client.withdraw_treasury(
+    let result = ctx.client.try_withdraw_treasury(
         &ctx.admin,
         &non_wl_address,
         &400i128,
         &ctx.treasury,
     );
+    assert_eq!(
+        result,
+        Err(Ok(PredifiError::InsufficientBalance)),
+        "a token with no accrued fees must not be withdrawable"
+    );
 
+    // Emergency withdrawals take two admins by default.
+    let second_admin = Address::generate(&env);
+    ctx.ac.grant_role(&second_admin, &0u32);
+    for admin in [&ctx.admin, &second_admin] {
+        ctx.client
+            .emergency_withdraw(admin, &non_wl_address, &ctx.treasury, &400i128);
+    }
     assert_eq!(
         non_wl_token.balance(&ctx.treasury),
         400,
-        "treasury recipient must receive withdrawn non-whitelisted tokens"
+        "treasury recipient must receive recovered non-whitelisted tokens"
     );
     assert_eq!(
         non_wl_token.balance(&contract_id),
         600,
-        "contract balance must decrease by withdrawn amount"
+        "contract balance must decrease by recovered amount"
     );
 }
 
-/// Test 5: Verify treasury balance consistency after multiple consecutive withdrawals.
+/// Test 5: Balance held by the contract without accrued fees behind it (live
+/// stakes, unclaimed winnings) cannot be withdrawn in any number of steps.
 #[test]
-fn test_1323_withdraw_treasury_multiple_withdrawals_balance_consistency() {
+fn test_1323_withdraw_treasury_balance_without_fees_is_locked() {
     let env = Env::default();
     let ctx = TestEnv::new(&env);
 
     let contract_id = ctx.client.address.clone();
     ctx.token_admin.mint(&contract_id, &2000);
+    assert_eq!(ctx.client.get_accrued_fees(&ctx.token_address), 0);
 
-    // 1st withdrawal: 500
-    ctx.client.withdraw_treasury(&ctx.admin, &ctx.token_address, &500i128, &ctx.treasury);
-    assert_eq!(ctx.token.balance(&contract_id), 1500);
-    assert_eq!(ctx.token.balance(&ctx.treasury), 500);
-
-    // 2nd withdrawal: 700
-    ctx.client.withdraw_treasury(&ctx.admin, &ctx.token_address, &700i128, &ctx.treasury);
-    assert_eq!(ctx.token.balance(&contract_id), 800);
-    assert_eq!(ctx.token.balance(&ctx.treasury), 1200);
-
-    // 3rd withdrawal: 800
-    ctx.client.withdraw_treasury(&ctx.admin, &ctx.token_address, &800i128, &ctx.treasury);
-    assert_eq!(ctx.token.balance(&contract_id), 0);
-    assert_eq!(ctx.token.balance(&ctx.treasury), 2000);
+    for amount in [500i128, 700, 800] {
+        let result = ctx.client.try_withdraw_treasury(
+            &ctx.admin,
+            &ctx.token_address,
+            &amount,
+            &ctx.treasury,
+        );
+        assert_eq!(result, Err(Ok(PredifiError::InsufficientBalance)));
+    }
+    assert_eq!(ctx.token.balance(&contract_id), 2000);
+    assert_eq!(ctx.token.balance(&ctx.treasury), 0);
 }
 
@@ -49,6 +49,10 @@ fn make_pool(
                 String::from_str(env, "Outcome 0"),
                 String::from_str(env, "Outcome 1"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     )
 }
@@ -228,12 +232,17 @@ fn upgrade_contract_rejects_non_admin() {
 fn upgrade_contract_rejects_unknown_wasm_hash() {
     let env = Env::default();
     env.mock_all_auths();
-    let (_, client, _, _, _, _, _, _) = setup(&env);
+    let (ac_client, client, _, _, _, _, _, _) = setup(&env);
     let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &crate::test::ROLE_ADMIN);
 
-    // A hash with no uploaded WASM behind it must not be applied; the guard is
-    // what stops an upgrade bricking the contract into an empty executable.
+    // A hash with no uploaded WASM behind it must not be applied, even once
+    // it is approved and past its timelock; the guard is what stops an
+    // upgrade bricking the contract into an empty executable.
     let hash = BytesN::from_array(&env, &[7u8; 32]);
+    client.propose_upgrade(&admin, &hash);
+    env.ledger()
+        .with_mut(|li| li.timestamp += DEFAULT_UPGRADE_TIMELOCK_SECONDS);
     assert!(client.try_upgrade_contract(&admin, &hash).is_err());
 }
 
@@ -0,0 +1,152 @@
+//! Price brackets: an N-outcome pool resolved by `resolve_pool_from_price`
+//! to the bracket containing the feed price.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{PoolConfig, PredifiError};
+use soroban_sdk::{testutils::Ledger, vec, Address, Env, String, Symbol, Vec};
+
+const END_TIME: u64 = 10_000;
+
+/// "BTC < 50k", "50–60k", "60–70k", "> 70k".
+fn bracket_config(env: &Env) -> PoolConfig {
+    PoolConfig {
+        outcome_descriptions: vec![
+            env,
+            String::from_str(env, "BTC < 50k"),
+            String::from_str(env, "50-60k"),
+            String::from_str(env, "60-70k"),
+            String::from_str(env, "> 70k"),
+        ],
+        ..default_pool_config(env)
+    }
+}
+
+fn thresholds(env: &Env) -> Vec<i128> {
+    vec![env, 50_000i128, 60_000i128, 70_000i128]
+}
+
+fn feed(env: &Env) -> Symbol {
+    Symbol::new(env, "BTC_USD")
+}
+
+/// A four-bracket pool and the oracle that reports `feed`. Returns
+/// `(pool_id, oracle)`.
+fn bracket_pool(ctx: &TestContext) -> (u64, Address) {
+    let oracle = ctx.add_price_oracle(3_600, 500);
+    let pool_id = ctx.create_pool(END_TIME, &bracket_config(&ctx.client.env));
+    (pool_id, oracle)
+}
+
+fn resolve_at(ctx: &TestContext, oracle: &Address, pool_id: u64, price: i128) -> u32 {
+    let env = &ctx.client.env;
+    ctx.client
+        .set_price_brackets(&ctx.operator, &pool_id, &feed(env), &thresholds(env));
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 10);
+    ctx.client.update_price_feed(
+        oracle,
+        &feed(env),
+        &price,
+        &1,
+        &(END_TIME + 5),
+        &(END_TIME + 100),
+    );
+    ctx.client.resolve_pool_from_price(&pool_id);
+    ctx.client.get_pool(&pool_id).outcome
+}
+
+/// Prices resolve to the bracket containing them; a price exactly on a
+/// threshold belongs to the bracket above it.
+#[test]
+fn test_price_resolves_to_matching_bracket() {
+    for (price, expected) in [
+        (42_000i128, 0u32),
+        (50_000, 1),
+        (65_000, 2),
+        (70_000, 3),
+        (99_000, 3),
+    ] {
+        let env = Env::default();
+        let ctx = TestContext::new(&env);
+        let (pool_id, oracle) = bracket_pool(&ctx);
+        assert_eq!(resolve_at(&ctx, &oracle, pool_id, price), expected);
+    }
+}
+
+/// Thresholds must be positive, strictly increasing and one fewer than the
+/// pool's outcomes.
+#[test]
+fn test_set_price_brackets_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, _oracle) = bracket_pool(&ctx);
+
+    let too_few = ctx.client.try_set_price_brackets(
+        &ctx.operator,
+        &pool_id,
+        &feed(&env),
+        &vec![&env, 50_000i128, 60_000i128],
+    );
+    assert_eq!(too_few, Err(Ok(PredifiError::InvalidOutcome)));
+
+    let unordered = ctx.client.try_set_price_brackets(
+        &ctx.operator,
+        &pool_id,
+        &feed(&env),
+        &vec![&env, 50_000i128, 50_000i128, 70_000i128],
+    );
+    assert_eq!(unordered, Err(Ok(PredifiError::InvalidData)));
+
+    let negative = ctx.client.try_set_price_brackets(
+        &ctx.operator,
+        &pool_id,
+        &feed(&env),
+        &vec![&env, -1i128, 60_000i128, 70_000i128],
+    );
+    assert_eq!(negative, Err(Ok(PredifiError::InvalidTargetPrice)));
+
+    let not_operator =
+        ctx.client
+            .try_set_price_brackets(&ctx.creator, &pool_id, &feed(&env), &thresholds(&env));
+    assert_eq!(not_operator, Err(Ok(PredifiError::Unauthorized)));
+
+    let missing =
+        ctx.client
+            .try_set_price_brackets(&ctx.operator, &999u64, &feed(&env), &thresholds(&env));
+    assert_eq!(missing, Err(Ok(PredifiError::PoolNotFound)));
+}
+
+/// Brackets replace a previously configured binary price condition, and a
+/// scalar range in turn replaces the brackets.
+#[test]
+fn test_price_brackets_replace_other_conditions() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, _oracle) = bracket_pool(&ctx);
+
+    ctx.client
+        .set_price_condition(&ctx.operator, &pool_id, &feed(&env), &60_000, &1, &0);
+    ctx.client
+        .set_price_brackets(&ctx.operator, &pool_id, &feed(&env), &thresholds(&env));
+    let brackets = ctx.client.get_price_brackets(&pool_id).unwrap();
+    assert_eq!(brackets.thresholds, thresholds(&env));
+
+    let binary = ctx.create_pool(
+        END_TIME,
+        &PoolConfig {
+            outcome_descriptions: vec![
+                &env,
+                String::from_str(&env, "Below"),
+                String::from_str(&env, "Above"),
+            ],
+            ..default_pool_config(&env)
+        },
+    );
+    ctx.client
+        .set_price_brackets(&ctx.operator, &binary, &feed(&env), &vec![&env, 60_000i128]);
+    ctx.client
+        .set_scalar_range(&ctx.operator, &binary, &feed(&env), &50_000, &70_000);
+    assert_eq!(ctx.client.get_price_brackets(&binary), None);
+    assert!(ctx.client.get_scalar_range(&binary).is_some());
+}
@@ -157,6 +157,10 @@ fn two_outcome_config(env: &Env) -> PoolConfig {
             String::from_str(env, "No"),
             String::from_str(env, "Yes"),
         ],
+        optimistic_liveness: 0,
+        optimistic_bond: 0,
+        pricing_mode: PricingMode::Parimutuel,
+        creator_fee_bps: 0,
     }
 }
 
@@ -906,6 +910,10 @@ fn test_all_same_outcome_with_fee_payout_reduced_proportionally() {
                 String::from_str(&env, "No"),
                 String::from_str(&env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 
@@ -0,0 +1,423 @@
+//! Commit-reveal domain: stakes placed on a hidden outcome before `end_time`
+//! and revealed in a window after it, so that late bettors cannot copy or
+//! front-run large positions.
+
+use soroban_sdk::{contractimpl, token, Address, Bytes, BytesN, Env};
+
+use crate::{
+    CommitRevealConfig, CommitRevealEnabledEvent, Commitment, CommitmentSettledEvent, DataKey,
+    Invite, MarketState, Pool, PoolKey, PredictionCommittedEvent, PredictionPlacedEvent,
+    PredifiContract, PredifiContractArgs, PredifiContractClient, PredifiError, PricingMode,
+    UnrevealedPolicy, MAX_REVEAL_WINDOW, UNREVEALED_REFUND_PENALTY_BPS,
+};
+
+#[contractimpl]
+impl PredifiContract {
+    /// Switch a pool to commit-reveal mode. Callable by the pool creator
+    /// before anyone has staked.
+    ///
+    /// From then on the pool only takes stakes through
+    /// [`Self::commit_prediction`], and cannot resolve until
+    /// `end_time + config.reveal_window`.
+    ///
+    /// # Errors
+    /// - `PoolNotFound` – `pool_id` does not exist.
+    /// - `Unauthorized` – `creator` did not create the pool.
+    /// - `InvalidPoolState` – the pool is not an `Active` parimutuel pool, or
+    ///   already has stakes or commitments.
+    /// - `TimeConstraintError` – `end_time` has passed, or `reveal_window` is
+    ///   zero or exceeds `MAX_REVEAL_WINDOW`.
+    pub fn enable_commit_reveal(
+        env: Env,
+        creator: Address,
+        pool_id: u64,
+        config: CommitRevealConfig,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        creator.require_auth();
+
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.creator != creator {
+            return Err(PredifiError::Unauthorized);
+        }
+        if !Self::is_pool_active(&pool)
+            || pool.pricing_mode != PricingMode::Parimutuel
+            || pool.participants_count > 0
+            || Self::committed_stake(&env, pool_id) > 0
+        {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if env.ledger().timestamp() >= pool.end_time
+            || config.reveal_window == 0
+            || config.reveal_window > MAX_REVEAL_WINDOW
+        {
+            return Err(PredifiError::TimeConstraintError);
+        }
+
+        let key = PoolKey::CommitReveal(pool_id);
+        env.storage().persistent().set(&key, &config);
+        Self::extend_persistent(&env, &key);
+
+        CommitRevealEnabledEvent {
+            pool_id,
+            creator,
+            reveal_window: config.reveal_window,
+            unrevealed_policy: config.unrevealed_policy,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Commit-reveal mode of a pool, or `None` for a regular pool.
+    pub fn get_commit_reveal(env: Env, pool_id: u64) -> Option<CommitRevealConfig> {
+        Self::commit_reveal_config(&env, pool_id)
+    }
+
+    /// The commitment hash for `outcome` and `salt`: the SHA-256 of the
+    /// outcome as 4 big-endian bytes followed by the salt.
+    ///
+    /// Clients should compute it locally; this view is for checking.
+    pub fn compute_commitment(env: Env, outcome: u32, salt: BytesN<32>) -> BytesN<32> {
+        let mut preimage = Bytes::from_array(&env, &outcome.to_be_bytes());
+        preimage.append(&salt.into());
+        env.crypto().sha256(&preimage).to_bytes()
+    }
+
+    /// Stake `amount` on a hidden outcome of a commit-reveal pool.
+    ///
+    /// `hash` is [`Self::compute_commitment`] of the chosen outcome and a
+    /// secret salt. The stake is held by the contract but counts towards no
+    /// outcome, and `PredictionCommittedEvent` carries no outcome, until
+    /// [`Self::reveal_prediction`]. One commitment per user and pool.
+    ///
+    /// Stake limits, the prediction cooldown, private-pool access and the
+    /// per-user pool limit are enforced as in `place_prediction`; the
+    /// referrer is recorded at reveal.
+    ///
+    /// # Errors
+    /// - `PoolNotFound` – `pool_id` does not exist.
+    /// - `InvalidPoolState` – the pool is not `Active` or not in commit-reveal mode.
+    /// - `TimeConstraintError` – `end_time` has passed.
+    /// - `InvalidAmount` – `amount` is not positive.
+    /// - `InsufficientStake` / `StakeBelowMinimum` / `StakeAboveMaximum` /
+    ///   `MaxTotalStakeExceeded` – stake limits.
+    /// - `TokenNotWhitelisted` – the pool token was delisted.
+    /// - `Unauthorized` – the user may not enter this private pool, or
+    ///   `referrer` is the user.
+    /// - `RateLimitOrSuspiciousActivity` – the prediction cooldown has not passed.
+    /// - `MaxPredictionsExceeded` – the user is at `max_predictions_per_user`.
+    /// - `InvalidData` – the user already has a commitment in the pool.
+    pub fn commit_prediction(
+        env: Env,
+        user: Address,
+        pool_id: u64,
+        amount: i128,
+        hash: BytesN<32>,
+        referrer: Option<Address>,
+        invite: Option<Invite>,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        user.require_auth();
+
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if !Self::is_pool_active(&pool) || Self::commit_reveal_config(&env, pool_id).is_none() {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        let now = env.ledger().timestamp();
+        if now >= pool.end_time {
+            return Err(PredifiError::TimeConstraintError);
+        }
+        if amount <= 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+        let config = Self::get_config(&env);
+        if amount < config.min_stake {
+            return Err(PredifiError::InsufficientStake);
+        }
+        if amount < pool.min_stake {
+            return Err(PredifiError::StakeBelowMinimum);
+        }
+        if pool.max_stake > 0 && amount > pool.max_stake {
+            return Err(PredifiError::StakeAboveMaximum);
+        }
+        let committed = Self::committed_stake(&env, pool_id);
+        if pool.max_total_stake > 0 {
+            let new_total = pool
+                .total_stake
+                .checked_add(committed)
+                .and_then(|total| total.checked_add(amount))
+                .ok_or(PredifiError::ArithmeticError)?;
+            if new_total > pool.max_total_stake {
+                return Err(PredifiError::MaxTotalStakeExceeded);
+            }
+        }
+        if !Self::is_token_whitelisted(&env, &pool.token) {
+            return Err(PredifiError::TokenNotWhitelisted);
+        }
+        if referrer.as_ref() == Some(&user) {
+            return Err(PredifiError::Unauthorized);
+        }
+        if pool.private {
+            Self::require_private_access(&env, pool_id, &pool, &user, &invite)?;
+        }
+
+        let last_prediction_key = DataKey::LastPredictionTime(user.clone());
+        if config.prediction_cooldown_seconds > 0 {
+            let last: Option<u64> = env.storage().persistent().get(&last_prediction_key);
+            if last
+                .is_some_and(|last| now.saturating_sub(last) < config.prediction_cooldown_seconds)
+            {
+                return Err(PredifiError::RateLimitOrSuspiciousActivity);
+            }
+        }
+        if config.max_predictions_per_user > 0 {
+            let count: u32 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::UsrPrdCnt(user.clone()))
+                .unwrap_or(0);
+            if count >= config.max_predictions_per_user {
+                return Err(PredifiError::MaxPredictionsExceeded);
+            }
+        }
+
+        let key = PoolKey::Commitment(user.clone(), pool_id);
+        if env.storage().persistent().has(&key) {
+            return Err(PredifiError::InvalidData);
+        }
+        let commitment = Commitment {
+            amount,
+            hash,
+            referrer,
+        };
+        env.storage().persistent().set(&key, &commitment);
+        Self::extend_persistent(&env, &key);
+        Self::set_committed_stake(&env, pool_id, committed + amount);
+
+        env.storage().persistent().set(&last_prediction_key, &now);
+        Self::extend_persistent(&env, &last_prediction_key);
+
+        Self::validate_token_transfer(
+            &env,
+            &pool.token,
+            &user,
+            &env.current_contract_address(),
+            amount,
+        )?;
+        token::Client::new(&env, &pool.token).transfer(
+            &user,
+            env.current_contract_address(),
+            &amount,
+        );
+
+        PredictionCommittedEvent {
+            pool_id,
+            user,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Reveal the outcome behind `user`'s commitment, turning it into a
+    /// regular stake on that outcome.
+    ///
+    /// Only accepted between `end_time` and `end_time + reveal_window`. The
+    /// stake is tallied in the outcome stakes, `total_stake` and the user's
+    /// position, and `PredictionPlacedEvent` is published.
+    ///
+    /// # Errors
+    /// - `PoolNotFound` – `pool_id` does not exist.
+    /// - `InvalidPoolState` – the pool is not `Active` or not in commit-reveal mode.
+    /// - `StakingStillOpen` – `end_time` has not passed yet.
+    /// - `TimeConstraintError` – the reveal window has closed.
+    /// - `InvalidOutcome` – outcome is out of range.
+    /// - `InvalidData` – no commitment, or `outcome` and `salt` do not match it.
+    pub fn reveal_prediction(
+        env: Env,
+        user: Address,
+        pool_id: u64,
+        outcome: u32,
+        salt: BytesN<32>,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        user.require_auth();
+
+        let mut pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        let config =
+            Self::commit_reveal_config(&env, pool_id).ok_or(PredifiError::InvalidPoolState)?;
+        if !Self::is_pool_active(&pool) {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        let now = env.ledger().timestamp();
+        if now < pool.end_time {
+            return Err(PredifiError::StakingStillOpen);
+        }
+        if now >= pool.end_time.saturating_add(config.reveal_window) {
+            return Err(PredifiError::TimeConstraintError);
+        }
+        if outcome >= pool.options_count {
+            return Err(PredifiError::InvalidOutcome);
+        }
+
+        let key = PoolKey::Commitment(user.clone(), pool_id);
+        let commitment: Commitment = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(PredifiError::InvalidData)?;
+        if Self::compute_commitment(env.clone(), outcome, salt) != commitment.hash {
+            return Err(PredifiError::InvalidData);
+        }
+
+        env.storage().persistent().remove(&key);
+        let committed = Self::committed_stake(&env, pool_id);
+        Self::set_committed_stake(&env, pool_id, committed - commitment.amount);
+        Self::record_stake(
+            &env,
+            pool_id,
+            &mut pool,
+            &user,
+            outcome,
+            commitment.amount,
+            commitment.referrer.as_ref(),
+        )?;
+
+        PredictionPlacedEvent {
+            pool_id,
+            user,
+            amount: commitment.amount,
+            outcome,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Settle a commitment that was never revealed, per the pool's
+    /// `unrevealed_policy`: refund it to `user` less
+    /// `UNREVEALED_REFUND_PENALTY_BPS`, or forfeit it to the treasury.
+    /// Callable by anyone once the reveal window has closed. Commitments in a
+    /// canceled pool are always refunded in full, at any time.
+    ///
+    /// Returns the amount settled.
+    ///
+    /// # Errors
+    /// - `PoolNotFound` – `pool_id` does not exist.
+    /// - `InvalidPoolState` – the pool is not in commit-reveal mode.
+    /// - `TimeConstraintError` – the reveal window is still open.
+    /// - `InvalidData` – `user` has no unrevealed commitment in the pool.
+    pub fn settle_commitment(env: Env, pool_id: u64, user: Address) -> Result<i128, PredifiError> {
+        Self::require_not_paused(&env)?;
+
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        let config =
+            Self::commit_reveal_config(&env, pool_id).ok_or(PredifiError::InvalidPoolState)?;
+        let canceled = pool.state == MarketState::Canceled;
+        let reveal_end = pool.end_time.saturating_add(config.reveal_window);
+        if !canceled && env.ledger().timestamp() < reveal_end {
+            return Err(PredifiError::TimeConstraintError);
+        }
+
+        let key = PoolKey::Commitment(user.clone(), pool_id);
+        let commitment: Commitment = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(PredifiError::InvalidData)?;
+        env.storage().persistent().remove(&key);
+        let committed = Self::committed_stake(&env, pool_id);
+        Self::set_committed_stake(&env, pool_id, committed - commitment.amount);
+
+        let refunded = canceled || config.unrevealed_policy == UnrevealedPolicy::Refund;
+        // Skipping a reveal must cost something, or a committer holds a
+        // free option on the outcome.
+        let penalty = if canceled {
+            0
+        } else if refunded {
+            commitment
+                .amount
+                .checked_mul(UNREVEALED_REFUND_PENALTY_BPS as i128)
+                .ok_or(PredifiError::ArithmeticError)?
+                / 10_000
+        } else {
+            commitment.amount
+        };
+        Self::credit_accrued_fees(&env, &pool.token, penalty)?;
+        if refunded {
+            let refund = commitment.amount - penalty;
+            Self::validate_token_transfer(
+                &env,
+                &pool.token,
+                &env.current_contract_address(),
+                &user,
+                refund,
+            )?;
+            Self::enter_reentrancy_guard(&env);
+            token::Client::new(&env, &pool.token).transfer(
+                &env.current_contract_address(),
+                &user,
+                &refund,
+            );
+            Self::exit_reentrancy_guard(&env);
+        }
+
+        CommitmentSettledEvent {
+            pool_id,
+            user,
+            amount: commitment.amount,
+            refunded,
+            penalty,
+        }
+        .publish(&env);
+
+        Ok(commitment.amount)
+    }
+
+    /// `user`'s unrevealed commitment in a pool, if any.
+    pub fn get_commitment(env: Env, user: Address, pool_id: u64) -> Option<Commitment> {
+        env.storage()
+            .persistent()
+            .get(&PoolKey::Commitment(user, pool_id))
+    }
+
+    /// Total stake held in unrevealed commitments of a pool.
+    pub fn get_committed_stake(env: Env, pool_id: u64) -> i128 {
+        Self::committed_stake(&env, pool_id)
+    }
+
+    fn committed_stake(env: &Env, pool_id: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&PoolKey::CommittedStake(pool_id))
+            .unwrap_or(0)
+    }
+
+    fn set_committed_stake(env: &Env, pool_id: u64, amount: i128) {
+        let key = PoolKey::CommittedStake(pool_id);
+        if amount == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &amount);
+            Self::extend_persistent(env, &key);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! Hidden stakes: commitments tallied only on reveal, resolution held until
+//! the reveal window closes, and unrevealed stakes refunded or forfeited.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{
+    CommitRevealConfig, PoolConfig, PredifiError, UnrevealedPolicy,
+    DEFAULT_PREDICTION_COOLDOWN_SECONDS, MAX_REVEAL_WINDOW,
+};
+use soroban_sdk::{testutils::Ledger, vec, Address, BytesN, Env, String};
+
+const END_TIME: u64 = 10_000;
+const REVEAL_WINDOW: u64 = 1_000;
+
+/// A two-outcome pool switched to commit-reveal mode with `policy`. Returns
+/// `pool_id`.
+fn commit_pool(ctx: &TestContext, policy: UnrevealedPolicy) -> u64 {
+    let env = &ctx.client.env;
+    let config = PoolConfig {
+        description: String::from_str(env, "Match winner"),
+        metadata_url: String::from_str(env, "ipfs://commit"),
+        outcome_descriptions: vec![
+            env,
+            String::from_str(env, "Home"),
+            String::from_str(env, "Away"),
+        ],
+        ..default_pool_config(env)
+    };
+    let pool_id = ctx.create_pool(END_TIME, &config);
+    ctx.client.enable_commit_reveal(
+        &ctx.creator,
+        &pool_id,
+        &CommitRevealConfig {
+            reveal_window: REVEAL_WINDOW,
+            unrevealed_policy: policy,
+        },
+    );
+    pool_id
+}
+
+fn salt(env: &Env, seed: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[seed; 32])
+}
+
+/// Commit 1_000 from a fresh user to `outcome`, salted with `seed`.
+fn commit(ctx: &TestContext, pool_id: u64, outcome: u32, seed: u8) -> Address {
+    let user = ctx.funded_user(1_000);
+    let hash = ctx
+        .client
+        .compute_commitment(&outcome, &salt(&ctx.client.env, seed));
+    ctx.client
+        .commit_prediction(&user, &pool_id, &1_000, &hash, &None, &None);
+    user
+}
+
+/// Committed stakes stay off the outcome tallies until revealed, and the
+/// pool resolves only once the reveal window has closed.
+#[test]
+fn test_commit_reveal_tallies_on_reveal() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = commit_pool(&ctx, UnrevealedPolicy::Refund);
+    let home = commit(&ctx, pool_id, 0, 1);
+    let away = commit(&ctx, pool_id, 1, 2);
+
+    assert_eq!(ctx.client.get_committed_stake(&pool_id), 2_000);
+    assert_eq!(ctx.client.get_outcome_stake(&pool_id, &0u32), 0);
+    assert_eq!(ctx.client.get_pool(&pool_id).total_stake, 0);
+    assert_eq!(ctx.token.balance(&ctx.client.address), 2_000);
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME);
+    ctx.client
+        .reveal_prediction(&home, &pool_id, &0u32, &salt(&env, 1));
+    ctx.client
+        .reveal_prediction(&away, &pool_id, &1u32, &salt(&env, 2));
+    assert_eq!(ctx.client.get_commitment(&home, &pool_id), None);
+    assert_eq!(ctx.client.get_committed_stake(&pool_id), 0);
+    assert_eq!(ctx.client.get_outcome_stake(&pool_id, &0u32), 1_000);
+    let pool = ctx.client.get_pool(&pool_id);
+    assert_eq!((pool.total_stake, pool.participants_count), (2_000, 2));
+
+    let early = ctx.client.try_resolve_pool(&ctx.operator, &pool_id, &0u32);
+    assert_eq!(early, Err(Ok(PredifiError::ResolutionDelayNotMet)));
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = END_TIME + REVEAL_WINDOW);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &0u32);
+    assert_eq!(ctx.client.claim_winnings(&home, &pool_id), 2_000);
+}
+
+/// Reveals need the matching outcome and salt, inside the window; stakes
+/// cannot be placed in the clear.
+#[test]
+fn test_reveal_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = commit_pool(&ctx, UnrevealedPolicy::Refund);
+    let user = commit(&ctx, pool_id, 1, 3);
+
+    let early = ctx
+        .client
+        .try_reveal_prediction(&user, &pool_id, &1u32, &salt(&env, 3));
+    assert_eq!(early, Err(Ok(PredifiError::StakingStillOpen)));
+    env.ledger()
+        .with_mut(|li| li.timestamp += DEFAULT_PREDICTION_COOLDOWN_SECONDS);
+    let again =
+        ctx.client
+            .try_commit_prediction(&user, &pool_id, &1_000, &salt(&env, 3), &None, &None);
+    assert_eq!(again, Err(Ok(PredifiError::InvalidData)));
+    let clear = ctx
+        .client
+        .try_place_prediction(&user, &pool_id, &1_000, &0u32, &None, &None);
+    assert!(clear.is_err());
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME);
+    let wrong_outcome = ctx
+        .client
+        .try_reveal_prediction(&user, &pool_id, &0u32, &salt(&env, 3));
+    assert_eq!(wrong_outcome, Err(Ok(PredifiError::InvalidData)));
+    let wrong_salt = ctx
+        .client
+        .try_reveal_prediction(&user, &pool_id, &1u32, &salt(&env, 4));
+    assert_eq!(wrong_salt, Err(Ok(PredifiError::InvalidData)));
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = END_TIME + REVEAL_WINDOW);
+    let late = ctx
+        .client
+        .try_reveal_prediction(&user, &pool_id, &1u32, &salt(&env, 3));
+    assert_eq!(late, Err(Ok(PredifiError::TimeConstraintError)));
+}
+
+/// Unrevealed stakes are refunded less the penalty, or forfeited to the
+/// treasury, once the reveal window closes.
+#[test]
+fn test_settle_unrevealed_commitments() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let refund_pool = commit_pool(&ctx, UnrevealedPolicy::Refund);
+    let refunded = commit(&ctx, refund_pool, 0, 5);
+    let forfeit_pool = commit_pool(&ctx, UnrevealedPolicy::Forfeit);
+    let forfeited = commit(&ctx, forfeit_pool, 0, 6);
+
+    let open = ctx.client.try_settle_commitment(&refund_pool, &refunded);
+    assert_eq!(open, Err(Ok(PredifiError::TimeConstraintError)));
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = END_TIME + REVEAL_WINDOW);
+    assert_eq!(ctx.client.settle_commitment(&refund_pool, &refunded), 1_000);
+    assert_eq!(ctx.token.balance(&refunded), 900);
+    assert_eq!(ctx.client.get_accrued_fees(&ctx.token_address), 100);
+
+    ctx.client.settle_commitment(&forfeit_pool, &forfeited);
+    assert_eq!(ctx.token.balance(&forfeited), 0);
+    assert_eq!(ctx.client.get_accrued_fees(&ctx.token_address), 1_100);
+
+    let twice = ctx.client.try_settle_commitment(&forfeit_pool, &forfeited);
+    assert_eq!(twice, Err(Ok(PredifiError::InvalidData)));
+}
+
+/// A canceled pool refunds commitments straight away, whatever its policy.
+#[test]
+fn test_canceled_pool_refunds_commitments() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = commit_pool(&ctx, UnrevealedPolicy::Forfeit);
+    let user = commit(&ctx, pool_id, 1, 7);
+
+    ctx.client.cancel_pool(
+        &ctx.operator,
+        &pool_id,
+        &String::from_str(&env, "fixture postponed"),
+    );
+    assert_eq!(ctx.client.settle_commitment(&pool_id, &user), 1_000);
+    assert_eq!(ctx.token.balance(&user), 1_000);
+}
+
+/// Hidden commitments count as bets, so the creator can no longer cancel the
+/// pool alone.
+#[test]
+fn test_creator_cannot_cancel_over_commitments() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = commit_pool(&ctx, UnrevealedPolicy::Refund);
+    commit(&ctx, pool_id, 0, 9);
+
+    let reason = String::from_str(&env, "changed my mind");
+    let canceled = ctx.client.try_cancel_pool(&ctx.creator, &pool_id, &reason);
+    assert_eq!(canceled, Err(Ok(PredifiError::Unauthorized)));
+}
+
+/// Only the creator may enable commit-reveal, with a bounded window, and
+/// only before anyone has staked.
+#[test]
+fn test_enable_commit_reveal_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = commit_pool(&ctx, UnrevealedPolicy::Refund);
+    let config = |reveal_window: u64| CommitRevealConfig {
+        reveal_window,
+        unrevealed_policy: UnrevealedPolicy::Forfeit,
+    };
+    let stored = ctx.client.get_commit_reveal(&pool_id).unwrap();
+    assert_eq!(stored.reveal_window, REVEAL_WINDOW);
+
+    let not_creator = ctx
+        .client
+        .try_enable_commit_reveal(&ctx.operator, &pool_id, &config(100));
+    assert_eq!(not_creator, Err(Ok(PredifiError::Unauthorized)));
+    let zero = ctx
+        .client
+        .try_enable_commit_reveal(&ctx.creator, &pool_id, &config(0));
+    assert_eq!(zero, Err(Ok(PredifiError::TimeConstraintError)));
+    let too_long =
+        ctx.client
+            .try_enable_commit_reveal(&ctx.creator, &pool_id, &config(MAX_REVEAL_WINDOW + 1));
+    assert_eq!(too_long, Err(Ok(PredifiError::TimeConstraintError)));
+
+    commit(&ctx, pool_id, 0, 8);
+    let staked = ctx
+        .client
+        .try_enable_commit_reveal(&ctx.creator, &pool_id, &config(100));
+    assert_eq!(staked, Err(Ok(PredifiError::InvalidPoolState)));
+}
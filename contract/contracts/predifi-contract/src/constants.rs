@@ -313,6 +313,75 @@ pub const INITIAL_LIQUIDITY_SAFETY_MARGIN_BPS: u32 = 100;
 /// `emergency_cancel_pool` (issue #1119).
 pub const EMERGENCY_CANCEL_MULTISIG_THRESHOLD: u32 = 2;
 
+/// Maximum post-resolution challenge window for disputes.
+///
+/// **Units:** Seconds
+/// **Value:** 604,800 seconds (7 days)
+///
+/// **Rationale:** Winnings cannot be claimed while a resolved pool can still be
+/// disputed, so the window directly delays every payout. Capping it at a week
+/// keeps a misconfigured `DisputeConfig` from locking funds indefinitely.
+///
+/// **Impact of changes:**
+/// - Increasing this value allows longer review periods but delays claims further.
+/// - The configured window must also stay below `Config.claim_window_seconds`,
+///   otherwise no claim could ever succeed.
+///
+/// **Used for:** Validating `DisputeConfig.challenge_window` in `set_dispute_config`.
+pub const MAX_DISPUTE_CHALLENGE_WINDOW: u64 = 604_800;
+
+/// Maximum reveal window of a commit-reveal pool.
+///
+/// **Units:** Seconds
+/// **Value:** 259,200 seconds (3 days)
+///
+/// **Rationale:** A commit-reveal pool cannot resolve until its reveal window
+/// after `end_time` closes, so the window delays every payout. Three days is
+/// ample time for committed bettors to reveal.
+///
+/// **Impact of changes:**
+/// - Increasing this value lets creators give bettors more time to reveal.
+/// - Decreasing it speeds up resolution but risks forfeiting slow revealers.
+///
+/// **Used for:** Validating `CommitRevealConfig.reveal_window` in `enable_commit_reveal`.
+pub const MAX_REVEAL_WINDOW: u64 = 259_200;
+
+/// Share of an unrevealed commitment kept when a pool refunds it.
+///
+/// **Units:** Basis points (1 bp = 0.01%)
+/// **Value:** 1,000 bps (10%)
+///
+/// **Rationale:** Under `UnrevealedPolicy::Refund` a bettor can commit, watch
+/// the reveals, and simply not reveal a pick that looks lost. Without a cost
+/// that is a free option against everyone who revealed. The penalty goes to
+/// the treasury as protocol fees.
+///
+/// **Impact of changes:**
+/// - Increasing this value makes skipping a reveal costlier, approaching
+///   `UnrevealedPolicy::Forfeit`.
+/// - Decreasing it cheapens the option of not revealing.
+///
+/// **Used for:** `settle_commitment` on refund-policy pools that were not
+/// canceled.
+pub const UNREVEALED_REFUND_PENALTY_BPS: u32 = 1_000;
+
+/// Maximum liveness window for optimistic outcome proposals.
+///
+/// **Units:** Seconds
+/// **Value:** 259,200 seconds (3 days)
+///
+/// **Rationale:** An unchallenged proposal only becomes final once its liveness
+/// window ends, so the window delays resolution of every optimistic pool.
+/// Three days gives watchers time to challenge a wrong proposal without
+/// leaving winners waiting for a week.
+///
+/// **Impact of changes:**
+/// - Increasing this value allows creators to choose slower, safer pools.
+/// - Decreasing it shortens the time available to spot and challenge bad proposals.
+///
+/// **Used for:** Validating `PoolConfig.optimistic_liveness` in `create_pool`.
+pub const MAX_OPTIMISTIC_LIVENESS: u64 = 259_200;
+
 // ═══════════════════════════════════════════════════════════════════════════
 // MONITORING & ALERT THRESHOLDS
 // ═══════════════════════════════════════════════════════════════════════════
@@ -339,6 +408,43 @@ pub const EMERGENCY_CANCEL_MULTISIG_THRESHOLD: u32 = 2;
 /// **Used for:** Triggering `HighValuePredictionEvent` in `place_prediction`.
 pub const HIGH_VALUE_THRESHOLD: i128 = 1_000_000_000;
 
+/// Exit fee charged by `withdraw_prediction` until `set_exit_fee_bps` is
+/// called for the pool.
+///
+/// **Units:** Basis points (1 bp = 0.01%)
+/// **Value:** 200 bps (2%)
+///
+/// **Rationale:** A free exit would let anyone who learns of bad news pull
+/// their stake at no cost while the other side cannot, turning every open
+/// pool into a free option. The fee stays in the pool for the remaining
+/// participants, so they are compensated for the liquidity that leaves.
+///
+/// **Used for:** `withdraw_prediction` when no per-pool exit fee is stored.
+pub const DEFAULT_EXIT_FEE_BPS: u32 = 200;
+
+/// Upper bound for a pool's exit fee.
+///
+/// **Units:** Basis points
+/// **Value:** 2,000 bps (20%)
+///
+/// **Rationale:** Keeps an operator from effectively locking stakes in by
+/// setting a confiscatory exit fee.
+///
+/// **Used for:** Validating `set_exit_fee_bps`.
+pub const MAX_EXIT_FEE_BPS: u32 = 2_000;
+
+/// Default cap on the creator fee a pool may set.
+///
+/// **Units:** Basis points of the pool's total stake
+/// **Value:** 100 bps (1%)
+///
+/// **Rationale:** The creator fee is carved out of the protocol fee, so the
+/// cap bounds how much of that fee the admin is prepared to hand to pool
+/// creators. Admins can change it with `set_max_creator_fee_bps`.
+///
+/// **Used for:** Validating `PoolConfig::creator_fee_bps` in `create_pool`.
+pub const DEFAULT_MAX_CREATOR_FEE_BPS: u32 = 100;
+
 /// Maximum tolerance in basis points (1 bp = 0.01%).
 ///
 /// **Units:** Basis points (bps)
@@ -378,6 +484,85 @@ pub const MAX_TOLERANCE: u32 = 10_000;
 /// **Used for:** Validating price condition complexity during oracle-based resolution.
 pub const MAX_PRICE_CONDITION_MATCH_STEPS: u32 = 4;
 
+/// Number of observations kept per feed pair for aggregated resolution.
+///
+/// **Units:** Count (dimensionless)
+/// **Value:** 32 observations
+///
+/// **Rationale:** TWAP and median resolution read the whole buffer, so its size
+/// bounds the cost of `resolve_pool_from_price`. Thirty-two entries cover
+/// several oracles pushing every few minutes over a typical averaging window.
+///
+/// **Impact of changes:**
+/// - Increasing this value allows longer windows or more oracles at a higher
+///   storage and resolution cost.
+/// - Decreasing it may leave a window with too few observations to resolve.
+///
+/// **Used for:** Bounding `OracleKey::PriceObservations` in `update_price_feed`.
+pub const MAX_PRICE_OBSERVATIONS: u32 = 32;
+
+/// Maximum number of legs in one parlay.
+///
+/// **Units:** Count (dimensionless)
+/// **Value:** 8 legs
+///
+/// **Rationale:** Settling a parlay loads every leg's pool, so the leg count
+/// bounds the cost of `settle_parlay` and `claim_parlay`. Eight legs covers a
+/// full weekend accumulator while keeping the combined odds, and so the
+/// liability reserved in the combo vault, within reason.
+///
+/// **Impact of changes:**
+/// - Increasing this value allows longer accumulators at a higher settlement
+///   cost and larger worst-case vault liabilities.
+/// - Decreasing it rejects parlays that are valid today.
+///
+/// **Used for:** Validating `legs` in `place_parlay`.
+pub const MAX_PARLAY_LEGS: u32 = 8;
+
+/// Highest odds a single parlay leg may be priced at.
+///
+/// **Units:** Basis points of the stake (10_000 = 1.0x)
+/// **Value:** 100_000 (10.0x)
+///
+/// **Rationale:** Legs are priced from a live parimutuel snapshot, which a
+/// bettor can skew with a few opposing stakes placed just before the parlay.
+/// Capping each leg bounds what such a snapshot can be worth to the vault.
+///
+/// **Impact of changes:**
+/// - Increasing this value admits longer-shot legs and larger skewed odds.
+/// - Decreasing it rejects more legs on lopsided pools.
+///
+/// **Used for:** Validating each leg's odds in `place_parlay`.
+pub const MAX_PARLAY_LEG_ODDS_BPS: u64 = 100_000;
+
+/// Highest combined multiplier of a parlay, before the protocol fee.
+///
+/// **Units:** Basis points of the stake (10_000 = 1.0x)
+/// **Value:** 1_000_000 (100.0x)
+///
+/// **Rationale:** Leg odds compound, so several capped legs can still add up
+/// to a payout the combo vault should not underwrite. This bounds the
+/// vault's liability per unit staked.
+///
+/// **Impact of changes:**
+/// - Increasing this value allows bigger accumulators and vault liabilities.
+/// - Decreasing it rejects parlays with many legs at high odds.
+///
+/// **Used for:** Validating the gross payout in `place_parlay`.
+pub const MAX_PARLAY_ODDS_BPS: i128 = 1_000_000;
+
+/// Maximum number of referral campaigns open at once.
+///
+/// **Units:** Count (dimensionless)
+/// **Value:** 10 campaigns
+///
+/// **Rationale:** A referred user's first prediction in a pool scans every
+/// open campaign for one covering the pool, so the count bounds the cost of
+/// `place_prediction`. Campaigns past their `end_time` stop counting.
+///
+/// **Used for:** Validating `create_referral_campaign`.
+pub const MAX_OPEN_REFERRAL_CAMPAIGNS: u32 = 10;
+
 // ═══════════════════════════════════════════════════════════════════════════
 // VERSION CONSTANTS
 // ═══════════════════════════════════════════════════════════════════════════
@@ -385,7 +570,7 @@ pub const MAX_PRICE_CONDITION_MATCH_STEPS: u32 = 4;
 /// Current contract version. Bump on each release to support safe migrations.
 ///
 /// **Units:** Version number (dimensionless)
-/// **Value:** 1
+/// **Value:** 2
 ///
 /// **Rationale:** Contract upgrades require version tracking to enable safe state migrations.
 /// This version is stored in instance storage and checked during upgrades to ensure
@@ -399,7 +584,7 @@ pub const MAX_PRICE_CONDITION_MATCH_STEPS: u32 = 4;
 /// - Changing this without proper migration logic can cause upgrade failures or data corruption.
 ///
 /// **Used for:** Version tracking in instance storage and upgrade migration logic.
-pub const CONTRACT_VERSION: u32 = 1;
+pub const CONTRACT_VERSION: u32 = 2;
 
 /// Minimum timelock delay in seconds for protocol fee changes.
 ///
@@ -422,6 +607,73 @@ pub const CONTRACT_VERSION: u32 = 1;
 /// **Used for:** Calculating `PendingFeeChange.effective_at` when queuing fee changes.
 pub const FEE_CHANGE_TIMELOCK_SECONDS: u64 = 86_400;
 
+/// Minimum delay between proposing a contract upgrade and executing it.
+///
+/// **Units:** Seconds
+/// **Value:** 86,400 seconds (1 day)
+///
+/// **Rationale:** An upgrade replaces every other control in the contract.
+/// Integrators and users need a guaranteed window to review the queued WASM
+/// hash and exit before new code goes live, whatever delay admins configure.
+///
+/// **Impact of changes:**
+/// - Increasing this value lengthens the guaranteed exit window but slows
+///   down emergency fixes.
+/// - Lowering it weakens the guarantee for every deployment.
+///
+/// **Used for:** Validating `UpgradePolicy.delay` in `set_upgrade_policy`.
+pub const MIN_UPGRADE_TIMELOCK_SECONDS: u64 = 86_400;
+
+/// Upgrade delay used until `set_upgrade_policy` is called.
+///
+/// **Units:** Seconds
+/// **Value:** 172,800 seconds (2 days)
+///
+/// **Rationale:** Leaves a full day beyond the minimum for approvers to
+/// coordinate and for integrators to react to `UpgradeProposedEvent`.
+///
+/// **Used for:** `PendingUpgrade.executable_at` when no policy is stored.
+pub const DEFAULT_UPGRADE_TIMELOCK_SECONDS: u64 = 172_800;
+
+/// Admin approvals an upgrade needs until `set_upgrade_policy` is called.
+///
+/// **Value:** 1 (the proposer's own approval)
+///
+/// **Rationale:** The number of admins is only known to the access-control
+/// contract, so the default cannot assume a second signer exists. Deployments
+/// with several admins should raise it to N-of-M with `set_upgrade_policy`.
+///
+/// **Used for:** The approval check in `upgrade_contract` when no policy is stored.
+pub const DEFAULT_UPGRADE_APPROVALS: u32 = 1;
+
+/// Approvals `pause` and `emergency_withdraw` need until
+/// `set_approval_policy` is called.
+///
+/// **Value:** 2 (the proposer and one other admin)
+///
+/// **Rationale:** `emergency_withdraw` can move every token the contract
+/// holds and `pause` freezes all users, so one compromised admin key must
+/// not be enough for either. Deployments have to grant a second admin before
+/// these actions can run.
+///
+/// **Used for:** `ApprovalPolicy.threshold` of every sensitive action except
+/// emergency cancellation, which defaults to [`EMERGENCY_CANCEL_MULTISIG_THRESHOLD`].
+pub const DEFAULT_APPROVAL_THRESHOLD: u32 = 2;
+
+/// How long a sensitive-action proposal collects approvals until
+/// `set_approval_policy` is called.
+///
+/// **Units:** Seconds
+/// **Value:** 86,400 seconds (24 hours)
+///
+/// **Rationale:** Emergency actions are meant to be signed off quickly; a
+/// proposal still short of its threshold a day later is stale, and letting
+/// it linger would allow approvals collected for an old situation to be
+/// completed long after the fact.
+///
+/// **Used for:** `ApprovalRequest.expires_at` when no policy is stored.
+pub const DEFAULT_APPROVAL_EXPIRY_SECONDS: u64 = 86_400;
+
 #[cfg(test)]
 #[allow(clippy::assertions_on_constants)]
 mod tests {
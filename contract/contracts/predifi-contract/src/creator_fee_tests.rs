@@ -0,0 +1,121 @@
+//! The pool creator's cut of the protocol fee: `creator_fee_bps` against the
+//! admin cap, the split at resolution, and `claim_creator_fees`.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{FeeTier, MarketState, PoolConfig, PredifiError, DEFAULT_MAX_CREATOR_FEE_BPS};
+use soroban_sdk::{symbol_short, testutils::Ledger, vec, Address, Env};
+
+const END_TIME: u64 = 10_000;
+
+/// Charges a flat `protocol_fee_bps` on every pool and lets creators take up
+/// to 5% of it.
+fn set_protocol_fee(ctx: &TestContext, protocol_fee_bps: u32) {
+    let tier = FeeTier {
+        stake_threshold: 0,
+        fee_bps: protocol_fee_bps,
+    };
+    ctx.client
+        .set_fee_tiers(&ctx.admin, &vec![&ctx.client.env, tier]);
+    ctx.client.set_max_creator_fee_bps(&ctx.admin, &500u32);
+}
+
+fn pool_config(env: &Env, creator_fee_bps: u32) -> PoolConfig {
+    PoolConfig {
+        creator_fee_bps,
+        ..default_pool_config(env)
+    }
+}
+
+/// A pool with 1_000 staked on each outcome. Returns `(pool_id, home)`.
+fn staked_pool(ctx: &TestContext, creator_fee_bps: u32) -> (u64, Address) {
+    let env = &ctx.client.env;
+    let pool_id = ctx.create_pool(END_TIME, &pool_config(env, creator_fee_bps));
+    let home = ctx.staker(pool_id, 1_000, 0);
+    ctx.staker(pool_id, 1_000, 1);
+    (pool_id, home)
+}
+
+/// A 2% creator fee on a 5% protocol fee: winners are paid as before, the
+/// creator takes 40 of the 100 fee and the treasury accrues the other 60.
+#[test]
+fn test_creator_fee_carved_out_of_protocol_fee() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    set_protocol_fee(&ctx, 500);
+    let (pool_id, home) = staked_pool(&ctx, 200);
+    assert_eq!(ctx.client.get_creator_fee_bps(&pool_id), 200);
+    assert_eq!(ctx.client.get_pool_config(&pool_id).creator_fee_bps, 200);
+
+    let early = ctx.client.try_claim_creator_fees(&pool_id);
+    assert_eq!(early, Err(Ok(PredifiError::PoolNotResolved)));
+    assert_eq!(ctx.client.get_pool_stats(&pool_id).creator_fee, 0);
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &0u32);
+    assert_eq!(ctx.client.get_pool(&pool_id).state, MarketState::Resolved);
+    assert_eq!(ctx.client.get_pool_stats(&pool_id).creator_fee, 40);
+
+    assert_eq!(ctx.client.claim_winnings(&home, &pool_id), 1_900);
+    assert_eq!(ctx.client.get_accrued_fees(&ctx.token_address), 60);
+
+    let before = ctx.token.balance(&ctx.creator);
+    assert_eq!(ctx.client.claim_creator_fees(&pool_id), 40);
+    assert_eq!(ctx.token.balance(&ctx.creator), before + 40);
+    assert_eq!(ctx.token.balance(&ctx.client.address), 60);
+
+    let again = ctx.client.try_claim_creator_fees(&pool_id);
+    assert_eq!(again, Err(Ok(PredifiError::AlreadyClaimed)));
+}
+
+/// A creator fee above the protocol fee is capped at it.
+#[test]
+fn test_creator_fee_capped_at_protocol_fee() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    set_protocol_fee(&ctx, 100);
+    let (pool_id, home) = staked_pool(&ctx, 300);
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &0u32);
+
+    assert_eq!(ctx.client.claim_winnings(&home, &pool_id), 1_980);
+    assert_eq!(ctx.client.get_accrued_fees(&ctx.token_address), 0);
+    assert_eq!(ctx.client.claim_creator_fees(&pool_id), 20);
+}
+
+/// The creator fee is capped by the admin limit, which is admin-only and
+/// itself at most 100%; pools without a creator fee owe nothing.
+#[test]
+fn test_creator_fee_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    set_protocol_fee(&ctx, 500);
+    assert_eq!(ctx.client.get_max_creator_fee_bps(), 500);
+
+    let too_high = ctx.client.try_create_pool(
+        &ctx.creator,
+        &END_TIME,
+        &ctx.token_address,
+        &2u32,
+        &symbol_short!("Sports"),
+        &pool_config(&env, 501),
+    );
+    assert_eq!(too_high, Err(Ok(PredifiError::InvalidFeeBps)));
+
+    let not_admin = ctx
+        .client
+        .try_set_max_creator_fee_bps(&ctx.operator, &DEFAULT_MAX_CREATOR_FEE_BPS);
+    assert_eq!(not_admin, Err(Ok(PredifiError::Unauthorized)));
+    let above_total = ctx
+        .client
+        .try_set_max_creator_fee_bps(&ctx.admin, &10_001u32);
+    assert_eq!(above_total, Err(Ok(PredifiError::InvalidFeeBps)));
+
+    let (pool_id, _) = staked_pool(&ctx, 0);
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &0u32);
+    let nothing = ctx.client.try_claim_creator_fees(&pool_id);
+    assert_eq!(nothing, Err(Ok(PredifiError::InsufficientBalance)));
+}
@@ -0,0 +1,339 @@
+//! Dispute domain: moderator-opened disputes with a bond, the post-resolution
+//! challenge window, and admin arbitration of the final outcome.
+
+use soroban_sdk::{contractimpl, token, Address, Env, String};
+
+use crate::{
+    DataKey, Dispute, DisputeConfig, DisputeConfigUpdateEvent, DisputeOpenedEvent, DisputeRuling,
+    DisputeSettledEvent, MarketState, Pool, PoolCanceledEvent, PoolDisputedEvent,
+    PoolResolvedEvent, PredifiContract, PredifiContractArgs, PredifiContractClient, PredifiError,
+    ScalarRange, MAX_DISPUTE_CHALLENGE_WINDOW,
+};
+
+#[contractimpl]
+impl PredifiContract {
+    /// Configure the dispute bond and the post-resolution challenge window.
+    /// Caller must have Admin role (0).
+    ///
+    /// # Errors
+    /// - `InvalidFeeBps` – `bond_bps > 10_000`.
+    /// - `InvalidAmount` – `min_bond` is negative.
+    /// - `InvalidData`   – `challenge_window` exceeds `MAX_DISPUTE_CHALLENGE_WINDOW`
+    ///   or is not shorter than the configured claim window.
+    pub fn set_dispute_config(
+        env: Env,
+        admin: Address,
+        config: DisputeConfig,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        admin.require_auth();
+        Self::require_admin_role(&env, &admin, "set_dispute_config")?;
+
+        if !Self::is_valid_fee_bps(config.bond_bps) {
+            return Err(PredifiError::InvalidFeeBps);
+        }
+        if config.min_bond < 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+        if config.challenge_window > MAX_DISPUTE_CHALLENGE_WINDOW
+            || config.challenge_window >= Self::get_config(&env).claim_window_seconds
+        {
+            return Err(PredifiError::InvalidData);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::DisputeConfig, &config);
+        Self::extend_instance(&env);
+
+        DisputeConfigUpdateEvent {
+            admin,
+            bond_bps: config.bond_bps,
+            min_bond: config.min_bond,
+            challenge_window: config.challenge_window,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Return the current dispute parameters (all zero when never configured).
+    pub fn get_dispute_config(env: Env) -> DisputeConfig {
+        Self::read_dispute_config(&env)
+    }
+
+    /// Open a dispute on a pool. Only callable by a Moderator (role 2).
+    ///
+    /// An `Active` pool can be disputed at any time; a `Resolved` pool only
+    /// while its challenge window (`DisputeConfig.challenge_window` seconds
+    /// after resolution) is open. Each phase allows one dispute, so a pool
+    /// disputed while active can be disputed again once resolved. The
+    /// moderator posts a bond in the pool token of
+    /// `max(min_bond, total_stake * bond_bps / 10_000)`, which is returned or
+    /// slashed when the dispute is settled.
+    ///
+    /// The pool moves to `Disputed`, which blocks predictions, resolution,
+    /// cancellation and claims until [`Self::settle_dispute`] is called.
+    ///
+    /// # Errors
+    /// - `Unauthorized` – caller does not hold the Moderator role.
+    /// - `PoolNotFound` – `pool_id` does not exist.
+    /// - `InvalidPoolState` – pool is neither `Active` nor `Resolved`.
+    /// - `ChallengeWindowClosed` – the pool was already disputed in its
+    ///   current phase (before or after resolution), or it is resolved and its
+    ///   challenge window has elapsed.
+    pub fn flag_disputed_pool(
+        env: Env,
+        moderator: Address,
+        pool_id: u64,
+        reason: String,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        moderator.require_auth();
+        Self::require_role(&env, &moderator, 2)?;
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .ok_or(PredifiError::PoolNotFound)?;
+        Self::extend_persistent(&env, &pool_key);
+
+        // One dispute before resolution and one during the challenge window.
+        let dispute_key = DataKey::Dispute(pool_id);
+        if let Some(previous) = env.storage().persistent().get::<_, Dispute>(&dispute_key) {
+            if previous.prior_state == MarketState::Resolved || pool.state != MarketState::Resolved
+            {
+                return Err(PredifiError::ChallengeWindowClosed);
+            }
+        }
+
+        let dispute_config = Self::read_dispute_config(&env);
+        match pool.state {
+            MarketState::Active => {}
+            MarketState::Resolved => {
+                let window_end = pool
+                    .resolution_timestamp
+                    .map(|t| t.saturating_add(dispute_config.challenge_window))
+                    .unwrap_or(0);
+                if env.ledger().timestamp() >= window_end {
+                    return Err(PredifiError::ChallengeWindowClosed);
+                }
+            }
+            _ => return Err(PredifiError::InvalidPoolState),
+        }
+
+        let bond = Self::dispute_bond(&pool, &dispute_config)?;
+
+        let dispute = Dispute {
+            moderator: moderator.clone(),
+            bond,
+            reason: reason.clone(),
+            prior_state: pool.state,
+            prior_outcome: pool.outcome,
+            opened_at: env.ledger().timestamp(),
+            ruling: DisputeRuling::Pending,
+        };
+
+        pool.state = MarketState::Disputed;
+        env.storage().persistent().set(&pool_key, &pool);
+        Self::extend_persistent(&env, &pool_key);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Disputed(pool_id), &());
+        Self::extend_persistent(&env, &DataKey::Disputed(pool_id));
+
+        env.storage().persistent().set(&dispute_key, &dispute);
+        Self::extend_persistent(&env, &dispute_key);
+
+        if bond > 0 {
+            Self::enter_reentrancy_guard(&env);
+            let token_client = token::Client::new(&env, &pool.token);
+            token_client.transfer(&moderator, env.current_contract_address(), &bond);
+            Self::exit_reentrancy_guard(&env);
+        }
+
+        PoolDisputedEvent {
+            pool_id,
+            moderator: moderator.clone(),
+            reason: reason.clone(),
+        }
+        .publish(&env);
+
+        DisputeOpenedEvent {
+            pool_id,
+            moderator,
+            bond,
+            prior_state: dispute.prior_state,
+            reason,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Settle an open dispute. Caller must have Admin role (0).
+    ///
+    /// - `UpholdOriginal` restores the pool's prior state and outcome and
+    ///   slashes the moderator's bond to the treasury.
+    /// - `OverrideOutcome(n)` resolves the pool to outcome `n` and returns the bond.
+    /// - `CancelAndRefund` cancels the pool so stakers can call `claim_refund`,
+    ///   and returns the bond.
+    ///
+    /// A pool that ends up `Resolved` gets a fresh `resolution_timestamp`, so
+    /// users keep their full claim window after the dispute.
+    ///
+    /// # Errors
+    /// - `Unauthorized` – caller lacks Admin role.
+    /// - `PoolNotFound` – no pool exists for `pool_id`.
+    /// - `DisputeNotFound` – the pool has no open dispute.
+    /// - `InvalidOutcome` – override outcome is out of range.
+    /// - `InvalidData` – `ruling` is `Pending`.
+    pub fn settle_dispute(
+        env: Env,
+        admin: Address,
+        pool_id: u64,
+        ruling: DisputeRuling,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        admin.require_auth();
+        Self::require_admin_role(&env, &admin, "settle_dispute")?;
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .ok_or(PredifiError::PoolNotFound)?;
+        Self::extend_persistent(&env, &pool_key);
+
+        let dispute_key = DataKey::Dispute(pool_id);
+        let mut dispute: Dispute = env
+            .storage()
+            .persistent()
+            .get(&dispute_key)
+            .ok_or(PredifiError::DisputeNotFound)?;
+        if pool.state != MarketState::Disputed || dispute.ruling != DisputeRuling::Pending {
+            return Err(PredifiError::DisputeNotFound);
+        }
+
+        let now = env.ledger().timestamp();
+        let bond_slashed = match ruling {
+            DisputeRuling::UpholdOriginal => {
+                pool.state = dispute.prior_state;
+                pool.outcome = dispute.prior_outcome;
+                if pool.state == MarketState::Resolved {
+                    pool.resolution_timestamp = Some(now);
+                }
+                true
+            }
+            DisputeRuling::OverrideOutcome(outcome) => {
+                if outcome >= pool.options_count {
+                    return Err(PredifiError::InvalidOutcome);
+                }
+                if dispute.prior_state != MarketState::Resolved {
+                    pool.fee_bps = Self::calculate_dynamic_fee(&env, &pool);
+                }
+                pool.state = MarketState::Resolved;
+                pool.outcome = outcome;
+                pool.resolution_timestamp = Some(now);
+                // An overridden scalar pool pays the ruled outcome only.
+                let range_key = DataKey::ScalarRange(pool_id);
+                if let Some(mut range) =
+                    env.storage().persistent().get::<_, ScalarRange>(&range_key)
+                {
+                    range.settlement_bps = None;
+                    env.storage().persistent().set(&range_key, &range);
+                }
+                false
+            }
+            DisputeRuling::CancelAndRefund => {
+                pool.state = MarketState::Canceled;
+                false
+            }
+            DisputeRuling::Pending => return Err(PredifiError::InvalidData),
+        };
+
+        env.storage().persistent().set(&pool_key, &pool);
+        Self::bump_ttl(&env, &pool_key);
+        if pool.state != MarketState::Active {
+            Self::remove_from_active_index(&env, pool_id);
+            Self::enter_reentrancy_guard(&env);
+            Self::settle_outcome_proposal(&env, pool_id, &pool)?;
+            Self::exit_reentrancy_guard(&env);
+        }
+        if pool.state == MarketState::Canceled {
+            Self::credit_retained_exit_fees(&env, pool_id, &pool)?;
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Disputed(pool_id));
+
+        dispute.ruling = ruling.clone();
+        env.storage().persistent().set(&dispute_key, &dispute);
+        Self::extend_persistent(&env, &dispute_key);
+
+        if dispute.bond > 0 {
+            let recipient = if bond_slashed {
+                Self::get_config(&env).treasury
+            } else {
+                dispute.moderator.clone()
+            };
+            Self::enter_reentrancy_guard(&env);
+            let token_client = token::Client::new(&env, &pool.token);
+            token_client.transfer(&env.current_contract_address(), &recipient, &dispute.bond);
+            Self::exit_reentrancy_guard(&env);
+        }
+
+        match ruling {
+            DisputeRuling::OverrideOutcome(outcome) => PoolResolvedEvent {
+                pool_id,
+                operator: admin.clone(),
+                outcome,
+            }
+            .publish(&env),
+            DisputeRuling::CancelAndRefund => PoolCanceledEvent {
+                pool_id,
+                caller: admin.clone(),
+                reason: dispute.reason.clone(),
+                operator: admin.clone(),
+            }
+            .publish(&env),
+            DisputeRuling::UpholdOriginal | DisputeRuling::Pending => {}
+        }
+
+        DisputeSettledEvent {
+            pool_id,
+            arbitrator: admin,
+            ruling,
+            new_state: pool.state,
+            bond: dispute.bond,
+            bond_slashed,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Return the dispute record for a pool, if one was ever opened.
+    pub fn get_dispute(env: Env, pool_id: u64) -> Option<Dispute> {
+        let key = DataKey::Dispute(pool_id);
+        let dispute = env.storage().persistent().get(&key);
+        if dispute.is_some() {
+            Self::extend_persistent(&env, &key);
+        }
+        dispute
+    }
+
+    /// Bond required to dispute `pool`: `max(min_bond, total_stake * bond_bps / 10_000)`.
+    fn dispute_bond(pool: &Pool, config: &DisputeConfig) -> Result<i128, PredifiError> {
+        let proportional = pool
+            .total_stake
+            .checked_mul(config.bond_bps as i128)
+            .ok_or(PredifiError::ArithmeticError)?
+            / 10_000;
+        Ok(core::cmp::max(proportional, config.min_bond))
+    }
+}
@@ -0,0 +1,319 @@
+//! Bonded disputes: the post-resolution challenge window and each
+//! arbitration ruling.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{DisputeConfig, DisputeRuling, MarketState, PredifiError};
+use soroban_sdk::{testutils::Ledger, Address, Env, String};
+
+const ROLE_MODERATOR: u32 = 2;
+
+/// A pool with 700 staked on outcome 0 and 300 on outcome 1, a moderator
+/// holding 1_000, and a dispute config of 10% bond (min 50) with a one-hour
+/// challenge window. Returns `(pool_id, moderator, user_a, user_b)`.
+fn staked_pool(ctx: &TestContext) -> (u64, Address, Address, Address) {
+    let moderator = ctx.grant(ROLE_MODERATOR);
+    ctx.token_admin_client.mint(&moderator, &1_000);
+    ctx.client.set_dispute_config(
+        &ctx.admin,
+        &DisputeConfig {
+            bond_bps: 1_000,
+            min_bond: 50,
+            challenge_window: 3_600,
+        },
+    );
+
+    let pool_id = ctx.create_pool(10_000, &default_pool_config(&ctx.client.env));
+    let user_a = ctx.staker(pool_id, 700, 0);
+    let user_b = ctx.staker(pool_id, 300, 1);
+    (pool_id, moderator, user_a, user_b)
+}
+
+/// Opening a dispute escrows a bond proportional to total stake.
+#[test]
+fn test_flag_disputed_pool_escrows_bond() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, moderator, _user_a, _user_b) = staked_pool(&ctx);
+
+    ctx.client
+        .flag_disputed_pool(&moderator, &pool_id, &String::from_str(&env, "bad"));
+
+    assert_eq!(ctx.client.get_pool(&pool_id).state, MarketState::Disputed);
+    let dispute = ctx.client.get_dispute(&pool_id).unwrap();
+    assert_eq!(dispute.bond, 100);
+    assert_eq!(dispute.prior_state, MarketState::Active);
+    assert_eq!(dispute.ruling, DisputeRuling::Pending);
+    assert_eq!(ctx.token.balance(&moderator), 900);
+}
+
+/// The minimum bond applies when the proportional bond is smaller.
+#[test]
+fn test_flag_disputed_pool_applies_min_bond() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, moderator, _user_a, _user_b) = staked_pool(&ctx);
+
+    ctx.client.set_dispute_config(
+        &ctx.admin,
+        &DisputeConfig {
+            bond_bps: 10,
+            min_bond: 50,
+            challenge_window: 3_600,
+        },
+    );
+    ctx.client
+        .flag_disputed_pool(&moderator, &pool_id, &String::from_str(&env, "bad"));
+
+    assert_eq!(ctx.client.get_dispute(&pool_id).unwrap().bond, 50);
+}
+
+/// A pool cannot be disputed twice.
+#[test]
+fn test_flag_disputed_pool_twice_fails() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, moderator, _user_a, _user_b) = staked_pool(&ctx);
+    let reason = String::from_str(&env, "bad");
+
+    ctx.client.flag_disputed_pool(&moderator, &pool_id, &reason);
+    ctx.client
+        .settle_dispute(&ctx.admin, &pool_id, &DisputeRuling::UpholdOriginal);
+
+    let result = ctx
+        .client
+        .try_flag_disputed_pool(&moderator, &pool_id, &reason);
+    assert_eq!(result, Err(Ok(PredifiError::ChallengeWindowClosed)));
+}
+
+/// Flagging a pool that does not exist is an error, not a trap.
+#[test]
+fn test_flag_unknown_pool_fails() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (_pool_id, moderator, _user_a, _user_b) = staked_pool(&ctx);
+
+    let result =
+        ctx.client
+            .try_flag_disputed_pool(&moderator, &999, &String::from_str(&env, "bad"));
+    assert_eq!(result, Err(Ok(PredifiError::PoolNotFound)));
+}
+
+/// Overriding the outcome resolves the pool, returns the bond, and pays the
+/// new winners once settled.
+#[test]
+fn test_settle_dispute_override_outcome() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, moderator, user_a, user_b) = staked_pool(&ctx);
+
+    env.ledger().with_mut(|li| li.timestamp = 10_001);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &0u32);
+
+    ctx.client
+        .flag_disputed_pool(&moderator, &pool_id, &String::from_str(&env, "wrong"));
+    assert_eq!(
+        ctx.client.get_dispute(&pool_id).unwrap().prior_state,
+        MarketState::Resolved
+    );
+
+    ctx.client
+        .settle_dispute(&ctx.admin, &pool_id, &DisputeRuling::OverrideOutcome(1));
+
+    let pool = ctx.client.get_pool(&pool_id);
+    assert_eq!(pool.state, MarketState::Resolved);
+    assert_eq!(pool.outcome, 1);
+    assert_eq!(ctx.token.balance(&moderator), 1_000);
+    assert_eq!(
+        ctx.client.get_dispute(&pool_id).unwrap().ruling,
+        DisputeRuling::OverrideOutcome(1)
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 10_001 + 3_600);
+    assert_eq!(ctx.client.claim_winnings(&user_b, &pool_id), 1_000);
+    assert_eq!(ctx.client.claim_winnings(&user_a, &pool_id), 0);
+}
+
+/// Upholding the original outcome slashes the bond to the treasury.
+#[test]
+fn test_settle_dispute_uphold_slashes_bond() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, moderator, _user_a, _user_b) = staked_pool(&ctx);
+
+    ctx.client
+        .flag_disputed_pool(&moderator, &pool_id, &String::from_str(&env, "bad"));
+    ctx.client
+        .settle_dispute(&ctx.admin, &pool_id, &DisputeRuling::UpholdOriginal);
+
+    assert_eq!(ctx.client.get_pool(&pool_id).state, MarketState::Active);
+    assert_eq!(ctx.token.balance(&ctx.treasury), 100);
+    assert_eq!(ctx.token.balance(&moderator), 900);
+
+    // The pool is back in play and can be resolved normally.
+    env.ledger().with_mut(|li| li.timestamp = 10_001);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &0u32);
+    assert_eq!(ctx.client.get_pool(&pool_id).state, MarketState::Resolved);
+}
+
+/// Cancel-and-refund lets every staker recover their stake.
+#[test]
+fn test_settle_dispute_cancel_and_refund() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, moderator, user_a, user_b) = staked_pool(&ctx);
+
+    ctx.client
+        .flag_disputed_pool(&moderator, &pool_id, &String::from_str(&env, "bad"));
+    ctx.client
+        .settle_dispute(&ctx.admin, &pool_id, &DisputeRuling::CancelAndRefund);
+
+    assert_eq!(ctx.client.get_pool(&pool_id).state, MarketState::Canceled);
+    assert_eq!(ctx.token.balance(&moderator), 1_000);
+    assert_eq!(ctx.client.claim_refund(&user_a, &pool_id), 700);
+    assert_eq!(ctx.client.claim_refund(&user_b, &pool_id), 300);
+}
+
+/// Overriding to an out-of-range outcome is rejected.
+#[test]
+fn test_settle_dispute_invalid_outcome() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, moderator, _user_a, _user_b) = staked_pool(&ctx);
+
+    ctx.client
+        .flag_disputed_pool(&moderator, &pool_id, &String::from_str(&env, "bad"));
+    let result =
+        ctx.client
+            .try_settle_dispute(&ctx.admin, &pool_id, &DisputeRuling::OverrideOutcome(2));
+    assert_eq!(result, Err(Ok(PredifiError::InvalidOutcome)));
+}
+
+/// Settling a pool with no open dispute fails.
+#[test]
+fn test_settle_dispute_without_dispute_fails() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, _moderator, _user_a, _user_b) = staked_pool(&ctx);
+
+    let result =
+        ctx.client
+            .try_settle_dispute(&ctx.admin, &pool_id, &DisputeRuling::UpholdOriginal);
+    assert_eq!(result, Err(Ok(PredifiError::DisputeNotFound)));
+}
+
+/// Only admins may arbitrate.
+#[test]
+fn test_settle_dispute_requires_admin() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, moderator, _user_a, _user_b) = staked_pool(&ctx);
+
+    ctx.client
+        .flag_disputed_pool(&moderator, &pool_id, &String::from_str(&env, "bad"));
+    let result =
+        ctx.client
+            .try_settle_dispute(&moderator, &pool_id, &DisputeRuling::UpholdOriginal);
+    assert_eq!(result, Err(Ok(PredifiError::Unauthorized)));
+}
+
+/// Claims are held until the challenge window elapses.
+#[test]
+fn test_claims_blocked_during_challenge_window() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, _moderator, user_a, _user_b) = staked_pool(&ctx);
+
+    env.ledger().with_mut(|li| li.timestamp = 10_001);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &0u32);
+
+    let result = ctx.client.try_claim_winnings(&user_a, &pool_id);
+    assert_eq!(result, Err(Ok(PredifiError::ChallengeWindowOpen)));
+
+    env.ledger().with_mut(|li| li.timestamp = 10_001 + 3_600);
+    assert_eq!(ctx.client.claim_winnings(&user_a, &pool_id), 1_000);
+}
+
+/// A dispute settled while the pool was active leaves the post-resolution
+/// challenge window in place, and the resolved outcome can still be disputed.
+#[test]
+fn test_active_dispute_keeps_challenge_window() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, moderator, user_a, _user_b) = staked_pool(&ctx);
+    let reason = String::from_str(&env, "early");
+
+    ctx.client.flag_disputed_pool(&moderator, &pool_id, &reason);
+    ctx.client
+        .settle_dispute(&ctx.admin, &pool_id, &DisputeRuling::UpholdOriginal);
+
+    env.ledger().with_mut(|li| li.timestamp = 10_001);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &0u32);
+    let result = ctx.client.try_claim_winnings(&user_a, &pool_id);
+    assert_eq!(result, Err(Ok(PredifiError::ChallengeWindowOpen)));
+
+    ctx.client.flag_disputed_pool(&moderator, &pool_id, &reason);
+    ctx.client
+        .settle_dispute(&ctx.admin, &pool_id, &DisputeRuling::UpholdOriginal);
+    let again = ctx
+        .client
+        .try_flag_disputed_pool(&moderator, &pool_id, &reason);
+    assert_eq!(again, Err(Ok(PredifiError::ChallengeWindowClosed)));
+    assert_eq!(ctx.client.claim_winnings(&user_a, &pool_id), 1_000);
+}
+
+/// A resolved pool cannot be disputed after its challenge window closes.
+#[test]
+fn test_flag_resolved_pool_after_window_fails() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, moderator, _user_a, _user_b) = staked_pool(&ctx);
+
+    env.ledger().with_mut(|li| li.timestamp = 10_001);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &0u32);
+
+    env.ledger().with_mut(|li| li.timestamp = 10_001 + 3_600);
+    let result =
+        ctx.client
+            .try_flag_disputed_pool(&moderator, &pool_id, &String::from_str(&env, "late"));
+    assert_eq!(result, Err(Ok(PredifiError::ChallengeWindowClosed)));
+}
+
+/// Dispute config is validated against its bounds.
+#[test]
+fn test_set_dispute_config_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    staked_pool(&ctx);
+
+    let bad_bps = ctx.client.try_set_dispute_config(
+        &ctx.admin,
+        &DisputeConfig {
+            bond_bps: 10_001,
+            min_bond: 0,
+            challenge_window: 0,
+        },
+    );
+    assert_eq!(bad_bps, Err(Ok(PredifiError::InvalidFeeBps)));
+
+    let bad_bond = ctx.client.try_set_dispute_config(
+        &ctx.admin,
+        &DisputeConfig {
+            bond_bps: 0,
+            min_bond: -1,
+            challenge_window: 0,
+        },
+    );
+    assert_eq!(bad_bond, Err(Ok(PredifiError::InvalidAmount)));
+
+    let bad_window = ctx.client.try_set_dispute_config(
+        &ctx.admin,
+        &DisputeConfig {
+            bond_bps: 0,
+            min_bond: 0,
+            challenge_window: crate::MAX_DISPUTE_CHALLENGE_WINDOW + 1,
+        },
+    );
+    assert_eq!(bad_window, Err(Ok(PredifiError::InvalidData)));
+}
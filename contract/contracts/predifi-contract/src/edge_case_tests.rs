@@ -24,7 +24,7 @@
 
 #![cfg(test)]
 
-use crate::{MarketState, PoolConfig, PredifiContract, PredifiContractClient};
+use crate::{MarketState, PoolConfig, PredifiContract, PredifiContractClient, PricingMode};
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Ledger},
@@ -129,6 +129,10 @@ fn two_outcome_config(env: &Env) -> PoolConfig {
             String::from_str(env, "No"),
             String::from_str(env, "Yes"),
         ],
+        optimistic_liveness: 0,
+        optimistic_bond: 0,
+        pricing_mode: PricingMode::Parimutuel,
+        creator_fee_bps: 0,
     }
 }
 
@@ -485,7 +489,9 @@ fn test_staker_top_up_does_not_double_count_participant() {
 
     // First stake: 400 on outcome 0.
     client.place_prediction(&user, &pool_id, &400, &0, &None, &None);
-    // Top-up: additional 600 on the same outcome.
+    // Top-up: additional 600 on the same outcome, once the cooldown has passed.
+    env.ledger()
+        .with_mut(|li| li.timestamp += crate::DEFAULT_PREDICTION_COOLDOWN_SECONDS);
     client.place_prediction(&user, &pool_id, &600, &0, &None, &None);
 
     let pool = client.get_pool(&pool_id);
@@ -597,10 +603,10 @@ fn test_add_token_to_whitelist_duplicate_token() {
     let token = Address::generate(&env);
 
     client.add_token_to_whitelist(&admin, &token);
-    assert!(client.is_token_whitelisted(&token), "Token should be whitelisted");
+    assert!(client.is_token_allowed(&token), "Token should be whitelisted");
 
     client.add_token_to_whitelist(&admin, &token);
-    assert!(client.is_token_whitelisted(&token), "Token should remain whitelisted after duplicate call");
+    assert!(client.is_token_allowed(&token), "Token should remain whitelisted after duplicate call");
 }
 
 /// Test adding token to whitelist by non-admin is rejected.
@@ -613,7 +619,7 @@ fn test_add_token_to_whitelist_unauthorized() {
 
     let res = client.try_add_token_to_whitelist(&non_admin, &token);
     assert!(res.is_err(), "Non-admin cannot add token to whitelist");
-    assert!(!client.is_token_whitelisted(&token), "Token should not be whitelisted");
+    assert!(!client.is_token_allowed(&token), "Token should not be whitelisted");
 }
 
 /// Test removing and re-adding same token to whitelist.
@@ -624,13 +630,13 @@ fn test_add_token_to_whitelist_remove_and_readd() {
     let token = Address::generate(&env);
 
     client.add_token_to_whitelist(&admin, &token);
-    assert!(client.is_token_whitelisted(&token));
+    assert!(client.is_token_allowed(&token));
 
     client.remove_token_from_whitelist(&admin, &token);
-    assert!(!client.is_token_whitelisted(&token));
+    assert!(!client.is_token_allowed(&token));
 
     client.add_token_to_whitelist(&admin, &token);
-    assert!(client.is_token_whitelisted(&token));
+    assert!(client.is_token_allowed(&token));
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -644,9 +650,10 @@ fn test_remove_token_from_whitelist_non_existent() {
     let (client, _, _, admin) = setup(&env);
     let token = Address::generate(&env);
 
-    assert!(!client.is_token_whitelisted(&token));
+    assert!(!client.is_token_allowed(&token));
     let res = client.try_remove_token_from_whitelist(&admin, &token);
-    assert!(!client.is_token_whitelisted(&token));
+    assert!(res.is_ok(), "Removing an unlisted token is a no-op");
+    assert!(!client.is_token_allowed(&token));
 }
 
 /// Test non-admin attempt to remove token from whitelist is rejected.
@@ -658,11 +665,11 @@ fn test_remove_token_from_whitelist_unauthorized() {
     let token = Address::generate(&env);
 
     client.add_token_to_whitelist(&admin, &token);
-    assert!(client.is_token_whitelisted(&token));
+    assert!(client.is_token_allowed(&token));
 
     let res = client.try_remove_token_from_whitelist(&non_admin, &token);
     assert!(res.is_err(), "Non-admin cannot remove token from whitelist");
-    assert!(client.is_token_whitelisted(&token), "Token remains whitelisted after failed removal");
+    assert!(client.is_token_allowed(&token), "Token remains whitelisted after failed removal");
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -674,12 +681,21 @@ fn test_remove_token_from_whitelist_unauthorized() {
 fn test_pause_already_paused_contract() {
     let env = Env::default();
     let (client, _, _, admin) = setup(&env);
+    // Pausing takes two admins by default.
+    let second_admin = Address::generate(&env);
+    let ac_id = env.as_contract(&client.address, || {
+        PredifiContract::get_config(&env).access_control
+    });
+    dummy_ac::DummyACClient::new(&env, &ac_id).grant_role(&second_admin, &0u32);
 
     client.pause(&admin);
-    assert!(client.is_paused(), "Contract should be paused");
+    assert!(!client.is_contract_paused(), "One approval does not pause");
+    client.pause(&second_admin);
+    assert!(client.is_contract_paused(), "Contract should be paused");
 
     let res = client.try_pause(&admin);
-    assert!(client.is_paused(), "Contract should remain paused");
+    assert!(res.is_ok(), "Pausing twice is a no-op");
+    assert!(client.is_contract_paused(), "Contract should remain paused");
 }
 
 /// Test unpausing an unpaused contract.
@@ -688,10 +704,11 @@ fn test_unpause_already_unpaused_contract() {
     let env = Env::default();
     let (client, _, _, admin) = setup(&env);
 
-    assert!(!client.is_paused(), "Contract initially unpaused");
+    assert!(!client.is_contract_paused(), "Contract initially unpaused");
 
     let res = client.try_unpause(&admin);
-    assert!(!client.is_paused(), "Contract remains unpaused");
+    assert!(res.is_ok(), "Unpausing an unpaused contract is a no-op");
+    assert!(!client.is_contract_paused(), "Contract remains unpaused");
 }
 
 /// Test non-admin pause and unpause attempts are rejected.
@@ -703,7 +720,7 @@ fn test_pause_unpause_unauthorized() {
 
     let res_pause = client.try_pause(&non_admin);
     assert!(res_pause.is_err(), "Non-admin cannot pause contract");
-    assert!(!client.is_paused());
+    assert!(!client.is_contract_paused());
 
     let res_unpause = client.try_unpause(&non_admin);
     assert!(res_unpause.is_err(), "Non-admin cannot unpause contract");
@@ -6,7 +6,7 @@
 #![cfg(test)]
 
 use crate::test::ROLE_ADMIN;
-use crate::{FeeTier, PoolConfig};
+use crate::{FeeTier, PoolConfig, PricingMode};
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Ledger},
@@ -43,6 +43,10 @@ fn create_pool(
                 String::from_str(env, "No"),
                 String::from_str(env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     )
 }
@@ -129,7 +133,9 @@ fn test_treasury_accumulation_across_pools() {
     let client = crate::PredifiContractClient::new(&env, &contract_id);
 
     let token_admin_addr = Address::generate(&env);
-    let token_contract = env.register_stellar_asset_contract(token_admin_addr.clone());
+    let token_contract = env
+        .register_stellar_asset_contract_v2(token_admin_addr.clone())
+        .address();
     let tok = token::Client::new(&env, &token_contract);
     let token_admin_client = token::StellarAssetClient::new(&env, &token_contract);
     let token_address = token_contract;
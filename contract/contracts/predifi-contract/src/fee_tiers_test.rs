@@ -1,7 +1,7 @@
 #![cfg(test)]
 
 use crate::test::ROLE_ADMIN;
-use crate::{FeeTier, PoolConfig};
+use crate::{FeeTier, PoolConfig, PricingMode};
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Ledger},
@@ -77,6 +77,10 @@ fn test_dynamic_fee_tiers_application() {
                 String::from_str(&env, "A"),
                 String::from_str(&env, "B"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 
@@ -112,6 +116,10 @@ fn test_dynamic_fee_tiers_application() {
                 String::from_str(&env, "A"),
                 String::from_str(&env, "B"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 
@@ -157,6 +165,10 @@ fn test_dynamic_fee_tiers_application() {
                 String::from_str(&env, "A"),
                 String::from_str(&env, "B"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
     token_admin_client.mint(&user, &(6_000_000 * 10_000_000));
@@ -264,6 +276,10 @@ fn test_fee_tier_treasury_intake() {
                     String::from_str(&env, "A"),
                     String::from_str(&env, "B"),
                 ],
+                optimistic_liveness: 0,
+                optimistic_bond: 0,
+                pricing_mode: PricingMode::Parimutuel,
+                creator_fee_bps: 0,
             },
         )
     };
@@ -442,6 +458,10 @@ fn test_fee_tiers_full_lifecycle() {
                 String::from_str(&env, "A"),
                 String::from_str(&env, "B"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 
@@ -483,6 +503,10 @@ fn test_fee_tiers_full_lifecycle() {
                 String::from_str(&env, "A"),
                 String::from_str(&env, "B"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 
@@ -524,6 +548,10 @@ fn test_fee_tiers_full_lifecycle() {
                 String::from_str(&env, "A"),
                 String::from_str(&env, "B"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 
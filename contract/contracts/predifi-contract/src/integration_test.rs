@@ -141,6 +141,10 @@ fn test_full_market_lifecycle() {
                 String::from_str(&env, "Outcome 1"),
                 String::from_str(&env, "Outcome 2"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 
@@ -231,6 +235,10 @@ fn test_multi_user_betting_and_balance_verification() {
                 String::from_str(&env, "Outcome 2"),
                 String::from_str(&env, "Outcome 3"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 
@@ -314,6 +322,10 @@ fn test_market_resolution_multiple_winners() {
                 String::from_str(&env, "Outcome 1"),
                 String::from_str(&env, "Outcome 2"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 
@@ -385,6 +397,10 @@ fn test_full_pool_lifecycle_creation_to_payout() {
                 String::from_str(&env, "Team A wins"),
                 String::from_str(&env, "Team B wins"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 
@@ -0,0 +1,234 @@
+//! Signed invites to private pools: ed25519 invites from the pool's signer
+//! admit their invitee until expired, revoked or re-keyed.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{Invite, PoolConfig, PredifiError, SignedInvite};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env, String, Symbol,
+};
+
+const END_TIME: u64 = 10_000;
+const EXPIRES_AT: u64 = 5_000;
+
+/// A private pool with the shared key `LEAGUE` and an invite signer.
+/// Returns `(pool_id, signer)`.
+fn invite_pool(ctx: &TestContext) -> (u64, SigningKey) {
+    let env = &ctx.client.env;
+    let config = PoolConfig {
+        description: String::from_str(env, "Office league"),
+        metadata_url: String::from_str(env, "ipfs://invites"),
+        private: true,
+        whitelist_key: Some(Symbol::new(env, "LEAGUE")),
+        outcome_descriptions: vec![
+            env,
+            String::from_str(env, "Home"),
+            String::from_str(env, "Away"),
+        ],
+        ..default_pool_config(env)
+    };
+    let pool_id = ctx.create_pool(END_TIME, &config);
+
+    let signer = SigningKey::from_bytes(&[1; 32]);
+    ctx.client
+        .set_invite_signer(&ctx.creator, &pool_id, &public_key(env, &signer));
+    (pool_id, signer)
+}
+
+fn public_key(env: &Env, key: &SigningKey) -> BytesN<32> {
+    BytesN::from_array(env, &key.verifying_key().to_bytes())
+}
+
+/// An invite for `invitee` signed with `key`.
+fn sign(ctx: &TestContext, pool_id: u64, key: &SigningKey, invitee: &Address) -> Option<Invite> {
+    let message = ctx.client.invite_message(&pool_id, invitee, &EXPIRES_AT);
+    let mut buf = [0u8; 512];
+    let bytes = &mut buf[..message.len() as usize];
+    message.copy_into_slice(bytes);
+    Some(Invite::Signed(SignedInvite {
+        expires_at: EXPIRES_AT,
+        signature: BytesN::from_array(&ctx.client.env, &key.sign(bytes).to_bytes()),
+    }))
+}
+
+/// A signed invite admits its invitee without a whitelist entry; the shared
+/// key keeps working.
+#[test]
+fn test_signed_invite_admits_invitee() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, signer) = invite_pool(&ctx);
+    let invitee = ctx.funded_user(1_000);
+    let outsider = ctx.funded_user(1_000);
+    let key_holder = ctx.funded_user(1_000);
+
+    let invite = sign(&ctx, pool_id, &signer, &invitee);
+    ctx.client
+        .place_prediction_with_invite(&invitee, &pool_id, &100, &0u32, &None, &invite);
+    assert_eq!(ctx.client.get_outcome_stake(&pool_id, &0u32), 100);
+    assert!(!ctx.client.is_whitelisted(&pool_id, &invitee));
+
+    let uninvited = ctx
+        .client
+        .try_place_prediction(&outsider, &pool_id, &100, &0u32, &None, &None);
+    assert_eq!(uninvited, Err(Ok(PredifiError::Unauthorized)));
+
+    let shared_key = Some(Symbol::new(&env, "LEAGUE"));
+    ctx.client
+        .place_prediction(&key_holder, &pool_id, &100, &1u32, &None, &shared_key);
+    assert_eq!(ctx.client.get_pool(&pool_id).participants_count, 2);
+}
+
+/// Expired and revoked invites are `Unauthorized`; an invite used by anyone
+/// but its invitee does not verify.
+#[test]
+fn test_invalid_invites_are_rejected() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, signer) = invite_pool(&ctx);
+    let invitee = ctx.funded_user(1_000);
+    let other = ctx.funded_user(1_000);
+    let invite = sign(&ctx, pool_id, &signer, &invitee);
+
+    let borrowed = ctx
+        .client
+        .try_place_prediction_with_invite(&other, &pool_id, &100, &0u32, &None, &invite);
+    assert!(matches!(borrowed, Err(Err(_))));
+
+    ctx.client
+        .revoke_invite(&ctx.creator, &pool_id, &invitee, &EXPIRES_AT);
+    assert!(ctx
+        .client
+        .is_invite_revoked(&pool_id, &invitee, &EXPIRES_AT));
+    let revoked = ctx
+        .client
+        .try_place_prediction_with_invite(&invitee, &pool_id, &100, &0u32, &None, &invite);
+    assert_eq!(revoked, Err(Ok(PredifiError::Unauthorized)));
+
+    let other_invite = sign(&ctx, pool_id, &signer, &other);
+    env.ledger().with_mut(|li| li.timestamp = EXPIRES_AT);
+    let expired = ctx.client.try_place_prediction_with_invite(
+        &other,
+        &pool_id,
+        &100,
+        &0u32,
+        &None,
+        &other_invite,
+    );
+    assert_eq!(expired, Err(Ok(PredifiError::Unauthorized)));
+}
+
+/// Replacing the signer key invalidates invites signed with the old one.
+#[test]
+fn test_new_signer_invalidates_old_invites() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, signer) = invite_pool(&ctx);
+    let invitee = ctx.funded_user(1_000);
+    let stale = sign(&ctx, pool_id, &signer, &invitee);
+
+    let new_signer = SigningKey::from_bytes(&[2; 32]);
+    ctx.client
+        .set_invite_signer(&ctx.creator, &pool_id, &public_key(&env, &new_signer));
+    assert_eq!(
+        ctx.client.get_invite_signer(&pool_id),
+        Some(public_key(&env, &new_signer))
+    );
+
+    let rejected = ctx
+        .client
+        .try_place_prediction_with_invite(&invitee, &pool_id, &100, &0u32, &None, &stale);
+    assert!(matches!(rejected, Err(Err(_))));
+    let fresh = sign(&ctx, pool_id, &new_signer, &invitee);
+    ctx.client
+        .place_prediction_with_invite(&invitee, &pool_id, &100, &0u32, &None, &fresh);
+}
+
+/// A signed invite is only verified once the pool has a signer: without one
+/// it is `Unauthorized`. A bad signature traps, failing the whole
+/// transaction rather than returning a contract error.
+#[test]
+fn test_bad_signature_fails_transaction() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, _signer) = invite_pool(&ctx);
+    let invitee = ctx.funded_user(1_000);
+
+    let forger = SigningKey::from_bytes(&[3; 32]);
+    let forged = sign(&ctx, pool_id, &forger, &invitee);
+    let result = ctx
+        .client
+        .try_place_prediction_with_invite(&invitee, &pool_id, &100, &0u32, &None, &forged);
+    assert!(matches!(result, Err(Err(_))));
+    assert_eq!(ctx.client.get_outcome_stake(&pool_id, &0u32), 0);
+    assert_eq!(ctx.token.balance(&invitee), 1_000);
+
+    let config = PoolConfig {
+        private: true,
+        ..default_pool_config(&env)
+    };
+    let unsigned_pool = ctx.create_pool(END_TIME, &config);
+    let no_signer = ctx.client.try_place_prediction_with_invite(
+        &invitee,
+        &unsigned_pool,
+        &100,
+        &0u32,
+        &None,
+        &forged,
+    );
+    assert_eq!(no_signer, Err(Ok(PredifiError::Unauthorized)));
+}
+
+/// Commit-reveal pools accept signed invites too.
+#[test]
+fn test_commit_prediction_accepts_signed_invite() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, signer) = invite_pool(&ctx);
+    let invitee = ctx.funded_user(1_000);
+    ctx.client.enable_commit_reveal(
+        &ctx.creator,
+        &pool_id,
+        &crate::CommitRevealConfig {
+            reveal_window: 1_000,
+            unrevealed_policy: crate::UnrevealedPolicy::Refund,
+        },
+    );
+
+    let hash = ctx
+        .client
+        .compute_commitment(&0u32, &BytesN::from_array(&env, &[9; 32]));
+    let uninvited = ctx
+        .client
+        .try_commit_prediction(&invitee, &pool_id, &100, &hash, &None, &None);
+    assert_eq!(uninvited, Err(Ok(PredifiError::Unauthorized)));
+
+    let invite = sign(&ctx, pool_id, &signer, &invitee);
+    ctx.client
+        .commit_prediction(&invitee, &pool_id, &100, &hash, &None, &invite);
+    assert_eq!(ctx.client.get_committed_stake(&pool_id), 100);
+}
+
+/// Only the creator of a private pool may set its signer or revoke invites.
+#[test]
+fn test_invite_management_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, signer) = invite_pool(&ctx);
+    let key = public_key(&env, &signer);
+    let stranger = Address::generate(&env);
+
+    let not_creator = ctx.client.try_set_invite_signer(&stranger, &pool_id, &key);
+    assert_eq!(not_creator, Err(Ok(PredifiError::Unauthorized)));
+    let revoke = ctx
+        .client
+        .try_revoke_invite(&stranger, &pool_id, &stranger, &EXPIRES_AT);
+    assert_eq!(revoke, Err(Ok(PredifiError::Unauthorized)));
+    let missing = ctx
+        .client
+        .try_set_invite_signer(&ctx.creator, &(pool_id + 1), &key);
+    assert_eq!(missing, Err(Ok(PredifiError::PoolNotFound)));
+}
@@ -0,0 +1,114 @@
+//! Private pool invites domain: ed25519 invites signed off-chain by the pool
+//! creator's invite signer, checked by `place_prediction` and
+//! `commit_prediction`, so members need no on-chain whitelist entry and no
+//! shared `whitelist_key`.
+
+use soroban_sdk::{contractimpl, Address, Bytes, BytesN, Env};
+
+use crate::{
+    DataKey, InviteRevokedEvent, InviteSignerSetEvent, Pool, PoolKey, PredifiContract,
+    PredifiContractArgs, PredifiContractClient, PredifiError,
+};
+
+#[contractimpl]
+impl PredifiContract {
+    /// Set the ed25519 public key whose signatures admit users to the private
+    /// pool `pool_id`, replacing any previous key. Invites signed with a
+    /// replaced key stop being accepted. Callable by the pool creator.
+    ///
+    /// # Errors
+    /// - `PoolNotFound` – `pool_id` does not exist.
+    /// - `Unauthorized` – `creator` did not create the pool.
+    /// - `InvalidPoolState` – the pool is not private.
+    pub fn set_invite_signer(
+        env: Env,
+        creator: Address,
+        pool_id: u64,
+        public_key: BytesN<32>,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        creator.require_auth();
+        Self::load_private_pool(&env, &creator, pool_id)?;
+
+        let key = PoolKey::InviteSigner(pool_id);
+        env.storage().persistent().set(&key, &public_key);
+        Self::extend_persistent(&env, &key);
+
+        InviteSignerSetEvent {
+            pool_id,
+            creator,
+            public_key,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Invite signer key of a private pool, if set.
+    pub fn get_invite_signer(env: Env, pool_id: u64) -> Option<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&PoolKey::InviteSigner(pool_id))
+    }
+
+    /// The bytes an invite signer signs to admit `invitee` to `pool_id` until
+    /// `expires_at`: the XDR of the matching `InviteTerms`.
+    pub fn invite_message(env: Env, pool_id: u64, invitee: Address, expires_at: u64) -> Bytes {
+        Self::invite_terms(&env, pool_id, &invitee, expires_at)
+    }
+
+    /// Revoke the invite admitting `invitee` to `pool_id` until `expires_at`.
+    /// Other invites for the same user are unaffected, and stakes already
+    /// placed with the invite stay in the pool. Callable by the pool creator.
+    ///
+    /// # Errors
+    /// - `PoolNotFound` – `pool_id` does not exist.
+    /// - `Unauthorized` – `creator` did not create the pool.
+    /// - `InvalidPoolState` – the pool is not private.
+    pub fn revoke_invite(
+        env: Env,
+        creator: Address,
+        pool_id: u64,
+        invitee: Address,
+        expires_at: u64,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        creator.require_auth();
+        Self::load_private_pool(&env, &creator, pool_id)?;
+
+        let key = PoolKey::RevokedInvite(pool_id, invitee.clone(), expires_at);
+        env.storage().persistent().set(&key, &true);
+        Self::extend_persistent(&env, &key);
+
+        InviteRevokedEvent {
+            pool_id,
+            invitee,
+            expires_at,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Whether the invite admitting `invitee` to `pool_id` until `expires_at`
+    /// has been revoked.
+    pub fn is_invite_revoked(env: Env, pool_id: u64, invitee: Address, expires_at: u64) -> bool {
+        env.storage()
+            .persistent()
+            .has(&PoolKey::RevokedInvite(pool_id, invitee, expires_at))
+    }
+
+    /// Load a private pool created by `creator`.
+    fn load_private_pool(env: &Env, creator: &Address, pool_id: u64) -> Result<Pool, PredifiError> {
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.creator != *creator {
+            return Err(PredifiError::Unauthorized);
+        }
+        if !pool.private {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        Ok(pool)
+    }
+}
@@ -2,23 +2,38 @@
 #![allow(clippy::too_many_arguments)]
 
 mod admin;
+mod amm;
+mod approvals;
 mod benchmark_test;
+mod bond;
 #[cfg(test)]
 mod boundary_edge_case_tests;
+mod commit_reveal;
 mod constants;
+mod dispute;
 mod gas_opt;
+mod invites;
+mod lmsr;
+#[cfg(test)]
+mod lmsr_proptests;
+mod market;
+mod migration;
+mod optimistic;
 mod oracle;
 #[cfg(test)]
 mod payout_proptests;
 mod payouts;
+mod parlay;
 mod pool;
 mod prediction;
-mod price_feed;
+mod price_aggregation;
 mod price_feed_simple;
+mod price_source;
 mod referral;
 mod safe_math;
 #[cfg(test)]
 mod safe_math_examples;
+mod shares;
 #[cfg(test)]
 mod access_control_audit_tests;
 #[cfg(test)]
@@ -35,15 +50,18 @@ mod treasury;
 
 use soroban_sdk::{
     contract, contracterror, contractevent, contractimpl, contracttype, symbol_short, token,
-    Address, BytesN, Env, IntoVal, String, Symbol, SymbolStr, TryFromVal, Vec,
+    xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, String, Symbol, SymbolStr, TryFromVal, Vec,
 };
 
 pub use constants::*;
 pub use payouts::{
-    calculate_claim_payout, calculate_odds_bps, calculate_protocol_fee, calculate_referral_amount,
-    calculate_winnings, PayoutBreakdown, PayoutInput,
+    calculate_claim_payout, calculate_fee_share, calculate_odds_bps, calculate_parlay_payout,
+    calculate_payout_pool, calculate_protocol_fee, calculate_referral_amount,
+    calculate_scalar_claim_payout, calculate_scalar_long_bps, calculate_winnings,
+    PayoutBreakdown, PayoutInput,
 };
 pub use price_feed_simple::PriceFeedAdapter;
+pub use price_source::PriceSource;
 pub use safe_math::{RoundingMode, SafeMath};
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -57,29 +75,42 @@ pub use safe_math::{RoundingMode, SafeMath};
 // ┌──────────┬───────┬──────────────────────────────────────────────────────┐
 // │ Role     │ Value │ Permitted operations in predifi-contract              │
 // ├──────────┼───────┼──────────────────────────────────────────────────────┤
-// │ Admin    │   0   │ pause / unpause                                       │
+// │ Admin    │   0   │ pause (N-of-M approvals) / unpause                    │
 // │          │       │ set_fee_bps                                           │
 // │          │       │ set_treasury                                          │
 // │          │       │ set_resolution_delay                                  │
 // │          │       │ set_referral_cut_bps                                  │
 // │          │       │ add_token_to_whitelist / remove_token_from_whitelist  │
 // │          │       │ withdraw_treasury                                     │
-// │          │       │ upgrade_contract                                      │
+// │          │       │ emergency_withdraw (N-of-M approvals)                 │
+// │          │       │ emergency_cancel_pool (N-of-M, with Operators)        │
+// │          │       │ set_approval_policy                                   │
+// │          │       │ propose_upgrade / approve_upgrade / cancel_upgrade    │
+// │          │       │ upgrade_contract (N-of-M approvals + timelock)        │
+// │          │       │ set_upgrade_policy                                    │
 // │          │       │ migrate_state                                         │
+// │          │       │ set_dispute_config / settle_dispute                   │
+// │          │       │ set_creation_bond                                     │
 // ├──────────┼───────┼──────────────────────────────────────────────────────┤
 // │ Operator │   1   │ resolve_pool (multi-vote; finalises when threshold    │
 // │          │       │   of required_resolutions is reached)                 │
 // │          │       │ cancel_pool                                           │
 // │          │       │ set_stake_limits                                      │
+// │          │       │ emergency_cancel_pool (N-of-M, with Admins)           │
+// ├──────────┼───────┼──────────────────────────────────────────────────────┤
+// │ Moderator│   2   │ flag_disputed_pool (opens a bonded dispute)           │
+// │          │       │ reject_pool (cancels; slashes the creation bond)      │
 // ├──────────┼───────┼──────────────────────────────────────────────────────┤
 // │ Oracle   │   3   │ oracle_resolve (OracleCallback trait; multi-vote;     │
 // │          │       │   finalises when required_resolutions threshold met)  │
 // └──────────┴───────┴──────────────────────────────────────────────────────┘
 //
-// Note: roles 2 (Moderator) and 4 (User) are defined in the access-control
-// contract but are not currently enforced by predifi-contract.
-// Role 2 (Moderator) is RESERVED FOR FUTURE USE — it is intended for dispute
-// resolution functionality. See issue #595 for the implementation plan.
+// Note: role 4 (User) is defined in the access-control contract but is not
+// currently enforced by predifi-contract.
+//
+// Resolution also accepts an Operator grant scoped to the pool or to its
+// category (`access_control::assign_scoped_role`), checked through
+// `access_control::has_role_for_pool`. Scoped grants confer nothing else.
 //
 // HOW ROLES ARE ASSIGNED
 // ──────────────────────
@@ -144,8 +175,11 @@ pub const MIN_WITHDRAWAL_AMOUNT: i128 = 1;
 // PROTOCOL INVARIANTS (for formal verification)
 // ═══════════════════════════════════════════════════════════════════════════
 //
-// INV-1: Pool.total_stake = Σ(OutcomeStake(pool_id, outcome)) for all outcomes
+// INV-1: Pool.total_stake = Σ(OutcomeStake(pool_id, outcome)) for all outcomes,
+//        plus house money: initial_liquidity and exit fees retained by
+//        withdraw_prediction
 // INV-2: Pool.state transitions: Active → {Resolved | Canceled}, never reversed
+//        (Active | Resolved) → Disputed → {Active | Resolved | Canceled} only via settle_dispute
 // INV-3: HasClaimed(user, pool) is write-once (prevents double-claim)
 // INV-4: Winnings ≤ Pool.total_stake (no value creation)
 // INV-5: For resolved pools: Σ(claimed_winnings) ≤ Pool.total_stake
@@ -234,25 +268,44 @@ pub enum PredifiError {
     /// relative to `max_total_stake` (issue #1131).
     InitialLiquidityBelowSafetyMargin = 133,
     /// `emergency_cancel_pool` was called but the multisig threshold
-    /// has not yet been reached (issue #1119).
+    /// has not yet been reached (issue #1119), or `upgrade_contract` was
+    /// called before the upgrade collected its required approvals.
     EmergencyCancelPending = 134,
-    /// The caller has already approved this emergency-cancel proposal
-    /// (issue #1119).
+    /// The caller has already approved this emergency-cancel or upgrade
+    /// proposal (issue #1119).
     EmergencyCancelAlreadyApproved = 135,
     /// The contract is currently paused; all state-mutating operations are blocked.
+    /// Also returned while an upgrade's state migration is incomplete (see
+    /// `get_migration_status`).
     ///
     /// Callers should check `is_contract_paused()` before submitting a transaction,
     /// or listen for `PauseEvent` / `UnpauseEvent` on-chain to stay in sync.
     ContractPaused = 83,
     InvalidAddressOrToken = 94,
+    /// A fee change or contract upgrade is already queued.
     FeeChangePending = 95,
+    /// No fee change or contract upgrade is queued.
     NoFeeChangePending = 96,
+    /// The queued fee change or contract upgrade is still timelocked.
     TimelockNotExpired = 97,
+    /// No open dispute exists for the pool.
+    DisputeNotFound = 28,
+    /// The resolution can no longer be challenged: the dispute challenge
+    /// window or the optimistic liveness window has elapsed, or a challenge
+    /// has already been made.
+    ChallengeWindowClosed = 29,
+    /// The action must wait until a challenge window elapses: claims during
+    /// the post-resolution dispute window, or finalizing an optimistic
+    /// proposal during its liveness window.
+    ChallengeWindowOpen = 30,
 }
 
 /// Represents the current state of a prediction market.
 ///
 /// State transitions are one-way: `Active` can only transition to `Resolved`, `Canceled`, or `Disputed`.
+/// A `Resolved` pool may also move to `Disputed` while its challenge window is open.
+/// `Disputed` is left only through `settle_dispute`, which moves the pool to
+/// `Active`, `Resolved` or `Canceled` depending on the ruling.
 #[contracttype]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum MarketState {
@@ -266,6 +319,20 @@ pub enum MarketState {
     Disputed = 3,
 }
 
+/// How a pool prices stakes and computes payouts.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PricingMode {
+    /// Stakes go into one pot per outcome and winners split the losing
+    /// stakes pro-rata (`place_prediction` / `claim_winnings`).
+    Parimutuel = 0,
+    /// Users trade outcome shares against an LMSR market maker at a quoted
+    /// price; each winning share pays one token unit (`buy_shares` /
+    /// `sell_shares` / `claim_amm_payout`). The liquidity parameter is
+    /// derived from `initial_liquidity`, which must be positive.
+    Lmsr = 1,
+}
+
 /// Parameters for creating a new prediction pool.
 ///
 /// This struct is used internally to validate and organize pool creation data.
@@ -310,7 +377,8 @@ pub struct CreatePoolParams {
 /// # Invariants
 /// - `end_time` must be in the future when the pool is created (INV-8).
 /// - `state` can only transition from `Active` to either `Resolved` or `Canceled` (INV-2).
-/// - `total_stake` must always equal the sum of all individual outcome stakes (INV-1).
+/// - `total_stake` must always equal the sum of all individual outcome stakes,
+///   plus `initial_liquidity` and retained exit fees (INV-1).
 /// - For resolved pools: total winnings ≤ `total_stake` (INV-5)
 #[contracttype]
 #[derive(Clone)]
@@ -372,6 +440,12 @@ pub struct Pool {
     /// Unix timestamp when the pool was resolved. None for pools created before this feature.
     /// Used to enforce claim window expiration. Set when pool transitions to MarketState::Resolved.
     pub resolution_timestamp: Option<u64>,
+    /// Liveness window in seconds for optimistic resolution (0 = operator vote only).
+    pub optimistic_liveness: u64,
+    /// Bond required to propose or challenge an outcome in optimistic mode.
+    pub optimistic_bond: i128,
+    /// Pricing model of the pool.
+    pub pricing_mode: PricingMode,
 }
 
 /// Configuration parameters for creating a prediction pool.
@@ -410,6 +484,22 @@ pub struct PoolConfig {
     pub whitelist_key: Option<Symbol>,
     /// Human-readable labels for each outcome (length must equal options_count).
     pub outcome_descriptions: Vec<String>,
+    /// Liveness window in seconds for optimistic resolution (0 disables it).
+    /// When enabled, anyone may propose an outcome with a bond; if it is not
+    /// challenged before the window ends the outcome becomes final without an
+    /// operator vote. Must not exceed `MAX_OPTIMISTIC_LIVENESS`.
+    pub optimistic_liveness: u64,
+    /// Bond in the pool token that a proposer (and a challenger) must post.
+    /// Must be > 0 when `optimistic_liveness` is non-zero.
+    pub optimistic_bond: i128,
+    /// Pricing model. `Lmsr` requires a positive `initial_liquidity`, which
+    /// funds the market maker's worst-case loss.
+    pub pricing_mode: PricingMode,
+    /// Creator's share of the pool, in basis points of its total stake (0 for
+    /// none). Carved out of the protocol fee at resolution and paid through
+    /// `claim_creator_fees`. Must not exceed the admin's `max_creator_fee_bps`;
+    /// parimutuel pools only.
+    pub creator_fee_bps: u32,
 }
 
 /// Statistics for a prediction pool.
@@ -430,6 +520,11 @@ pub struct PoolStats {
     /// Current odds for each outcome in fixed-point format with 4 decimals.
     /// For example, 10000 represents 1.00x, 5000 represents 0.50x, 20000 represents 2.00x.
     pub current_odds: Vec<u64>,
+    /// Creator's share of the pool in basis points of its total stake.
+    pub creator_fee_bps: u32,
+    /// Creator fee owed for a resolved pool (0 until it resolves), whether
+    /// or not it has been claimed yet.
+    pub creator_fee: i128,
 }
 
 /// Global protocol configuration.
@@ -503,6 +598,120 @@ pub struct PendingFeeChange {
     pub proposed_by: Address,
 }
 
+/// Delay and approval threshold applied to contract upgrades, set with
+/// `set_upgrade_policy`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradePolicy {
+    /// Seconds between `propose_upgrade` and the earliest `upgrade_contract`.
+    /// At least [`MIN_UPGRADE_TIMELOCK_SECONDS`].
+    pub delay: u64,
+    /// Distinct Admin role holders that must approve an upgrade (N of the M
+    /// admins). The proposer's approval counts.
+    pub required_approvals: u32,
+}
+
+/// Progress of stored state towards the layout this build expects, as
+/// returned by `migrate_state` and `get_migration_status`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationStatus {
+    /// Schema version of the stored state.
+    pub version: u32,
+    /// Schema version this build expects ([`CONTRACT_VERSION`]).
+    pub target_version: u32,
+    /// Next pool id the step towards `version + 1` will visit; 0 when no
+    /// step is in progress.
+    pub next_pool_id: u64,
+}
+
+/// A contract upgrade queued by `propose_upgrade`.
+///
+/// Only one upgrade may be pending at a time; it is removed when executed
+/// by `upgrade_contract` or withdrawn by `cancel_upgrade`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingUpgrade {
+    /// Hash of the already-uploaded WASM to switch to.
+    pub new_wasm_hash: BytesN<32>,
+    /// The admin address that submitted this proposal.
+    pub proposed_by: Address,
+    /// Unix timestamp (seconds) at or after which `upgrade_contract` may execute.
+    pub executable_at: u64,
+    /// Admins that approved, proposer first.
+    pub approvals: Vec<Address>,
+}
+
+/// Kinds of high-risk admin actions that go through the approval queue;
+/// each kind has its own [`ApprovalPolicy`].
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SensitiveActionKind {
+    /// `pause`.
+    Pause = 0,
+    /// `emergency_withdraw`.
+    EmergencyWithdraw = 1,
+    /// `emergency_cancel_pool`.
+    EmergencyCancelPool = 2,
+    /// `set_upgrade_policy`. Its threshold is the current
+    /// `UpgradePolicy.required_approvals` and cannot be set separately.
+    UpgradePolicy = 3,
+}
+
+/// A high-risk admin action together with its arguments. Approvals are
+/// collected per action, so admins must agree on the exact arguments.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SensitiveAction {
+    Pause,
+    /// `(token, destination, amount)`.
+    EmergencyWithdraw(Address, Address, i128),
+    /// `pool_id`. The proposer's reason is kept in `DataKey::EmergencyCancelReason`.
+    EmergencyCancelPool(u64),
+    /// `(delay, required_approvals)`.
+    SetUpgradePolicy(u64, u32),
+    /// `(kind, threshold, expiry)`. Approved under the current policy of
+    /// `kind`.
+    SetApprovalPolicy(SensitiveActionKind, u32, u64),
+}
+
+impl SensitiveAction {
+    pub fn kind(&self) -> SensitiveActionKind {
+        match self {
+            SensitiveAction::Pause => SensitiveActionKind::Pause,
+            SensitiveAction::EmergencyWithdraw(..) => SensitiveActionKind::EmergencyWithdraw,
+            SensitiveAction::EmergencyCancelPool(_) => SensitiveActionKind::EmergencyCancelPool,
+            SensitiveAction::SetUpgradePolicy(..) => SensitiveActionKind::UpgradePolicy,
+            SensitiveAction::SetApprovalPolicy(kind, ..) => *kind,
+        }
+    }
+}
+
+/// Approval threshold and proposal lifetime for one [`SensitiveActionKind`],
+/// set with `set_approval_policy`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalPolicy {
+    /// Distinct eligible approvers needed before the action executes. The
+    /// proposer's approval counts.
+    pub threshold: u32,
+    /// Seconds a proposal collects approvals before it goes stale.
+    pub expiry: u64,
+}
+
+/// Approvals collected so far for a pending [`SensitiveAction`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalRequest {
+    pub action: SensitiveAction,
+    /// Approvers, proposer first.
+    pub approvals: Vec<Address>,
+    /// Unix timestamp (seconds) at which the proposal goes stale; the next
+    /// approval after it starts a fresh proposal.
+    pub expires_at: u64,
+}
+
 /// Aggregated contract metadata for frontend consumption.
 ///
 /// This read model allows clients to fetch protocol configuration and core stats
@@ -553,6 +762,31 @@ pub struct FeeTier {
     pub fee_bps: u32,
 }
 
+/// A volume discount tier: users whose lifetime staked volume reaches
+/// `volume_threshold` get `discount_bps` of their share of the protocol fee
+/// back when they claim.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VolumeTier {
+    /// Lifetime staked volume at or above which this tier applies.
+    pub volume_threshold: i128,
+    /// Portion of the user's protocol fee share rebated, in basis points.
+    /// Must be between 0 and 10,000 (inclusive).
+    pub discount_bps: u32,
+}
+
+/// A user's lifetime staking record and the volume tier it earns.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserStats {
+    /// Total staked across all pools, less stakes withdrawn before close.
+    pub volume: i128,
+    /// 1-based index of the [`VolumeTier`] reached, or 0 for none.
+    pub tier: u32,
+    /// Discount of the tier reached, in basis points.
+    pub discount_bps: u32,
+}
+
 /// Detailed information about a user's prediction in a specific pool.
 ///
 /// This struct is a convenient "read-only" view that combines user-specific prediction
@@ -579,6 +813,445 @@ pub struct UserPredictionDetail {
     pub pool_outcome: u32,
 }
 
+/// Dispute bond and challenge-window parameters, set via
+/// [`PredifiContract::set_dispute_config`].
+///
+/// When no config has been stored, disputes require no bond and resolved
+/// pools cannot be disputed (challenge window of 0).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeConfig {
+    /// Bond as a share of the pool's `total_stake`, in basis points (0-10,000).
+    pub bond_bps: u32,
+    /// Minimum bond in the pool token's base units, applied when `bond_bps`
+    /// yields less (e.g. for thin pools).
+    pub min_bond: i128,
+    /// Seconds after `resolution_timestamp` during which a moderator may
+    /// dispute a resolved pool. Winnings cannot be claimed until it elapses.
+    pub challenge_window: u64,
+}
+
+/// Final decision recorded by [`PredifiContract::settle_dispute`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DisputeRuling {
+    /// The dispute is still open. Not accepted by `settle_dispute`.
+    Pending,
+    /// The pool returns to the state and outcome it had before the dispute.
+    /// The moderator's bond is slashed to the treasury.
+    UpholdOriginal,
+    /// The pool is resolved to the given outcome index. The bond is returned.
+    OverrideOutcome(u32),
+    /// The pool is canceled and stakers can claim refunds. The bond is returned.
+    CancelAndRefund,
+}
+
+/// What happens to a commitment that is not revealed in time.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnrevealedPolicy {
+    /// The stake is returned to the user, less
+    /// `UNREVEALED_REFUND_PENALTY_BPS` kept as protocol fees.
+    Refund,
+    /// The stake goes to the treasury as protocol fees.
+    Forfeit,
+}
+
+/// Commit-reveal mode of a pool, set by its creator with
+/// `enable_commit_reveal`.
+///
+/// Bettors commit `compute_commitment(outcome, salt)` with their stake before
+/// `end_time` and reveal it with `reveal_prediction` in the
+/// `reveal_window` seconds after `end_time`. The pool cannot resolve until
+/// the window has closed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommitRevealConfig {
+    /// Seconds after `end_time` during which commitments can be revealed.
+    /// Must be in `1..=MAX_REVEAL_WINDOW`.
+    pub reveal_window: u64,
+    /// Applied by `settle_commitment` to commitments left unrevealed.
+    pub unrevealed_policy: UnrevealedPolicy,
+}
+
+/// A stake committed to a hidden outcome.
+///
+/// Stored under `PoolKey::Commitment(user, pool_id)` until it is revealed or
+/// settled.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Commitment {
+    /// Amount staked, held by the contract.
+    pub amount: i128,
+    /// SHA-256 of the outcome and salt; see `compute_commitment`.
+    pub hash: BytesN<32>,
+    /// Referrer recorded when the stake is revealed.
+    pub referrer: Option<Address>,
+}
+
+/// Proof of access to a private pool, passed to `place_prediction` and
+/// `commit_prediction` by users who are not on its whitelist.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Invite {
+    /// The pool's shared `whitelist_key`.
+    Key(Symbol),
+    /// An invite for the caller signed with the pool's invite signer key.
+    Signed(SignedInvite),
+}
+
+/// An ed25519 signature by the pool's invite signer over the XDR of the
+/// matching `InviteTerms` (see `invite_message`).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignedInvite {
+    /// Unix timestamp from which the invite is no longer accepted.
+    pub expires_at: u64,
+    pub signature: BytesN<64>,
+}
+
+/// What an invite signer signs. `contract` binds the invite to this
+/// deployment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InviteTerms {
+    pub contract: Address,
+    pub pool_id: u64,
+    pub invitee: Address,
+    pub expires_at: u64,
+}
+
+/// Permission for `spender` to move up to `amount` of an owner's shares in
+/// one pool outcome, set with `approve_shares`.
+///
+/// Stored in temporary storage under `ShareKey::Allowance` and void after
+/// `expiration_ledger`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShareAllowance {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+/// An outcome proposed under optimistic resolution.
+///
+/// Stored under `DataKey::OutcomeProposal(pool_id)` until the pool is
+/// finalized, resolved by operators after a challenge, or canceled.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutcomeProposal {
+    /// Address that proposed the outcome and posted the bond.
+    pub proposer: Address,
+    /// Proposed winning outcome index.
+    pub outcome: u32,
+    /// Bond posted by the proposer (and matched by a challenger).
+    pub bond: i128,
+    /// Ledger timestamp when the proposal was made.
+    pub proposed_at: u64,
+    /// Address that challenged the proposal, if any. A challenge escalates
+    /// resolution to the operator vote in `resolve_pool`.
+    pub challenger: Option<Address>,
+}
+
+/// Market maker state of a `PricingMode::Lmsr` pool.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AmmState {
+    /// LMSR liquidity parameter `b`, in token base units.
+    pub liquidity: i128,
+    /// Outstanding shares per outcome.
+    pub shares: Vec<i128>,
+}
+
+/// A user's holdings in a `PricingMode::Lmsr` pool.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AmmPosition {
+    /// Shares held per outcome.
+    pub shares: Vec<i128>,
+    /// Net tokens paid in (buys minus sells). A canceled pool refunds it, up
+    /// to what the shares are worth at the final prices.
+    pub cost_basis: i128,
+}
+
+/// What a settled `PricingMode::Lmsr` pool still holds and may still owe.
+///
+/// Stored under `PoolKey::AmmSettlement(pool_id)` by the first redemption
+/// after the pool resolves or is canceled. Anything `balance` holds beyond
+/// `reserved` is left-over subsidy the creator can reclaim.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AmmSettlement {
+    /// Tokens still held for the pool.
+    pub balance: i128,
+    /// Most the positions not yet redeemed can claim.
+    pub reserved: i128,
+}
+
+/// Where an unfinished `sweep_expired_pool` resumes.
+///
+/// Stored under `PoolKey::SweepProgress(pool_id)` until the last participant
+/// is visited, when `amount` moves to `PoolKey::Swept(pool_id)`.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SweepProgress {
+    /// Index of the next participant to visit.
+    pub next: u32,
+    /// Winnings swept by the earlier calls.
+    pub amount: i128,
+    /// Winnings and realized fees owed to the predictions visited so far,
+    /// claimed or not.
+    pub owed: i128,
+}
+
+/// Price range of a scalar pool, resolved from an oracle feed.
+///
+/// Scalar pools are binary parimutuel pools where outcome 0 is "short" and
+/// outcome 1 is "long". Instead of one side taking the whole payout pool, the
+/// final price's position in `[lower, upper]` splits it: the long side gets
+/// `settlement_bps / 10_000` of it and the short side the rest.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScalarRange {
+    /// Price feed the pool settles against.
+    pub feed_pair: Symbol,
+    /// Price at (or below) which the short side takes everything.
+    pub lower: i128,
+    /// Price at (or above) which the long side takes everything.
+    pub upper: i128,
+    /// Long side's share of the payout pool in basis points, set by
+    /// `resolve_pool_from_price`. `None` until then.
+    pub settlement_bps: Option<u32>,
+}
+
+/// Price returned by a Pyth-compatible price contract's
+/// `get_price_no_older_than(price_id, age)`.
+///
+/// `price` and `conf` are mantissas at the feed's `expo`; the contract uses
+/// them as-is, so targets and bounds of pools settling on a Pyth feed must
+/// use the same exponent.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PythPrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: u64,
+}
+
+/// One price pushed through `update_price_feed`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceObservation {
+    pub oracle: Address,
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// How observations are combined into a resolution price.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AggregationMode {
+    /// Time-weighted average over the window.
+    Twap = 0,
+    /// Median of each oracle's latest price in the window.
+    Median = 1,
+}
+
+/// Aggregated resolution settings of a pool.
+///
+/// Both modes read the observations of the pool's feed pair in
+/// `[end_time - window, end_time]`, so pushes after the pool closes cannot
+/// move its resolution price.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceAggregation {
+    pub mode: AggregationMode,
+    /// Window length in seconds, ending at the pool's `end_time`.
+    pub window: u64,
+    /// `Median` only: prices further than this from the raw median are
+    /// dropped before taking the final median.
+    pub max_deviation_bps: u32,
+    /// `Median` only: distinct oracles that must remain after outlier
+    /// rejection.
+    pub min_sources: u32,
+}
+
+/// Where a pool's resolution price comes from.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PriceSourceKind {
+    /// Pyth when the feed pair is bound to a price id, otherwise the pool's
+    /// aggregation settings when set, otherwise the latest push.
+    Auto = 0,
+    /// The latest `update_price_feed` push.
+    Pushed = 1,
+    /// The configured Pyth contract; the pair must be bound to a price id.
+    Pyth = 2,
+    /// TWAP or median of pushed observations; the pool must have
+    /// aggregation settings.
+    Aggregated = 3,
+}
+
+/// One (pool, outcome) pick of a parlay, as passed to `place_parlay`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParlayPick {
+    pub pool_id: u64,
+    pub outcome: u32,
+}
+
+/// A parlay leg with the odds it was priced at.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParlayLeg {
+    pub pool_id: u64,
+    pub outcome: u32,
+    /// Parimutuel odds of `outcome` when the parlay was placed, in basis
+    /// points (`total_stake * 10_000 / outcome_stake`).
+    pub odds_bps: u64,
+}
+
+/// Lifecycle of a parlay.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParlayStatus {
+    /// At least one leg is undecided and none has lost.
+    Open = 0,
+    /// A leg resolved against its pick; the stake stays in the vault.
+    Lost = 1,
+    /// Every leg won or was voided; `payout` can be claimed.
+    Won = 2,
+    /// The payout was paid out.
+    Claimed = 3,
+}
+
+/// A combo prediction: one stake on several legs, paid from the combo vault
+/// only if every leg resolves as picked.
+///
+/// A leg whose pool is canceled is void and drops out of the odds; if every
+/// leg is void the payout is the stake.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Parlay {
+    pub user: Address,
+    pub token: Address,
+    /// Category shared by every leg's pool.
+    pub category: Symbol,
+    pub stake: i128,
+    pub legs: Vec<ParlayLeg>,
+    /// Protocol fee on winnings at placement, applied to the profit.
+    pub fee_bps: u32,
+    /// Amount paid if every remaining leg wins, reserved in the vault.
+    pub payout: i128,
+    /// Protocol fee on that payout's profit, reserved alongside it and
+    /// credited to the accrued fees when the parlay is claimed.
+    pub fee: i128,
+    pub status: ParlayStatus,
+}
+
+/// Per-token combo vault backing parlay payouts.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ComboVault {
+    /// Funds free to back new parlays or be withdrawn to the treasury.
+    pub liquidity: i128,
+    /// Payouts owed to open and won parlays.
+    pub reserved: i128,
+}
+
+/// Terms of a referral campaign, as passed to `create_referral_campaign`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralCampaignConfig {
+    /// Token the budget is denominated in; only pools in it are covered.
+    pub token: Address,
+    /// Referrer's cut of the protocol fee share, in basis points. Replaces
+    /// the global referral cut for referrals attributed to the campaign.
+    pub cut_bps: u32,
+    /// Cut for the referrer's own referrer, in basis points; 0 for none.
+    pub second_level_cut_bps: u32,
+    /// Referrals made from this time are attributed to the campaign.
+    pub start_time: u64,
+    /// Referrals made from this time are no longer attributed.
+    pub end_time: u64,
+    /// Pools covered; empty for any pool.
+    pub pool_ids: Vec<u64>,
+    /// Pool categories covered; empty for any category.
+    pub categories: Vec<Symbol>,
+    /// Most the campaign pays out in total, across both levels.
+    pub budget: i128,
+}
+
+/// A referral campaign and how much of its budget has been paid out.
+///
+/// Referrals made while the campaign is open are attributed to it for
+/// good: their rewards use the campaign's cuts, even after it ends, until
+/// the budget runs out. After that they earn the global cuts.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralCampaign {
+    pub config: ReferralCampaignConfig,
+    pub spent: i128,
+}
+
+/// Price brackets of a multi-outcome pool, resolved from an oracle feed.
+///
+/// Outcome `i` wins when `thresholds[i - 1] <= price < thresholds[i]`; there is
+/// one more outcome than thresholds.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceBrackets {
+    /// Price feed the pool settles against.
+    pub feed_pair: Symbol,
+    /// Strictly increasing bracket boundaries.
+    pub thresholds: Vec<i128>,
+}
+
+/// A fixed-price offer to sell part or all of a prediction position.
+///
+/// The stake is not escrowed: it stays in the seller's `Prediction` (and
+/// remains claimable) until `buy_listing` moves it to the buyer. A listing
+/// whose seller no longer holds `amount` on `outcome` cannot be filled.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Listing {
+    /// Owner of the listed position.
+    pub seller: Address,
+    pub pool_id: u64,
+    /// Outcome index of the listed position.
+    pub outcome: u32,
+    /// Stake offered, in pool token base units.
+    pub amount: i128,
+    /// Asking price for the whole listing, in pool token base units.
+    pub price: i128,
+}
+
+/// A dispute opened on a pool by a moderator.
+///
+/// Stored under `DataKey::Dispute(pool_id)`. A pool can be disputed at most
+/// once while active and once during its challenge window; the latest record
+/// is kept after settlement as an audit trail.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dispute {
+    /// Moderator that opened the dispute and posted the bond.
+    pub moderator: Address,
+    /// Bond locked in the pool token (0 when no bond was required).
+    pub bond: i128,
+    /// Reason supplied by the moderator.
+    pub reason: String,
+    /// Pool state at the time the dispute was opened (`Active` or `Resolved`).
+    pub prior_state: MarketState,
+    /// Pool outcome at the time the dispute was opened
+    /// (`UNRESOLVED_OUTCOME` if the pool was still active).
+    pub prior_outcome: u32,
+    /// Ledger timestamp when the dispute was opened.
+    pub opened_at: u64,
+    /// Ruling recorded on settlement; `Pending` while the dispute is open.
+    pub ruling: DisputeRuling,
+}
+
 /// Internal storage keys for contract data.
 ///
 /// All variants use PascalCase. Abbreviated names are preserved for existing
@@ -722,20 +1395,211 @@ pub enum DataKey {
     ReferralMinVolumeBps,
 
     // ── Issue #1119: Multi-sig emergency cancellation ───────────────────────
-    /// Pending emergency-cancel approver set: `EmergencyCancelApprovers(pool_id)` -> `Vec<Address>`.
-    /// Empty / absent when no emergency cancel is currently pending.
-    EmergencyCancelApprovers(u64),
+    // Approvals live in the approval queue (`ApprovalKey`).
     /// Optional reason string captured when the first approval is recorded.
     /// `EmergencyCancelReason(pool_id)` -> `String`.
     EmergencyCancelReason(u64),
+
+    // ── Disputes ─────────────────────────────────────────────────────────────
+    /// Dispute record for a pool: `Dispute(pool_id)` -> `Dispute`
+    Dispute(u64),
+    /// Dispute bond and challenge-window parameters: `DisputeConfig` -> `DisputeConfig`
+    DisputeConfig,
+
+    // ── Optimistic resolution ────────────────────────────────────────────────
+    /// Pending outcome proposal: `OutcomeProposal(pool_id)` -> `OutcomeProposal`
+    OutcomeProposal(u64),
+
+    // ── Secondary market ─────────────────────────────────────────────────────
+    /// Open fixed-price listing: `Listing(listing_id)` -> `Listing`
+    Listing(u64),
+    /// Listing ID counter for generating unique listing IDs: `ListingIdCtr` -> `u64`
+    ListingIdCtr,
+
+    // ── LMSR pools ───────────────────────────────────────────────────────────
+    /// Market maker state of an `Lmsr` pool: `AmmState(pool_id)` -> `AmmState`
+    AmmState(u64),
+    /// A user's shares in an `Lmsr` pool: `AmmPos(user, pool_id)` -> `AmmPosition`
+    AmmPos(Address, u64),
+    /// Price range and settlement of a scalar pool: `ScalarRange(pool_id)` -> `ScalarRange`
+    ScalarRange(u64),
+    /// Price brackets of a multi-outcome pool: `PriceBrackets(pool_id)` -> `PriceBrackets`
+    PriceBrackets(u64),
+    /// Pyth price id a feed pair is pulled from at resolution:
+    /// `PythPriceId(feed_pair)` -> `BytesN<32>`
+    PythPriceId(Symbol),
 }
 
-/// Represents a user's individual stake in a prediction market.
+/// Storage keys for oracle data added after `DataKey` reached the 50-variant
+/// limit of a contract type.
 ///
-/// This is the core structure for tracking participation. It is stored as part of the
-/// ledger state for each user-pool pair, mapping a specific outcome to a staked amount.
+/// Variant names must not repeat a `DataKey` variant with the same fields,
+/// since both encode as `[name, fields...]`.
+#[contracttype]
+#[derive(Clone)]
+pub enum OracleKey {
+    /// Recent pushes for a feed pair, oldest first:
+    /// `PriceObservations(feed_pair)` -> `Vec<PriceObservation>`
+    PriceObservations(Symbol),
+    /// How a pool aggregates observations at resolution:
+    /// `PriceAggregation(pool_id)` -> `PriceAggregation`
+    PriceAggregation(u64),
+    /// Price source a pool resolves on, when not `Auto`:
+    /// `SourceKind(pool_id)` -> `PriceSourceKind`
+    SourceKind(u64),
+}
+
+/// Storage keys for the upgrade timelock (instance storage).
+#[contracttype]
+#[derive(Clone)]
+pub enum UpgradeKey {
+    /// `UpgradePolicy` -> `UpgradePolicy`
+    UpgradePolicy,
+    /// `PendingUpgrade` -> `PendingUpgrade`
+    PendingUpgrade,
+}
+
+/// Storage keys for state migrations (instance storage).
+#[contracttype]
+#[derive(Clone)]
+pub enum MigrationKey {
+    /// `MigrationCursor` -> `u64`, next pool id of the step in progress.
+    MigrationCursor,
+    /// `SchemaVersion` -> `u32`, layout version of the stored state. Absent
+    /// on contracts initialized before it was tracked, which hold version 1
+    /// state whatever `DataKey::Version` says: the version 1 `upgrade_contract`
+    /// bumps `DataKey::Version` without migrating anything.
+    SchemaVersion,
+}
+
+/// Storage keys for the sensitive-action approval queue.
+#[contracttype]
+#[derive(Clone)]
+pub enum ApprovalKey {
+    /// `ApprovalPolicy(kind)` -> `ApprovalPolicy` (instance storage)
+    ApprovalPolicy(SensitiveActionKind),
+    /// `ApprovalRequest(action)` -> `ApprovalRequest` (persistent storage)
+    ApprovalRequest(SensitiveAction),
+}
+
+/// Storage keys for parlays and the combo vault.
+#[contracttype]
+#[derive(Clone)]
+pub enum ParlayKey {
+    /// Next parlay id: `ParlayCount` -> `u64`
+    ParlayCount,
+    /// `Parlay(parlay_id)` -> `Parlay`
+    Parlay(u64),
+    /// `ComboVault(token)` -> `ComboVault`
+    ComboVault(Address),
+    /// Least total stake a leg's pool must hold: `MinLegStake(token)` -> `i128`
+    MinLegStake(Address),
+}
+
+/// Storage keys for referral campaigns and second-level referrals.
+#[contracttype]
+#[derive(Clone)]
+pub enum ReferralKey {
+    /// Next campaign id: `CampaignCount` -> `u32`
+    CampaignCount,
+    /// `Campaign(campaign_id)` -> `ReferralCampaign`
+    Campaign(u32),
+    /// Campaigns taking new referrals: `OpenCampaigns` -> `Vec<u32>`
+    OpenCampaigns,
+    /// Campaign a referral was made under:
+    /// `Attribution(user, pool_id)` -> `u32`
+    Attribution(Address, u64),
+    /// The first referrer a user ever staked under: `Upline(user)` -> `Address`
+    Upline(Address),
+    /// Global second-level cut: `SecondLevelCutBps` -> `u32`
+    SecondLevelCutBps,
+}
+
+/// Storage keys for the protocol fee ledger.
+#[contracttype]
+#[derive(Clone)]
+pub enum TreasuryKey {
+    /// Protocol fees realized and not yet withdrawn:
+    /// `AccruedFees(token)` -> `i128`
+    AccruedFees(Address),
+    /// Recipient of swept unclaimed winnings, when not the treasury:
+    /// `UnclaimedRecipient` -> `Address`
+    UnclaimedRecipient,
+    /// Cap on `PoolConfig::creator_fee_bps`: `MaxCreatorFeeBps` -> `u32`
+    MaxCreatorFeeBps,
+    /// Volume discount schedule: `VolumeTiers` -> `Vec<VolumeTier>`
+    VolumeTiers,
+    /// Lifetime staking record: `UserStats(user)` -> `UserStats`
+    UserStats(Address),
+    /// Bond locked by `create_pool` for pools in a token:
+    /// `CreationBond(token)` -> `i128`
+    CreationBond(Address),
+}
+
+/// Storage keys for per-pool data added after `DataKey` reached the
+/// 50-variant limit of a contract type.
+#[contracttype]
+#[derive(Clone)]
+pub enum PoolKey {
+    /// Append-only participant index, in order of first prediction:
+    /// `Participant(pool_id, index)` -> `Address`
+    Participant(u64, u32),
+    /// Length of the participant index: `ParticipantCt(pool_id)` -> `u32`
+    ParticipantCt(u64),
+    /// Amount swept once the claim window closed: `Swept(pool_id)` -> `i128`
+    Swept(u64),
+    /// Exit fee charged by `withdraw_prediction`: `ExitFeeBps(pool_id)` -> `u32`
+    ExitFeeBps(u64),
+    /// A user's stakes on outcomes other than the one in `DataKey::Pred`:
+    /// `Hedges(user, pool_id)` -> `Vec<Prediction>`
+    Hedges(Address, u64),
+    /// Creator fee set at creation: `CreatorFeeBps(pool_id)` -> `u32`
+    CreatorFeeBps(u64),
+    /// Creator fee paid out by `claim_creator_fees`:
+    /// `CreatorFeesPaid(pool_id)` -> `i128`
+    CreatorFeesPaid(u64),
+    /// Commit-reveal mode of a pool: `CommitReveal(pool_id)` -> `CommitRevealConfig`
+    CommitReveal(u64),
+    /// A user's unrevealed stake: `Commitment(user, pool_id)` -> `Commitment`
+    Commitment(Address, u64),
+    /// Stake held in unrevealed commitments: `CommittedStake(pool_id)` -> `i128`
+    CommittedStake(u64),
+    /// Ed25519 key signing a private pool's invites:
+    /// `InviteSigner(pool_id)` -> `BytesN<32>`
+    InviteSigner(u64),
+    /// A revoked signed invite:
+    /// `RevokedInvite(pool_id, invitee, expires_at)` -> `bool`
+    RevokedInvite(u64, Address, u64),
+    /// Creation bond held until the pool ends: `CreationBond(pool_id)` -> `i128`
+    CreationBond(u64),
+    /// Funds of a settled LMSR pool: `AmmSettlement(pool_id)` -> `AmmSettlement`
+    AmmSettlement(u64),
+    /// Progress of a sweep spread over several calls:
+    /// `SweepProgress(pool_id)` -> `SweepProgress`
+    SweepProgress(u64),
+}
+
+/// Storage keys for outcome shares and their SEP-41 token contracts.
 #[contracttype]
 #[derive(Clone)]
+pub enum ShareKey {
+    /// Wasm deployed by `deploy_outcome_tokens`: `TokenWasm` -> `BytesN<32>`
+    /// (instance storage)
+    TokenWasm,
+    /// Token contract of a pool outcome: `Token(pool_id, outcome)` -> `Address`
+    Token(u64, u32),
+    /// `Allowance(owner, spender, pool_id, outcome)` -> `ShareAllowance`
+    /// (temporary storage)
+    Allowance(Address, Address, u64, u32),
+}
+
+/// Represents a user's individual stake in a prediction market.
+///
+/// This is the core structure for tracking participation. A user's position in a
+/// pool is one `Prediction` per outcome they have staked on: the first is stored
+/// under `DataKey::Pred(user, pool_id)`, any others under `PoolKey::Hedges`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Prediction {
     /// Total amount of tokens staked by the user on this outcome.
     pub amount: i128,
@@ -964,7 +1828,41 @@ pub struct StakeLimitsUpdatedEvent {
     pub max_stake: i128,
 }
 
-#[contractevent(topics = ["pool_description_updated"])]
+#[contractevent(topics = ["exit_fee_updated"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExitFeeUpdatedEvent {
+    pub pool_id: u64,
+    pub operator: Address,
+    pub exit_fee_bps: u32,
+}
+
+#[contractevent(topics = ["creator_fees_claimed"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreatorFeesClaimedEvent {
+    pub pool_id: u64,
+    pub creator: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["max_creator_fee_updated"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MaxCreatorFeeUpdatedEvent {
+    pub admin: Address,
+    pub max_creator_fee_bps: u32,
+}
+
+#[contractevent(topics = ["volume_tiers_update"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VolumeTiersUpdateEvent {
+    pub admin: Address,
+    pub tiers_count: u32,
+}
+
+#[contractevent(topics = ["pool_description_updated"])]
 #[contracttype(export = false)]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PoolDescriptionUpdatedEvent {
@@ -973,6 +1871,125 @@ pub struct PoolDescriptionUpdatedEvent {
     pub new_description: String,
 }
 
+#[contractevent(topics = ["commit_reveal_enabled"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommitRevealEnabledEvent {
+    pub pool_id: u64,
+    pub creator: Address,
+    pub reveal_window: u64,
+    pub unrevealed_policy: UnrevealedPolicy,
+}
+
+#[contractevent(topics = ["prediction_committed"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PredictionCommittedEvent {
+    pub pool_id: u64,
+    pub user: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["commitment_settled"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommitmentSettledEvent {
+    pub pool_id: u64,
+    pub user: Address,
+    pub amount: i128,
+    /// `false` when the stake was forfeited to the treasury.
+    pub refunded: bool,
+    /// Part of a refunded stake kept as protocol fees.
+    pub penalty: i128,
+}
+
+#[contractevent(topics = ["creation_bond_set"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreationBondSetEvent {
+    pub admin: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["creation_bond_locked"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreationBondLockedEvent {
+    pub pool_id: u64,
+    pub creator: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["creation_bond_returned"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreationBondReturnedEvent {
+    pub pool_id: u64,
+    pub creator: Address,
+    pub amount: i128,
+}
+
+/// Emitted by `reject_pool`, after `PoolCanceledEvent`. `bond_slashed` went
+/// to the treasury.
+#[contractevent(topics = ["pool_rejected"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolRejectedEvent {
+    pub pool_id: u64,
+    pub moderator: Address,
+    pub reason: String,
+    pub bond_slashed: i128,
+}
+
+#[contractevent(topics = ["invite_signer_set"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InviteSignerSetEvent {
+    pub pool_id: u64,
+    pub creator: Address,
+    pub public_key: BytesN<32>,
+}
+
+#[contractevent(topics = ["invite_revoked"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InviteRevokedEvent {
+    pub pool_id: u64,
+    pub invitee: Address,
+    pub expires_at: u64,
+}
+
+#[contractevent(topics = ["outcome_token_wasm_set"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutcomeTokenWasmSetEvent {
+    pub admin: Address,
+    pub wasm_hash: BytesN<32>,
+}
+
+/// Emitted by `deploy_outcome_tokens`. `tokens[i]` serves the shares of
+/// outcome `i`.
+#[contractevent(topics = ["outcome_tokens_deployed"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutcomeTokensDeployedEvent {
+    pub pool_id: u64,
+    pub tokens: Vec<Address>,
+}
+
+#[contractevent(topics = ["shares_approved"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SharesApprovedEvent {
+    pub pool_id: u64,
+    pub owner: Address,
+    pub spender: Address,
+    pub outcome: u32,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
 #[contractevent(topics = ["prediction_placed"])]
 #[contracttype(export = false)]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -1012,6 +2029,60 @@ pub struct ReferralPaidEvent {
     pub amount: i128,
 }
 
+#[contractevent(topics = ["referral_campaign_created"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralCampaignCreatedEvent {
+    pub admin: Address,
+    pub campaign_id: u32,
+    pub token: Address,
+    pub cut_bps: u32,
+    pub second_level_cut_bps: u32,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub budget: i128,
+}
+
+#[contractevent(topics = ["referral_campaign_ended"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralCampaignEndedEvent {
+    pub admin: Address,
+    pub campaign_id: u32,
+}
+
+#[contractevent(topics = ["referral_attributed"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralAttributedEvent {
+    pub campaign_id: u32,
+    pub pool_id: u64,
+    pub user: Address,
+    pub referrer: Address,
+}
+
+/// Emitted alongside `ReferralPaidEvent` for rewards paid from a campaign
+/// budget. `level` is 1 for the referrer and 2 for the referrer's referrer.
+#[contractevent(topics = ["campaign_referral_paid"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CampaignReferralPaidEvent {
+    pub campaign_id: u32,
+    pub pool_id: u64,
+    pub referrer: Address,
+    pub referred_user: Address,
+    pub amount: i128,
+    pub level: u32,
+}
+
+#[contractevent(topics = ["second_level_referral_updated"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SecondLevelReferralUpdatedEvent {
+    pub admin: Address,
+    pub second_level_cut_bps: u32,
+}
+
 // ── Monitoring & Alert Events ─────────────────────────────────────────────────
 // These events are classified by severity and are intended for consumption by
 // off-chain monitoring tools (Horizon event streaming, Grafana, SIEM, etc.).
@@ -1197,6 +2268,29 @@ pub struct TreasuryWithdrawnEvent {
     pub remaining_balance: i128,
     pub timestamp: u64,
 }
+#[contractevent(topics = ["unclaimed_swept"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnclaimedSweptEvent {
+    pub pool_id: u64,
+    pub recipient: Address,
+    /// Winnings no one claimed before the claim window closed.
+    pub amount: i128,
+    /// Protocol fee those claims would have realized, plus on the last call
+    /// the stake no prediction was owed, now accrued.
+    pub fees: i128,
+    /// Participants left for later calls; 0 once the sweep is complete.
+    pub remaining: u32,
+}
+
+#[contractevent(topics = ["unclaimed_recipient_set"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnclaimedRecipientSetEvent {
+    pub admin: Address,
+    pub recipient: Option<Address>,
+}
+
 #[contractevent(topics = ["emergency_withdraw"])]
 #[contracttype(export = false)]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -1223,6 +2317,89 @@ pub struct UpgradeEvent {
     pub new_wasm_hash: BytesN<32>,
 }
 
+#[contractevent(topics = ["upgrade_proposed"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeProposedEvent {
+    pub admin: Address,
+    pub new_wasm_hash: BytesN<32>,
+    pub executable_at: u64,
+    pub required_approvals: u32,
+}
+
+#[contractevent(topics = ["upgrade_approved"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeApprovedEvent {
+    pub admin: Address,
+    pub new_wasm_hash: BytesN<32>,
+    pub approvals: u32,
+}
+
+#[contractevent(topics = ["upgrade_canceled"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeCanceledEvent {
+    pub admin: Address,
+    pub new_wasm_hash: BytesN<32>,
+}
+
+#[contractevent(topics = ["upgrade_policy_updated"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradePolicyUpdatedEvent {
+    pub admin: Address,
+    pub delay: u64,
+    pub required_approvals: u32,
+}
+
+#[contractevent(topics = ["migration_progress"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationProgressEvent {
+    pub admin: Address,
+    pub target_version: u32,
+    pub next_pool_id: u64,
+}
+
+#[contractevent(topics = ["approval_proposed"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalProposedEvent {
+    pub action: SensitiveAction,
+    pub proposer: Address,
+    pub threshold: u32,
+    pub expires_at: u64,
+}
+
+#[contractevent(topics = ["approval_granted"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalGrantedEvent {
+    pub action: SensitiveAction,
+    pub approver: Address,
+    pub approvals: u32,
+}
+
+#[contractevent(topics = ["approval_executed"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalExecutedEvent {
+    pub action: SensitiveAction,
+    pub executor: Address,
+    pub approvals: u32,
+}
+
+#[contractevent(topics = ["approval_policy_updated"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalPolicyUpdatedEvent {
+    pub admin: Address,
+    pub kind: SensitiveActionKind,
+    pub threshold: u32,
+    pub expiry: u64,
+}
+
 #[contractevent(topics = ["contract_upgraded"])]
 #[contracttype(export = false)]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -1242,6 +2419,99 @@ pub struct OracleInitEvent {
     pub min_confidence_ratio: u32,
 }
 
+/// Emitted when a pool's aggregated price resolution changes. A `window` of
+/// zero means the pool went back to resolving on the latest push.
+#[contractevent(topics = ["price_aggregation_set"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceAggregationSetEvent {
+    pub pool_id: u64,
+    pub aggregation: PriceAggregation,
+}
+
+/// Emitted when the treasury adds liquidity to a combo vault.
+#[contractevent(topics = ["combo_vault_funded"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComboVaultFundedEvent {
+    pub token: Address,
+    pub amount: i128,
+    pub liquidity: i128,
+}
+
+/// Emitted when an admin sets the minimum pool stake for parlay legs in a token.
+#[contractevent(topics = ["parlay_min_leg_stake_set"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParlayMinLegStakeSetEvent {
+    pub admin: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Emitted when free combo vault liquidity is returned to the treasury.
+#[contractevent(topics = ["combo_vault_withdrawn"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComboVaultWithdrawnEvent {
+    pub admin: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub liquidity: i128,
+}
+
+#[contractevent(topics = ["parlay_placed"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParlayPlacedEvent {
+    pub parlay_id: u64,
+    pub user: Address,
+    pub token: Address,
+    pub stake: i128,
+    pub legs: u32,
+    pub payout: i128,
+}
+
+/// Emitted when a parlay is decided (`Lost` or `Won`) or a voided leg
+/// lowers its payout while it stays `Open`.
+#[contractevent(topics = ["parlay_settled"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParlaySettledEvent {
+    pub parlay_id: u64,
+    pub status: ParlayStatus,
+    pub payout: i128,
+}
+
+#[contractevent(topics = ["parlay_claimed"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParlayClaimedEvent {
+    pub parlay_id: u64,
+    pub user: Address,
+    pub amount: i128,
+}
+
+/// Emitted when the price source a pool resolves on changes.
+#[contractevent(topics = ["price_source_set"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceSourceSetEvent {
+    pub pool_id: u64,
+    pub source: PriceSourceKind,
+}
+
+/// Emitted when a feed pair is bound to (`Some`) or unbound from (`None`) a
+/// Pyth price id.
+#[contractevent(topics = ["pyth_price_id_set"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PythPriceIdSetEvent {
+    pub admin: Address,
+    pub feed_pair: Symbol,
+    pub price_id: Option<BytesN<32>>,
+}
+
 #[contractevent(topics = ["price_feed_updated"])]
 #[contracttype(export = false)]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -1265,22 +2535,53 @@ pub struct PriceConditionSetEvent {
     pub tolerance_bps: u32,
 }
 
-#[contractevent(topics = ["price_resolved"])]
+#[contractevent(topics = ["scalar_range_set"])]
 #[contracttype(export = false)]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PriceResolvedEvent {
+pub struct ScalarRangeSetEvent {
     pub pool_id: u64,
     pub feed_pair: Symbol,
-    pub current_price: i128,
-    pub target_price: i128,
-    pub outcome: u32,
+    pub lower: i128,
+    pub upper: i128,
 }
 
-/// Emitted when expired price feeds are pruned from storage.
-///
-/// `feeds_removed` is the count of `DataKey::PriceFeed` entries deleted.
-/// `timestamp` is the ledger time at which the cleanup ran.
-#[contractevent(topics = ["price_feeds_cleaned"])]
+#[contractevent(topics = ["price_brackets_set"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceBracketsSetEvent {
+    pub pool_id: u64,
+    pub feed_pair: Symbol,
+    pub thresholds: Vec<i128>,
+}
+
+/// Emitted when a scalar pool settles. `long_bps` is the long side's share
+/// of the payout pool.
+#[contractevent(topics = ["scalar_resolved"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScalarResolvedEvent {
+    pub pool_id: u64,
+    pub feed_pair: Symbol,
+    pub price: i128,
+    pub long_bps: u32,
+}
+
+#[contractevent(topics = ["price_resolved"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceResolvedEvent {
+    pub pool_id: u64,
+    pub feed_pair: Symbol,
+    pub current_price: i128,
+    pub target_price: i128,
+    pub outcome: u32,
+}
+
+/// Emitted when expired price feeds are pruned from storage.
+///
+/// `feeds_removed` is the count of `DataKey::PriceFeed` entries deleted.
+/// `timestamp` is the ledger time at which the cleanup ran.
+#[contractevent(topics = ["price_feeds_cleaned"])]
 #[contracttype(export = false)]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PriceFeedsCleanedEvent {
@@ -1308,6 +2609,169 @@ pub struct ResolutionVoteCastEvent {
     pub vote_count: u32,
     pub required_resolutions: u32,
 }
+/// Emitted when a moderator opens a dispute and locks the bond.
+#[contractevent(topics = ["dispute_opened"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeOpenedEvent {
+    pub pool_id: u64,
+    pub moderator: Address,
+    pub bond: i128,
+    /// State the pool was in before it became `Disputed`.
+    pub prior_state: MarketState,
+    pub reason: String,
+}
+
+/// Emitted when an admin settles a dispute.
+///
+/// `bond_slashed` is true when the bond was sent to the treasury
+/// (`UpholdOriginal`); otherwise it was returned to the moderator.
+#[contractevent(topics = ["dispute_settled"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeSettledEvent {
+    pub pool_id: u64,
+    pub arbitrator: Address,
+    pub ruling: DisputeRuling,
+    /// Pool state after the ruling was applied.
+    pub new_state: MarketState,
+    pub bond: i128,
+    pub bond_slashed: bool,
+}
+
+#[contractevent(topics = ["dispute_config_update"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeConfigUpdateEvent {
+    pub admin: Address,
+    pub bond_bps: u32,
+    pub min_bond: i128,
+    pub challenge_window: u64,
+}
+
+#[contractevent(topics = ["outcome_proposed"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutcomeProposedEvent {
+    pub pool_id: u64,
+    pub proposer: Address,
+    pub outcome: u32,
+    pub bond: i128,
+    /// Timestamp after which the proposal can be finalized if unchallenged.
+    pub liveness_ends_at: u64,
+}
+
+#[contractevent(topics = ["outcome_challenged"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutcomeChallengedEvent {
+    pub pool_id: u64,
+    pub challenger: Address,
+    pub proposed_outcome: u32,
+    pub bond: i128,
+}
+
+/// Emitted when the bonds of an outcome proposal are paid out.
+#[contractevent(topics = ["proposal_settled"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalSettledEvent {
+    pub pool_id: u64,
+    pub proposed_outcome: u32,
+    /// Pool state after settlement (`Resolved` or `Canceled`).
+    pub final_state: MarketState,
+    /// Whether the proposal was challenged before settlement.
+    pub challenged: bool,
+    /// Whether the proposed outcome matched the final outcome.
+    pub proposer_correct: bool,
+}
+
+/// Emitted when a user buys or sells shares in an LMSR pool.
+#[contractevent(topics = ["amm_trade"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AmmTradeEvent {
+    pub pool_id: u64,
+    pub trader: Address,
+    pub outcome: u32,
+    /// Shares bought (positive) or sold (negative).
+    pub shares: i128,
+    /// Tokens paid for a buy or received for a sale.
+    pub amount: i128,
+    /// Price of `outcome` after the trade, in basis points.
+    pub price_bps: u32,
+}
+
+/// Emitted when the creator of a settled LMSR pool reclaims the market
+/// maker's left-over subsidy.
+#[contractevent(topics = ["amm_surplus_claimed"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AmmSurplusClaimedEvent {
+    pub pool_id: u64,
+    pub creator: Address,
+    pub amount: i128,
+}
+
+/// Emitted whenever ownership of (part of) a prediction changes hands,
+/// either through `transfer_prediction` or a filled listing.
+#[contractevent(topics = ["prediction_transferred"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PredictionTransferredEvent {
+    pub pool_id: u64,
+    pub from: Address,
+    pub to: Address,
+    pub outcome: u32,
+    pub amount: i128,
+}
+
+/// Emitted by `withdraw_prediction`. `amount` leaves the user's position;
+/// `refund = amount - fee` is paid out and `fee` stays in the pool.
+#[contractevent(topics = ["prediction_withdrawn"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PredictionWithdrawnEvent {
+    pub pool_id: u64,
+    pub user: Address,
+    pub outcome: u32,
+    pub amount: i128,
+    pub fee: i128,
+    pub refund: i128,
+}
+
+#[contractevent(topics = ["prediction_listed"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PredictionListedEvent {
+    pub listing_id: u64,
+    pub pool_id: u64,
+    pub seller: Address,
+    pub outcome: u32,
+    pub amount: i128,
+    pub price: i128,
+}
+
+#[contractevent(topics = ["prediction_sold"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PredictionSoldEvent {
+    pub listing_id: u64,
+    pub pool_id: u64,
+    pub seller: Address,
+    pub buyer: Address,
+    pub amount: i128,
+    pub price: i128,
+}
+
+#[contractevent(topics = ["listing_canceled"])]
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ListingCanceledEvent {
+    pub listing_id: u64,
+    pub pool_id: u64,
+    pub seller: Address,
+}
 mod events;
 use events::ClaimWindowUpdateEvent;
 // pub use events::*; // Unused import
@@ -1708,7 +3172,7 @@ impl PredifiContract {
             .extend_ttl(BUMP_THRESHOLD, BUMP_AMOUNT);
     }
 
-    fn extend_persistent(env: &Env, key: &DataKey) {
+    fn extend_persistent<K: IntoVal<Env, soroban_sdk::Val>>(env: &Env, key: &K) {
         env.storage()
             .persistent()
             .extend_ttl(key, BUMP_THRESHOLD, BUMP_AMOUNT);
@@ -1750,6 +3214,34 @@ impl PredifiContract {
         // inner: XDR error
     }
 
+    /// Call `has_role_for_pool` on the access-control contract.
+    ///
+    /// Returns `false` when the call fails, so an access-control contract
+    /// without scoped grants simply grants nothing through this path.
+    fn has_role_for_pool(
+        env: &Env,
+        contract: &Address,
+        user: &Address,
+        role: u32,
+        pool_id: u64,
+        category: &Symbol,
+    ) -> bool {
+        env.try_invoke_contract::<bool, PredifiError>(
+            contract,
+            &Symbol::new(env, "has_role_for_pool"),
+            soroban_sdk::vec![
+                env,
+                user.into_val(env),
+                role.into_val(env),
+                pool_id.into_val(env),
+                category.into_val(env),
+            ],
+        )
+        .ok()
+        .and_then(|inner| inner.ok())
+        .unwrap_or(false)
+    }
+
     /// Call `get_admin` on the access-control contract.
     ///
     /// Maps external call failures to [`PredifiError::InvalidData`] so callers
@@ -1792,12 +3284,132 @@ impl PredifiContract {
         Ok(())
     }
 
+    /// Admins may approve every sensitive action; operators may also approve
+    /// emergency cancellations.
+    fn can_approve(env: &Env, approver: &Address, kind: SensitiveActionKind) -> bool {
+        Self::require_role(env, approver, 0).is_ok()
+            || (kind == SensitiveActionKind::EmergencyCancelPool
+                && Self::require_role(env, approver, 1).is_ok())
+    }
+
+    fn read_approval_policy(env: &Env, kind: SensitiveActionKind) -> ApprovalPolicy {
+        // Changing the upgrade policy takes as many admins as an upgrade.
+        if kind == SensitiveActionKind::UpgradePolicy {
+            return ApprovalPolicy {
+                threshold: Self::get_upgrade_policy(env.clone()).required_approvals,
+                expiry: DEFAULT_APPROVAL_EXPIRY_SECONDS,
+            };
+        }
+        env.storage()
+            .instance()
+            .get(&ApprovalKey::ApprovalPolicy(kind))
+            .unwrap_or(ApprovalPolicy {
+                threshold: match kind {
+                    SensitiveActionKind::EmergencyCancelPool => EMERGENCY_CANCEL_MULTISIG_THRESHOLD,
+                    _ => DEFAULT_APPROVAL_THRESHOLD,
+                },
+                expiry: DEFAULT_APPROVAL_EXPIRY_SECONDS,
+            })
+    }
+
+    /// Records `approver`'s approval of `action`, proposing it when no live
+    /// request is pending. Returns `None` once the policy threshold is met,
+    /// after clearing the request, so the caller executes the action; returns
+    /// the still-pending request otherwise. Callers check the approver's role
+    /// first.
+    fn record_approval(
+        env: &Env,
+        approver: &Address,
+        action: &SensitiveAction,
+    ) -> Result<Option<ApprovalRequest>, PredifiError> {
+        let kind = action.kind();
+        let policy = Self::read_approval_policy(env, kind);
+        let key = ApprovalKey::ApprovalRequest(action.clone());
+        let now = env.ledger().timestamp();
+
+        let mut request = match env.storage().persistent().get::<_, ApprovalRequest>(&key) {
+            Some(request) if now < request.expires_at => {
+                if request.approvals.contains(approver) {
+                    return Err(PredifiError::EmergencyCancelAlreadyApproved);
+                }
+                request
+            }
+            // Nothing pending, or a stale proposal: start over.
+            _ => {
+                let expires_at = now.saturating_add(policy.expiry);
+                ApprovalProposedEvent {
+                    action: action.clone(),
+                    proposer: approver.clone(),
+                    threshold: policy.threshold,
+                    expires_at,
+                }
+                .publish(env);
+                ApprovalRequest {
+                    action: action.clone(),
+                    approvals: Vec::new(env),
+                    expires_at,
+                }
+            }
+        };
+        request.approvals.push_back(approver.clone());
+        if request.approvals.len() > 1 {
+            ApprovalGrantedEvent {
+                action: action.clone(),
+                approver: approver.clone(),
+                approvals: request.approvals.len(),
+            }
+            .publish(env);
+        }
+
+        // Approvers who have since lost their role no longer count.
+        let approvals = request
+            .approvals
+            .iter()
+            .filter(|a| Self::can_approve(env, a, kind))
+            .count() as u32;
+        if approvals >= policy.threshold {
+            env.storage().persistent().remove(&key);
+            ApprovalExecutedEvent {
+                action: action.clone(),
+                executor: approver.clone(),
+                approvals,
+            }
+            .publish(env);
+            return Ok(None);
+        }
+
+        env.storage().persistent().set(&key, &request);
+        Self::extend_persistent(env, &key);
+        Ok(Some(request))
+    }
+
+    /// Operators with a global grant may resolve any pool; others need an
+    /// Operator grant scoped to `pool_id` or to the pool's category.
     fn require_operator_role_for_resolution(
         env: &Env,
         operator: &Address,
         pool_id: u64,
     ) -> Result<(), PredifiError> {
-        if let Err(e) = Self::require_role(env, operator, 1) {
+        let result = Self::require_role(env, operator, 1).or_else(|e| {
+            let pool: Option<Pool> = env.storage().persistent().get(&DataKey::Pool(pool_id));
+            let scoped = pool.is_some_and(|pool| {
+                let config = Self::get_config(env);
+                Self::has_role_for_pool(
+                    env,
+                    &config.access_control,
+                    operator,
+                    1,
+                    pool_id,
+                    &pool.category,
+                )
+            });
+            if scoped {
+                Ok(())
+            } else {
+                Err(e)
+            }
+        });
+        if let Err(e) = result {
             UnauthorizedResolveAttemptEvent {
                 caller: operator.clone(),
                 pool_id,
@@ -1840,6 +3452,179 @@ impl PredifiContract {
     ///
     /// Prefers `Config.referral_bps` (set via `set_referral_rate`) when non-zero,
     /// then falls back to the legacy `ReferralCutBps` storage key, then to 5000.
+    /// Bind a referral made on `user`'s first prediction in `pool_id`: record
+    /// `referrer` as the user's upline if they have none, and attribute the
+    /// referral to the first open campaign covering the pool.
+    fn attribute_referral(
+        env: &Env,
+        user: &Address,
+        pool_id: u64,
+        pool: &Pool,
+        referrer: &Address,
+    ) {
+        let upline_key = ReferralKey::Upline(user.clone());
+        if referrer != user && !env.storage().persistent().has(&upline_key) {
+            env.storage().persistent().set(&upline_key, referrer);
+        }
+        if env.storage().persistent().has(&upline_key) {
+            Self::extend_persistent(env, &upline_key);
+        }
+
+        let now = env.ledger().timestamp();
+        let open: Vec<u32> = env
+            .storage()
+            .instance()
+            .get(&ReferralKey::OpenCampaigns)
+            .unwrap_or_else(|| Vec::new(env));
+        for campaign_id in open.iter() {
+            let Some(campaign) = env
+                .storage()
+                .persistent()
+                .get::<_, ReferralCampaign>(&ReferralKey::Campaign(campaign_id))
+            else {
+                continue;
+            };
+            let config = &campaign.config;
+            let covers = now >= config.start_time
+                && now < config.end_time
+                && campaign.spent < config.budget
+                && config.token == pool.token
+                && (config.pool_ids.is_empty() || config.pool_ids.contains(pool_id))
+                && (config.categories.is_empty() || config.categories.contains(&pool.category));
+            if !covers {
+                continue;
+            }
+
+            let key = ReferralKey::Attribution(user.clone(), pool_id);
+            env.storage().persistent().set(&key, &campaign_id);
+            Self::extend_persistent(env, &key);
+            ReferralAttributedEvent {
+                campaign_id,
+                pool_id,
+                user: user.clone(),
+                referrer: referrer.clone(),
+            }
+            .publish(env);
+            return;
+        }
+    }
+
+    /// Pay the referral rewards on `user`'s claim of `paid_stake` from a pool
+    /// whose protocol fee is `protocol_fee`, and return the total paid.
+    ///
+    /// The referrer earns the referral cut and their own referrer, if any,
+    /// the second-level cut. A referral attributed to a campaign with budget
+    /// left uses the campaign's cuts and is capped by what remains of its
+    /// budget. The second-level reward only comes out of what is left of
+    /// `realized_fee` after the first.
+    fn pay_referrals(
+        env: &Env,
+        pool_id: u64,
+        pool: &Pool,
+        user: &Address,
+        paid_stake: i128,
+        protocol_fee: i128,
+        realized_fee: i128,
+    ) -> Result<i128, PredifiError> {
+        let referrer_key = DataKey::Referrer(user.clone(), pool_id);
+        let Some(referrer) = env.storage().persistent().get::<_, Address>(&referrer_key) else {
+            return Ok(0);
+        };
+        Self::extend_persistent(env, &referrer_key);
+        if protocol_fee <= 0 || pool.total_stake <= 0 {
+            return Ok(0);
+        }
+
+        let mut campaign = env
+            .storage()
+            .persistent()
+            .get::<_, u32>(&ReferralKey::Attribution(user.clone(), pool_id))
+            .and_then(|campaign_id| {
+                env.storage()
+                    .persistent()
+                    .get::<_, ReferralCampaign>(&ReferralKey::Campaign(campaign_id))
+                    .map(|campaign| (campaign_id, campaign))
+            })
+            .filter(|(_, campaign)| campaign.spent < campaign.config.budget);
+        let (cut_bps, second_level_cut_bps) = match &campaign {
+            Some((_, campaign)) => (
+                campaign.config.cut_bps,
+                campaign.config.second_level_cut_bps,
+            ),
+            None => (
+                Self::read_referral_cut_bps(env),
+                Self::get_second_level_referral_bps(env.clone()),
+            ),
+        };
+        let upline = env
+            .storage()
+            .persistent()
+            .get::<_, Address>(&ReferralKey::Upline(referrer.clone()))
+            .filter(|upline| upline != user);
+
+        let token_client = token::Client::new(env, &pool.token);
+        let mut paid: i128 = 0;
+        for (level, recipient, bps) in [
+            (1u32, Some(referrer), cut_bps),
+            (2u32, upline, second_level_cut_bps),
+        ] {
+            let Some(recipient) = recipient else {
+                continue;
+            };
+            let mut amount =
+                calculate_referral_amount(paid_stake, pool.total_stake, protocol_fee, bps as i128)
+                    .map_err(|_| PredifiError::InvalidAmount)?;
+            if level == 2 {
+                amount = amount.min(realized_fee - paid);
+            }
+            if let Some((_, campaign)) = &mut campaign {
+                amount = amount.min(campaign.config.budget - campaign.spent);
+                campaign.spent += amount.max(0);
+            }
+            if amount <= 0 {
+                continue;
+            }
+
+            Self::validate_token_transfer(
+                env,
+                &pool.token,
+                &env.current_contract_address(),
+                &recipient,
+                amount,
+            )?;
+            token_client.transfer(&env.current_contract_address(), &recipient, &amount);
+            paid = paid
+                .checked_add(amount)
+                .ok_or(PredifiError::ArithmeticError)?;
+
+            if let Some((campaign_id, _)) = &campaign {
+                CampaignReferralPaidEvent {
+                    campaign_id: *campaign_id,
+                    pool_id,
+                    referrer: recipient.clone(),
+                    referred_user: user.clone(),
+                    amount,
+                    level,
+                }
+                .publish(env);
+            }
+            ReferralPaidEvent {
+                pool_id,
+                referrer: recipient,
+                referred_user: user.clone(),
+                amount,
+            }
+            .publish(env);
+        }
+
+        if let Some((campaign_id, campaign)) = campaign {
+            let key = ReferralKey::Campaign(campaign_id);
+            env.storage().persistent().set(&key, &campaign);
+            Self::extend_persistent(env, &key);
+        }
+        Ok(paid)
+    }
+
     fn read_referral_cut_bps(env: &Env) -> u32 {
         // Prefer the value stored in Config (set via set_referral_rate).
         let config_bps: Option<Config> = env.storage().instance().get(&DataKey::Config);
@@ -1859,6 +3644,589 @@ impl PredifiContract {
         bps
     }
 
+    /// Add `amount` of `token` to the protocol fees `withdraw_treasury` may
+    /// pay out.
+    fn credit_accrued_fees(env: &Env, token: &Address, amount: i128) -> Result<(), PredifiError> {
+        if amount <= 0 {
+            return Ok(());
+        }
+        let key = TreasuryKey::AccruedFees(token.clone());
+        let accrued = env
+            .storage()
+            .persistent()
+            .get::<_, i128>(&key)
+            .unwrap_or(0)
+            .checked_add(amount)
+            .ok_or(PredifiError::ArithmeticError)?;
+        env.storage().persistent().set(&key, &accrued);
+        Self::extend_persistent(env, &key);
+        Ok(())
+    }
+
+    /// Credit the exit fees `withdraw_prediction` left in a canceled pool to
+    /// the accrued fees. Refunds return each user's remaining stake, so the
+    /// part of `total_stake` beyond the outcome stakes and the creator's
+    /// liquidity is owed to nobody.
+    fn credit_retained_exit_fees(env: &Env, pool_id: u64, pool: &Pool) -> Result<(), PredifiError> {
+        if pool.pricing_mode != PricingMode::Parimutuel {
+            return Ok(());
+        }
+        let staked = Self::get_outcome_stakes(env, pool_id, pool.options_count)
+            .iter()
+            .try_fold(0i128, |total, stake| total.checked_add(stake))
+            .ok_or(PredifiError::ArithmeticError)?;
+        let retained = pool
+            .total_stake
+            .checked_sub(pool.initial_liquidity)
+            .and_then(|rest| rest.checked_sub(staked))
+            .ok_or(PredifiError::ArithmeticError)?;
+        Self::credit_accrued_fees(env, &pool.token, retained)
+    }
+
+    /// Swap-remove `pool_id` from `user`'s prediction index.
+    fn remove_from_user_index(env: &Env, user: &Address, pool_id: u64) {
+        let count_key = DataKey::UsrPrdCnt(user.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+        for i in 0..count {
+            let key = DataKey::UsrPrdIdx(user.clone(), i);
+            let indexed: Option<u64> = env.storage().persistent().get(&key);
+            if indexed != Some(pool_id) {
+                continue;
+            }
+
+            let last = count - 1;
+            let last_key = DataKey::UsrPrdIdx(user.clone(), last);
+            if i != last {
+                let last_pool: u64 = env
+                    .storage()
+                    .persistent()
+                    .get(&last_key)
+                    .expect("prediction index out of sync");
+                env.storage().persistent().set(&key, &last_pool);
+                Self::extend_persistent(env, &key);
+            }
+            env.storage().persistent().remove(&last_key);
+            env.storage().persistent().set(&count_key, &last);
+            Self::extend_persistent(env, &count_key);
+            return;
+        }
+    }
+
+
+    /// Append `user` to the pool's participant index, which
+    /// `sweep_expired_pool` walks. Call whenever a user with no position in
+    /// the pool opens one.
+    fn record_participant(env: &Env, pool_id: u64, user: &Address) {
+        let count_key = PoolKey::ParticipantCt(pool_id);
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let index_key = PoolKey::Participant(pool_id, count);
+        env.storage().persistent().set(&index_key, user);
+        Self::extend_persistent(env, &index_key);
+        env.storage().persistent().set(&count_key, &(count + 1));
+        Self::extend_persistent(env, &count_key);
+    }
+
+    /// `user`'s position in `pool_id`: one stake per outcome, in the order
+    /// the outcomes were first staked on. Empty if the user holds nothing.
+    fn load_position(env: &Env, user: &Address, pool_id: u64) -> Vec<Prediction> {
+        let mut position = Vec::new(env);
+        let pred_key = DataKey::Pred(user.clone(), pool_id);
+        let Some(first) = env.storage().persistent().get::<_, Prediction>(&pred_key) else {
+            return position;
+        };
+        Self::extend_persistent(env, &pred_key);
+        position.push_back(first);
+
+        let hedges_key = PoolKey::Hedges(user.clone(), pool_id);
+        if let Some(hedges) = env
+            .storage()
+            .persistent()
+            .get::<_, Vec<Prediction>>(&hedges_key)
+        {
+            Self::extend_persistent(env, &hedges_key);
+            position.append(&hedges);
+        }
+        position
+    }
+
+    /// Persist a position read with [`Self::load_position`]. The first stake
+    /// goes to `DataKey::Pred`, so single-outcome positions keep their
+    /// original layout; an empty position removes both entries.
+    fn store_position(env: &Env, user: &Address, pool_id: u64, position: &Vec<Prediction>) {
+        let pred_key = DataKey::Pred(user.clone(), pool_id);
+        match position.first() {
+            Some(first) => {
+                env.storage().persistent().set(&pred_key, &first);
+                Self::extend_persistent(env, &pred_key);
+            }
+            None => env.storage().persistent().remove(&pred_key),
+        }
+
+        let hedges_key = PoolKey::Hedges(user.clone(), pool_id);
+        if position.len() > 1 {
+            env.storage()
+                .persistent()
+                .set(&hedges_key, &position.slice(1..));
+            Self::extend_persistent(env, &hedges_key);
+        } else {
+            env.storage().persistent().remove(&hedges_key);
+        }
+    }
+
+    /// Add `amount` on `outcome` to `user`'s position in `pool` with its
+    /// bookkeeping: lifetime and referred volume, the participant and
+    /// prediction indexes on a first stake, `total_stake` and the outcome
+    /// stake (INV-1). Stores the pool; moves no tokens.
+    fn record_stake(
+        env: &Env,
+        pool_id: u64,
+        pool: &mut Pool,
+        user: &Address,
+        outcome: u32,
+        amount: i128,
+        referrer: Option<&Address>,
+    ) -> Result<(), PredifiError> {
+        let mut position = Self::load_position(env, user, pool_id);
+        let is_new_participant = position.is_empty();
+        Self::add_stake(&mut position, outcome, amount)?;
+        Self::store_position(env, user, pool_id, &position);
+        Self::record_user_volume(env, user, amount)?;
+
+        if !is_new_participant {
+            // Track referred volume: if this user already has a referrer, add to their volume
+            let referrer_key = DataKey::Referrer(user.clone(), pool_id);
+            if let Some(referrer_addr) = env.storage().persistent().get::<_, Address>(&referrer_key)
+            {
+                Self::extend_persistent(env, &referrer_key);
+                let vol_key = DataKey::ReferredVolume(referrer_addr.clone(), pool_id);
+                let vol: i128 = env.storage().persistent().get(&vol_key).unwrap_or(0);
+                env.storage().persistent().set(&vol_key, &(vol + amount));
+                Self::extend_persistent(env, &vol_key);
+            }
+        } else {
+            // Store referrer on first prediction and track referred volume.
+            // NOTE: Only one referrer per (user, pool) is supported today.
+            // See DataKey::Referrer for a note on extending this to multiple referrers.
+            if let Some(referrer_addr) = referrer {
+                let referrer_key = DataKey::Referrer(user.clone(), pool_id);
+                env.storage().persistent().set(&referrer_key, referrer_addr);
+                Self::extend_persistent(env, &referrer_key);
+                let vol_key = DataKey::ReferredVolume(referrer_addr.clone(), pool_id);
+                let vol: i128 = env.storage().persistent().get(&vol_key).unwrap_or(0);
+                env.storage().persistent().set(&vol_key, &(vol + amount));
+                Self::extend_persistent(env, &vol_key);
+                Self::attribute_referral(env, user, pool_id, pool, referrer_addr);
+            }
+
+            // Increment participants_count in the pool struct
+            pool.participants_count = pool.participants_count.saturating_add(1);
+            Self::record_participant(env, pool_id, user);
+
+            let count_key = DataKey::UsrPrdCnt(user.clone());
+            let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+            let index_key = DataKey::UsrPrdIdx(user.clone(), count);
+            env.storage().persistent().set(&index_key, &pool_id);
+            Self::extend_persistent(env, &index_key);
+
+            env.storage().persistent().set(&count_key, &(count + 1));
+            Self::extend_persistent(env, &count_key);
+        }
+
+        // Update total stake (INV-1)
+        pool.total_stake = pool
+            .total_stake
+            .checked_add(amount)
+            .ok_or(PredifiError::ArithmeticError)?;
+        let pool_key = DataKey::Pool(pool_id);
+        env.storage().persistent().set(&pool_key, pool);
+        Self::bump_ttl(env, &pool_key);
+
+        // Update outcome stake (INV-1) - using optimized batch storage
+        Self::update_outcome_stake(env, pool_id, outcome, amount, pool.options_count);
+        Ok(())
+    }
+
+    /// Commit-reveal mode of `pool_id`, if its creator enabled it.
+    fn commit_reveal_config(env: &Env, pool_id: u64) -> Option<CommitRevealConfig> {
+        env.storage()
+            .persistent()
+            .get(&PoolKey::CommitReveal(pool_id))
+    }
+
+    /// Earliest time `pool` can be resolved: after `resolution_delay` and,
+    /// for commit-reveal pools, after the reveal window.
+    fn resolution_eligible_at(
+        env: &Env,
+        pool_id: u64,
+        pool: &Pool,
+        resolution_delay: u64,
+    ) -> u64 {
+        let eligible_at = pool.end_time.saturating_add(resolution_delay);
+        match Self::commit_reveal_config(env, pool_id) {
+            Some(config) => eligible_at.max(pool.end_time.saturating_add(config.reveal_window)),
+            None => eligible_at,
+        }
+    }
+
+    /// Add `amount` to the stake on `outcome`, opening it if needed.
+    fn add_stake(
+        position: &mut Vec<Prediction>,
+        outcome: u32,
+        amount: i128,
+    ) -> Result<(), PredifiError> {
+        for (i, mut stake) in position.iter().enumerate() {
+            if stake.outcome == outcome {
+                stake.amount = stake
+                    .amount
+                    .checked_add(amount)
+                    .ok_or(PredifiError::ArithmeticError)?;
+                position.set(i as u32, stake);
+                return Ok(());
+            }
+        }
+        position.push_back(Prediction { amount, outcome });
+        Ok(())
+    }
+
+    /// Remove `amount` from the stake on `outcome`, closing it at zero.
+    ///
+    /// # Errors
+    /// - `InsufficientBalance` – the stake on `outcome` is less than `amount`.
+    fn take_stake(
+        position: &mut Vec<Prediction>,
+        outcome: u32,
+        amount: i128,
+    ) -> Result<(), PredifiError> {
+        let index = position
+            .iter()
+            .position(|stake| stake.outcome == outcome)
+            .ok_or(PredifiError::InsufficientBalance)? as u32;
+        let mut stake = position.get_unchecked(index);
+        if stake.amount < amount {
+            return Err(PredifiError::InsufficientBalance);
+        }
+        stake.amount -= amount;
+        if stake.amount == 0 {
+            position.remove(index);
+        } else {
+            position.set(index, stake);
+        }
+        Ok(())
+    }
+
+    /// Transfer the creation bond set for `token` from `creator` and hold it
+    /// for `pool_id`. No-op when the token has no bond.
+    fn lock_creation_bond(
+        env: &Env,
+        pool_id: u64,
+        creator: &Address,
+        token: &Address,
+    ) -> Result<(), PredifiError> {
+        let amount: i128 = env
+            .storage()
+            .instance()
+            .get(&TreasuryKey::CreationBond(token.clone()))
+            .unwrap_or(0);
+        if amount <= 0 {
+            return Ok(());
+        }
+
+        let key = PoolKey::CreationBond(pool_id);
+        env.storage().persistent().set(&key, &amount);
+        Self::extend_persistent(env, &key);
+        token::Client::new(env, token).transfer(creator, env.current_contract_address(), &amount);
+
+        CreationBondLockedEvent {
+            pool_id,
+            creator: creator.clone(),
+            amount,
+        }
+        .publish(env);
+        Ok(())
+    }
+
+    /// Check that `user` may stake in the private pool `pool_id`: as its
+    /// creator, through a whitelist entry, or with a valid `invite`.
+    ///
+    /// A signed invite is first checked for a pool invite signer, expiry and
+    /// revocation, each failing with `Unauthorized`. Only then is its
+    /// signature verified: one that does not verify against the signer traps
+    /// in the host, failing the whole transaction instead of returning an
+    /// error.
+    fn require_private_access(
+        env: &Env,
+        pool_id: u64,
+        pool: &Pool,
+        user: &Address,
+        invite: &Option<Invite>,
+    ) -> Result<(), PredifiError> {
+        if *user == pool.creator
+            || env
+                .storage()
+                .persistent()
+                .get(&DataKey::Whitelist(pool_id, user.clone()))
+                .unwrap_or(false)
+        {
+            return Ok(());
+        }
+        let invite = match invite {
+            None => return Err(PredifiError::Unauthorized),
+            Some(Invite::Key(key)) if pool.whitelist_key.as_ref() == Some(key) => return Ok(()),
+            Some(Invite::Key(_)) => return Err(PredifiError::Unauthorized),
+            Some(Invite::Signed(invite)) => invite,
+        };
+
+        let signer: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&PoolKey::InviteSigner(pool_id))
+            .ok_or(PredifiError::Unauthorized)?;
+        if env.ledger().timestamp() >= invite.expires_at {
+            return Err(PredifiError::Unauthorized);
+        }
+        let revoked_key = PoolKey::RevokedInvite(pool_id, user.clone(), invite.expires_at);
+        if env.storage().persistent().has(&revoked_key) {
+            return Err(PredifiError::Unauthorized);
+        }
+
+        let message = Self::invite_terms(env, pool_id, user, invite.expires_at);
+        env.crypto()
+            .ed25519_verify(&signer, &message, &invite.signature);
+        Ok(())
+    }
+
+    /// XDR of the `InviteTerms` an invite signer signs.
+    fn invite_terms(env: &Env, pool_id: u64, invitee: &Address, expires_at: u64) -> Bytes {
+        InviteTerms {
+            contract: env.current_contract_address(),
+            pool_id,
+            invitee: invitee.clone(),
+            expires_at,
+        }
+        .to_xdr(env)
+    }
+
+    /// Load a pool that is `Active` and has not reached its `end_time`.
+    fn load_tradable_pool(env: &Env, pool_id: u64) -> Result<(DataKey, Pool), PredifiError> {
+        let pool_key = DataKey::Pool(pool_id);
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .ok_or(PredifiError::PoolNotFound)?;
+        Self::extend_persistent(env, &pool_key);
+
+        if !Self::is_pool_active(&pool) {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if env.ledger().timestamp() >= pool.end_time {
+            return Err(PredifiError::TimeConstraintError);
+        }
+        Ok((pool_key, pool))
+    }
+
+    /// Move `amount` of stake on `outcome` from `from` to `to`, keeping
+    /// `participants_count`, both users' prediction indexes and their
+    /// lifetime volume in sync. The caller persists `pool`. For private
+    /// pools `to` needs access as in [`Self::require_private_access`].
+    fn move_position(
+        env: &Env,
+        pool: &mut Pool,
+        pool_id: u64,
+        from: &Address,
+        to: &Address,
+        outcome: u32,
+        amount: i128,
+        invite: &Option<Invite>,
+    ) -> Result<(), PredifiError> {
+        if amount <= 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+        if from == to {
+            return Err(PredifiError::InvalidAddressOrToken);
+        }
+        if pool.private {
+            Self::require_private_access(env, pool_id, pool, to, invite)?;
+        }
+
+        let mut sender = Self::load_position(env, from, pool_id);
+        Self::take_stake(&mut sender, outcome, amount)?;
+
+        let mut recipient = Self::load_position(env, to, pool_id);
+        if recipient.is_empty() {
+            let max = Self::get_config(env).max_predictions_per_user;
+            let count_key = DataKey::UsrPrdCnt(to.clone());
+            let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+            if max > 0 && count >= max {
+                return Err(PredifiError::MaxPredictionsExceeded);
+            }
+
+            let index_key = DataKey::UsrPrdIdx(to.clone(), count);
+            env.storage().persistent().set(&index_key, &pool_id);
+            Self::extend_persistent(env, &index_key);
+            env.storage().persistent().set(&count_key, &(count + 1));
+            Self::extend_persistent(env, &count_key);
+
+            pool.participants_count = pool.participants_count.saturating_add(1);
+            Self::record_participant(env, pool_id, to);
+        }
+        Self::add_stake(&mut recipient, outcome, amount)?;
+        Self::store_position(env, to, pool_id, &recipient);
+
+        Self::store_position(env, from, pool_id, &sender);
+        if sender.is_empty() {
+            Self::remove_from_user_index(env, from, pool_id);
+            pool.participants_count = pool.participants_count.saturating_sub(1);
+        }
+
+        // Volume follows the stake, so passing a position around cannot
+        // inflate anyone's fee tier.
+        Self::record_user_volume(env, from, -amount)?;
+        Self::record_user_volume(env, to, amount)?;
+
+        Ok(())
+    }
+
+    /// Total staked across every outcome of a position.
+    fn position_total(position: &Vec<Prediction>) -> Result<i128, PredifiError> {
+        position.iter().try_fold(0i128, |total, stake| {
+            total
+                .checked_add(stake.amount)
+                .ok_or(PredifiError::ArithmeticError)
+        })
+    }
+
+    /// What a resolved pool owes `prediction`: the payout breakdown and the
+    /// part of the protocol fee its claim realizes, before any referral cut.
+    ///
+    /// `None` when the prediction is owed nothing.
+    fn resolved_claim(
+        env: &Env,
+        pool_id: u64,
+        pool: &Pool,
+        prediction: &Prediction,
+    ) -> Result<Option<(PayoutBreakdown, i128)>, PredifiError> {
+        // A settled scalar pool pays both sides by their share of the
+        // range; every other pool pays only the winning outcome.
+        let scalar_long_bps = env
+            .storage()
+            .persistent()
+            .get::<_, ScalarRange>(&DataKey::ScalarRange(pool_id))
+            .and_then(|range| range.settlement_bps);
+        let (winning_stake, side_bps) = match scalar_long_bps {
+            Some(long_bps) => {
+                let side_stake =
+                    Self::get_outcome_stake(env.clone(), pool_id, prediction.outcome);
+                let other_stake =
+                    Self::get_outcome_stake(env.clone(), pool_id, 1 - prediction.outcome);
+                let side_bps = if other_stake == 0 {
+                    10_000
+                } else if prediction.outcome == 1 {
+                    long_bps
+                } else {
+                    10_000 - long_bps
+                };
+                (side_stake, Some(side_bps as i128))
+            }
+            None => {
+                if prediction.outcome != pool.outcome {
+                    return Ok(None);
+                }
+                (
+                    Self::get_outcome_stake(env.clone(), pool_id, pool.outcome),
+                    None,
+                )
+            }
+        };
+
+        if winning_stake == 0 {
+            return Ok(None);
+        }
+
+        let fee_bps_i = if pool.fee_bps > 0 || pool.state == MarketState::Resolved {
+            pool.fee_bps as i128
+        } else {
+            let config = Self::get_config(env);
+            config.fee_bps as i128
+        };
+
+        // Payout math lives in `payouts` — keeps lib.rs focused on orchestration
+        let input = PayoutInput {
+            pool_total_stake: pool.total_stake,
+            fee_bps: fee_bps_i,
+            user_stake: prediction.amount,
+            winning_stake,
+        };
+        let breakdown = match side_bps {
+            Some(side_bps) => calculate_scalar_claim_payout(&input, side_bps),
+            None => calculate_claim_payout(&input),
+        }
+        .map_err(|_| PredifiError::InvalidAmount)?;
+
+        // Every side of a scalar pool claims, so the fee is realized over
+        // the whole pool; elsewhere only winners claim.
+        let fee_share_base = if side_bps.is_some() {
+            pool.total_stake
+        } else {
+            winning_stake
+        };
+        // The creator's cut is paid by `claim_creator_fees`, not realized
+        // claim by claim.
+        let creator_fee =
+            Self::creator_fee_cut(env, pool_id, pool.total_stake, breakdown.protocol_fee)?;
+        let fee_share = calculate_fee_share(
+            prediction.amount,
+            fee_share_base,
+            breakdown.protocol_fee - creator_fee,
+        )
+        .map_err(|_| PredifiError::InvalidAmount)?;
+
+        Ok(Some((breakdown, fee_share)))
+    }
+
+    /// The part of a pool's protocol fee set aside for its creator:
+    /// `creator_fee_bps` of `total_stake`, capped at `protocol_fee`.
+    fn creator_fee_cut(
+        env: &Env,
+        pool_id: u64,
+        total_stake: i128,
+        protocol_fee: i128,
+    ) -> Result<i128, PredifiError> {
+        let creator_fee_bps = Self::get_creator_fee_bps(env.clone(), pool_id);
+        if creator_fee_bps == 0 || protocol_fee <= 0 {
+            return Ok(0);
+        }
+        let fee = calculate_protocol_fee(total_stake, creator_fee_bps as i128)
+            .map_err(|_| PredifiError::ArithmeticError)?;
+        Ok(fee.min(protocol_fee))
+    }
+
+    /// Creator fee owed for `pool`. Zero until a parimutuel pool resolves,
+    /// and zero if it resolved with no winning stake, since nobody's claim
+    /// realizes the protocol fee the cut comes out of.
+    fn creator_fee_owed(env: &Env, pool_id: u64, pool: &Pool) -> Result<i128, PredifiError> {
+        if pool.pricing_mode != PricingMode::Parimutuel
+            || pool.state != MarketState::Resolved
+            || !Self::is_pool_resolved(pool)
+        {
+            return Ok(0);
+        }
+        let scalar_settled = env
+            .storage()
+            .persistent()
+            .get::<_, ScalarRange>(&DataKey::ScalarRange(pool_id))
+            .is_some_and(|range| range.settlement_bps.is_some());
+        if !scalar_settled && Self::get_outcome_stake(env.clone(), pool_id, pool.outcome) == 0 {
+            return Ok(0);
+        }
+
+        let protocol_fee = calculate_protocol_fee(pool.total_stake, pool.fee_bps as i128)
+            .map_err(|_| PredifiError::ArithmeticError)?;
+        Self::creator_fee_cut(env, pool_id, pool.total_stake, protocol_fee)
+    }
+
     fn is_paused(env: &Env) -> bool {
         let paused = env
             .storage()
@@ -1870,15 +4238,35 @@ impl PredifiContract {
     }
 
     /// Returns `Err(PredifiError::ContractPaused)` if the contract is currently
-    /// paused, `Ok(())` otherwise. All state-mutating entry points call this at
-    /// their top to block execution while the emergency pause is active.
+    /// paused or its stored state still awaits `migrate_state`, `Ok(())`
+    /// otherwise. All state-mutating entry points call this at their top to
+    /// block execution while the emergency pause is active.
     fn require_not_paused(env: &Env) -> Result<(), PredifiError> {
-        if Self::is_paused(env) {
+        if Self::is_paused(env) || Self::is_migration_pending(env) {
             return Err(PredifiError::ContractPaused);
         }
         Ok(())
     }
 
+    /// True while the stored schema version is behind [`CONTRACT_VERSION`].
+    /// An uninitialized contract has nothing to migrate.
+    fn is_migration_pending(env: &Env) -> bool {
+        Self::read_schema_version(env).is_some_and(|version| version < CONTRACT_VERSION)
+    }
+
+    /// Schema version of the stored state, or `None` before `init`.
+    fn read_schema_version(env: &Env) -> Option<u32> {
+        if !env.storage().instance().has(&DataKey::Config) {
+            return None;
+        }
+        Some(
+            env.storage()
+                .instance()
+                .get(&MigrationKey::SchemaVersion)
+                .unwrap_or(1),
+        )
+    }
+
     fn enter_reentrancy_guard(env: &Env) {
         let key = DataKey::RentGuard;
         if env.storage().temporary().has(&key) {
@@ -2016,6 +4404,168 @@ impl PredifiContract {
         }
         applied_fee
     }
+
+    /// `user`'s staking record, with the tier and discount re-read from the
+    /// current volume schedule.
+    fn read_user_stats(env: &Env, user: &Address) -> UserStats {
+        let volume = env
+            .storage()
+            .persistent()
+            .get::<_, UserStats>(&TreasuryKey::UserStats(user.clone()))
+            .map_or(0, |stats| stats.volume);
+        Self::volume_stats(env, volume)
+    }
+
+    /// The tier `volume` reaches in the current volume schedule.
+    fn volume_stats(env: &Env, volume: i128) -> UserStats {
+        let mut stats = UserStats {
+            volume,
+            tier: 0,
+            discount_bps: 0,
+        };
+        // Tiers are stored in ascending threshold order.
+        let tiers = Self::get_volume_tiers(env.clone());
+        for (i, tier) in tiers.iter().enumerate() {
+            if volume >= tier.volume_threshold {
+                stats.tier = i as u32 + 1;
+                stats.discount_bps = tier.discount_bps;
+            }
+        }
+        stats
+    }
+
+    /// Add `delta` (negative for a withdrawal) to `user`'s lifetime volume.
+    fn record_user_volume(env: &Env, user: &Address, delta: i128) -> Result<(), PredifiError> {
+        let volume = Self::read_user_stats(env, user)
+            .volume
+            .checked_add(delta)
+            .ok_or(PredifiError::ArithmeticError)?
+            .max(0);
+        let key = TreasuryKey::UserStats(user.clone());
+        env.storage()
+            .persistent()
+            .set(&key, &Self::volume_stats(env, volume));
+        Self::extend_persistent(env, &key);
+        Ok(())
+    }
+
+    /// Dispute parameters, or the disabled defaults (no bond, no challenge
+    /// window) when `set_dispute_config` has never been called.
+    fn read_dispute_config(env: &Env) -> DisputeConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::DisputeConfig)
+            .unwrap_or(DisputeConfig {
+                bond_bps: 0,
+                min_bond: 0,
+                challenge_window: 0,
+            })
+    }
+
+    /// Rejects claims on a resolved pool while a moderator can still dispute it.
+    ///
+    /// Once the resolved outcome has been disputed and settled the window no
+    /// longer applies, since it cannot be disputed again.
+    fn require_challenge_window_elapsed(
+        env: &Env,
+        pool_id: u64,
+        pool: &Pool,
+    ) -> Result<(), PredifiError> {
+        if pool.state == MarketState::Disputed {
+            return Err(PredifiError::PoolDisputed);
+        }
+        let window = Self::read_dispute_config(env).challenge_window;
+        if window == 0 {
+            return Ok(());
+        }
+        // A settled dispute of the resolved outcome already served as the
+        // challenge; one opened while the pool was active did not.
+        let challenged = env
+            .storage()
+            .persistent()
+            .get::<_, Dispute>(&DataKey::Dispute(pool_id))
+            .is_some_and(|d| d.prior_state == MarketState::Resolved);
+        if challenged {
+            return Ok(());
+        }
+        if let Some(resolved_at) = pool.resolution_timestamp {
+            if env.ledger().timestamp() < resolved_at.saturating_add(window) {
+                return Err(PredifiError::ChallengeWindowOpen);
+            }
+        }
+        Ok(())
+    }
+
+    /// Reject operator resolution while an unchallenged outcome proposal is
+    /// pending: such a proposal is either challenged or finalized, never
+    /// overridden by a vote.
+    fn require_no_unchallenged_proposal(env: &Env, pool_id: u64) -> Result<(), PredifiError> {
+        let proposal: Option<OutcomeProposal> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OutcomeProposal(pool_id));
+        match proposal {
+            Some(p) if p.challenger.is_none() => Err(PredifiError::ResolutionConflict),
+            _ => Ok(()),
+        }
+    }
+
+    /// Pay out the bonds of a pool's outcome proposal once the pool has left
+    /// `Active`, and delete the proposal. No-op if there is no proposal.
+    ///
+    /// - Resolved, unchallenged: the proposer gets their bond back.
+    /// - Resolved, challenged: whichever side matched the final outcome takes
+    ///   both bonds (the challenger wins if the proposal was wrong).
+    /// - Canceled: every posted bond is returned.
+    fn settle_outcome_proposal(
+        env: &Env,
+        pool_id: u64,
+        pool: &Pool,
+    ) -> Result<(), PredifiError> {
+        let key = DataKey::OutcomeProposal(pool_id);
+        let proposal: OutcomeProposal = match env.storage().persistent().get(&key) {
+            Some(p) => p,
+            None => return Ok(()),
+        };
+        env.storage().persistent().remove(&key);
+
+        let token_client = token::Client::new(env, &pool.token);
+        let contract = env.current_contract_address();
+        let resolved = pool.state == MarketState::Resolved;
+        let proposer_correct = resolved && pool.outcome == proposal.outcome;
+
+        match (&proposal.challenger, resolved) {
+            (Some(challenger), true) => {
+                let winner = if proposer_correct {
+                    &proposal.proposer
+                } else {
+                    challenger
+                };
+                let both_bonds = proposal
+                    .bond
+                    .checked_mul(2)
+                    .ok_or(PredifiError::ArithmeticError)?;
+                token_client.transfer(&contract, winner, &both_bonds);
+            }
+            (Some(challenger), false) => {
+                token_client.transfer(&contract, &proposal.proposer, &proposal.bond);
+                token_client.transfer(&contract, challenger, &proposal.bond);
+            }
+            (None, _) => {
+                token_client.transfer(&contract, &proposal.proposer, &proposal.bond);
+            }
+        }
+
+        ProposalSettledEvent {
+            pool_id,
+            proposed_outcome: proposal.outcome,
+            final_state: pool.state,
+            challenged: proposal.challenger.is_some(),
+            proposer_correct,
+        }
+        .publish(env);
+        Ok(())
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -2033,4 +4583,28 @@ mod integration_test;
 mod lifecycle_integration_tests;
 mod oracle_edge_case_tests;
 mod referral_integration_tests;
+mod dispute_tests;
+mod optimistic_tests;
+mod market_tests;
+mod amm_tests;
+mod scalar_tests;
+mod bracket_tests;
+mod pyth_tests;
+mod aggregation_tests;
+mod price_source_tests;
+mod parlay_tests;
+mod sweep_tests;
+mod upgrade_tests;
+mod migration_tests;
+mod approval_tests;
+mod withdraw_tests;
+mod position_tests;
+mod creator_fee_tests;
+mod volume_tier_tests;
+mod referral_campaign_tests;
+mod role_scope_tests;
+mod commit_reveal_tests;
+mod shares_tests;
+mod invite_tests;
+mod bond_tests;
 mod test;
@@ -3,7 +3,7 @@
 #![cfg(test)]
 
 use crate::test::ROLE_ADMIN;
-use crate::PoolConfig;
+use crate::{PoolConfig, PricingMode};
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Ledger},
@@ -57,6 +57,10 @@ fn test_pool_full_lifecycle_with_balance_verification() {
                 String::from_str(&env, "No"),
                 String::from_str(&env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 
@@ -113,7 +117,9 @@ fn test_pool_lifecycle_with_fee_and_treasury_withdrawal() {
     let client = crate::PredifiContractClient::new(&env, &contract_id);
 
     let token_admin_addr = Address::generate(&env);
-    let token_contract = env.register_stellar_asset_contract(token_admin_addr.clone());
+    let token_contract = env
+        .register_stellar_asset_contract_v2(token_admin_addr.clone())
+        .address();
     let token = token::Client::new(&env, &token_contract);
     let token_admin_client = token::StellarAssetClient::new(&env, &token_contract);
     let token_address = token_contract;
@@ -157,6 +163,10 @@ fn test_pool_lifecycle_with_fee_and_treasury_withdrawal() {
                 String::from_str(&env, "No"),
                 String::from_str(&env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 
@@ -0,0 +1,266 @@
+//! # LMSR pricing module
+//!
+//! Fixed-point math for pools using the logarithmic market scoring rule
+//! (`PricingMode::Lmsr`). Like [`crate::payouts`], everything here is pure,
+//! `no_std`-safe and independent of contract storage.
+//!
+//! For outstanding shares `q` and liquidity parameter `b` the market maker's
+//! cost function is
+//!
+//! ```text
+//! C(q) = b * ln(Σ exp(q_i / b))
+//! ```
+//!
+//! Buying `x` shares of outcome `i` costs `C(q + x·e_i) - C(q)`, selling
+//! refunds `C(q) - C(q - x·e_i)`, and each share of the winning outcome pays
+//! out one token base unit. The worst-case loss of the market maker is
+//! `b * ln(n)`, which the pool creator's `initial_liquidity` covers.
+//!
+//! ## Invariants
+//!
+//! - Costs are computed in `FIXED_ONE` precision and rounded in the pool's
+//!   favor (buys round up, sells round down).
+//! - `C(q) >= max(q_i)`, so a pool funded with `C(0)` plus every trade's
+//!   cost can always pay out the winning outcome's shares.
+
+use predifi_errors::PrediFiError;
+
+/// Fixed-point scale (18 decimals) used for exponentials, logarithms and prices.
+pub const FIXED_ONE: i128 = 1_000_000_000_000_000_000;
+
+/// `ln(2)` in `FIXED_ONE` precision.
+const LN_2: i128 = 693_147_180_559_945_309;
+
+/// Largest number of outcomes the cost function accepts (matches
+/// `MAX_OPTIONS_COUNT`).
+pub const MAX_LMSR_OUTCOMES: usize = 100;
+
+/// `exp(x)` for `x <= 0`, both in `FIXED_ONE` precision.
+///
+/// Range-reduces `x = r - k·ln2` with `r ∈ (-ln2, 0]` and evaluates the Taylor
+/// series of `exp(r)`. Results below `1 / FIXED_ONE` flush to zero.
+pub fn exp_neg(x: i128) -> Result<i128, PrediFiError> {
+    if x > 0 {
+        return Err(PrediFiError::ArithmeticError);
+    }
+    let k = (-x) / LN_2;
+    if k >= 64 {
+        return Ok(0);
+    }
+    let r = x + k * LN_2;
+
+    let mut sum = FIXED_ONE;
+    let mut term = FIXED_ONE;
+    let mut i = 1i128;
+    while term != 0 {
+        term = term * r / (i * FIXED_ONE);
+        sum += term;
+        i += 1;
+    }
+    Ok(sum >> k)
+}
+
+/// `ln(y)` for `y >= 1`, both in `FIXED_ONE` precision.
+///
+/// Range-reduces `y = m · 2^k` with `m ∈ [1, 2)` and evaluates
+/// `ln(m) = 2·atanh((m - 1) / (m + 1))`.
+pub fn ln_fixed(y: i128) -> Result<i128, PrediFiError> {
+    if y < FIXED_ONE {
+        return Err(PrediFiError::ArithmeticError);
+    }
+    let mut m = y;
+    let mut k = 0i128;
+    while m >= 2 * FIXED_ONE {
+        m >>= 1;
+        k += 1;
+    }
+
+    let z = (m - FIXED_ONE) * FIXED_ONE / (m + FIXED_ONE);
+    let z2 = z * z / FIXED_ONE;
+    let mut term = z;
+    let mut sum = 0i128;
+    let mut i = 1i128;
+    while term != 0 {
+        sum += term / i;
+        term = term * z2 / FIXED_ONE;
+        i += 2;
+    }
+    Ok(k * LN_2 + 2 * sum)
+}
+
+/// Liquidity parameter `b` whose worst-case loss `b * ln(n)` does not exceed
+/// `subsidy` (the creator's `initial_liquidity`).
+pub fn liquidity_from_subsidy(subsidy: i128, outcomes: u32) -> Result<i128, PrediFiError> {
+    if subsidy <= 0 || outcomes < 2 || outcomes as usize > MAX_LMSR_OUTCOMES {
+        return Err(PrediFiError::InvalidAmount);
+    }
+    let ln_n = ln_fixed((outcomes as i128) * FIXED_ONE)?;
+    subsidy
+        .checked_mul(FIXED_ONE)
+        .map(|v| v / ln_n)
+        .ok_or(PrediFiError::ArithmeticError)
+}
+
+/// Cost function `C(q)` in `FIXED_ONE`-scaled token units, with `delta` added
+/// to the shares of `outcome` first.
+fn cost_with_delta(
+    shares: &[i128],
+    liquidity: i128,
+    outcome: usize,
+    delta: i128,
+) -> Result<i128, PrediFiError> {
+    if liquidity <= 0 || shares.len() < 2 || shares.len() > MAX_LMSR_OUTCOMES {
+        return Err(PrediFiError::InvalidAmount);
+    }
+    if outcome >= shares.len() {
+        return Err(PrediFiError::InvalidData);
+    }
+
+    let share_at = |i: usize| -> Result<i128, PrediFiError> {
+        if i == outcome {
+            shares[i]
+                .checked_add(delta)
+                .ok_or(PrediFiError::ArithmeticError)
+        } else {
+            Ok(shares[i])
+        }
+    };
+
+    let mut max = i128::MIN;
+    for i in 0..shares.len() {
+        let q = share_at(i)?;
+        if q < 0 {
+            return Err(PrediFiError::InvalidAmount);
+        }
+        max = max.max(q);
+    }
+
+    // Log-sum-exp shifted by the largest share so every exponent is <= 0.
+    let mut sum = 0i128;
+    for i in 0..shares.len() {
+        let scaled = (share_at(i)? - max)
+            .checked_mul(FIXED_ONE)
+            .ok_or(PrediFiError::ArithmeticError)?
+            / liquidity;
+        sum += exp_neg(scaled)?;
+    }
+
+    let log_sum = ln_fixed(sum)?;
+    max.checked_mul(FIXED_ONE)
+        .and_then(|m| {
+            liquidity
+                .checked_mul(log_sum)
+                .and_then(|l| m.checked_add(l))
+        })
+        .ok_or(PrediFiError::ArithmeticError)
+}
+
+/// Cost function `C(q)` in `FIXED_ONE`-scaled token units.
+#[cfg(test)]
+pub fn cost(shares: &[i128], liquidity: i128) -> Result<i128, PrediFiError> {
+    cost_with_delta(shares, liquidity, 0, 0)
+}
+
+/// Tokens required to buy `amount` shares of `outcome` (rounded up).
+pub fn buy_cost(
+    shares: &[i128],
+    liquidity: i128,
+    outcome: usize,
+    amount: i128,
+) -> Result<i128, PrediFiError> {
+    if amount <= 0 {
+        return Err(PrediFiError::InvalidAmount);
+    }
+    let before = cost_with_delta(shares, liquidity, outcome, 0)?;
+    let after = cost_with_delta(shares, liquidity, outcome, amount)?;
+    let diff = after - before;
+    Ok((diff + FIXED_ONE - 1) / FIXED_ONE)
+}
+
+/// Tokens refunded for selling `amount` shares of `outcome` (rounded down).
+pub fn sell_proceeds(
+    shares: &[i128],
+    liquidity: i128,
+    outcome: usize,
+    amount: i128,
+) -> Result<i128, PrediFiError> {
+    if amount <= 0 {
+        return Err(PrediFiError::InvalidAmount);
+    }
+    let before = cost_with_delta(shares, liquidity, outcome, 0)?;
+    let after = cost_with_delta(shares, liquidity, outcome, -amount)?;
+    Ok((before - after).max(0) / FIXED_ONE)
+}
+
+/// Instantaneous price of `outcome` in basis points (the market's implied
+/// probability), `exp(q_i / b) / Σ exp(q_j / b)`.
+pub fn price_bps(shares: &[i128], liquidity: i128, outcome: usize) -> Result<u32, PrediFiError> {
+    if liquidity <= 0 || outcome >= shares.len() {
+        return Err(PrediFiError::InvalidData);
+    }
+    let max = shares.iter().copied().max().unwrap_or(0);
+    let mut sum = 0i128;
+    let mut own = 0i128;
+    for (i, q) in shares.iter().enumerate() {
+        let scaled = (q - max)
+            .checked_mul(FIXED_ONE)
+            .ok_or(PrediFiError::ArithmeticError)?
+            / liquidity;
+        let e = exp_neg(scaled)?;
+        if i == outcome {
+            own = e;
+        }
+        sum += e;
+    }
+    Ok((own * 10_000 / sum) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exp_and_ln_known_values() {
+        assert_eq!(exp_neg(0).unwrap(), FIXED_ONE);
+        // exp(-1) = 0.367879441171442321...
+        let e_inv = exp_neg(-FIXED_ONE).unwrap();
+        assert!((e_inv - 367_879_441_171_442_321).abs() < 1_000);
+        assert_eq!(ln_fixed(FIXED_ONE).unwrap(), 0);
+        // ln(2)
+        assert!((ln_fixed(2 * FIXED_ONE).unwrap() - LN_2).abs() < 1_000);
+    }
+
+    #[test]
+    fn fresh_market_prices_are_uniform() {
+        let shares = [0i128; 4];
+        for i in 0..4 {
+            assert_eq!(price_bps(&shares, 1_000_000, i).unwrap(), 2_500);
+        }
+    }
+
+    #[test]
+    fn subsidy_covers_initial_cost() {
+        let b = liquidity_from_subsidy(1_000_000, 2).unwrap();
+        let c0 = cost(&[0, 0], b).unwrap();
+        assert!(c0 <= 1_000_000 * FIXED_ONE);
+        assert!(c0 > 999_999 * FIXED_ONE);
+    }
+
+    #[test]
+    fn buying_moves_price_up() {
+        let b = liquidity_from_subsidy(1_000_000, 2).unwrap();
+        let mut shares = [0i128, 0];
+        let paid = buy_cost(&shares, b, 1, 500_000).unwrap();
+        assert!(paid > 250_000 && paid < 500_000);
+        shares[1] = 500_000;
+        assert!(price_bps(&shares, b, 1).unwrap() > 5_000);
+    }
+
+    #[test]
+    fn selling_more_than_outstanding_fails() {
+        assert_eq!(
+            sell_proceeds(&[10, 0], 1_000, 1, 1),
+            Err(PrediFiError::InvalidAmount)
+        );
+    }
+}
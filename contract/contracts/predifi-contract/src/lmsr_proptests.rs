@@ -0,0 +1,76 @@
+#[cfg(test)]
+extern crate std;
+#[cfg(test)]
+use crate::lmsr::{buy_cost, cost, liquidity_from_subsidy, price_bps, sell_proceeds, FIXED_ONE};
+#[cfg(test)]
+use proptest::prelude::*;
+#[cfg(test)]
+use std::vec::Vec;
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn test_round_trip_never_profits(
+        subsidy in 1_000..10_000_000_000_000i128,
+        outcomes in 2..8u32,
+        outcome_seed in 0..8usize,
+        amount in 1..10_000_000_000_000i128,
+    ) {
+        let b = liquidity_from_subsidy(subsidy, outcomes).unwrap();
+        let outcome = outcome_seed % outcomes as usize;
+        let mut shares: Vec<i128> = (0..outcomes).map(|_| 0).collect();
+
+        let paid = buy_cost(&shares, b, outcome, amount).unwrap();
+        shares[outcome] += amount;
+        let refunded = sell_proceeds(&shares, b, outcome, amount).unwrap();
+
+        // Invariant: buying and immediately selling cannot create value.
+        prop_assert!(refunded <= paid);
+        // Invariant: a share never costs more than its maximum payout.
+        prop_assert!(paid <= amount + 1);
+    }
+
+    #[test]
+    fn test_pool_stays_solvent(
+        subsidy in 1_000..1_000_000_000_000i128,
+        outcomes in 2..6u32,
+        trades in proptest::collection::vec((0..6usize, 1..1_000_000_000_000i128, any::<bool>()), 1..20),
+    ) {
+        let b = liquidity_from_subsidy(subsidy, outcomes).unwrap();
+        let mut shares: Vec<i128> = (0..outcomes).map(|_| 0).collect();
+        let mut balance = subsidy;
+
+        for (seed, amount, is_buy) in trades {
+            let outcome = seed % outcomes as usize;
+            if is_buy {
+                balance += buy_cost(&shares, b, outcome, amount).unwrap();
+                shares[outcome] += amount;
+            } else if shares[outcome] > 0 {
+                let sold = amount.min(shares[outcome]);
+                balance -= sell_proceeds(&shares, b, outcome, sold).unwrap();
+                shares[outcome] -= sold;
+            }
+        }
+
+        // Invariant: whichever outcome wins, the pool can redeem every
+        // winning share at one token unit.
+        let max_shares = shares.iter().copied().max().unwrap();
+        prop_assert!(balance >= max_shares);
+        prop_assert!(balance * FIXED_ONE >= cost(&shares, b).unwrap());
+    }
+
+    #[test]
+    fn test_prices_sum_to_one(
+        subsidy in 1_000..1_000_000_000_000i128,
+        shares in proptest::collection::vec(0..1_000_000_000_000i128, 2..10),
+    ) {
+        let b = liquidity_from_subsidy(subsidy, shares.len() as u32).unwrap();
+        let total: u32 = (0..shares.len())
+            .map(|i| price_bps(&shares, b, i).unwrap())
+            .sum();
+
+        // Each price rounds down, so the sum is within one bp per outcome.
+        prop_assert!(total <= 10_000);
+        prop_assert!(total + shares.len() as u32 >= 10_000);
+    }
+}
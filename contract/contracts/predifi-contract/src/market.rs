@@ -0,0 +1,252 @@
+//! Secondary market domain: peer-to-peer transfers of prediction positions
+//! and fixed-price listings, both only while the pool is still open.
+
+use soroban_sdk::{contractimpl, token, Address, Env};
+
+use crate::{
+    DataKey, Invite, Listing, ListingCanceledEvent, PredictionListedEvent, PredictionSoldEvent,
+    PredictionTransferredEvent, PredifiContract, PredifiContractArgs, PredifiContractClient,
+    PredifiError,
+};
+
+#[contractimpl]
+impl PredifiContract {
+    /// Transfer `amount` of `from`'s stake on `outcome` in `pool_id` to `to`.
+    ///
+    /// The stake is added to the recipient's stake on the same outcome, next
+    /// to any stakes they hold on other outcomes. The recipient is added to
+    /// the pool's participants (and their own prediction index) if they had
+    /// no position yet; a sender left with no stake in the pool is removed
+    /// from both. The amount moves from the sender's lifetime volume to the
+    /// recipient's. Referral attribution stays with the sender.
+    ///
+    /// `invite` grants `to` access to a private pool they are not
+    /// whitelisted for, as in [`Self::place_prediction`].
+    ///
+    /// # Errors
+    /// - `InvalidPoolState` – pool is not `Active`.
+    /// - `TimeConstraintError` – the pool's `end_time` has passed.
+    /// - `InvalidAmount` – `amount` is not positive.
+    /// - `InsufficientBalance` – `from` holds less than `amount` on `outcome`.
+    /// - `InvalidAddressOrToken` – `from` and `to` are the same address.
+    /// - `Unauthorized` – the pool is private and `to` has no access.
+    /// - `MaxPredictionsExceeded` – `to` is at `max_predictions_per_user`.
+    pub fn transfer_prediction(
+        env: Env,
+        from: Address,
+        to: Address,
+        pool_id: u64,
+        outcome: u32,
+        amount: i128,
+        invite: Option<Invite>,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        from.require_auth();
+
+        let (pool_key, mut pool) = Self::load_tradable_pool(&env, pool_id)?;
+        Self::move_position(
+            &env, &mut pool, pool_id, &from, &to, outcome, amount, &invite,
+        )?;
+
+        env.storage().persistent().set(&pool_key, &pool);
+        Self::bump_ttl(&env, &pool_key);
+
+        PredictionTransferredEvent {
+            pool_id,
+            from,
+            to,
+            outcome,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// List `amount` of the seller's stake on `outcome` in `pool_id` for sale
+    /// at `price` (in the pool token). Returns the new listing ID.
+    ///
+    /// The stake is not escrowed; the listing can only be filled while the
+    /// seller still holds it.
+    ///
+    /// # Errors
+    /// - `InvalidPoolState` / `TimeConstraintError` – pool is closed for trading.
+    /// - `InvalidAmount` – `amount` or `price` is not positive.
+    /// - `InsufficientBalance` – the seller holds less than `amount` on `outcome`.
+    pub fn list_prediction(
+        env: Env,
+        seller: Address,
+        pool_id: u64,
+        outcome: u32,
+        amount: i128,
+        price: i128,
+    ) -> Result<u64, PredifiError> {
+        Self::require_not_paused(&env)?;
+        seller.require_auth();
+
+        Self::load_tradable_pool(&env, pool_id)?;
+        if amount <= 0 || price <= 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+        Self::read_stake(&env, &seller, pool_id, outcome, amount)?;
+
+        let listing_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ListingIdCtr)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::ListingIdCtr, &(listing_id + 1));
+        Self::extend_instance(&env);
+
+        let listing_key = DataKey::Listing(listing_id);
+        env.storage().persistent().set(
+            &listing_key,
+            &Listing {
+                seller: seller.clone(),
+                pool_id,
+                outcome,
+                amount,
+                price,
+            },
+        );
+        Self::extend_persistent(&env, &listing_key);
+
+        PredictionListedEvent {
+            listing_id,
+            pool_id,
+            seller,
+            outcome,
+            amount,
+            price,
+        }
+        .publish(&env);
+
+        Ok(listing_id)
+    }
+
+    /// Fill a listing: the buyer pays `listing.price` in the pool token
+    /// directly to the seller and receives the listed stake. `invite` grants
+    /// the buyer access to a private pool, as in [`Self::transfer_prediction`].
+    ///
+    /// # Errors
+    /// - `InvalidData` – the listing does not exist.
+    /// - `InvalidAddressOrToken` – the buyer is the seller.
+    /// - `InvalidPoolState` / `TimeConstraintError` – pool is closed for trading.
+    /// - `InsufficientBalance` – the seller no longer holds the listed stake.
+    /// - Any error of [`Self::transfer_prediction`] for the buyer side.
+    pub fn buy_listing(
+        env: Env,
+        buyer: Address,
+        listing_id: u64,
+        invite: Option<Invite>,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        buyer.require_auth();
+
+        let listing_key = DataKey::Listing(listing_id);
+        let listing: Listing = env
+            .storage()
+            .persistent()
+            .get(&listing_key)
+            .ok_or(PredifiError::InvalidData)?;
+
+        let (pool_key, mut pool) = Self::load_tradable_pool(&env, listing.pool_id)?;
+        Self::move_position(
+            &env,
+            &mut pool,
+            listing.pool_id,
+            &listing.seller,
+            &buyer,
+            listing.outcome,
+            listing.amount,
+            &invite,
+        )?;
+
+        env.storage().persistent().set(&pool_key, &pool);
+        Self::bump_ttl(&env, &pool_key);
+        env.storage().persistent().remove(&listing_key);
+
+        Self::enter_reentrancy_guard(&env);
+        let token_client = token::Client::new(&env, &pool.token);
+        token_client.transfer(&buyer, &listing.seller, &listing.price);
+        Self::exit_reentrancy_guard(&env);
+
+        PredictionTransferredEvent {
+            pool_id: listing.pool_id,
+            from: listing.seller.clone(),
+            to: buyer.clone(),
+            outcome: listing.outcome,
+            amount: listing.amount,
+        }
+        .publish(&env);
+
+        PredictionSoldEvent {
+            listing_id,
+            pool_id: listing.pool_id,
+            seller: listing.seller,
+            buyer,
+            amount: listing.amount,
+            price: listing.price,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Withdraw an open listing. Only callable by its seller, at any time.
+    ///
+    /// # Errors
+    /// - `InvalidData` – the listing does not exist.
+    /// - `Unauthorized` – caller is not the seller.
+    pub fn cancel_listing(env: Env, seller: Address, listing_id: u64) -> Result<(), PredifiError> {
+        seller.require_auth();
+
+        let listing_key = DataKey::Listing(listing_id);
+        let listing: Listing = env
+            .storage()
+            .persistent()
+            .get(&listing_key)
+            .ok_or(PredifiError::InvalidData)?;
+        if listing.seller != seller {
+            return Err(PredifiError::Unauthorized);
+        }
+
+        env.storage().persistent().remove(&listing_key);
+
+        ListingCanceledEvent {
+            listing_id,
+            pool_id: listing.pool_id,
+            seller,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Return an open listing, if it exists.
+    pub fn get_listing(env: Env, listing_id: u64) -> Option<Listing> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Listing(listing_id))
+    }
+
+    /// Require `owner` to hold at least `amount` on `outcome` in `pool_id`.
+    fn read_stake(
+        env: &Env,
+        owner: &Address,
+        pool_id: u64,
+        outcome: u32,
+        amount: i128,
+    ) -> Result<(), PredifiError> {
+        let held = Self::load_position(env, owner, pool_id)
+            .iter()
+            .find(|stake| stake.outcome == outcome)
+            .map_or(0, |stake| stake.amount);
+        if held < amount {
+            return Err(PredifiError::InsufficientBalance);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,248 @@
+//! Secondary market: direct position transfers and fixed-price listings,
+//! which both close at the pool's end time.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{Invite, PoolConfig, Prediction, PredifiError};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, Symbol,
+};
+
+const END_TIME: u64 = 10_000;
+
+fn open_pool(ctx: &TestContext) -> u64 {
+    ctx.create_pool(END_TIME, &default_pool_config(&ctx.client.env))
+}
+
+/// A partial transfer splits the position; a full transfer moves the sender
+/// out of the pool's participants and their prediction index.
+#[test]
+fn test_transfer_prediction_updates_participants() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = open_pool(&ctx);
+    let alice = ctx.staker(pool_id, 100, 1);
+    let bob = Address::generate(&env);
+
+    ctx.client
+        .transfer_prediction(&alice, &bob, &pool_id, &1u32, &40, &None);
+    assert_eq!(ctx.client.get_pool(&pool_id).participants_count, 2);
+    assert_eq!(
+        ctx.client
+            .get_user_predictions(&alice, &0, &10)
+            .get(0)
+            .unwrap()
+            .amount,
+        60
+    );
+    let bob_preds = ctx.client.get_user_predictions(&bob, &0, &10);
+    assert_eq!(bob_preds.len(), 1);
+    assert_eq!(bob_preds.get(0).unwrap().amount, 40);
+    assert_eq!(bob_preds.get(0).unwrap().user_outcome, 1);
+
+    ctx.client
+        .transfer_prediction(&alice, &bob, &pool_id, &1u32, &60, &None);
+    let pool = ctx.client.get_pool(&pool_id);
+    assert_eq!(pool.participants_count, 1);
+    assert_eq!(pool.total_stake, 100);
+    assert_eq!(ctx.client.get_user_predictions(&alice, &0, &10).len(), 0);
+    assert_eq!(
+        ctx.client
+            .get_user_predictions(&bob, &0, &10)
+            .get(0)
+            .unwrap()
+            .amount,
+        100
+    );
+}
+
+/// The new owner claims the winnings of a transferred position.
+#[test]
+fn test_transferred_position_claims_winnings() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = open_pool(&ctx);
+    let alice = ctx.staker(pool_id, 100, 1);
+    let _loser = ctx.staker(pool_id, 100, 0);
+    let bob = Address::generate(&env);
+
+    ctx.client
+        .transfer_prediction(&alice, &bob, &pool_id, &1u32, &100, &None);
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &1u32);
+
+    assert_eq!(ctx.client.claim_winnings(&bob, &pool_id), 200);
+    assert_eq!(ctx.client.claim_winnings(&alice, &pool_id), 0);
+}
+
+/// Transfers are validated against the sender's stake on the outcome and
+/// add to the recipient's existing position.
+#[test]
+fn test_transfer_prediction_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = open_pool(&ctx);
+    let alice = ctx.staker(pool_id, 100, 1);
+    let carol = ctx.staker(pool_id, 50, 0);
+    let bob = Address::generate(&env);
+
+    let too_much = ctx
+        .client
+        .try_transfer_prediction(&alice, &bob, &pool_id, &1u32, &101, &None);
+    assert_eq!(too_much, Err(Ok(PredifiError::InsufficientBalance)));
+
+    let zero = ctx
+        .client
+        .try_transfer_prediction(&alice, &bob, &pool_id, &1u32, &0, &None);
+    assert_eq!(zero, Err(Ok(PredifiError::InvalidAmount)));
+
+    let to_self = ctx
+        .client
+        .try_transfer_prediction(&alice, &alice, &pool_id, &1u32, &10, &None);
+    assert_eq!(to_self, Err(Ok(PredifiError::InvalidAddressOrToken)));
+
+    let not_held = ctx
+        .client
+        .try_transfer_prediction(&alice, &bob, &pool_id, &0u32, &10, &None);
+    assert_eq!(not_held, Err(Ok(PredifiError::InsufficientBalance)));
+
+    // A recipient holding another outcome keeps both stakes.
+    ctx.client
+        .transfer_prediction(&alice, &carol, &pool_id, &1u32, &10, &None);
+    let carol_position = ctx.client.get_user_position(&pool_id, &carol);
+    assert_eq!(
+        carol_position,
+        vec![
+            &env,
+            Prediction {
+                amount: 50,
+                outcome: 0
+            },
+            Prediction {
+                amount: 10,
+                outcome: 1
+            },
+        ]
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME);
+    let ended = ctx
+        .client
+        .try_transfer_prediction(&alice, &bob, &pool_id, &1u32, &10, &None);
+    assert_eq!(ended, Err(Ok(PredifiError::TimeConstraintError)));
+}
+
+/// A filled listing pays the seller and moves the stake to the buyer.
+#[test]
+fn test_buy_listing() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = open_pool(&ctx);
+    let seller = ctx.staker(pool_id, 100, 1);
+    let buyer = Address::generate(&env);
+    ctx.token_admin_client.mint(&buyer, &1_000);
+
+    let listing_id = ctx
+        .client
+        .list_prediction(&seller, &pool_id, &1u32, &100, &150);
+    let listing = ctx.client.get_listing(&listing_id).unwrap();
+    assert_eq!(listing.outcome, 1);
+    assert_eq!(listing.price, 150);
+
+    ctx.client.buy_listing(&buyer, &listing_id, &None);
+
+    assert_eq!(ctx.token.balance(&seller), 150);
+    assert_eq!(ctx.token.balance(&buyer), 1_000 - 150);
+    assert_eq!(ctx.client.get_listing(&listing_id), None);
+    assert_eq!(
+        ctx.client
+            .get_user_predictions(&buyer, &0, &10)
+            .get(0)
+            .unwrap()
+            .amount,
+        100
+    );
+    assert_eq!(ctx.client.get_pool(&pool_id).participants_count, 1);
+}
+
+/// A listing the seller no longer backs cannot be filled; only the seller
+/// can cancel it.
+#[test]
+fn test_stale_and_canceled_listings() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = open_pool(&ctx);
+    let seller = ctx.staker(pool_id, 100, 1);
+    let buyer = Address::generate(&env);
+    ctx.token_admin_client.mint(&buyer, &1_000);
+
+    let listing_id = ctx
+        .client
+        .list_prediction(&seller, &pool_id, &1u32, &80, &100);
+    ctx.client.transfer_prediction(
+        &seller,
+        &Address::generate(&env),
+        &pool_id,
+        &1u32,
+        &50,
+        &None,
+    );
+
+    let stale = ctx.client.try_buy_listing(&buyer, &listing_id, &None);
+    assert_eq!(stale, Err(Ok(PredifiError::InsufficientBalance)));
+    assert_eq!(ctx.token.balance(&buyer), 1_000);
+
+    let not_seller = ctx.client.try_cancel_listing(&buyer, &listing_id);
+    assert_eq!(not_seller, Err(Ok(PredifiError::Unauthorized)));
+
+    ctx.client.cancel_listing(&seller, &listing_id);
+    assert_eq!(ctx.client.get_listing(&listing_id), None);
+    let gone = ctx.client.try_buy_listing(&buyer, &listing_id, &None);
+    assert_eq!(gone, Err(Ok(PredifiError::InvalidData)));
+}
+
+/// In a private pool, transfers and listing fills need the recipient to have
+/// access: a whitelist entry or an invite.
+#[test]
+fn test_private_pool_transfers_need_access() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let key = Symbol::new(&env, "INSIDERS");
+    let config = PoolConfig {
+        private: true,
+        whitelist_key: Some(key.clone()),
+        ..default_pool_config(&env)
+    };
+    let pool_id = ctx.create_pool(END_TIME, &config);
+    ctx.token_admin_client.mint(&ctx.creator, &1_000);
+    ctx.client
+        .place_prediction(&ctx.creator, &pool_id, &100, &1u32, &None, &None);
+    let invite = Some(Invite::Key(key));
+    let bob = Address::generate(&env);
+
+    let uninvited =
+        ctx.client
+            .try_transfer_prediction(&ctx.creator, &bob, &pool_id, &1u32, &40, &None);
+    assert_eq!(uninvited, Err(Ok(PredifiError::Unauthorized)));
+    let wrong_key = Some(Invite::Key(Symbol::new(&env, "GUESS")));
+    let guessed =
+        ctx.client
+            .try_transfer_prediction(&ctx.creator, &bob, &pool_id, &1u32, &40, &wrong_key);
+    assert_eq!(guessed, Err(Ok(PredifiError::Unauthorized)));
+    ctx.client
+        .transfer_prediction(&ctx.creator, &bob, &pool_id, &1u32, &40, &invite);
+
+    let buyer = Address::generate(&env);
+    ctx.token_admin_client.mint(&buyer, &1_000);
+    let listing_id = ctx
+        .client
+        .list_prediction(&ctx.creator, &pool_id, &1u32, &60, &70);
+    let unlisted = ctx.client.try_buy_listing(&buyer, &listing_id, &None);
+    assert_eq!(unlisted, Err(Ok(PredifiError::Unauthorized)));
+    ctx.client.buy_listing(&buyer, &listing_id, &invite);
+    assert_eq!(ctx.token.balance(&buyer), 1_000 - 70);
+    assert_eq!(ctx.client.get_pool(&pool_id).participants_count, 2);
+}
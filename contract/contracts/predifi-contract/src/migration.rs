@@ -0,0 +1,236 @@
+//! Migration domain: versioned rewrites of stored state after an upgrade.
+//!
+//! `MigrationKey::SchemaVersion` holds the schema version of the stored state
+//! and [`CONTRACT_VERSION`] the version this build expects. It is kept apart
+//! from `DataKey::Version`, which the version 1 `upgrade_contract` bumps on
+//! every upgrade; a contract without it still holds version 1 state. Each version bump
+//! registers a step in [`migration_step`] that rewrites one pool at a time;
+//! `migrate_state` runs those steps in bounded batches, keeping its place
+//! in `MigrationKey::MigrationCursor` so a step spanning more pools than
+//! fit in one transaction resumes where the previous call stopped. User
+//! entry points return `ContractPaused` until the stored version catches up.
+
+use soroban_sdk::{
+    contractimpl, contracttype, Address, Env, FromVal, Map, String, Symbol, Val, Vec,
+};
+
+use crate::{
+    ContractUpgradedEvent, DataKey, MarketState, MigrationKey, MigrationProgressEvent,
+    MigrationStatus, Pool, PredifiContract, PredifiContractArgs, PredifiContractClient,
+    PredifiError, PricingMode, CONTRACT_VERSION,
+};
+
+/// Rewrites the pool with the given id, if it needs it.
+type PoolStep = fn(&Env, u64);
+
+/// The step that brings stored state from `version - 1` to `version`.
+/// Versions without a registered step have nothing to rewrite.
+fn migration_step(version: u32) -> Option<PoolStep> {
+    match version {
+        2 => Some(migrate_pool_v2),
+        _ => None,
+    }
+}
+
+/// `Pool` as stored by schema version 1, before optimistic resolution and
+/// LMSR pricing.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolV1 {
+    pub start_time: u64,
+    pub end_time: u64,
+    pub state: MarketState,
+    pub outcome: u32,
+    pub token: Address,
+    pub total_stake: i128,
+    pub category: Symbol,
+    pub description: String,
+    pub metadata_url: String,
+    pub options_count: u32,
+    pub min_stake: i128,
+    pub max_stake: i128,
+    pub min_total_stake: i128,
+    pub max_total_stake: i128,
+    pub initial_liquidity: i128,
+    pub creator: Address,
+    pub required_resolutions: u32,
+    pub private: bool,
+    pub whitelist_key: Option<Symbol>,
+    pub outcome_descriptions: Vec<String>,
+    pub fee_bps: u32,
+    pub participants_count: u32,
+    pub resolution_timestamp: Option<u64>,
+}
+
+impl From<PoolV1> for Pool {
+    /// Version 1 pools were operator-resolved and parimutuel.
+    fn from(old: PoolV1) -> Self {
+        Pool {
+            start_time: old.start_time,
+            end_time: old.end_time,
+            state: old.state,
+            outcome: old.outcome,
+            token: old.token,
+            total_stake: old.total_stake,
+            category: old.category,
+            description: old.description,
+            metadata_url: old.metadata_url,
+            options_count: old.options_count,
+            min_stake: old.min_stake,
+            max_stake: old.max_stake,
+            min_total_stake: old.min_total_stake,
+            max_total_stake: old.max_total_stake,
+            initial_liquidity: old.initial_liquidity,
+            creator: old.creator,
+            required_resolutions: old.required_resolutions,
+            private: old.private,
+            whitelist_key: old.whitelist_key,
+            outcome_descriptions: old.outcome_descriptions,
+            fee_bps: old.fee_bps,
+            participants_count: old.participants_count,
+            resolution_timestamp: old.resolution_timestamp,
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+        }
+    }
+}
+
+/// v2: adds `optimistic_liveness`, `optimistic_bond` and `pricing_mode` to
+/// `Pool`, and drops the pool's version 1 emergency-cancel approver set.
+/// Pools already in the new layout are left alone, so re-running the step
+/// is harmless.
+///
+/// Pending emergency cancels now live in the approval queue, which does not
+/// carry the old approvals over: admins approve again under the current
+/// policy.
+fn migrate_pool_v2(env: &Env, pool_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&legacy_emergency_cancel_approvers(env, pool_id));
+
+    let key = DataKey::Pool(pool_id);
+    // Decoding a struct from a map with other fields traps in the host, so
+    // tell the layouts apart by their fields first.
+    let Some(fields) = env.storage().persistent().get::<_, Map<Symbol, Val>>(&key) else {
+        return;
+    };
+    if fields.contains_key(Symbol::new(env, "pricing_mode")) {
+        return;
+    }
+    let old = PoolV1::from_val(env, &fields.to_val());
+    env.storage().persistent().set(&key, &Pool::from(old));
+    PredifiContract::extend_persistent(env, &key);
+}
+
+/// Storage key of version 1's `DataKey::EmergencyCancelApprovers(pool_id)`,
+/// a `Vec<Address>` of the admins who had approved canceling the pool. The
+/// variant is gone, but an enum variant with one field encodes as
+/// `[name, field]`, the same as this tuple.
+pub(crate) fn legacy_emergency_cancel_approvers(env: &Env, pool_id: u64) -> (Symbol, u64) {
+    (Symbol::new(env, "EmergencyCancelApprovers"), pool_id)
+}
+
+#[contractimpl]
+impl PredifiContract {
+    /// Migrate stored state towards [`CONTRACT_VERSION`], rewriting at most
+    /// `max_pools` pools. Only callable by Admin (role 0).
+    ///
+    /// Call repeatedly until the returned status has `version ==
+    /// target_version`; each call resumes where the previous one stopped.
+    /// Every completed step emits `ContractUpgradedEvent`, and a batch that
+    /// ends mid-step emits `MigrationProgressEvent`.
+    ///
+    /// # Errors
+    /// - [`PredifiError::ContractPaused`] – the contract is paused.
+    /// - [`PredifiError::Unauthorized`]   – caller lacks Admin role (0).
+    /// - [`PredifiError::InvalidData`]    – `max_pools` is zero.
+    pub fn migrate_state(
+        env: Env,
+        admin: Address,
+        max_pools: u32,
+    ) -> Result<MigrationStatus, PredifiError> {
+        // Not `require_not_paused`: that also refuses while a migration is
+        // pending, which is exactly when this has to run.
+        if Self::is_paused(&env) {
+            return Err(PredifiError::ContractPaused);
+        }
+        admin.require_auth();
+        Self::require_admin_role(&env, &admin, "migrate_state")?;
+        if max_pools == 0 {
+            return Err(PredifiError::InvalidData);
+        }
+
+        let pool_count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PoolIdCtr)
+            .unwrap_or(0);
+        let mut version = Self::read_schema_version(&env).unwrap_or(CONTRACT_VERSION);
+        let mut budget = max_pools;
+
+        while version < CONTRACT_VERSION {
+            let target_version = version + 1;
+            let mut next_pool_id: u64 = env
+                .storage()
+                .instance()
+                .get(&MigrationKey::MigrationCursor)
+                .unwrap_or(0);
+
+            let step = migration_step(target_version);
+            if let Some(step) = step {
+                while next_pool_id < pool_count && budget > 0 {
+                    step(&env, next_pool_id);
+                    next_pool_id += 1;
+                    budget -= 1;
+                }
+            }
+
+            if step.is_some() && next_pool_id < pool_count {
+                env.storage()
+                    .instance()
+                    .set(&MigrationKey::MigrationCursor, &next_pool_id);
+                MigrationProgressEvent {
+                    admin: admin.clone(),
+                    target_version,
+                    next_pool_id,
+                }
+                .publish(&env);
+                break;
+            }
+
+            env.storage()
+                .instance()
+                .remove(&MigrationKey::MigrationCursor);
+            env.storage()
+                .instance()
+                .set(&MigrationKey::SchemaVersion, &target_version);
+            env.storage()
+                .instance()
+                .set(&DataKey::Version, &target_version);
+            ContractUpgradedEvent {
+                old_version: version,
+                new_version: target_version,
+                upgraded_by: admin.clone(),
+            }
+            .publish(&env);
+            version = target_version;
+        }
+        Self::extend_instance(&env);
+
+        Ok(Self::get_migration_status(env))
+    }
+
+    /// Return how far stored state is from [`CONTRACT_VERSION`].
+    pub fn get_migration_status(env: Env) -> MigrationStatus {
+        MigrationStatus {
+            version: Self::read_schema_version(&env).unwrap_or(0),
+            target_version: CONTRACT_VERSION,
+            next_pool_id: env
+                .storage()
+                .instance()
+                .get(&MigrationKey::MigrationCursor)
+                .unwrap_or(0),
+        }
+    }
+}
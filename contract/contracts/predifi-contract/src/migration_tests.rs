@@ -0,0 +1,187 @@
+//! Migrating pools stored in an older layout after an upgrade, in bounded
+//! batches, with user entry points held until it completes.
+
+#![cfg(test)]
+
+use crate::migration::{legacy_emergency_cancel_approvers, PoolV1};
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{
+    DataKey, MarketState, MigrationKey, MigrationStatus, PoolConfig, PredifiError, PricingMode,
+    CONTRACT_VERSION, UNRESOLVED_OUTCOME,
+};
+use soroban_sdk::{symbol_short, testutils::Address as _, vec, Address, Env, String};
+
+fn legacy_pool(env: &Env, token: &Address, creator: &Address) -> PoolV1 {
+    PoolV1 {
+        start_time: 0,
+        end_time: 10_000,
+        state: MarketState::Active,
+        outcome: UNRESOLVED_OUTCOME,
+        token: token.clone(),
+        total_stake: 0,
+        category: symbol_short!("Sports"),
+        description: String::from_str(env, "Legacy market"),
+        metadata_url: String::from_str(env, "ipfs://legacy"),
+        options_count: 2,
+        min_stake: 1,
+        max_stake: 0,
+        min_total_stake: 1,
+        max_total_stake: 0,
+        initial_liquidity: 0,
+        creator: creator.clone(),
+        required_resolutions: 1,
+        private: false,
+        whitelist_key: None,
+        outcome_descriptions: vec![
+            env,
+            String::from_str(env, "Home"),
+            String::from_str(env, "Away"),
+        ],
+        fee_bps: 0,
+        participants_count: 0,
+        resolution_timestamp: None,
+    }
+}
+
+fn pool_config(env: &Env) -> PoolConfig {
+    PoolConfig {
+        description: String::from_str(env, "Current market"),
+        metadata_url: String::from_str(env, "ipfs://current"),
+        outcome_descriptions: vec![
+            env,
+            String::from_str(env, "Home"),
+            String::from_str(env, "Away"),
+        ],
+        ..default_pool_config(env)
+    }
+}
+
+/// A pool created by the current code followed by three pools in the
+/// version 1 layout, as left behind by an upgrade from version 1, are
+/// migrated two at a time.
+///
+/// Version 1 never stored a schema version, and its `upgrade_contract` bumped
+/// `DataKey::Version` from 1 to 2 while swapping in this code, so that is the
+/// state the migration has to recognize.
+#[test]
+fn test_migration_rewrites_v1_pools_in_batches() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+
+    let current = ctx.create_pool(10_000, &pool_config(&env));
+    env.as_contract(&ctx.client.address, || {
+        for pool_id in 1..4u64 {
+            env.storage().persistent().set(
+                &DataKey::Pool(pool_id),
+                &legacy_pool(&env, &ctx.token_address, &ctx.creator),
+            );
+        }
+        env.storage().instance().set(&DataKey::PoolIdCtr, &4u64);
+        env.storage()
+            .instance()
+            .remove(&MigrationKey::SchemaVersion);
+        env.storage().instance().set(&DataKey::Version, &2u32);
+    });
+    assert_eq!(ctx.client.get_version(), CONTRACT_VERSION);
+    assert_eq!(ctx.client.get_migration_status().version, 1);
+
+    let user = ctx.funded_user(100);
+    let blocked = ctx
+        .client
+        .try_place_prediction(&user, &current, &10, &0u32, &None, &None);
+    assert_eq!(blocked, Err(Ok(PredifiError::ContractPaused)));
+
+    let status = ctx.client.migrate_state(&ctx.admin, &2u32);
+    assert_eq!(
+        status,
+        MigrationStatus {
+            version: 1,
+            target_version: CONTRACT_VERSION,
+            next_pool_id: 2,
+        }
+    );
+    let blocked = ctx
+        .client
+        .try_place_prediction(&user, &current, &10, &0u32, &None, &None);
+    assert_eq!(blocked, Err(Ok(PredifiError::ContractPaused)));
+
+    let status = ctx.client.migrate_state(&ctx.admin, &2u32);
+    assert_eq!(status.version, CONTRACT_VERSION);
+    assert_eq!(status.next_pool_id, 0);
+    assert_eq!(ctx.client.get_migration_status(), status);
+
+    let migrated = ctx.client.get_pool(&3u64);
+    assert_eq!(
+        migrated.description,
+        String::from_str(&env, "Legacy market")
+    );
+    assert_eq!(migrated.optimistic_liveness, 0);
+    assert_eq!(migrated.optimistic_bond, 0);
+    assert_eq!(migrated.pricing_mode, PricingMode::Parimutuel);
+    assert_eq!(
+        ctx.client.get_pool(&current).description,
+        String::from_str(&env, "Current market")
+    );
+
+    // User entry points work again, on migrated pools too.
+    ctx.client
+        .place_prediction(&user, &3u64, &10, &0u32, &None, &None);
+    let other = ctx.funded_user(100);
+    ctx.client
+        .place_prediction(&other, &current, &10, &0u32, &None, &None);
+}
+
+/// Emergency-cancel approvals collected by version 1 are dropped rather than
+/// left behind under a key nothing reads.
+#[test]
+fn test_migration_clears_legacy_emergency_cancel_approvals() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+
+    let legacy_key = legacy_emergency_cancel_approvers(&env, 0);
+    env.as_contract(&ctx.client.address, || {
+        env.storage().persistent().set(
+            &DataKey::Pool(0),
+            &legacy_pool(&env, &ctx.token_address, &ctx.creator),
+        );
+        env.storage()
+            .persistent()
+            .set(&legacy_key, &vec![&env, ctx.admin.clone()]);
+        env.storage().instance().set(&DataKey::PoolIdCtr, &1u64);
+        env.storage()
+            .instance()
+            .remove(&MigrationKey::SchemaVersion);
+        env.storage().instance().set(&DataKey::Version, &2u32);
+    });
+
+    ctx.client.migrate_state(&ctx.admin, &10u32);
+
+    env.as_contract(&ctx.client.address, || {
+        assert!(!env.storage().persistent().has(&legacy_key));
+    });
+    assert!(ctx.client.get_emergency_cancel_approvals(&0).is_empty());
+}
+
+/// Migration is ctx.admin-only, needs a positive batch size, and is a no-op once
+/// stored state is current.
+#[test]
+fn test_migrate_state_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+
+    let stranger = Address::generate(&env);
+    let not_admin = ctx.client.try_migrate_state(&stranger, &10u32);
+    assert_eq!(not_admin, Err(Ok(PredifiError::Unauthorized)));
+    let empty_batch = ctx.client.try_migrate_state(&ctx.admin, &0u32);
+    assert_eq!(empty_batch, Err(Ok(PredifiError::InvalidData)));
+
+    let status = ctx.client.migrate_state(&ctx.admin, &10u32);
+    assert_eq!(
+        status,
+        MigrationStatus {
+            version: CONTRACT_VERSION,
+            target_version: CONTRACT_VERSION,
+            next_pool_id: 0,
+        }
+    );
+}
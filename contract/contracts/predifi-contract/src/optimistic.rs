@@ -0,0 +1,231 @@
+//! Optimistic resolution domain: bonded outcome proposals that become final
+//! after a liveness window unless challenged, in which case resolution
+//! escalates to the operator vote in `resolve_pool`.
+
+use soroban_sdk::{contractimpl, token, Address, Env};
+
+use crate::{
+    DataKey, MarketState, OutcomeChallengedEvent, OutcomeProposal, OutcomeProposedEvent, Pool,
+    PoolResolvedEvent, PredifiContract, PredifiContractArgs, PredifiContractClient, PredifiError,
+    UNRESOLVED_OUTCOME,
+};
+
+#[contractimpl]
+impl PredifiContract {
+    /// Propose the winning outcome of an optimistic pool. Callable by anyone.
+    ///
+    /// The proposer posts `pool.optimistic_bond` in the pool token. If nobody
+    /// calls [`Self::challenge_outcome`] within `pool.optimistic_liveness`
+    /// seconds, anyone can call [`Self::finalize_outcome`] to resolve the pool
+    /// to the proposed outcome and return the bond.
+    ///
+    /// # Errors
+    /// - `InvalidPoolState` – pool is not `Active` or was created without a
+    ///   liveness window.
+    /// - `ResolutionDelayNotMet` – `end_time + resolution_delay` has not passed.
+    /// - `InvalidOutcome` – outcome is out of range.
+    /// - `ResolutionConflict` – a proposal already exists for the pool.
+    pub fn propose_outcome(
+        env: Env,
+        proposer: Address,
+        pool_id: u64,
+        outcome: u32,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        proposer.require_auth();
+
+        let pool_key = DataKey::Pool(pool_id);
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .ok_or(PredifiError::PoolNotFound)?;
+        Self::extend_persistent(&env, &pool_key);
+
+        if pool.optimistic_liveness == 0 {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if !Self::is_pool_active(&pool) {
+            return Err(PredifiError::InvalidPoolState);
+        }
+
+        let now = env.ledger().timestamp();
+        let config = Self::get_config(&env);
+        if now < Self::resolution_eligible_at(&env, pool_id, &pool, config.resolution_delay) {
+            return Err(PredifiError::ResolutionDelayNotMet);
+        }
+        if outcome >= pool.options_count || outcome == UNRESOLVED_OUTCOME {
+            return Err(PredifiError::InvalidOutcome);
+        }
+
+        let proposal_key = DataKey::OutcomeProposal(pool_id);
+        if env.storage().persistent().has(&proposal_key) {
+            return Err(PredifiError::ResolutionConflict);
+        }
+
+        let proposal = OutcomeProposal {
+            proposer: proposer.clone(),
+            outcome,
+            bond: pool.optimistic_bond,
+            proposed_at: now,
+            challenger: None,
+        };
+        env.storage().persistent().set(&proposal_key, &proposal);
+        Self::extend_persistent(&env, &proposal_key);
+
+        Self::enter_reentrancy_guard(&env);
+        let token_client = token::Client::new(&env, &pool.token);
+        token_client.transfer(&proposer, env.current_contract_address(), &proposal.bond);
+        Self::exit_reentrancy_guard(&env);
+
+        OutcomeProposedEvent {
+            pool_id,
+            proposer,
+            outcome,
+            bond: proposal.bond,
+            liveness_ends_at: now.saturating_add(pool.optimistic_liveness),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Challenge a pending outcome proposal before its liveness window ends.
+    /// Callable by anyone other than the proposer.
+    ///
+    /// The challenger matches the proposer's bond. The pool is then resolved
+    /// through the regular operator vote (`resolve_pool`); whichever side
+    /// matches the final outcome receives both bonds. If the pool is canceled
+    /// instead, both bonds are returned.
+    ///
+    /// # Errors
+    /// - `InvalidPoolState` – no proposal exists or the pool is no longer `Active`.
+    /// - `ChallengeWindowClosed` – the liveness window has elapsed or the
+    ///   proposal was already challenged.
+    /// - `Unauthorized` – the proposer tried to challenge their own proposal.
+    pub fn challenge_outcome(
+        env: Env,
+        challenger: Address,
+        pool_id: u64,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        challenger.require_auth();
+
+        let pool_key = DataKey::Pool(pool_id);
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .ok_or(PredifiError::PoolNotFound)?;
+
+        let proposal_key = DataKey::OutcomeProposal(pool_id);
+        let mut proposal: OutcomeProposal = env
+            .storage()
+            .persistent()
+            .get(&proposal_key)
+            .ok_or(PredifiError::InvalidPoolState)?;
+
+        if pool.state != MarketState::Active {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if proposal.challenger.is_some() {
+            return Err(PredifiError::ChallengeWindowClosed);
+        }
+        if challenger == proposal.proposer {
+            return Err(PredifiError::Unauthorized);
+        }
+        let liveness_ends_at = proposal
+            .proposed_at
+            .saturating_add(pool.optimistic_liveness);
+        if env.ledger().timestamp() >= liveness_ends_at {
+            return Err(PredifiError::ChallengeWindowClosed);
+        }
+
+        proposal.challenger = Some(challenger.clone());
+        env.storage().persistent().set(&proposal_key, &proposal);
+        Self::extend_persistent(&env, &proposal_key);
+
+        Self::enter_reentrancy_guard(&env);
+        let token_client = token::Client::new(&env, &pool.token);
+        token_client.transfer(&challenger, env.current_contract_address(), &proposal.bond);
+        Self::exit_reentrancy_guard(&env);
+
+        OutcomeChallengedEvent {
+            pool_id,
+            challenger,
+            proposed_outcome: proposal.outcome,
+            bond: proposal.bond,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Resolve an optimistic pool to its proposed outcome once the liveness
+    /// window has passed without a challenge. Callable by anyone.
+    ///
+    /// The proposer's bond is returned.
+    ///
+    /// # Errors
+    /// - `InvalidPoolState` – no proposal exists, the pool is no longer
+    ///   `Active`, or the proposal was challenged (use `resolve_pool`).
+    /// - `ChallengeWindowOpen` – the liveness window has not elapsed yet.
+    pub fn finalize_outcome(env: Env, pool_id: u64) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .ok_or(PredifiError::PoolNotFound)?;
+
+        let proposal: OutcomeProposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OutcomeProposal(pool_id))
+            .ok_or(PredifiError::InvalidPoolState)?;
+
+        if !Self::is_pool_active(&pool) {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if proposal.challenger.is_some() {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        let liveness_ends_at = proposal
+            .proposed_at
+            .saturating_add(pool.optimistic_liveness);
+        if env.ledger().timestamp() < liveness_ends_at {
+            return Err(PredifiError::ChallengeWindowOpen);
+        }
+
+        pool.state = MarketState::Resolved;
+        pool.outcome = proposal.outcome;
+        pool.fee_bps = Self::calculate_dynamic_fee(&env, &pool);
+        pool.resolution_timestamp = Some(env.ledger().timestamp());
+
+        env.storage().persistent().set(&pool_key, &pool);
+        Self::bump_ttl(&env, &pool_key);
+        Self::remove_from_active_index(&env, pool_id);
+
+        Self::enter_reentrancy_guard(&env);
+        Self::settle_outcome_proposal(&env, pool_id, &pool)?;
+        Self::exit_reentrancy_guard(&env);
+
+        PoolResolvedEvent {
+            pool_id,
+            operator: proposal.proposer,
+            outcome: proposal.outcome,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Return the pending outcome proposal for a pool, if any.
+    pub fn get_outcome_proposal(env: Env, pool_id: u64) -> Option<OutcomeProposal> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OutcomeProposal(pool_id))
+    }
+}
@@ -0,0 +1,204 @@
+//! Optimistic resolution: bonded proposals, the liveness window, and
+//! challenges that fall back to the operator vote.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{MarketState, PoolConfig, PredifiError};
+use soroban_sdk::{symbol_short, testutils::Ledger, Env, String, Symbol};
+
+const LIVENESS: u64 = 3_600;
+const BOND: i128 = 100;
+const END_TIME: u64 = 10_000;
+
+fn optimistic_config(env: &Env, liveness: u64, bond: i128) -> PoolConfig {
+    PoolConfig {
+        optimistic_liveness: liveness,
+        optimistic_bond: bond,
+        ..default_pool_config(env)
+    }
+}
+
+/// An optimistic pool with a `LIVENESS` window and a `BOND` bond.
+fn optimistic_pool(ctx: &TestContext) -> u64 {
+    ctx.create_pool(
+        END_TIME,
+        &optimistic_config(&ctx.client.env, LIVENESS, BOND),
+    )
+}
+
+/// An unchallenged proposal resolves the pool after the liveness window and
+/// returns the proposer's bond.
+#[test]
+fn test_unchallenged_proposal_finalizes() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = optimistic_pool(&ctx);
+    let proposer = ctx.funded_user(1_000);
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.propose_outcome(&proposer, &pool_id, &1u32);
+    assert_eq!(ctx.token.balance(&proposer), 1_000 - BOND);
+
+    let early = ctx.client.try_finalize_outcome(&pool_id);
+    assert_eq!(early, Err(Ok(PredifiError::ChallengeWindowOpen)));
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = END_TIME + 1 + LIVENESS);
+    ctx.client.finalize_outcome(&pool_id);
+
+    let pool = ctx.client.get_pool(&pool_id);
+    assert_eq!(pool.state, MarketState::Resolved);
+    assert_eq!(pool.outcome, 1);
+    assert_eq!(ctx.token.balance(&proposer), 1_000);
+    assert_eq!(ctx.client.get_outcome_proposal(&pool_id), None);
+}
+
+/// A challenged proposal is resolved by operators; the challenger takes both
+/// bonds when the proposal was wrong.
+#[test]
+fn test_challenge_escalates_to_operator_vote() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = optimistic_pool(&ctx);
+    let proposer = ctx.funded_user(1_000);
+    let challenger = ctx.funded_user(1_000);
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.propose_outcome(&proposer, &pool_id, &1u32);
+
+    // Operators, oracles and price triggers cannot override an unchallenged
+    // proposal.
+    let blocked = ctx.client.try_resolve_pool(&ctx.operator, &pool_id, &0u32);
+    assert_eq!(blocked, Err(Ok(PredifiError::ResolutionConflict)));
+    let oracle = ctx.grant(3);
+    let proof = String::from_str(&env, "proof");
+    let by_oracle = ctx
+        .client
+        .try_oracle_resolve(&oracle, &pool_id, &0u32, &proof);
+    assert_eq!(by_oracle, Err(Ok(PredifiError::ResolutionConflict)));
+    ctx.client.set_price_condition(
+        &ctx.operator,
+        &pool_id,
+        &Symbol::new(&env, "ETH_USD"),
+        &3_000,
+        &1,
+        &0,
+    );
+    let by_price = ctx.client.try_resolve_pool_from_price(&pool_id);
+    assert_eq!(by_price, Err(Ok(PredifiError::ResolutionConflict)));
+
+    ctx.client.challenge_outcome(&challenger, &pool_id);
+    assert_eq!(ctx.token.balance(&challenger), 1_000 - BOND);
+
+    let finalize = ctx.client.try_finalize_outcome(&pool_id);
+    assert_eq!(finalize, Err(Ok(PredifiError::InvalidPoolState)));
+
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &0u32);
+
+    let pool = ctx.client.get_pool(&pool_id);
+    assert_eq!(pool.state, MarketState::Resolved);
+    assert_eq!(pool.outcome, 0);
+    assert_eq!(ctx.token.balance(&challenger), 1_000 + BOND);
+    assert_eq!(ctx.token.balance(&proposer), 1_000 - BOND);
+}
+
+/// The proposer takes both bonds when the operator vote confirms the proposal.
+#[test]
+fn test_failed_challenge_pays_proposer() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = optimistic_pool(&ctx);
+    let proposer = ctx.funded_user(1_000);
+    let challenger = ctx.funded_user(1_000);
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.propose_outcome(&proposer, &pool_id, &1u32);
+    ctx.client.challenge_outcome(&challenger, &pool_id);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &1u32);
+
+    assert_eq!(ctx.token.balance(&proposer), 1_000 + BOND);
+    assert_eq!(ctx.token.balance(&challenger), 1_000 - BOND);
+}
+
+/// Canceling a challenged pool returns both bonds.
+#[test]
+fn test_cancel_returns_bonds() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = optimistic_pool(&ctx);
+    let proposer = ctx.funded_user(1_000);
+    let challenger = ctx.funded_user(1_000);
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.propose_outcome(&proposer, &pool_id, &1u32);
+    ctx.client.challenge_outcome(&challenger, &pool_id);
+    ctx.client
+        .cancel_pool(&ctx.operator, &pool_id, &String::from_str(&env, "void"));
+
+    assert_eq!(ctx.token.balance(&proposer), 1_000);
+    assert_eq!(ctx.token.balance(&challenger), 1_000);
+}
+
+/// Challenges after the liveness window are rejected.
+#[test]
+fn test_challenge_after_liveness_fails() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = optimistic_pool(&ctx);
+    let proposer = ctx.funded_user(1_000);
+    let challenger = ctx.funded_user(1_000);
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.propose_outcome(&proposer, &pool_id, &1u32);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = END_TIME + 1 + LIVENESS);
+    let result = ctx.client.try_challenge_outcome(&challenger, &pool_id);
+    assert_eq!(result, Err(Ok(PredifiError::ChallengeWindowClosed)));
+}
+
+/// Proposals are rejected before the pool ends, twice, or on non-optimistic pools.
+#[test]
+fn test_propose_outcome_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = optimistic_pool(&ctx);
+    let proposer = ctx.funded_user(1_000);
+
+    let too_early = ctx.client.try_propose_outcome(&proposer, &pool_id, &1u32);
+    assert_eq!(too_early, Err(Ok(PredifiError::ResolutionDelayNotMet)));
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    let bad_outcome = ctx.client.try_propose_outcome(&proposer, &pool_id, &2u32);
+    assert_eq!(bad_outcome, Err(Ok(PredifiError::InvalidOutcome)));
+
+    ctx.client.propose_outcome(&proposer, &pool_id, &1u32);
+    let twice = ctx.client.try_propose_outcome(&proposer, &pool_id, &0u32);
+    assert_eq!(twice, Err(Ok(PredifiError::ResolutionConflict)));
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let plain_pool = ctx.create_pool(END_TIME, &optimistic_config(&env, 0, 0));
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    let disabled = ctx
+        .client
+        .try_propose_outcome(&proposer, &plain_pool, &1u32);
+    assert_eq!(disabled, Err(Ok(PredifiError::InvalidPoolState)));
+}
+
+/// A liveness window without a bond is rejected at creation.
+#[test]
+fn test_create_pool_rejects_unbonded_optimistic_config() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+
+    let result = ctx.client.try_create_pool(
+        &ctx.creator,
+        &END_TIME,
+        &ctx.token_address,
+        &2u32,
+        &symbol_short!("Tech"),
+        &optimistic_config(&env, LIVENESS, 0),
+    );
+    assert_eq!(result, Err(Ok(PredifiError::InvalidData)));
+}
@@ -1,14 +1,22 @@
 //! Oracle domain: oracle registration, price feeds, price conditions and
 //! oracle-driven pool resolution.
 
-use soroban_sdk::{contractimpl, log, Address, Env, String, Symbol, Vec};
+use soroban_sdk::{contractimpl, log, Address, BytesN, Env, String, Symbol, Vec};
 
+use crate::price_source::{
+    price_condition_outcome, remove_expired_feeds, resolution_price, store_price_feed,
+    validate_price_condition, OracleSettings, PriceReading,
+};
 use crate::{
-    DataKey, MarketState, OracleCallback, OracleInitEvent, OracleResolvedEvent,
-    OracleWhitelistAddedEvent, OracleWhitelistRemovedEvent, Pool, PoolResolvedDiagEvent,
-    PoolResolvedEvent, PredifiContract, PredifiContractArgs, PredifiContractClient, PredifiError,
-    PriceConditionSetEvent, PriceFeedUpdatedEvent, PriceFeedsCleanedEvent, ResolutionConflictEvent,
-    ResolutionVoteCastEvent, MAX_PRICE_CONDITION_MATCH_STEPS, MAX_TOLERANCE, UNRESOLVED_OUTCOME,
+    calculate_scalar_long_bps, AggregationMode, DataKey, MarketState, OracleCallback,
+    OracleInitEvent, OracleKey, OracleResolvedEvent, OracleWhitelistAddedEvent,
+    OracleWhitelistRemovedEvent, Pool, PoolResolvedDiagEvent, PoolResolvedEvent, PredifiContract,
+    PredifiContractArgs, PredifiContractClient, PredifiError, PriceAggregation,
+    PriceAggregationSetEvent, PriceBrackets, PriceBracketsSetEvent, PriceConditionSetEvent,
+    PriceFeedUpdatedEvent, PriceFeedsCleanedEvent, PriceObservation, PriceSourceKind,
+    PriceSourceSetEvent, PricingMode, PythPriceIdSetEvent, ResolutionConflictEvent,
+    ResolutionVoteCastEvent, ScalarRange, ScalarRangeSetEvent, ScalarResolvedEvent,
+    MAX_PRICE_OBSERVATIONS, MAX_TOLERANCE, UNRESOLVED_OUTCOME,
 };
 
 #[contractimpl]
@@ -104,18 +112,12 @@ impl PredifiContract {
         admin.require_auth();
         Self::require_admin_role(&env, &admin, "init_oracle")?;
 
-        if max_price_age == 0 {
-            return Err(PredifiError::InvalidData);
-        }
-        if min_confidence_ratio > 10_000 {
-            return Err(PredifiError::InvalidFeeBps);
+        OracleSettings {
+            pyth_contract: pyth_contract.clone(),
+            max_price_age,
+            min_confidence_ratio,
         }
-
-        env.storage().persistent().set(
-            &DataKey::OracleConfig,
-            &(pyth_contract.clone(), max_price_age, min_confidence_ratio),
-        );
-        Self::extend_persistent(&env, &DataKey::OracleConfig);
+        .store(&env)?;
 
         OracleInitEvent {
             admin,
@@ -135,6 +137,74 @@ impl PredifiContract {
             .get::<DataKey, (Address, u64, u32)>(&DataKey::OracleConfig)
     }
 
+    /// Resolve `feed_pair` by pulling from the configured Pyth contract
+    /// instead of pushed `update_price_feed` data. Caller must have Admin
+    /// role (0).
+    ///
+    /// At resolution the contract calls `get_price_no_older_than(price_id,
+    /// max_price_age)` on `pyth_contract` and rejects the answer if it is older
+    /// than `max_price_age` or its confidence ratio exceeds
+    /// `min_confidence_ratio`.
+    pub fn set_pyth_price_id(
+        env: Env,
+        admin: Address,
+        feed_pair: Symbol,
+        price_id: BytesN<32>,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        admin.require_auth();
+        Self::require_admin_role(&env, &admin, "set_pyth_price_id")?;
+
+        if Self::get_oracle_config(env.clone()).is_none() {
+            return Err(PredifiError::OracleNotInitialized);
+        }
+
+        let key = DataKey::PythPriceId(feed_pair.clone());
+        env.storage().persistent().set(&key, &price_id);
+        Self::extend_persistent(&env, &key);
+
+        PythPriceIdSetEvent {
+            admin,
+            feed_pair,
+            price_id: Some(price_id),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Return `feed_pair` to pushed price updates. Caller must have Admin
+    /// role (0).
+    pub fn remove_pyth_price_id(
+        env: Env,
+        admin: Address,
+        feed_pair: Symbol,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        admin.require_auth();
+        Self::require_admin_role(&env, &admin, "remove_pyth_price_id")?;
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PythPriceId(feed_pair.clone()));
+
+        PythPriceIdSetEvent {
+            admin,
+            feed_pair,
+            price_id: None,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Return the Pyth price id `feed_pair` is pulled from, if any.
+    pub fn get_pyth_price_id(env: Env, feed_pair: Symbol) -> Option<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PythPriceId(feed_pair))
+    }
+
     /// Set a price-based condition for automated pool resolution.
     /// Only callable by Operator (role 1).
     pub fn set_price_condition(
@@ -154,13 +224,15 @@ impl PredifiContract {
             return Err(PredifiError::InvalidTargetPrice);
         }
 
-        Self::validate_price_condition_match_params(operator_type, tolerance_bps)?;
+        validate_price_condition(operator_type, tolerance_bps)?;
 
         let pool_key = DataKey::Pool(pool_id);
         if !env.storage().persistent().has(&pool_key) {
             return Err(PredifiError::PoolNotFound);
         }
 
+        Self::clear_price_resolution(&env, pool_id);
+
         let condition_key = DataKey::PriceCondition(pool_id);
         env.storage().persistent().set(
             &condition_key,
@@ -186,197 +258,338 @@ impl PredifiContract {
         Ok(())
     }
 
-    /// Maximum allowed price deviation as a multiplier of the previous price.
-    /// Prevents flash loan attacks and oracle manipulation where an attacker
-    /// submits a wildly different price to influence resolution outcomes.
-    /// Set to 5x (500%) to accommodate volatile crypto assets while still
-    /// catching extreme manipulation.
-    const MAX_PRICE_DEVIATION_MULTIPLIER: i128 = 5;
-
-    /// Minimum confidence ratio in basis points for price updates.
-    /// If the confidence/price ratio exceeds this, the update is rejected.
-    const MIN_CONFIDENCE_RATIO_BPS: u32 = 500; // 5%
-
-    /// Update price feed data from an external oracle.
-    /// Only callable by authorized oracles.
-    pub fn update_price_feed(
+    /// Turn a binary pool into a scalar pool settled on `[lower, upper]`.
+    /// Only callable by Operator (role 1).
+    ///
+    /// Outcome 0 is the short side and outcome 1 the long side. When
+    /// `resolve_pool_from_price` runs, the long side receives the share of the
+    /// payout pool given by where the feed price lands in the range, and the
+    /// short side the remainder. Replaces any price condition on the pool.
+    pub fn set_scalar_range(
         env: Env,
-        oracle: Address,
+        operator: Address,
+        pool_id: u64,
         feed_pair: Symbol,
-        price: i128,
-        confidence: i128,
-        timestamp: u64,
-        expires_at: u64,
+        lower: i128,
+        upper: i128,
     ) -> Result<(), PredifiError> {
         Self::require_not_paused(&env)?;
-        oracle.require_auth();
+        operator.require_auth();
+        Self::require_role(&env, &operator, 1)?; // Role Operator
 
-        if !Self::is_oracle_whitelisted(&env, &oracle) {
-            return Err(PredifiError::Unauthorized);
+        if lower <= 0 {
+            return Err(PredifiError::InvalidTargetPrice);
         }
-
-        // Security: reject prices with a future or current timestamp — the
-        // timestamp must be strictly in the past to prevent oracle manipulation
-        // via pre-dated or same-ledger price injections.
-        if timestamp >= env.ledger().timestamp() {
+        if upper <= lower {
             return Err(PredifiError::InvalidData);
         }
 
-        // expires_at must be after the timestamp
-        if expires_at <= timestamp {
-            return Err(PredifiError::InvalidData);
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.state != MarketState::Active || pool.pricing_mode != PricingMode::Parimutuel {
+            return Err(PredifiError::InvalidPoolState);
         }
-
-        // Price must be positive
-        if price <= 0 {
-            return Err(PredifiError::InvalidAmount);
+        if pool.options_count != 2 {
+            return Err(PredifiError::InvalidOutcome);
         }
 
-        // Confidence must be non-negative
-        if confidence < 0 {
-            return Err(PredifiError::InvalidAmount);
+        Self::clear_price_resolution(&env, pool_id);
+
+        let range_key = DataKey::ScalarRange(pool_id);
+        env.storage().persistent().set(
+            &range_key,
+            &ScalarRange {
+                feed_pair: feed_pair.clone(),
+                lower,
+                upper,
+                settlement_bps: None,
+            },
+        );
+        Self::extend_persistent(&env, &range_key);
+
+        ScalarRangeSetEvent {
+            pool_id,
+            feed_pair,
+            lower,
+            upper,
         }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Return the scalar range of a pool and, once resolved, its settlement.
+    pub fn get_scalar_range(env: Env, pool_id: u64) -> Option<ScalarRange> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ScalarRange(pool_id))
+    }
 
-        let feed_key = DataKey::PriceFeed(feed_pair.clone());
+    /// Resolve a pool on a TWAP or a median across oracles instead of the
+    /// latest push, or pass `None` to go back to the latest push.
+    /// Only callable by Operator (role 1).
+    ///
+    /// Applies to whichever price condition, scalar range or brackets the
+    /// pool settles on. Under the `Auto` price source, pairs bound to a Pyth
+    /// price id keep reading Pyth.
+    pub fn set_price_aggregation(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        aggregation: Option<PriceAggregation>,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        operator.require_auth();
+        Self::require_role(&env, &operator, 1)?; // Role Operator
 
-        // Price deviation protection: check against previous price to prevent
-        // flash loan manipulation. If the new price deviates excessively from
-        // the last recorded price, reject the update.
-        if let Some((prev_price, _, _, _)) = env
+        let pool: Pool = env
             .storage()
             .persistent()
-            .get::<DataKey, (i128, i128, u64, u64)>(&feed_key)
-        {
-            let (lower, upper) = if prev_price > 0 {
-                let lower = prev_price
-                    .checked_div(Self::MAX_PRICE_DEVIATION_MULTIPLIER)
-                    .ok_or(PredifiError::ArithmeticError)?;
-                let upper = prev_price
-                    .checked_mul(Self::MAX_PRICE_DEVIATION_MULTIPLIER)
-                    .ok_or(PredifiError::ArithmeticError)?;
-                (lower, upper)
-            } else {
-                (i128::MIN, i128::MAX)
-            };
-            if price < lower || price > upper {
-                return Err(PredifiError::InvalidData);
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.state != MarketState::Active {
+            return Err(PredifiError::InvalidPoolState);
+        }
+
+        let key = OracleKey::PriceAggregation(pool_id);
+        match &aggregation {
+            Some(config) => {
+                if config.window == 0 || config.max_deviation_bps > MAX_TOLERANCE {
+                    return Err(PredifiError::InvalidData);
+                }
+                if config.mode == AggregationMode::Median
+                    && (config.min_sources == 0 || config.min_sources > MAX_PRICE_OBSERVATIONS)
+                {
+                    return Err(PredifiError::InvalidData);
+                }
+                env.storage().persistent().set(&key, config);
+                Self::extend_persistent(&env, &key);
             }
+            None => env.storage().persistent().remove(&key),
         }
 
+        PriceAggregationSetEvent {
+            pool_id,
+            aggregation: aggregation.unwrap_or(PriceAggregation {
+                mode: AggregationMode::Twap,
+                window: 0,
+                max_deviation_bps: 0,
+                min_sources: 0,
+            }),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Return the aggregated resolution settings of a pool, if any.
+    pub fn get_price_aggregation(env: Env, pool_id: u64) -> Option<PriceAggregation> {
+        env.storage()
+            .persistent()
+            .get(&OracleKey::PriceAggregation(pool_id))
+    }
+
+    /// Return the buffered observations of a feed pair, oldest first.
+    pub fn get_price_observations(env: Env, feed_pair: Symbol) -> Vec<PriceObservation> {
         env.storage()
             .persistent()
-            .set(&feed_key, &(price, confidence, timestamp, expires_at));
-        Self::extend_persistent(&env, &feed_key);
+            .get(&OracleKey::PriceObservations(feed_pair))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Choose where a pool's resolution price comes from.
+    /// Only callable by Operator (role 1).
+    ///
+    /// `Auto` (the default) reads Pyth when the pool's feed pair is bound to a
+    /// Pyth price id, else aggregates observations when the pool has
+    /// aggregation settings, else uses the latest push. Any other choice is
+    /// used as is; resolution fails with `PriceFeedNotFound` if the chosen
+    /// source has no price id or aggregation settings by then.
+    pub fn set_price_source(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        source: PriceSourceKind,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        operator.require_auth();
+        Self::require_role(&env, &operator, 1)?; // Role Operator
 
-        // Track feed pair for cleanup
-        let mut list: Vec<Symbol> = env
+        let pool: Pool = env
             .storage()
             .persistent()
-            .get(&DataKey::PriceFeedList)
-            .unwrap_or_else(|| Vec::new(&env));
-        if !list.contains(feed_pair.clone()) {
-            list.push_back(feed_pair.clone());
-            env.storage()
-                .persistent()
-                .set(&DataKey::PriceFeedList, &list);
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.state != MarketState::Active {
+            return Err(PredifiError::InvalidPoolState);
         }
 
-        // Emit event so off-chain monitors and indexers can track price updates.
-        PriceFeedUpdatedEvent {
-            oracle,
-            feed_pair,
-            price,
-            confidence,
-            timestamp,
-            expires_at,
+        let key = OracleKey::SourceKind(pool_id);
+        if source == PriceSourceKind::Auto {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &source);
+            Self::extend_persistent(&env, &key);
         }
-        .publish(&env);
+
+        PriceSourceSetEvent { pool_id, source }.publish(&env);
 
         Ok(())
     }
 
-    /// Remove all expired price feeds from storage. Permissionless.
+    /// Return the price source a pool was set to, `Auto` if never set.
+    pub fn get_price_source(env: Env, pool_id: u64) -> PriceSourceKind {
+        env.storage()
+            .persistent()
+            .get(&OracleKey::SourceKind(pool_id))
+            .unwrap_or(PriceSourceKind::Auto)
+    }
+
+    /// Map a multi-outcome pool's outcomes to price brackets.
+    /// Only callable by Operator (role 1).
     ///
-    /// Returns the number of feeds removed.
-    pub fn cleanup_expired_feeds(env: Env) -> u32 {
-        let current_time = env.ledger().timestamp();
+    /// `thresholds` must be positive, strictly increasing, and one shorter
+    /// than the pool's `outcome_descriptions`. `resolve_pool_from_price` picks
+    /// outcome `i` when `thresholds[i - 1] <= price < thresholds[i]`, so
+    /// outcome 0 is everything below the first threshold and the last outcome
+    /// everything from the last threshold up. Replaces any price condition or
+    /// scalar range on the pool.
+    pub fn set_price_brackets(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        feed_pair: Symbol,
+        thresholds: Vec<i128>,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        operator.require_auth();
+        Self::require_role(&env, &operator, 1)?; // Role Operator
 
-        let list: Vec<Symbol> = env
+        let pool: Pool = env
             .storage()
             .persistent()
-            .get(&DataKey::PriceFeedList)
-            .unwrap_or_else(|| Vec::new(&env));
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.state != MarketState::Active {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if thresholds.len() + 1 != pool.outcome_descriptions.len() {
+            return Err(PredifiError::InvalidOutcome);
+        }
 
-        let mut remaining: Vec<Symbol> = Vec::new(&env);
-        let mut removed: u32 = 0;
-
-        for i in 0..list.len() {
-            let pair = list.get(i).unwrap();
-            let expired = env
-                .storage()
-                .persistent()
-                .get::<DataKey, (i128, i128, u64, u64)>(&DataKey::PriceFeed(pair.clone()))
-                .map(|(_, _, _, expires_at)| expires_at < current_time)
-                .unwrap_or(true);
-
-            if expired {
-                env.storage().persistent().remove(&DataKey::PriceFeed(pair));
-                removed += 1;
-            } else {
-                remaining.push_back(pair);
+        let mut previous = 0i128;
+        for threshold in thresholds.iter() {
+            if threshold <= 0 {
+                return Err(PredifiError::InvalidTargetPrice);
             }
+            if threshold <= previous {
+                return Err(PredifiError::InvalidData);
+            }
+            previous = threshold;
         }
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::PriceFeedList, &remaining);
+        Self::clear_price_resolution(&env, pool_id);
 
-        PriceFeedsCleanedEvent {
-            feeds_removed: removed,
-            timestamp: current_time,
+        let brackets_key = DataKey::PriceBrackets(pool_id);
+        env.storage().persistent().set(
+            &brackets_key,
+            &PriceBrackets {
+                feed_pair: feed_pair.clone(),
+                thresholds: thresholds.clone(),
+            },
+        );
+        Self::extend_persistent(&env, &brackets_key);
+
+        PriceBracketsSetEvent {
+            pool_id,
+            feed_pair,
+            thresholds,
         }
         .publish(&env);
 
-        removed
+        Ok(())
     }
 
-    /// Load the current price and expiry timestamp for the configured feed.
-    ///
-    /// This intentionally preserves the previous missing-feed behavior:
-    /// callers panic with "Feed not found" when the feed has not been updated.
-    fn load_price_feed_for_resolution(env: &Env, feed_pair: Symbol) -> (i128, u64, u64) {
-        let (price, _confidence, timestamp, expires_at): (i128, i128, u64, u64) = env
-            .storage()
+    /// Return the price brackets configured for a pool, if any.
+    pub fn get_price_brackets(env: Env, pool_id: u64) -> Option<PriceBrackets> {
+        env.storage()
             .persistent()
-            .get(&DataKey::PriceFeed(feed_pair))
-            .expect("Feed not found");
+            .get(&DataKey::PriceBrackets(pool_id))
+    }
 
-        (price, timestamp, expires_at)
+    /// A pool settles on exactly one of a price condition, a scalar range or
+    /// price brackets; setting one removes the others.
+    fn clear_price_resolution(env: &Env, pool_id: u64) {
+        let storage = env.storage().persistent();
+        storage.remove(&DataKey::PriceCondition(pool_id));
+        storage.remove(&DataKey::ScalarRange(pool_id));
+        storage.remove(&DataKey::PriceBrackets(pool_id));
     }
 
-    fn require_fresh_price_feed(
-        env: &Env,
+    /// Update price feed data from an external oracle.
+    /// Only callable by authorized oracles.
+    pub fn update_price_feed(
+        env: Env,
+        oracle: Address,
+        feed_pair: Symbol,
+        price: i128,
+        confidence: i128,
         timestamp: u64,
         expires_at: u64,
     ) -> Result<(), PredifiError> {
-        let current_time = env.ledger().timestamp();
+        Self::require_not_paused(&env)?;
+        oracle.require_auth();
 
-        // Check if price has expired
-        if current_time > expires_at {
-            return Err(PredifiError::PriceDataInvalid);
+        if !Self::is_oracle_whitelisted(&env, &oracle) {
+            return Err(PredifiError::Unauthorized);
         }
 
-        // Check if price is within the oracle's max_price_age limit
-        let (_pyth_contract, max_price_age, _min_confidence_ratio) =
-            Self::get_oracle_config(env.clone()).ok_or(PredifiError::OracleNotInitialized)?;
+        store_price_feed(
+            &env,
+            &oracle,
+            &feed_pair,
+            &PriceReading {
+                price,
+                confidence,
+                timestamp,
+                expires_at,
+            },
+        )?;
 
-        if current_time > timestamp.saturating_add(max_price_age) {
-            return Err(PredifiError::PriceDataInvalid);
+        // Emit event so off-chain monitors and indexers can track price updates.
+        PriceFeedUpdatedEvent {
+            oracle,
+            feed_pair,
+            price,
+            confidence,
+            timestamp,
+            expires_at,
         }
+        .publish(&env);
 
         Ok(())
     }
 
+    /// Remove all expired price feeds from storage. Permissionless.
+    ///
+    /// Returns the number of feeds removed.
+    pub fn cleanup_expired_feeds(env: Env) -> u32 {
+        let current_time = env.ledger().timestamp();
+
+        let removed = remove_expired_feeds(&env);
+
+        PriceFeedsCleanedEvent {
+            feeds_removed: removed,
+            timestamp: current_time,
+        }
+        .publish(&env);
+
+        removed
+    }
+
     fn load_price_resolution_condition(
         env: &Env,
         pool_id: u64,
@@ -388,71 +601,6 @@ impl PredifiContract {
             .ok_or(PredifiError::PriceConditionNotSet)
     }
 
-    fn validate_price_condition_match_params(
-        comparison_op: u32,
-        tolerance_bps: u32,
-    ) -> Result<(), PredifiError> {
-        if comparison_op > 2 || tolerance_bps > MAX_TOLERANCE {
-            return Err(PredifiError::InvalidData);
-        }
-
-        Ok(())
-    }
-
-    fn price_tolerance_amount(
-        target_price: i128,
-        tolerance_bps: u32,
-    ) -> Result<i128, PredifiError> {
-        target_price
-            .checked_mul(tolerance_bps as i128)
-            .and_then(|amount| amount.checked_div(MAX_TOLERANCE as i128))
-            .ok_or(PredifiError::ArithmeticError)
-    }
-
-    /// Match a stored price condition in a fixed, explicitly bounded number of checks.
-    ///
-    /// Operators: 0=Equal, 1=Greater, 2=Less. Outcome: 0=No, 1=Yes.
-    fn price_resolution_outcome(
-        price: i128,
-        target_price: i128,
-        comparison_op: u32,
-        tolerance_bps: u32,
-    ) -> Result<u32, PredifiError> {
-        Self::validate_price_condition_match_params(comparison_op, tolerance_bps)?;
-
-        let tolerance_amount = Self::price_tolerance_amount(target_price, tolerance_bps)?;
-        let lower_bound = target_price
-            .checked_sub(tolerance_amount)
-            .ok_or(PredifiError::ArithmeticError)?;
-        let upper_bound = target_price
-            .checked_add(tolerance_amount)
-            .ok_or(PredifiError::ArithmeticError)?;
-
-        let mut steps = 0u32;
-        let condition_met = match comparison_op {
-            0 => {
-                steps += 2;
-                price >= lower_bound && price <= upper_bound
-            }
-            1 => {
-                steps += 1;
-                price > upper_bound
-            }
-            2 => {
-                steps += 1;
-                price < lower_bound
-            }
-            _ => return Err(PredifiError::InvalidData),
-        };
-
-        steps += 2;
-        if steps > MAX_PRICE_CONDITION_MATCH_STEPS {
-            return Err(PredifiError::RateLimitOrSuspiciousActivity);
-        }
-
-        Ok(if condition_met { 1 } else { 0 })
-    }
-
     /// Load the pool and validate all non-outcome preconditions for price resolution.
     fn load_resolvable_price_pool(
         env: &Env,
@@ -474,7 +622,8 @@ impl PredifiContract {
         let current_time = env.ledger().timestamp();
         let config = Self::get_config(env);
 
-        if current_time < pool.end_time.saturating_add(config.resolution_delay) {
+        if current_time < Self::resolution_eligible_at(env, pool_id, &pool, config.resolution_delay)
+        {
             return Err(PredifiError::ResolutionDelayNotMet);
         }
 
@@ -517,7 +666,7 @@ impl PredifiContract {
         pool_id: u64,
         mut pool: Pool,
         outcome: u32,
-    ) {
+    ) -> Result<(), PredifiError> {
         pool.state = MarketState::Resolved;
         pool.outcome = outcome;
         pool.fee_bps = Self::calculate_dynamic_fee(env, &pool);
@@ -525,6 +674,9 @@ impl PredifiContract {
 
         env.storage().persistent().set(pool_key, &pool);
         Self::bump_ttl(env, pool_key);
+        Self::enter_reentrancy_guard(env);
+        Self::settle_outcome_proposal(env, pool_id, &pool)?;
+        Self::exit_reentrancy_guard(env);
 
         PoolResolvedEvent {
             pool_id,
@@ -532,23 +684,92 @@ impl PredifiContract {
             outcome,
         }
         .publish(env);
+        Ok(())
+    }
+
+    /// Settle a scalar pool: record the long side's share of the payout pool
+    /// and resolve to the side holding the larger share (long on a tie).
+    fn resolve_scalar_pool(
+        env: &Env,
+        pool_id: u64,
+        mut range: ScalarRange,
+    ) -> Result<(), PredifiError> {
+        let price = resolution_price(env, pool_id, &range.feed_pair)?;
+
+        let long_bps = calculate_scalar_long_bps(price, range.lower, range.upper)
+            .map_err(|_| PredifiError::ArithmeticError)?;
+        let (pool_key, pool) = Self::load_resolvable_price_pool(env, pool_id)?;
+
+        let range_key = DataKey::ScalarRange(pool_id);
+        range.settlement_bps = Some(long_bps);
+        env.storage().persistent().set(&range_key, &range);
+        Self::extend_persistent(env, &range_key);
+
+        let outcome = if long_bps >= 5_000 { 1 } else { 0 };
+        Self::persist_price_resolution(env, &pool_key, pool_id, pool, outcome)?;
+
+        ScalarResolvedEvent {
+            pool_id,
+            feed_pair: range.feed_pair,
+            price,
+            long_bps,
+        }
+        .publish(env);
+
+        Ok(())
     }
 
-    /// Automatically resolve a pool based on its configured price condition.
+    /// Index of the bracket containing `price`: the number of thresholds at
+    /// or below it.
+    fn price_bracket_outcome(price: i128, thresholds: &Vec<i128>) -> u32 {
+        let mut outcome = 0u32;
+        for threshold in thresholds.iter() {
+            if price < threshold {
+                break;
+            }
+            outcome += 1;
+        }
+        outcome
+    }
+
+    /// Automatically resolve a pool based on its configured price condition,
+    /// scalar range or price brackets.
     /// Anyone can trigger this once the pool's end time and resolution delay have passed.
     pub fn resolve_pool_from_price(env: Env, pool_id: u64) -> Result<(), PredifiError> {
         Self::require_not_paused(&env)?;
+        // A pending unchallenged proposal is finalized, not overridden.
+        Self::require_no_unchallenged_proposal(&env, pool_id)?;
+
+        if let Some(range) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, ScalarRange>(&DataKey::ScalarRange(pool_id))
+        {
+            return Self::resolve_scalar_pool(&env, pool_id, range);
+        }
+
+        if let Some(brackets) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, PriceBrackets>(&DataKey::PriceBrackets(pool_id))
+        {
+            let price = resolution_price(&env, pool_id, &brackets.feed_pair)?;
+
+            let outcome = Self::price_bracket_outcome(price, &brackets.thresholds);
+            let (pool_key, pool) = Self::load_resolvable_price_pool(&env, pool_id)?;
+            Self::validate_price_resolution_outcome(&env, pool_id, outcome, pool.options_count)?;
+            Self::persist_price_resolution(&env, &pool_key, pool_id, pool, outcome)?;
+            return Ok(());
+        }
 
         let (feed_pair, target_price, comparison_op, tolerance_bps) =
             Self::load_price_resolution_condition(&env, pool_id)?;
-        let (price, timestamp, expires_at) = Self::load_price_feed_for_resolution(&env, feed_pair);
-        Self::require_fresh_price_feed(&env, timestamp, expires_at)?;
+        let price = resolution_price(&env, pool_id, &feed_pair)?;
 
-        let outcome =
-            Self::price_resolution_outcome(price, target_price, comparison_op, tolerance_bps)?;
+        let outcome = price_condition_outcome(price, target_price, comparison_op, tolerance_bps)?;
         let (pool_key, pool) = Self::load_resolvable_price_pool(&env, pool_id)?;
         Self::validate_price_resolution_outcome(&env, pool_id, outcome, pool.options_count)?;
-        Self::persist_price_resolution(&env, &pool_key, pool_id, pool, outcome);
+        Self::persist_price_resolution(&env, &pool_key, pool_id, pool, outcome)?;
 
         Ok(())
     }
@@ -587,10 +808,16 @@ impl OracleCallback for PredifiContract {
         let current_time = env.ledger().timestamp();
         let config = Self::get_config(&env);
 
-        if current_time < pool.end_time.saturating_add(config.resolution_delay) {
+        if current_time
+            < Self::resolution_eligible_at(&env, pool_id, &pool, config.resolution_delay)
+        {
             return Err(PredifiError::ResolutionDelayNotMet);
         }
 
+        // Optimistic pools: oracles only vote once a proposal has been
+        // challenged (or if nobody proposed at all).
+        Self::require_no_unchallenged_proposal(&env, pool_id)?;
+
         // Validate: outcome must be within the valid options range
         if outcome >= pool.options_count {
             soroban_sdk::panic_with_error!(&env, PredifiError::InvalidOutcome);
@@ -686,6 +913,9 @@ impl OracleCallback for PredifiContract {
             Self::extend_persistent(&env, &pool_key);
             // Remove from global active index now that the pool is resolved.
             Self::remove_from_active_index(&env, pool_id);
+            Self::enter_reentrancy_guard(&env);
+            Self::settle_outcome_proposal(&env, pool_id, &pool)?;
+            Self::exit_reentrancy_guard(&env);
 
             // Retrieve winning-outcome stake for the diagnostic event efficiently
             let winning_stake = Self::get_outcome_stake(env.clone(), pool_id, outcome);
@@ -0,0 +1,500 @@
+//! Parlay domain: combo predictions spanning several pools, and the
+//! per-token combo vault that backs their payouts.
+//!
+//! A parlay is priced at placement from each leg's parimutuel odds and does
+//! not add to the legs' pools. Its stake goes into the vault and its full
+//! payout is reserved there, so the vault can always pay every open parlay.
+//! Reserved funds are released back to the vault's liquidity when a leg
+//! loses or a voided leg lowers the payout.
+//!
+//! Leg odds come from a snapshot the bettor could skew, so they are capped
+//! per leg and combined, a leg's pool must hold the token's minimum leg stake,
+//! and bettors cannot pick pools they hold a position in.
+
+use soroban_sdk::{contractimpl, token, Address, Env, Symbol, Vec};
+
+use crate::{
+    calculate_odds_bps, calculate_parlay_payout, ComboVault, ComboVaultFundedEvent,
+    ComboVaultWithdrawnEvent, DataKey, MarketState, Parlay, ParlayClaimedEvent, ParlayKey,
+    ParlayLeg, ParlayMinLegStakeSetEvent, ParlayPick, ParlayPlacedEvent, ParlaySettledEvent,
+    ParlayStatus, Pool, PredifiContract, PredifiContractArgs, PredifiContractClient, PredifiError,
+    PricingMode, MAX_PARLAY_LEGS, MAX_PARLAY_LEG_ODDS_BPS, MAX_PARLAY_ODDS_BPS,
+};
+
+#[contractimpl]
+impl PredifiContract {
+    /// Move `amount` of `token` from the treasury into the combo vault.
+    /// Must be called by the configured treasury address.
+    pub fn fund_combo_vault(
+        env: Env,
+        treasury: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        treasury.require_auth();
+
+        if treasury != Self::get_config(&env).treasury {
+            return Err(PredifiError::Unauthorized);
+        }
+        if amount <= 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+        if !Self::is_token_whitelisted(&env, &token) {
+            return Err(PredifiError::TokenNotWhitelisted);
+        }
+
+        token::Client::new(&env, &token).transfer(
+            &treasury,
+            env.current_contract_address(),
+            &amount,
+        );
+
+        let mut vault = Self::get_combo_vault(env.clone(), token.clone());
+        vault.liquidity = vault
+            .liquidity
+            .checked_add(amount)
+            .ok_or(PredifiError::ArithmeticError)?;
+        Self::write_combo_vault(&env, &token, &vault);
+
+        ComboVaultFundedEvent {
+            token,
+            amount,
+            liquidity: vault.liquidity,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Return unreserved combo vault liquidity to the treasury.
+    /// Caller must have Admin role (0).
+    pub fn withdraw_combo_vault(
+        env: Env,
+        admin: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        admin.require_auth();
+        Self::require_admin_role(&env, &admin, "withdraw_combo_vault")?;
+
+        if amount <= 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+        let mut vault = Self::get_combo_vault(env.clone(), token.clone());
+        if amount > vault.liquidity {
+            return Err(PredifiError::InsufficientBalance);
+        }
+        vault.liquidity -= amount;
+        Self::write_combo_vault(&env, &token, &vault);
+
+        let treasury = Self::get_config(&env).treasury;
+        Self::enter_reentrancy_guard(&env);
+        token::Client::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &treasury,
+            &amount,
+        );
+        Self::exit_reentrancy_guard(&env);
+
+        ComboVaultWithdrawnEvent {
+            admin,
+            token,
+            amount,
+            liquidity: vault.liquidity,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Set the least total stake a pool in `token` must hold to be used as a
+    /// parlay leg. An `amount` of 0 removes the floor. Caller must have Admin
+    /// role (0).
+    ///
+    /// # Errors
+    /// - `Unauthorized` – caller lacks Admin role (0).
+    /// - `InvalidAmount` – `amount` is negative.
+    pub fn set_parlay_min_leg_stake(
+        env: Env,
+        admin: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        admin.require_auth();
+        Self::require_admin_role(&env, &admin, "set_parlay_min_leg_stake")?;
+        if amount < 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+
+        let key = ParlayKey::MinLegStake(token.clone());
+        env.storage().persistent().set(&key, &amount);
+        Self::extend_persistent(&env, &key);
+
+        ParlayMinLegStakeSetEvent {
+            admin,
+            token,
+            amount,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Least total stake a parlay leg's pool in `token` must hold (0 if unset).
+    pub fn get_parlay_min_leg_stake(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&ParlayKey::MinLegStake(token))
+            .unwrap_or(0)
+    }
+
+    /// Return the combo vault of `token`.
+    pub fn get_combo_vault(env: Env, token: Address) -> ComboVault {
+        env.storage()
+            .persistent()
+            .get(&ParlayKey::ComboVault(token))
+            .unwrap_or_default()
+    }
+
+    /// Stake `stake` on every pick in `legs` winning. Returns the parlay id.
+    ///
+    /// Legs must be 2 to `MAX_PARLAY_LEGS` distinct public parimutuel pools
+    /// that are still open, share one token and one `category`, hold at
+    /// least the token's minimum leg stake, have stake on the picked outcome
+    /// to price it from, and hold no position of `user`. Each leg's current
+    /// odds may not exceed `MAX_PARLAY_LEG_ODDS_BPS`, and their product may
+    /// not exceed `MAX_PARLAY_ODDS_BPS`. The payout, compounded from those
+    /// odds less the protocol fee on the profit, is reserved in the token's
+    /// combo vault together with the fee.
+    ///
+    /// # Errors
+    /// - `InvalidAmount` – `stake` is not positive.
+    /// - `InsufficientStake` – `stake` is below the global minimum.
+    /// - `InvalidData` – wrong number of legs, a repeated pool, or mixed
+    ///   tokens or categories.
+    /// - `PoolNotFound` / `InvalidPoolState` – a leg's pool is missing, not
+    ///   open, private or not parimutuel.
+    /// - `Unauthorized` – `user` holds a position in a leg's pool.
+    /// - `InsufficientStake` – a leg's pool holds less than the minimum leg
+    ///   stake.
+    /// - `InvalidOutcome` – a pick is out of range, has no stake to price it,
+    ///   or is priced above `MAX_PARLAY_LEG_ODDS_BPS`.
+    /// - `InvalidAmount` – the combined odds exceed `MAX_PARLAY_ODDS_BPS`.
+    /// - `TokenNotWhitelisted` – the legs' token is not whitelisted.
+    /// - `InsufficientBalance` – the vault cannot cover the payout and fee.
+    pub fn place_parlay(
+        env: Env,
+        user: Address,
+        stake: i128,
+        legs: Vec<ParlayPick>,
+    ) -> Result<u64, PredifiError> {
+        Self::require_not_paused(&env)?;
+        user.require_auth();
+
+        if stake <= 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+        let config = Self::get_config(&env);
+        if stake < config.min_stake {
+            return Err(PredifiError::InsufficientStake);
+        }
+        if legs.len() < 2 || legs.len() > MAX_PARLAY_LEGS {
+            return Err(PredifiError::InvalidData);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut priced: Vec<ParlayLeg> = Vec::new(&env);
+        let mut odds = [0u64; MAX_PARLAY_LEGS as usize];
+        let mut market: Option<(Address, Symbol)> = None;
+        for (i, pick) in legs.iter().enumerate() {
+            if priced.iter().any(|leg| leg.pool_id == pick.pool_id) {
+                return Err(PredifiError::InvalidData);
+            }
+            let pool: Pool = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Pool(pick.pool_id))
+                .ok_or(PredifiError::PoolNotFound)?;
+            if !Self::is_pool_active(&pool)
+                || now >= pool.end_time
+                || pool.private
+                || pool.pricing_mode != PricingMode::Parimutuel
+            {
+                return Err(PredifiError::InvalidPoolState);
+            }
+            match &market {
+                None => market = Some((pool.token.clone(), pool.category.clone())),
+                Some((token, category)) => {
+                    if *token != pool.token || *category != pool.category {
+                        return Err(PredifiError::InvalidData);
+                    }
+                }
+            }
+            if pick.outcome >= pool.options_count {
+                return Err(PredifiError::InvalidOutcome);
+            }
+            // A bettor staking in a leg could have shaped its odds.
+            if !Self::load_position(&env, &user, pick.pool_id).is_empty() {
+                return Err(PredifiError::Unauthorized);
+            }
+            if pool.total_stake < Self::get_parlay_min_leg_stake(env.clone(), pool.token.clone()) {
+                return Err(PredifiError::InsufficientStake);
+            }
+
+            let outcome_stake = Self::get_outcome_stake(env.clone(), pick.pool_id, pick.outcome);
+            let odds_bps = calculate_odds_bps(outcome_stake, pool.total_stake);
+            if odds_bps == 0 || odds_bps > MAX_PARLAY_LEG_ODDS_BPS {
+                return Err(PredifiError::InvalidOutcome);
+            }
+            odds[i] = odds_bps;
+            priced.push_back(ParlayLeg {
+                pool_id: pick.pool_id,
+                outcome: pick.outcome,
+                odds_bps,
+            });
+        }
+        let (token, category) = market.ok_or(PredifiError::InvalidData)?;
+        if !Self::is_token_whitelisted(&env, &token) {
+            return Err(PredifiError::TokenNotWhitelisted);
+        }
+
+        let (payout, fee) =
+            Self::parlay_payout(stake, &odds[..legs.len() as usize], config.fee_bps)?;
+        let max_gross = stake
+            .checked_mul(MAX_PARLAY_ODDS_BPS)
+            .ok_or(PredifiError::ArithmeticError)?
+            / 10_000;
+        if payout + fee > max_gross {
+            return Err(PredifiError::InvalidAmount);
+        }
+
+        // The stake joins the vault's liquidity and the payout and fee move to
+        // reserved, so the vault must already cover `payout + fee - stake`.
+        let reserve = payout + fee;
+        let mut vault = Self::get_combo_vault(env.clone(), token.clone());
+        let available = vault
+            .liquidity
+            .checked_add(stake)
+            .ok_or(PredifiError::ArithmeticError)?;
+        if reserve > available {
+            return Err(PredifiError::InsufficientBalance);
+        }
+        vault.liquidity = available - reserve;
+        vault.reserved = vault
+            .reserved
+            .checked_add(reserve)
+            .ok_or(PredifiError::ArithmeticError)?;
+
+        token::Client::new(&env, &token).transfer(&user, env.current_contract_address(), &stake);
+        Self::write_combo_vault(&env, &token, &vault);
+
+        let count_key = ParlayKey::ParlayCount;
+        let parlay_id: u64 = env.storage().instance().get(&count_key).unwrap_or(0);
+        env.storage().instance().set(&count_key, &(parlay_id + 1));
+        Self::extend_instance(&env);
+
+        let parlay_key = ParlayKey::Parlay(parlay_id);
+        env.storage().persistent().set(
+            &parlay_key,
+            &Parlay {
+                user: user.clone(),
+                token: token.clone(),
+                category,
+                stake,
+                legs: priced,
+                fee_bps: config.fee_bps,
+                payout,
+                fee,
+                status: ParlayStatus::Open,
+            },
+        );
+        Self::extend_persistent(&env, &parlay_key);
+
+        ParlayPlacedEvent {
+            parlay_id,
+            user,
+            token,
+            stake,
+            legs: legs.len(),
+            payout,
+        }
+        .publish(&env);
+
+        Ok(parlay_id)
+    }
+
+    /// Apply the current state of a parlay's legs. Permissionless.
+    ///
+    /// A leg counts once its pool is resolved and past the challenge window,
+    /// or canceled. The parlay is `Lost` as soon as one leg resolves against
+    /// its pick and `Won` once every leg won or was voided; voided legs are
+    /// dropped from the payout. Returns the resulting status.
+    pub fn settle_parlay(env: Env, parlay_id: u64) -> Result<ParlayStatus, PredifiError> {
+        Self::require_not_paused(&env)?;
+        let parlay = Self::settle_parlay_internal(&env, parlay_id)?;
+        Ok(parlay.status)
+    }
+
+    /// Pay out a won parlay to its owner. Settles it first, so a single call
+    /// after the last leg is decided is enough. The parlay's protocol fee is
+    /// credited to the accrued fees for its token.
+    ///
+    /// # Errors
+    /// - `Unauthorized` – `user` does not own the parlay.
+    /// - `PoolNotResolved` – a leg is still undecided.
+    /// - `InvalidOutcome` – the parlay lost.
+    /// - `AlreadyClaimed` – the payout was already paid.
+    pub fn claim_parlay(env: Env, user: Address, parlay_id: u64) -> Result<i128, PredifiError> {
+        Self::require_not_paused(&env)?;
+        user.require_auth();
+
+        let mut parlay = Self::settle_parlay_internal(&env, parlay_id)?;
+        if parlay.user != user {
+            return Err(PredifiError::Unauthorized);
+        }
+        match parlay.status {
+            ParlayStatus::Won => {}
+            ParlayStatus::Open => return Err(PredifiError::PoolNotResolved),
+            ParlayStatus::Lost => return Err(PredifiError::InvalidOutcome),
+            ParlayStatus::Claimed => return Err(PredifiError::AlreadyClaimed),
+        }
+
+        let amount = parlay.payout;
+        let mut vault = Self::get_combo_vault(env.clone(), parlay.token.clone());
+        vault.reserved -= amount + parlay.fee;
+        Self::write_combo_vault(&env, &parlay.token, &vault);
+        Self::credit_accrued_fees(&env, &parlay.token, parlay.fee)?;
+
+        parlay.status = ParlayStatus::Claimed;
+        let parlay_key = ParlayKey::Parlay(parlay_id);
+        env.storage().persistent().set(&parlay_key, &parlay);
+        Self::extend_persistent(&env, &parlay_key);
+
+        Self::enter_reentrancy_guard(&env);
+        token::Client::new(&env, &parlay.token).transfer(
+            &env.current_contract_address(),
+            &user,
+            &amount,
+        );
+        Self::exit_reentrancy_guard(&env);
+
+        ParlayClaimedEvent {
+            parlay_id,
+            user,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(amount)
+    }
+
+    /// Return a parlay by id.
+    pub fn get_parlay(env: Env, parlay_id: u64) -> Option<Parlay> {
+        env.storage()
+            .persistent()
+            .get(&ParlayKey::Parlay(parlay_id))
+    }
+
+    /// Net payout of a parlay at `odds` and the protocol fee taken from its
+    /// profit.
+    fn parlay_payout(
+        stake: i128,
+        odds: &[u64],
+        fee_bps: u32,
+    ) -> Result<(i128, i128), PredifiError> {
+        let gross =
+            calculate_parlay_payout(stake, odds, 0).map_err(|_| PredifiError::ArithmeticError)?;
+        let payout = calculate_parlay_payout(stake, odds, fee_bps as i128)
+            .map_err(|_| PredifiError::ArithmeticError)?;
+        Ok((payout, gross - payout))
+    }
+
+    fn write_combo_vault(env: &Env, token: &Address, vault: &ComboVault) {
+        let key = ParlayKey::ComboVault(token.clone());
+        env.storage().persistent().set(&key, vault);
+        Self::extend_persistent(env, &key);
+    }
+
+    /// Re-evaluate an open parlay against its legs' pools, release whatever
+    /// part of its reservation it no longer needs, and store the result.
+    fn settle_parlay_internal(env: &Env, parlay_id: u64) -> Result<Parlay, PredifiError> {
+        let parlay_key = ParlayKey::Parlay(parlay_id);
+        let mut parlay: Parlay = env
+            .storage()
+            .persistent()
+            .get(&parlay_key)
+            .ok_or(PredifiError::InvalidData)?;
+        if parlay.status != ParlayStatus::Open {
+            return Ok(parlay);
+        }
+
+        let mut live_odds = [0u64; MAX_PARLAY_LEGS as usize];
+        let mut live = 0usize;
+        let mut pending = false;
+        let mut lost = false;
+        for leg in parlay.legs.iter() {
+            let pool: Pool = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Pool(leg.pool_id))
+                .ok_or(PredifiError::PoolNotFound)?;
+            match pool.state {
+                MarketState::Canceled => continue,
+                MarketState::Resolved
+                    if Self::require_challenge_window_elapsed(env, leg.pool_id, &pool).is_ok() =>
+                {
+                    if pool.outcome != leg.outcome {
+                        lost = true;
+                        break;
+                    }
+                }
+                _ => pending = true,
+            }
+            live_odds[live] = leg.odds_bps;
+            live += 1;
+        }
+
+        let (payout, fee) = if lost {
+            (0, 0)
+        } else {
+            let (payout, fee) =
+                Self::parlay_payout(parlay.stake, &live_odds[..live], parlay.fee_bps)?;
+            (payout.min(parlay.payout), fee.min(parlay.fee))
+        };
+        let status = if lost {
+            ParlayStatus::Lost
+        } else if pending {
+            ParlayStatus::Open
+        } else {
+            ParlayStatus::Won
+        };
+        if status == parlay.status && payout == parlay.payout && fee == parlay.fee {
+            return Ok(parlay);
+        }
+
+        let released = parlay.payout + parlay.fee - payout - fee;
+        if released > 0 {
+            let mut vault = Self::get_combo_vault(env.clone(), parlay.token.clone());
+            vault.reserved -= released;
+            vault.liquidity += released;
+            Self::write_combo_vault(env, &parlay.token, &vault);
+        }
+
+        parlay.payout = payout;
+        parlay.fee = fee;
+        parlay.status = status;
+        env.storage().persistent().set(&parlay_key, &parlay);
+        Self::extend_persistent(env, &parlay_key);
+
+        ParlaySettledEvent {
+            parlay_id,
+            status,
+            payout,
+        }
+        .publish(env);
+
+        Ok(parlay)
+    }
+}
@@ -0,0 +1,292 @@
+//! Parlays: legs priced against the combo vault, paid when every leg wins,
+//! released when one loses and voided when one is canceled.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{ComboVault, ParlayPick, ParlayStatus, PredifiError, FEE_CHANGE_TIMELOCK_SECONDS};
+use soroban_sdk::{symbol_short, testutils::Ledger, vec, Address, Env, String, Symbol, Vec};
+
+const END_TIME: u64 = 10_000;
+const VAULT: i128 = 1_000;
+
+fn new_pool(ctx: &TestContext, category: Symbol, stakes: (i128, i128)) -> u64 {
+    new_pool_until(ctx, category, stakes, END_TIME)
+}
+
+/// A binary pool in `category` with `stakes` placed on outcomes 0 and 1.
+fn new_pool_until(ctx: &TestContext, category: Symbol, stakes: (i128, i128), end_time: u64) -> u64 {
+    let pool_id = ctx.client.create_pool(
+        &ctx.creator,
+        &end_time,
+        &ctx.token_address,
+        &2u32,
+        &category,
+        &default_pool_config(&ctx.client.env),
+    );
+    for (outcome, amount) in [(0u32, stakes.0), (1u32, stakes.1)] {
+        if amount > 0 {
+            ctx.staker(pool_id, amount, outcome);
+        }
+    }
+    pool_id
+}
+
+/// A vault funded with `VAULT`, a user holding 100, and two Sports matches:
+/// `match_a` at 2.0x on outcome 1 and `match_b` at 4.0x on outcome 0.
+/// Returns `(user, match_a, match_b)`.
+fn parlay_setup(ctx: &TestContext) -> (Address, u64, u64) {
+    let user = ctx.funded_user(100);
+    ctx.token_admin_client.mint(&ctx.treasury, &VAULT);
+    ctx.client
+        .fund_combo_vault(&ctx.treasury, &ctx.token_address, &VAULT);
+
+    let match_a = new_pool(ctx, symbol_short!("Sports"), (100, 100));
+    let match_b = new_pool(ctx, symbol_short!("Sports"), (100, 300));
+    (user, match_a, match_b)
+}
+
+fn picks(env: &Env, match_a: u64, match_b: u64) -> Vec<ParlayPick> {
+    vec![
+        env,
+        ParlayPick {
+            pool_id: match_a,
+            outcome: 1,
+        },
+        ParlayPick {
+            pool_id: match_b,
+            outcome: 0,
+        },
+    ]
+}
+
+/// 10 at 2.0x and 4.0x reserves 80 in the vault and pays it once both legs
+/// win.
+#[test]
+fn test_parlay_pays_when_every_leg_wins() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (user, match_a, match_b) = parlay_setup(&ctx);
+
+    let parlay_id = ctx
+        .client
+        .place_parlay(&user, &10, &picks(&env, match_a, match_b));
+    let parlay = ctx.client.get_parlay(&parlay_id).unwrap();
+    assert_eq!(parlay.payout, 80);
+    assert_eq!(parlay.legs.get(1).unwrap().odds_bps, 40_000);
+    assert_eq!(
+        ctx.client.get_combo_vault(&ctx.token_address),
+        ComboVault {
+            liquidity: VAULT + 10 - 80,
+            reserved: 80,
+        }
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.resolve_pool(&ctx.operator, &match_a, &1u32);
+    assert_eq!(ctx.client.settle_parlay(&parlay_id), ParlayStatus::Open);
+    let early = ctx.client.try_claim_parlay(&user, &parlay_id);
+    assert_eq!(early, Err(Ok(PredifiError::PoolNotResolved)));
+
+    ctx.client.resolve_pool(&ctx.operator, &match_b, &0u32);
+    assert_eq!(ctx.client.claim_parlay(&user, &parlay_id), 80);
+    assert_eq!(ctx.token.balance(&user), 100 - 10 + 80);
+    assert_eq!(ctx.client.get_combo_vault(&ctx.token_address).reserved, 0);
+
+    let again = ctx.client.try_claim_parlay(&user, &parlay_id);
+    assert_eq!(again, Err(Ok(PredifiError::AlreadyClaimed)));
+}
+
+/// One losing leg settles the parlay as lost straight away and frees its
+/// reservation, stake included, for the vault.
+#[test]
+fn test_losing_leg_releases_reservation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (user, match_a, match_b) = parlay_setup(&ctx);
+    let parlay_id = ctx
+        .client
+        .place_parlay(&user, &10, &picks(&env, match_a, match_b));
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.resolve_pool(&ctx.operator, &match_a, &0u32);
+
+    assert_eq!(ctx.client.settle_parlay(&parlay_id), ParlayStatus::Lost);
+    assert_eq!(
+        ctx.client.get_combo_vault(&ctx.token_address),
+        ComboVault {
+            liquidity: VAULT + 10,
+            reserved: 0,
+        }
+    );
+    let lost = ctx.client.try_claim_parlay(&user, &parlay_id);
+    assert_eq!(lost, Err(Ok(PredifiError::InvalidOutcome)));
+
+    // The freed liquidity can go back to the treasury.
+    ctx.client
+        .withdraw_combo_vault(&ctx.admin, &ctx.token_address, &(VAULT + 10));
+    assert_eq!(ctx.token.balance(&ctx.treasury), VAULT + 10);
+}
+
+/// A canceled leg is void: the parlay pays at the remaining legs' odds.
+#[test]
+fn test_canceled_leg_is_void() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (user, match_a, match_b) = parlay_setup(&ctx);
+    let parlay_id = ctx
+        .client
+        .place_parlay(&user, &10, &picks(&env, match_a, match_b));
+
+    ctx.client.cancel_pool(
+        &ctx.operator,
+        &match_b,
+        &String::from_str(&env, "postponed"),
+    );
+    assert_eq!(ctx.client.settle_parlay(&parlay_id), ParlayStatus::Open);
+    assert_eq!(ctx.client.get_parlay(&parlay_id).unwrap().payout, 20);
+    assert_eq!(
+        ctx.client.get_combo_vault(&ctx.token_address).liquidity,
+        VAULT + 10 - 20
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.resolve_pool(&ctx.operator, &match_a, &1u32);
+    assert_eq!(ctx.client.claim_parlay(&user, &parlay_id), 20);
+}
+
+/// The fee on a winning parlay's profit is reserved with the payout and
+/// credited to the accrued fees when it is claimed.
+#[test]
+fn test_parlay_fee_credited_on_claim() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (user, _, _) = parlay_setup(&ctx);
+    ctx.client.set_fee_bps(&ctx.admin, &1_000u32);
+    let now = 1_000 + FEE_CHANGE_TIMELOCK_SECONDS;
+    env.ledger().with_mut(|li| li.timestamp = now);
+    ctx.client.apply_fee_bps(&ctx.admin);
+
+    let end_time = now + END_TIME;
+    let match_a = new_pool_until(&ctx, symbol_short!("Sports"), (100, 100), end_time);
+    let match_b = new_pool_until(&ctx, symbol_short!("Sports"), (100, 300), end_time);
+
+    // 10 at 8.0x: 70 profit, 7 of it to the protocol.
+    let parlay_id = ctx
+        .client
+        .place_parlay(&user, &10, &picks(&env, match_a, match_b));
+    let parlay = ctx.client.get_parlay(&parlay_id).unwrap();
+    assert_eq!((parlay.payout, parlay.fee), (73, 7));
+    assert_eq!(ctx.client.get_combo_vault(&ctx.token_address).reserved, 80);
+
+    env.ledger().with_mut(|li| li.timestamp = end_time + 1);
+    ctx.client.resolve_pool(&ctx.operator, &match_a, &1u32);
+    ctx.client.resolve_pool(&ctx.operator, &match_b, &0u32);
+    assert_eq!(ctx.client.claim_parlay(&user, &parlay_id), 73);
+    assert_eq!(ctx.client.get_combo_vault(&ctx.token_address).reserved, 0);
+    assert_eq!(ctx.client.get_accrued_fees(&ctx.token_address), 7);
+}
+
+/// Legs are capped at 10x each and 100x combined, must hold the token's
+/// minimum leg stake, and may not be pools the bettor has staked in.
+#[test]
+fn test_parlay_leg_guards() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (user, match_a, match_b) = parlay_setup(&ctx);
+    let long_pick = |pool_id| ParlayPick {
+        pool_id,
+        outcome: 1,
+    };
+
+    let skewed = new_pool(&ctx, symbol_short!("Sports"), (1_000, 50));
+    let mut over_leg_cap = picks(&env, match_a, match_b);
+    over_leg_cap.set(1, long_pick(skewed));
+    let over_leg_cap = ctx.client.try_place_parlay(&user, &10, &over_leg_cap);
+    assert_eq!(over_leg_cap, Err(Ok(PredifiError::InvalidOutcome)));
+
+    // Three legs at exactly 10x compound to 1000x.
+    let mut longshots = Vec::new(&env);
+    for _ in 0..3 {
+        let pool_id = new_pool(&ctx, symbol_short!("Sports"), (900, 100));
+        longshots.push_back(long_pick(pool_id));
+    }
+    let over_total_cap = ctx.client.try_place_parlay(&user, &1, &longshots);
+    assert_eq!(over_total_cap, Err(Ok(PredifiError::InvalidAmount)));
+
+    ctx.client
+        .set_parlay_min_leg_stake(&ctx.admin, &ctx.token_address, &300);
+    assert_eq!(ctx.client.get_parlay_min_leg_stake(&ctx.token_address), 300);
+    let shallow = ctx
+        .client
+        .try_place_parlay(&user, &10, &picks(&env, match_a, match_b));
+    assert_eq!(shallow, Err(Ok(PredifiError::InsufficientStake)));
+    ctx.client
+        .set_parlay_min_leg_stake(&ctx.admin, &ctx.token_address, &0);
+
+    ctx.client
+        .place_prediction(&user, &match_a, &10, &0u32, &None, &None);
+    let own_pool = ctx
+        .client
+        .try_place_parlay(&user, &10, &picks(&env, match_a, match_b));
+    assert_eq!(own_pool, Err(Ok(PredifiError::Unauthorized)));
+
+    let not_admin = ctx
+        .client
+        .try_set_parlay_min_leg_stake(&user, &ctx.token_address, &1);
+    assert_eq!(not_admin, Err(Ok(PredifiError::Unauthorized)));
+}
+
+/// Legs must share a category, be priceable, and fit in the vault; only the
+/// treasury funds the vault.
+#[test]
+fn test_place_parlay_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (user, match_a, match_b) = parlay_setup(&ctx);
+
+    let single = ctx.client.try_place_parlay(
+        &user,
+        &10,
+        &vec![&env, picks(&env, match_a, match_b).get(0).unwrap()],
+    );
+    assert_eq!(single, Err(Ok(PredifiError::InvalidData)));
+
+    let finance = new_pool(&ctx, symbol_short!("Finance"), (100, 100));
+    let mut mixed = picks(&env, match_a, match_b);
+    mixed.set(
+        1,
+        ParlayPick {
+            pool_id: finance,
+            outcome: 0,
+        },
+    );
+    let mixed = ctx.client.try_place_parlay(&user, &10, &mixed);
+    assert_eq!(mixed, Err(Ok(PredifiError::InvalidData)));
+
+    let one_sided = new_pool(&ctx, symbol_short!("Sports"), (100, 0));
+    let mut unpriced = picks(&env, match_a, match_b);
+    unpriced.set(
+        1,
+        ParlayPick {
+            pool_id: one_sided,
+            outcome: 1,
+        },
+    );
+    let unpriced = ctx.client.try_place_parlay(&user, &10, &unpriced);
+    assert_eq!(unpriced, Err(Ok(PredifiError::InvalidOutcome)));
+
+    // 8x on 100 needs 700 from the vault on top of the stake.
+    ctx.token_admin_client.mint(&user, &1_000);
+    ctx.client
+        .place_parlay(&user, &100, &picks(&env, match_a, match_b));
+    let too_big = ctx
+        .client
+        .try_place_parlay(&user, &100, &picks(&env, match_a, match_b));
+    assert_eq!(too_big, Err(Ok(PredifiError::InsufficientBalance)));
+
+    let not_treasury = ctx
+        .client
+        .try_fund_combo_vault(&user, &ctx.token_address, &10);
+    assert_eq!(not_treasury, Err(Ok(PredifiError::Unauthorized)));
+}
@@ -91,6 +91,112 @@ pub fn calculate_claim_payout(input: &PayoutInput) -> Result<PayoutBreakdown, Pr
     })
 }
 
+/// Long side's share of a scalar pool's payout pool, in basis points.
+///
+/// `0` at or below `lower`, `10_000` at or above `upper`, and linear (rounded
+/// down) in between.
+pub fn calculate_scalar_long_bps(
+    price: i128,
+    lower: i128,
+    upper: i128,
+) -> Result<u32, PrediFiError> {
+    if lower >= upper {
+        return Err(PrediFiError::InvalidAmount);
+    }
+    if price <= lower {
+        return Ok(0);
+    }
+    if price >= upper {
+        return Ok(10_000);
+    }
+    let bps = SafeMath::proportion(
+        price - lower,
+        upper - lower,
+        10_000,
+        RoundingMode::ProtocolFavor,
+    )?;
+    Ok(bps as u32)
+}
+
+/// Payout for one side of a scalar pool.
+///
+/// `side_bps` is the side's share of the payout pool and
+/// `input.winning_stake` the side's aggregate stake. Both sides round down,
+/// so the two sides together never receive more than the payout pool.
+pub fn calculate_scalar_claim_payout(
+    input: &PayoutInput,
+    side_bps: i128,
+) -> Result<PayoutBreakdown, PrediFiError> {
+    if !(0..=10_000).contains(&side_bps) {
+        return Err(PrediFiError::InvalidAmount);
+    }
+    let full = calculate_claim_payout(&PayoutInput {
+        user_stake: 0,
+        ..*input
+    })?;
+    let side_pool = SafeMath::percentage(full.payout_pool, side_bps, RoundingMode::ProtocolFavor)?;
+
+    let winnings = if input.winning_stake == 0 || input.user_stake == 0 {
+        0
+    } else {
+        calculate_winnings(input.user_stake, input.winning_stake, side_pool)?
+    };
+
+    Ok(PayoutBreakdown {
+        protocol_fee: full.protocol_fee,
+        payout_pool: side_pool,
+        winnings,
+    })
+}
+
+/// Payout of a parlay whose remaining legs all win.
+///
+/// The stake is compounded by each leg's `odds_bps` (rounding down at every
+/// step), then `fee_bps` is taken from the profit only, so a parlay with no
+/// remaining legs pays back exactly its stake.
+pub fn calculate_parlay_payout(
+    stake: i128,
+    leg_odds_bps: &[u64],
+    fee_bps: i128,
+) -> Result<i128, PrediFiError> {
+    if stake < 0 {
+        return Err(PrediFiError::InvalidAmount);
+    }
+    let mut gross = stake;
+    for &odds in leg_odds_bps {
+        gross = gross
+            .checked_mul(odds as i128)
+            .ok_or(PrediFiError::ArithmeticError)?
+            / 10_000;
+    }
+    let profit = gross
+        .checked_sub(stake)
+        .ok_or(PrediFiError::ArithmeticError)?
+        .max(0);
+    let fee = calculate_protocol_fee(profit, fee_bps)?;
+    gross.checked_sub(fee).ok_or(PrediFiError::ArithmeticError)
+}
+
+/// Part of a pool's protocol fee realized by one claim, rounded down.
+///
+/// `share_base` is the stake the fee is spread over: the winning stake when
+/// only winners claim, or the whole pool when every side does.
+pub fn calculate_fee_share(
+    user_stake: i128,
+    share_base: i128,
+    protocol_fee: i128,
+) -> Result<i128, PrediFiError> {
+    if protocol_fee <= 0 || share_base <= 0 || user_stake <= 0 {
+        return Ok(0);
+    }
+    SafeMath::proportion(
+        user_stake,
+        share_base,
+        protocol_fee,
+        RoundingMode::ProtocolFavor,
+    )
+}
+
 /// Referral cut taken from the claimer's proportional share of the protocol fee.
 ///
 /// `protocol_fee_share` is the portion of the total protocol fee attributable to
@@ -196,6 +302,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn scalar_long_bps_is_linear_and_clamped() {
+        assert_eq!(calculate_scalar_long_bps(1_000, 2_000, 4_000).unwrap(), 0);
+        assert_eq!(
+            calculate_scalar_long_bps(2_500, 2_000, 4_000).unwrap(),
+            2_500
+        );
+        assert_eq!(
+            calculate_scalar_long_bps(9_000, 2_000, 4_000).unwrap(),
+            10_000
+        );
+        assert_eq!(
+            calculate_scalar_long_bps(3_000, 4_000, 4_000),
+            Err(PrediFiError::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn scalar_sides_split_payout_pool() {
+        let long = PayoutInput {
+            pool_total_stake: 1_000,
+            fee_bps: 100,
+            user_stake: 300,
+            winning_stake: 600,
+        };
+        let short = PayoutInput {
+            winning_stake: 400,
+            user_stake: 400,
+            ..long
+        };
+        // Payout pool 990; long side gets 25%, short side 75%.
+        let long_out = calculate_scalar_claim_payout(&long, 2_500).unwrap();
+        let short_out = calculate_scalar_claim_payout(&short, 7_500).unwrap();
+        assert_eq!(long_out.protocol_fee, 10);
+        assert_eq!(long_out.winnings, 123);
+        assert_eq!(short_out.winnings, 742);
+        assert!(2 * long_out.winnings + short_out.winnings <= 990);
+    }
+
+    #[test]
+    fn parlay_compounds_odds_and_charges_fee_on_profit() {
+        // 100 at 2.0x then 1.5x = 300; 10% of the 200 profit is 20.
+        let payout = calculate_parlay_payout(100, &[20_000, 15_000], 1_000).unwrap();
+        assert_eq!(payout, 280);
+    }
+
+    #[test]
+    fn parlay_without_legs_returns_stake() {
+        assert_eq!(calculate_parlay_payout(100, &[], 1_000).unwrap(), 100);
+    }
+
     #[test]
     fn referral_cut_is_proportional() {
         // User has 50% of pool; fee total = 100; referral cut = 10% → 5
@@ -208,6 +365,14 @@ mod tests {
         assert_eq!(calculate_referral_amount(500, 1_000, 0, 1_000).unwrap(), 0);
     }
 
+    #[test]
+    fn fee_share_rounds_down() {
+        // 100 fee over 3 equal winners: 33 each, 1 left unrealized.
+        assert_eq!(calculate_fee_share(1, 3, 100).unwrap(), 33);
+        assert_eq!(calculate_fee_share(3, 3, 100).unwrap(), 100);
+        assert_eq!(calculate_fee_share(0, 3, 100).unwrap(), 0);
+    }
+
     #[test]
     fn odds_bps_even_split() {
         // 50/50 → odds = 2.0x = 20_000 bps
@@ -3,15 +3,16 @@
 
 use soroban_sdk::{contractimpl, log, token, Address, Env, String, Symbol, Vec};
 
-use crate::gas_opt;
+use crate::{gas_opt, lmsr};
 use crate::{
-    Config, DataKey, InitialLiquidityProvidedEvent, MarketState, MaxTotalStakeIncreasedEvent, Pool,
-    PoolCanceledEvent, PoolConfig, PoolCreatedEvent, PoolDescriptionUpdatedEvent,
-    PoolDisputedEvent, PoolReadyForResolutionEvent, PoolResolvedDiagEvent, PoolResolvedEvent,
-    PoolStats, PredifiContract, PredifiContractArgs, PredifiContractClient, PredifiError,
-    ResolutionConflictEvent, ResolutionVoteCastEvent, StakeLimitsUpdatedEvent, StakingClosedEvent,
-    CANCELATION_DELAY, DEFAULT_MIN_POOL_DURATION, EMERGENCY_CANCEL_MULTISIG_THRESHOLD,
-    INITIAL_LIQUIDITY_SAFETY_MARGIN_BPS, MAX_INITIAL_LIQUIDITY, MAX_OPTIONS_COUNT,
+    AmmState, Config, DataKey, ExitFeeUpdatedEvent, InitialLiquidityProvidedEvent, MarketState,
+    MaxTotalStakeIncreasedEvent, Pool, PoolCanceledEvent, PoolConfig, PoolCreatedEvent,
+    PoolDescriptionUpdatedEvent, PoolKey, PoolReadyForResolutionEvent, PoolResolvedDiagEvent,
+    PoolResolvedEvent, PoolStats, PredifiContract, PredifiContractArgs, PredifiContractClient,
+    PredifiError, PricingMode, ResolutionConflictEvent, ResolutionVoteCastEvent, SensitiveAction,
+    SensitiveActionKind, StakeLimitsUpdatedEvent, StakingClosedEvent, CANCELATION_DELAY,
+    DEFAULT_EXIT_FEE_BPS, DEFAULT_MIN_POOL_DURATION, INITIAL_LIQUIDITY_SAFETY_MARGIN_BPS,
+    MAX_EXIT_FEE_BPS, MAX_INITIAL_LIQUIDITY, MAX_OPTIMISTIC_LIVENESS, MAX_OPTIONS_COUNT,
     MAX_POOL_DURATION, UNRESOLVED_OUTCOME,
 };
 
@@ -44,6 +45,10 @@ impl PredifiContract {
     ///   - `private` - If true, only whitelisted addresses can participate
     ///   - `whitelist_key` - Optional symbol for private pool access
     ///   - `outcome_descriptions` - Human-readable labels for each outcome (length must equal options_count)
+    ///   - `optimistic_liveness` - Liveness window for optimistic resolution (0 = operator vote only)
+    ///   - `optimistic_bond` - Bond required to propose or challenge an outcome (> 0 if optimistic)
+    ///   - `pricing_mode` - `Parimutuel`, or `Lmsr` for share trading against a market maker
+    ///   - `creator_fee_bps` - Creator's cut of the protocol fee in bps of total stake (0 = none)
     ///
     /// # Return Value
     ///
@@ -82,7 +87,9 @@ impl PredifiContract {
     /// - `TokenNotWhitelisted` - The specified token is not on the allowed betting whitelist
     /// - `InvalidTimestamp` - `end_time` is not in the future, exceeds MAX_POOL_DURATION, or `end_time <= start_time`
     /// - `DeadlineInPast` - `end_time` or `start_time` is in the past (issue #1130)
-    /// - `InvalidData` - `options_count` < 2 or > MAX_OPTIONS_COUNT
+    /// - `InvalidData` - `options_count` < 2 or > MAX_OPTIONS_COUNT, invalid optimistic resolution parameters,
+    ///   an `Lmsr` pool without `initial_liquidity`, or an `Lmsr` pool with a creator fee
+    /// - `InvalidFeeBps` - `creator_fee_bps` exceeds `get_max_creator_fee_bps`
     /// - `MetadataUrlInvalid` - `metadata_url` exceeds 512 bytes
     /// - `InvalidTargetPrice` - Invalid target price (for price-based pools)
     /// - `InitialLiquidityBelowSafetyMargin` - Initial liquidity is insufficient relative to `max_total_stake` (issue #1131)
@@ -127,6 +134,9 @@ impl PredifiContract {
     /// - `required_resolutions` must be >= 1
     /// - `required_resolutions` must not exceed the number of active operators
     ///   (unless operator_count is 0, in which case oracle resolution is allowed)
+    /// - `optimistic_liveness` must not exceed MAX_OPTIMISTIC_LIVENESS (3 days)
+    /// - `optimistic_bond` must be >= 0, and > 0 when `optimistic_liveness` is set
+    /// - `Lmsr` pools require `initial_liquidity > 0` to fund the market maker
     ///
     /// **Category Validation:**
     /// - Category must be one of the canonical category symbols (e.g., CATEGORY_SPORTS)
@@ -153,6 +163,13 @@ impl PredifiContract {
     /// - Initial liquidity is part of `pool.total_stake` but typically excluded from fee calculations
     /// - The transfer occurs after all validation to avoid wasting gas on invalid pools
     ///
+    /// # Creation Bond
+    ///
+    /// If an admin has set a creation bond for `token` ([`Self::set_creation_bond`]), that
+    /// amount is also transferred from the creator and locked under
+    /// `PoolKey::CreationBond(pool_id)`. It is returned by [`Self::reclaim_creation_bond`] once
+    /// the pool resolves or is canceled, or slashed to the treasury by [`Self::reject_pool`].
+    ///
     /// # Category Indexing
     ///
     /// Pools are indexed by category for efficient querying:
@@ -164,7 +181,8 @@ impl PredifiContract {
     ///
     /// - `end_time > current_time` (INV-8)
     /// - Token must be whitelisted
-    /// - Creator must have sufficient balance for `initial_liquidity` (if provided)
+    /// - Creator must have sufficient balance for `initial_liquidity` (if provided) and the
+    ///   token's creation bond
     /// - Access control contract must be deployed and have sufficient operators (if required_resolutions > 0)
     ///
     /// # Post-conditions
@@ -176,6 +194,7 @@ impl PredifiContract {
     /// - Category indexes are updated
     /// - `PoolCreatedEvent` is emitted
     /// - `InitialLiquidityProvidedEvent` is emitted (if liquidity provided)
+    /// - `CreationBondLockedEvent` is emitted (if the token has a creation bond)
     ///
     /// # Usage Examples
     ///
@@ -204,6 +223,9 @@ impl PredifiContract {
     ///         String::from_str(&env, "No"),
     ///         String::from_str(&env, "Yes")
     ///     ],
+    ///     optimistic_liveness: 0, // Operator vote only
+    ///     optimistic_bond: 0,
+    ///     pricing_mode: PricingMode::Parimutuel,
     /// };
     ///
     /// let pool_id = contract.create_pool(
@@ -415,6 +437,23 @@ impl PredifiContract {
         );
         assert!(config.max_total_stake >= 0, "max_total_stake must be >= 0");
 
+        // Validate optimistic resolution: a non-zero liveness window needs a
+        // positive bond, otherwise proposals and challenges would be free.
+        if config.optimistic_bond < 0
+            || config.optimistic_liveness > MAX_OPTIMISTIC_LIVENESS
+            || (config.optimistic_liveness > 0 && config.optimistic_bond == 0)
+        {
+            return Err(PredifiError::InvalidData);
+        }
+        if config.pricing_mode == PricingMode::Lmsr
+            && (config.initial_liquidity <= 0 || config.creator_fee_bps > 0)
+        {
+            return Err(PredifiError::InvalidData);
+        }
+        if config.creator_fee_bps > Self::get_max_creator_fee_bps(env.clone()) {
+            return Err(PredifiError::InvalidFeeBps);
+        }
+
         if let Some(ref whitelist_key) = config.whitelist_key {
             if let Err(e) = Self::validate_referral_code(&env, whitelist_key) {
                 soroban_sdk::panic_with_error!(&env, e);
@@ -454,6 +493,9 @@ impl PredifiContract {
             fee_bps: 0, // Will be set at resolution
             participants_count: 0,
             resolution_timestamp: None, // Set when pool is resolved
+            optimistic_liveness: config.optimistic_liveness,
+            optimistic_bond: config.optimistic_bond,
+            pricing_mode: config.pricing_mode,
         };
 
         Self::validate_pool_invariants(&pool);
@@ -462,12 +504,36 @@ impl PredifiContract {
         env.storage().persistent().set(&pool_key, &pool);
         Self::bump_ttl(&env, &pool_key);
 
+        if config.creator_fee_bps > 0 {
+            let creator_fee_key = PoolKey::CreatorFeeBps(pool_id);
+            env.storage()
+                .persistent()
+                .set(&creator_fee_key, &config.creator_fee_bps);
+            Self::extend_persistent(&env, &creator_fee_key);
+        }
+
         // Initialize optimized batch storage with zeros to avoid expensive fallback reads
         let initial_stakes = gas_opt::alloc_zero_stakes(&env, options_count);
         let stakes_key = DataKey::OutStakes(pool_id);
         env.storage().persistent().set(&stakes_key, &initial_stakes);
         Self::extend_persistent(&env, &stakes_key);
 
+        // LMSR pools start with no outstanding shares; `b` is sized so the
+        // creator's liquidity covers the market maker's worst-case loss.
+        if config.pricing_mode == PricingMode::Lmsr {
+            let liquidity = lmsr::liquidity_from_subsidy(config.initial_liquidity, options_count)
+                .map_err(|_| PredifiError::InvalidData)?;
+            let amm_key = DataKey::AmmState(pool_id);
+            env.storage().persistent().set(
+                &amm_key,
+                &AmmState {
+                    liquidity,
+                    shares: initial_stakes,
+                },
+            );
+            Self::extend_persistent(&env, &amm_key);
+        }
+
         // Transfer initial liquidity from creator to contract if provided
         if config.initial_liquidity > 0 {
             let token_client = token::Client::new(&env, &token);
@@ -478,6 +544,9 @@ impl PredifiContract {
             );
         }
 
+        // Lock the anti-spam creation bond configured for the token, if any
+        Self::lock_creation_bond(&env, pool_id, &creator, &token)?;
+
         // Update category index
         let category_count_key = DataKey::CatPoolCt(category.clone());
         let category_count: u32 = env
@@ -651,8 +720,6 @@ impl PredifiContract {
         // Lock the description once any participant has joined — equivalent to
         // "pool has started" in this contract's model (no separate start_time).
         // We read the pool's participants_count field which is the authoritative participant counter.
-        let pool_key = DataKey::Pool(pool_id);
-        let pool: Pool = env.storage().persistent().get(&pool_key).expect("Pool not found");
         if pool.participants_count > 0 {
             return Err(PredifiError::InvalidPoolState);
         }
@@ -693,7 +760,8 @@ impl PredifiContract {
     ///    Resolved, and Cancelled pools are rejected with [`PredifiError::InvalidPoolState`].
     /// 3. **Resolution delay** — `current_ledger_time >= pool.end_time + config.resolution_delay`
     ///    must hold.  This cooling-off period allows late price feeds to settle before
-    ///    any outcome is locked in.  Violations return [`PredifiError::ResolutionDelayNotMet`].
+    ///    any outcome is locked in.  Commit-reveal pools also wait for
+    ///    `end_time + reveal_window`.  Violations return [`PredifiError::ResolutionDelayNotMet`].
     /// 4. **Outcome validation** — `outcome` must satisfy
     ///    `0 <= outcome < pool.options_count` and must not equal `UNRESOLVED_OUTCOME`
     ///    (the sentinel value reserved for pools that have not yet been decided).
@@ -797,7 +865,8 @@ impl PredifiContract {
 
         let current_time = env.ledger().timestamp();
         let config = Self::get_config(&env);
-        let eligible_at = pool.end_time.saturating_add(config.resolution_delay);
+        let eligible_at =
+            Self::resolution_eligible_at(&env, pool_id, &pool, config.resolution_delay);
 
         if current_time < eligible_at {
             log!(
@@ -812,6 +881,10 @@ impl PredifiContract {
             return Err(PredifiError::ResolutionDelayNotMet);
         }
 
+        // Optimistic pools: operators only vote once a proposal has been
+        // challenged (or if nobody proposed at all).
+        Self::require_no_unchallenged_proposal(&env, pool_id)?;
+
         // Validate: outcome must be within the valid options range
         if outcome >= pool.options_count {
             log!(
@@ -920,6 +993,9 @@ impl PredifiContract {
             // Remove from global active index now that the pool is resolved.
             Self::remove_from_active_index(&env, pool_id);
             Self::bump_ttl(&env, &pool_key);
+            Self::enter_reentrancy_guard(&env);
+            Self::settle_outcome_proposal(&env, pool_id, &pool)?;
+            Self::exit_reentrancy_guard(&env);
 
             // Retrieve winning-outcome stake for the diagnostic event efficiently
             let winning_stake = Self::get_outcome_stake(env.clone(), pool_id, outcome);
@@ -962,7 +1038,9 @@ impl PredifiContract {
         let config = Self::get_config(&env);
         let current_time = env.ledger().timestamp();
 
-        if current_time >= pool.end_time.saturating_add(config.resolution_delay) {
+        if current_time
+            >= Self::resolution_eligible_at(&env, pool_id, &pool, config.resolution_delay)
+        {
             PoolReadyForResolutionEvent {
                 pool_id,
                 timestamp: current_time,
@@ -1054,9 +1132,10 @@ impl PredifiContract {
     ///
     /// # Errors
     /// - `Unauthorized` if caller is not admin/operator and not the pool creator, or if creator
-    ///   attempts to cancel a pool that already has bets beyond initial liquidity.
+    ///   attempts to cancel a pool that already has bets or commitments beyond initial liquidity.
     /// - `PoolNotResolved` error (code 22) is returned if trying to cancel an already resolved pool.
-    /// PRE: pool.state = Active, caller has role 0/1 OR (caller == pool.creator AND total_stake <= initial_liquidity)
+    /// PRE: pool.state = Active, caller has role 0/1 OR
+    ///      (caller == pool.creator AND total_stake + committed stake <= initial_liquidity)
     /// POST: pool.state = Canceled, state transition valid (INV-2)
     pub fn cancel_pool(
         env: Env,
@@ -1091,12 +1170,14 @@ impl PredifiContract {
                 // Allow any user to cancel overdue pools
                 // This is a failsafe to unlock funds when resolution is delayed
             } else {
-                // Allow creator to cancel only if no bets have been placed beyond initial liquidity
+                // Allow creator to cancel only if no bets have been placed beyond
+                // initial liquidity, hidden commitments included
                 if operator != pool.creator {
                     Self::exit_reentrancy_guard(&env);
                     return Err(PredifiError::Unauthorized);
                 }
-                if pool.total_stake > pool.initial_liquidity {
+                let committed = Self::get_committed_stake(env.clone(), pool_id);
+                if pool.total_stake.saturating_add(committed) > pool.initial_liquidity {
                     Self::exit_reentrancy_guard(&env);
                     return Err(PredifiError::Unauthorized);
                 }
@@ -1114,6 +1195,8 @@ impl PredifiContract {
         env.storage().persistent().set(&pool_key, &pool);
         Self::bump_ttl(&env, &pool_key);
         Self::remove_from_active_index(&env, pool_id);
+        Self::settle_outcome_proposal(&env, pool_id, &pool)?;
+        Self::credit_retained_exit_fees(&env, pool_id, &pool)?;
 
         PoolCanceledEvent {
             pool_id,
@@ -1134,9 +1217,10 @@ impl PredifiContract {
     /// but for *emergency* cancellations of pools that already hold
     /// non-trivial stake we want sign-off from more than one privileged
     /// address. Each call by a distinct admin/operator address records an
-    /// approval; once `EMERGENCY_CANCEL_MULTISIG_THRESHOLD` distinct
-    /// approvals are collected, the pool is moved to `Canceled` exactly
-    /// like the single-signer path.
+    /// approval in the approval queue; once the
+    /// `SensitiveActionKind::EmergencyCancelPool` policy threshold
+    /// (`EMERGENCY_CANCEL_MULTISIG_THRESHOLD` by default) is met, the pool is
+    /// moved to `Canceled` exactly like the single-signer path.
     ///
     /// # Caller
     /// Any address with role 0 (admin) or 1 (operator).
@@ -1158,9 +1242,7 @@ impl PredifiContract {
         approver.require_auth();
 
         // Only admin (0) or operator (1) may participate in emergency cancel.
-        let is_privileged = Self::require_role(&env, &approver, 0).is_ok()
-            || Self::require_role(&env, &approver, 1).is_ok();
-        if !is_privileged {
+        if !Self::can_approve(&env, &approver, SensitiveActionKind::EmergencyCancelPool) {
             return Err(PredifiError::Unauthorized);
         }
 
@@ -1181,34 +1263,22 @@ impl PredifiContract {
             return Err(PredifiError::InvalidPoolState);
         }
 
-        // Load existing approvers (or start fresh).
-        let approvers_key = DataKey::EmergencyCancelApprovers(pool_id);
-        let mut approvers: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&approvers_key)
-            .unwrap_or_else(|| Vec::new(&env));
-
-        // Reject duplicate approval from the same address.
-        for a in approvers.iter() {
-            if a == approver {
+        let action = SensitiveAction::EmergencyCancelPool(pool_id);
+        let pending = match Self::record_approval(&env, &approver, &action) {
+            Ok(pending) => pending,
+            Err(e) => {
                 Self::exit_reentrancy_guard(&env);
-                return Err(PredifiError::EmergencyCancelAlreadyApproved);
+                return Err(e);
             }
-        }
-
-        approvers.push_back(approver.clone());
-        env.storage().persistent().set(&approvers_key, &approvers);
+        };
 
-        // Capture the first reason; subsequent approvers reaffirm by
+        // Capture the proposer's reason; subsequent approvers reaffirm by
         // approving, but the recorded reason is the proposer's.
         let reason_key = DataKey::EmergencyCancelReason(pool_id);
-        if !env.storage().persistent().has(&reason_key) {
-            env.storage().persistent().set(&reason_key, &reason);
-        }
-
-        // Below threshold → still pending; surface state but do not cancel.
-        if approvers.len() < EMERGENCY_CANCEL_MULTISIG_THRESHOLD {
+        if let Some(request) = pending {
+            if request.approvals.len() == 1 {
+                env.storage().persistent().set(&reason_key, &reason);
+            }
             Self::exit_reentrancy_guard(&env);
             return Ok(());
         }
@@ -1224,6 +1294,8 @@ impl PredifiContract {
         env.storage().persistent().set(&pool_key, &pool);
         Self::bump_ttl(&env, &pool_key);
         Self::remove_from_active_index(&env, pool_id);
+        Self::settle_outcome_proposal(&env, pool_id, &pool)?;
+        Self::credit_retained_exit_fees(&env, pool_id, &pool)?;
 
         PoolCanceledEvent {
             pool_id,
@@ -1233,8 +1305,6 @@ impl PredifiContract {
         }
         .publish(&env);
 
-        // Cleanup pending multisig state once the cancel has executed.
-        env.storage().persistent().remove(&approvers_key);
         env.storage().persistent().remove(&reason_key);
 
         Self::exit_reentrancy_guard(&env);
@@ -1242,13 +1312,12 @@ impl PredifiContract {
     }
 
     /// Read-only view of pending emergency-cancel approvers for `pool_id`.
-    /// Returns an empty vec when no proposal is currently pending or once
-    /// the threshold has been met and the pool has been cancelled (the
-    /// approvers set is cleared on execution).
+    /// Returns an empty vec when no proposal is currently pending, once it
+    /// has gone stale, or once the threshold has been met and the pool has
+    /// been cancelled.
     pub fn get_emergency_cancel_approvals(env: Env, pool_id: u64) -> Vec<Address> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::EmergencyCancelApprovers(pool_id))
+        Self::get_approval_request(env.clone(), SensitiveAction::EmergencyCancelPool(pool_id))
+            .map(|request| request.approvals)
             .unwrap_or_else(|| Vec::new(&env))
     }
 
@@ -1297,6 +1366,57 @@ impl PredifiContract {
         Ok(())
     }
 
+    /// Set the exit fee `withdraw_prediction` charges on an active pool.
+    /// Caller must have Operator role (1).
+    ///
+    /// # Errors
+    /// - `InvalidPoolState` if the pool is not `Active`.
+    /// - `InvalidFeeBps` if `exit_fee_bps` exceeds `MAX_EXIT_FEE_BPS`.
+    pub fn set_exit_fee_bps(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        exit_fee_bps: u32,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        operator.require_auth();
+        Self::require_role(&env, &operator, 1)?;
+
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.state != MarketState::Active {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if exit_fee_bps > MAX_EXIT_FEE_BPS {
+            return Err(PredifiError::InvalidFeeBps);
+        }
+
+        let key = PoolKey::ExitFeeBps(pool_id);
+        env.storage().persistent().set(&key, &exit_fee_bps);
+        Self::extend_persistent(&env, &key);
+
+        ExitFeeUpdatedEvent {
+            pool_id,
+            operator,
+            exit_fee_bps,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Return the exit fee of `pool_id` in basis points, or
+    /// `DEFAULT_EXIT_FEE_BPS` if none was set.
+    pub fn get_exit_fee_bps(env: Env, pool_id: u64) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&PoolKey::ExitFeeBps(pool_id))
+            .unwrap_or(DEFAULT_EXIT_FEE_BPS)
+    }
+
     /// This function is optimized for markets with many outcomes (e.g., 32+ teams).
     /// Instead of making N storage reads (one per outcome), it makes a single read.
     ///
@@ -1342,6 +1462,10 @@ impl PredifiContract {
             private: pool.private,
             whitelist_key: pool.whitelist_key,
             outcome_descriptions: pool.outcome_descriptions,
+            optimistic_liveness: pool.optimistic_liveness,
+            optimistic_bond: pool.optimistic_bond,
+            pricing_mode: pool.pricing_mode,
+            creator_fee_bps: Self::get_creator_fee_bps(env.clone(), pool_id),
         }
     }
 
@@ -1530,55 +1654,8 @@ impl PredifiContract {
             stakes_per_outcome: stakes,
             participants_count: pool.participants_count,
             current_odds,
+            creator_fee_bps: Self::get_creator_fee_bps(env.clone(), pool_id),
+            creator_fee: Self::creator_fee_owed(&env, pool_id, &pool).unwrap_or(0),
         }
     }
-
-    /// Flag a pool as disputed. Only callable by a Moderator (role 2).
-    ///
-    /// Transitions the pool state from `Active` to `Disputed`, preventing
-    /// further participation or resolution until the dispute is handled.
-    ///
-    /// # Errors
-    /// - `Unauthorized` – caller does not hold the Moderator role.
-    /// - `InvalidPoolState` – pool is not currently `Active`.
-    pub fn flag_disputed_pool(
-        env: Env,
-        moderator: Address,
-        pool_id: u64,
-        reason: String,
-    ) -> Result<(), PredifiError> {
-        Self::require_not_paused(&env)?;
-        moderator.require_auth();
-        Self::require_role(&env, &moderator, 2)?;
-
-        let pool_key = DataKey::Pool(pool_id);
-        let mut pool: Pool = env
-            .storage()
-            .persistent()
-            .get(&pool_key)
-            .expect("Pool not found");
-        Self::extend_persistent(&env, &pool_key);
-
-        if pool.state != MarketState::Active {
-            return Err(PredifiError::InvalidPoolState);
-        }
-
-        pool.state = MarketState::Disputed;
-        env.storage().persistent().set(&pool_key, &pool);
-        Self::extend_persistent(&env, &pool_key);
-
-        env.storage()
-            .persistent()
-            .set(&DataKey::Disputed(pool_id), &());
-        Self::extend_persistent(&env, &DataKey::Disputed(pool_id));
-
-        PoolDisputedEvent {
-            pool_id,
-            moderator,
-            reason,
-        }
-        .publish(&env);
-
-        Ok(())
-    }
 }
@@ -0,0 +1,144 @@
+//! One user staking on several outcomes of a pool: positions, per-outcome
+//! payouts and refunds, and withdrawing stakes one outcome at a time.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{PoolConfig, Prediction, DEFAULT_PREDICTION_COOLDOWN_SECONDS};
+use soroban_sdk::{testutils::Ledger, vec, Address, Env, String, Vec};
+
+const END_TIME: u64 = 10_000;
+
+/// Place a prediction, then wait out the cooldown so `user` can stake again.
+fn stake(ctx: &TestContext, pool_id: u64, user: &Address, amount: i128, outcome: u32) {
+    ctx.client
+        .place_prediction(user, &pool_id, &amount, &outcome, &None, &None);
+    ctx.client
+        .env
+        .ledger()
+        .with_mut(|li| li.timestamp += DEFAULT_PREDICTION_COOLDOWN_SECONDS);
+}
+
+/// A Home/Away pool where `hedger` holds 300 on Home and 100 on Away and
+/// `backer` holds 600 on Away, each from 1_000. Returns
+/// `(pool_id, hedger, backer)`.
+fn hedged_pool(ctx: &TestContext) -> (u64, Address, Address) {
+    let env = &ctx.client.env;
+    let config = PoolConfig {
+        description: String::from_str(env, "Match winner"),
+        metadata_url: String::from_str(env, "ipfs://position"),
+        outcome_descriptions: vec![
+            env,
+            String::from_str(env, "Home"),
+            String::from_str(env, "Away"),
+        ],
+        ..default_pool_config(env)
+    };
+    let pool_id = ctx.create_pool(END_TIME, &config);
+
+    let hedger = ctx.funded_user(1_000);
+    let backer = ctx.funded_user(1_000);
+    stake(ctx, pool_id, &hedger, 200, 0);
+    stake(ctx, pool_id, &hedger, 100, 1);
+    stake(ctx, pool_id, &hedger, 100, 0);
+    stake(ctx, pool_id, &backer, 600, 1);
+    (pool_id, hedger, backer)
+}
+
+fn stakes(env: &Env, entries: &[(i128, u32)]) -> Vec<Prediction> {
+    let mut position = Vec::new(env);
+    for &(amount, outcome) in entries {
+        position.push_back(Prediction { amount, outcome });
+    }
+    position
+}
+
+/// Stakes on each outcome accumulate separately, and the pool counts the
+/// hedger once.
+#[test]
+fn test_position_tracks_each_outcome() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, hedger, _backer) = hedged_pool(&ctx);
+
+    assert_eq!(
+        ctx.client.get_user_position(&pool_id, &hedger),
+        stakes(&env, &[(300, 0), (100, 1)])
+    );
+    assert_eq!(ctx.client.get_outcome_stake(&pool_id, &0u32), 300);
+    assert_eq!(ctx.client.get_outcome_stake(&pool_id, &1u32), 700);
+
+    let pool = ctx.client.get_pool(&pool_id);
+    assert_eq!(pool.participants_count, 2);
+    assert_eq!(pool.total_stake, 1_000);
+
+    let details = ctx.client.get_user_predictions(&hedger, &0, &10);
+    assert_eq!(details.len(), 2);
+    assert_eq!(details.get(1).unwrap().user_outcome, 1);
+    assert_eq!(details.get(1).unwrap().amount, 100);
+}
+
+/// Only the stake on the winning outcome is paid; the losing stake is lost.
+#[test]
+fn test_hedged_position_claims_winning_stake_only() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, hedger, backer) = hedged_pool(&ctx);
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &1u32);
+
+    // 100 and 600 of the 700 staked on Away share the 1_000 pool.
+    assert_eq!(ctx.client.claim_winnings(&hedger, &pool_id), 142);
+    assert_eq!(ctx.client.claim_winnings(&backer, &pool_id), 857);
+    assert_eq!(ctx.token.balance(&hedger), 600 + 142);
+}
+
+/// A canceled pool refunds the stakes on every outcome.
+#[test]
+fn test_hedged_position_refunds_every_outcome() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, hedger, _backer) = hedged_pool(&ctx);
+
+    ctx.client.cancel_pool(
+        &ctx.operator,
+        &pool_id,
+        &String::from_str(&env, "fixture postponed"),
+    );
+    assert_eq!(ctx.client.claim_refund(&hedger, &pool_id), 400);
+    assert_eq!(ctx.token.balance(&hedger), 1_000);
+}
+
+/// Closing one outcome keeps the user in the pool; closing the last one
+/// removes them.
+#[test]
+fn test_withdrawing_each_outcome_leaves_pool() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, hedger, _backer) = hedged_pool(&ctx);
+    ctx.client.set_exit_fee_bps(&ctx.operator, &pool_id, &0u32);
+
+    ctx.client
+        .withdraw_prediction(&hedger, &pool_id, &0u32, &300);
+    assert_eq!(
+        ctx.client.get_user_position(&pool_id, &hedger),
+        stakes(&env, &[(100, 1)])
+    );
+    assert_eq!(ctx.client.get_pool(&pool_id).participants_count, 2);
+
+    // The stake left over is now the first, and new outcomes follow it.
+    stake(&ctx, pool_id, &hedger, 50, 0);
+    assert_eq!(
+        ctx.client.get_user_position(&pool_id, &hedger),
+        stakes(&env, &[(100, 1), (50, 0)])
+    );
+
+    ctx.client
+        .withdraw_prediction(&hedger, &pool_id, &1u32, &100);
+    ctx.client
+        .withdraw_prediction(&hedger, &pool_id, &0u32, &50);
+    assert_eq!(ctx.client.get_user_position(&pool_id, &hedger).len(), 0);
+    assert_eq!(ctx.client.get_pool(&pool_id).participants_count, 1);
+    assert_eq!(ctx.client.get_user_predictions(&hedger, &0, &10).len(), 0);
+}
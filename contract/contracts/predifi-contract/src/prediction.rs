@@ -3,11 +3,12 @@
 use soroban_sdk::{contractimpl, token, Address, Env, String, Symbol, Vec};
 
 use crate::{
-    calculate_claim_payout, calculate_referral_amount, DataKey, HighValuePredictionEvent,
-    MarketState, OutcomeStakesUpdatedEvent, PayoutInput, Pool, Prediction,
-    PredictionBlockedDelistedEvent, PredictionPlacedEvent, PredifiContract, PredifiContractArgs,
-    PredifiContractClient, PredifiError, ReferralPaidEvent, RefundClaimedEvent, RewardClaimedEvent,
-    SuspiciousDoubleClaimEvent, UserPredictionDetail, WinningsClaimedEvent, HIGH_VALUE_THRESHOLD,
+    calculate_protocol_fee, DataKey, HighValuePredictionEvent, Invite, MarketState,
+    OutcomeStakesUpdatedEvent, Pool, Prediction, PredictionBlockedDelistedEvent,
+    PredictionPlacedEvent, PredictionWithdrawnEvent, PredifiContract, PredifiContractArgs,
+    PredifiContractClient, PredifiError, PricingMode, RefundClaimedEvent, RewardClaimedEvent,
+    RoundingMode, SafeMath, SuspiciousDoubleClaimEvent, UserPredictionDetail, WinningsClaimedEvent,
+    HIGH_VALUE_THRESHOLD,
 };
 
 #[contractimpl]
@@ -19,6 +20,9 @@ impl PredifiContract {
     /// evaluated when the pool is resolved. Winners can claim their share of the pool's total
     /// stake minus protocol fees via `claim_winnings`.
     ///
+    /// A user may stake on several outcomes of the same pool, for example to hedge. Each
+    /// outcome's stake is tracked and paid out separately; see `get_user_position`.
+    ///
     /// # Parameters
     ///
     /// - `env` - The Soroban environment, providing access to storage, ledger, and auth
@@ -30,7 +34,8 @@ impl PredifiContract {
     ///   a share of the protocol fee when the user claims winnings. Only stored on the first
     ///   prediction for a given `(user, pool_id)` pair. Cannot be the user or the contract.
     /// - `invite_key` - Optional symbol used to access private pools. Must match the pool's
-    ///   `whitelist_key` if the pool is private and the user is not whitelisted.
+    ///   `whitelist_key` if the pool is private and the user is not whitelisted. Use
+    ///   [`Self::place_prediction_with_invite`] to present a signed invite instead.
     ///
     /// # Prediction Cooldown Mechanism
     ///
@@ -64,8 +69,8 @@ impl PredifiContract {
     /// **User Prediction Count Limit:**
     /// - If `Config::max_predictions_per_user > 0`, a user cannot place predictions on more than
     ///   this number of distinct pools
-    /// - Increasing stake on an existing prediction (same pool, same outcome) does not count
-    ///   toward this limit
+    /// - Adding to a pool the user already holds a position in (on the same or another
+    ///   outcome) does not count toward this limit
     /// - Error: `MaxPredictionsExceeded`
     ///
     /// # Referral Handling
@@ -140,7 +145,9 @@ impl PredifiContract {
     /// - `InsufficientStake` - The amount is below the global protocol minimum (`Config::min_stake`)
     /// - `TokenNotWhitelisted` - The pool's token is not on the allowed betting whitelist
     /// - `PoolNotFound` - The specified `pool_id` does not exist
-    /// - `InvalidPoolState` - The pool is not in `Active` state (e.g., resolved, canceled, or disputed)
+    /// - `InvalidPoolState` - The pool is not in `Active` state (e.g., resolved, canceled, or disputed),
+    ///   it is an `Lmsr` pool (use `buy_shares`), or it is a commit-reveal pool
+    ///   (use `commit_prediction`)
     /// - `InvalidOutcome` - The outcome index is >= `pool.options_count`
     /// - `StakeBelowMinimum` - The amount is below the pool's `min_stake`
     /// - `StakeAboveMaximum` - The amount exceeds the pool's `max_stake` (if > 0)
@@ -158,7 +165,8 @@ impl PredifiContract {
     /// - `amount >= Config::min_stake` (global minimum)
     /// - `outcome < pool.options_count`
     /// - Pool's token must be whitelisted
-    /// - For private pools: user must be whitelisted, be the creator, or provide valid `invite_key`
+    /// - For private pools: user must be whitelisted, be the creator, or provide valid `invite_key`,
+    ///   else `Unauthorized`
     /// - If `prediction_cooldown_seconds > 0`: sufficient time must have elapsed since user's last prediction
     /// - If `max_predictions_per_user > 0`: user must not have exceeded the prediction count limit
     ///
@@ -166,10 +174,14 @@ impl PredifiContract {
     ///
     /// - `pool.total_stake` increases by `amount` (INV-1)
     /// - `OutcomeStake(pool_id, outcome)` increases by `amount` (INV-1)
-    /// - User's prediction record is created or updated with the new stake
+    /// - The user's stake on `outcome` is created or increased; stakes the user
+    ///   holds on other outcomes of the pool are unchanged
     /// - If first prediction for user on this pool: `participants_count` increments
-    /// - If referrer provided on first prediction: referrer is stored and referred volume is tracked
+    /// - If referrer provided on first prediction: referrer is stored, referred volume is tracked,
+    ///   the referral is attributed to the first open campaign covering the pool, and the
+    ///   referrer becomes the user's upline if they have none
     /// - `LastPredictionTime(user)` is updated to current timestamp
+    /// - The user's lifetime volume ([`Self::get_user_fee_tier`]) increases by `amount`
     /// - Tokens are transferred from `user` to the contract
     /// - `PredictionPlacedEvent` is emitted
     ///
@@ -178,7 +190,6 @@ impl PredifiContract {
     /// This function uses a reentrancy guard (`enter_reentrancy_guard` / `exit_reentrancy_guard`)
     /// to prevent reentrant calls. The guard is entered at the start and exited before token
     /// transfer and event emission.
-    #[allow(clippy::needless_borrows_for_generic_args)]
     pub fn place_prediction(
         env: Env,
         user: Address,
@@ -187,6 +198,35 @@ impl PredifiContract {
         outcome: u32,
         referrer: Option<Address>,
         invite_key: Option<Symbol>,
+    ) -> Result<(), PredifiError> {
+        Self::place_prediction_with_invite(
+            env,
+            user,
+            pool_id,
+            amount,
+            outcome,
+            referrer,
+            invite_key.map(Invite::Key),
+        )
+    }
+
+    /// Place a prediction like [`Self::place_prediction`], with access to a private pool
+    /// given by `invite`: either `Invite::Key` with the pool's `whitelist_key`, or
+    /// `Invite::Signed`, an invite signed by the pool's invite signer
+    /// (see [`Self::set_invite_signer`]).
+    ///
+    /// A signed invite is checked for a pool signer, expiry and revocation before its
+    /// signature is verified; failing any of these returns `Unauthorized`. A signature that
+    /// does not verify traps in the host and fails the whole transaction.
+    #[allow(clippy::needless_borrows_for_generic_args)]
+    pub fn place_prediction_with_invite(
+        env: Env,
+        user: Address,
+        pool_id: u64,
+        amount: i128,
+        outcome: u32,
+        referrer: Option<Address>,
+        invite: Option<Invite>,
     ) -> Result<(), PredifiError> {
         Self::require_not_paused(&env)?;
         user.require_auth();
@@ -210,7 +250,7 @@ impl PredifiContract {
             );
         }
 
-        if let Some(ref invite_key) = invite_key {
+        if let Some(Invite::Key(ref invite_key)) = invite {
             if let Err(e) = Self::validate_referral_code(&env, invite_key) {
                 soroban_sdk::panic_with_error!(&env, e);
             }
@@ -230,6 +270,15 @@ impl PredifiContract {
             panic!("Pool is not active");
         }
         assert!(env.ledger().timestamp() < pool.end_time, "Pool has ended");
+        if pool.pricing_mode != PricingMode::Parimutuel {
+            Self::exit_reentrancy_guard(&env);
+            soroban_sdk::panic_with_error!(&env, PredifiError::InvalidPoolState);
+        }
+        // Commit-reveal pools only take stakes through `commit_prediction`.
+        if Self::commit_reveal_config(&env, pool_id).is_some() {
+            Self::exit_reentrancy_guard(&env);
+            soroban_sdk::panic_with_error!(&env, PredifiError::InvalidPoolState);
+        }
 
         // Validate: token must be on the allowed betting whitelist
         if !Self::is_token_whitelisted(&env, &pool.token) {
@@ -244,30 +293,12 @@ impl PredifiContract {
             soroban_sdk::panic_with_error!(&env, PredifiError::TokenNotWhitelisted);
         }
 
-        // Check private pool authorization
         // Check private pool authorization
         if pool.private {
-            let whitelist_key_data = DataKey::Whitelist(pool_id, user.clone());
-            let is_whitelisted = env
-                .storage()
-                .persistent()
-                .get(&whitelist_key_data)
-                .unwrap_or(false);
-
-            let has_valid_invite = if let Some(ref pool_key) = pool.whitelist_key {
-                if let Some(ref prov_key) = invite_key {
-                    pool_key == prov_key
-                } else {
-                    false
-                }
-            } else {
-                false
-            };
-
-            assert!(
-                is_whitelisted || user == pool.creator || has_valid_invite,
-                "User not authorized for private pool"
-            );
+            if let Err(e) = Self::require_private_access(&env, pool_id, &pool, &user, &invite) {
+                Self::exit_reentrancy_guard(&env);
+                return Err(e);
+            }
         }
 
         // Validate: outcome must be within the valid options range
@@ -316,88 +347,37 @@ impl PredifiContract {
             }
         }
 
-        if config.max_predictions_per_user > 0 {
-            let pred_key = DataKey::Pred(user.clone(), pool_id);
-            let existing_pred = env.storage().persistent().get::<_, Prediction>(&pred_key);
-
-            // If user already has a prediction on this pool, allow increasing stake (same prediction)
-            // If this is a new prediction for this pool, check if user has reached the limit
-            if existing_pred.is_none() {
-                // Count current number of pools this user has predictions in
-                let user_prediction_count_key = DataKey::UsrPrdCnt(user.clone());
-                let current_count: u32 = env
-                    .storage()
-                    .persistent()
-                    .get(&user_prediction_count_key)
-                    .unwrap_or(0);
-
-                if current_count >= config.max_predictions_per_user {
-                    Self::exit_reentrancy_guard(&env);
-                    soroban_sdk::panic_with_error!(&env, PredifiError::MaxPredictionsExceeded);
-                }
-            }
-            // Note: If user already has a prediction on this pool, we allow increasing the stake
-            // as it's the same prediction, not a new pool participation
-        }
-
-        let pred_key = DataKey::Pred(user.clone(), pool_id);
-        let existing_pred = env.storage().persistent().get::<_, Prediction>(&pred_key);
-        if let Some(mut existing_pred) = existing_pred {
-            assert!(
-                existing_pred.outcome == outcome,
-                "Cannot change prediction outcome"
-            );
-            existing_pred.amount = existing_pred.amount.checked_add(amount).expect("overflow");
-            env.storage().persistent().set(&pred_key, &existing_pred);
-            Self::extend_persistent(&env, &pred_key);
-
-            // Track referred volume: if this user already has a referrer, add to their volume
-            let referrer_key = DataKey::Referrer(user.clone(), pool_id);
-            if let Some(referrer_addr) = env.storage().persistent().get::<_, Address>(&referrer_key)
-            {
-                Self::extend_persistent(&env, &referrer_key);
-                let vol_key = DataKey::ReferredVolume(referrer_addr.clone(), pool_id);
-                let vol: i128 = env.storage().persistent().get(&vol_key).unwrap_or(0);
-                env.storage().persistent().set(&vol_key, &(vol + amount));
-                Self::extend_persistent(&env, &vol_key);
-            }
-        } else {
-            env.storage()
+        // A user may stake on several outcomes of one pool; each is tracked
+        // and paid out separately. Only entering a new pool counts towards
+        // `max_predictions_per_user`.
+        let is_new_participant = Self::load_position(&env, &user, pool_id).is_empty();
+        if config.max_predictions_per_user > 0 && is_new_participant {
+            // Count current number of pools this user has predictions in
+            let user_prediction_count_key = DataKey::UsrPrdCnt(user.clone());
+            let current_count: u32 = env
+                .storage()
                 .persistent()
-                .set(&pred_key, &Prediction { amount, outcome });
-            Self::extend_persistent(&env, &pred_key);
-
-            // Store referrer on first prediction and track referred volume.
-            // NOTE: Only one referrer per (user, pool) is supported today.
-            // See DataKey::Referrer for a note on extending this to multiple referrers.
-            if let Some(ref referrer_addr) = referrer {
-                let referrer_key = DataKey::Referrer(user.clone(), pool_id);
-                env.storage().persistent().set(&referrer_key, referrer_addr);
-                Self::extend_persistent(&env, &referrer_key);
-                let vol_key = DataKey::ReferredVolume(referrer_addr.clone(), pool_id);
-                let vol: i128 = env.storage().persistent().get(&vol_key).unwrap_or(0);
-                env.storage().persistent().set(&vol_key, &(vol + amount));
-                Self::extend_persistent(&env, &vol_key);
-            }
-
-            // Increment participants_count in the pool struct
-            pool.participants_count = pool.participants_count.saturating_add(1);
-
-            let count_key = DataKey::UsrPrdCnt(user.clone());
-            let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
-
-            let index_key = DataKey::UsrPrdIdx(user.clone(), count);
-            env.storage().persistent().set(&index_key, &pool_id);
-            Self::extend_persistent(&env, &index_key);
+                .get(&user_prediction_count_key)
+                .unwrap_or(0);
 
-            env.storage().persistent().set(&count_key, &(count + 1));
-            Self::extend_persistent(&env, &count_key);
+            if current_count >= config.max_predictions_per_user {
+                Self::exit_reentrancy_guard(&env);
+                soroban_sdk::panic_with_error!(&env, PredifiError::MaxPredictionsExceeded);
+            }
         }
 
-        // Update total stake (INV-1)
-        pool.total_stake = pool.total_stake.checked_add(amount).expect("overflow");
-        env.storage().persistent().set(&pool_key, &pool);
-        Self::bump_ttl(&env, &pool_key);
+        if let Err(e) = Self::record_stake(
+            &env,
+            pool_id,
+            &mut pool,
+            &user,
+            outcome,
+            amount,
+            referrer.as_ref(),
+        ) {
+            Self::exit_reentrancy_guard(&env);
+            return Err(e);
+        }
 
         let last_prediction_key = DataKey::LastPredictionTime(user.clone());
         env.storage()
@@ -405,10 +385,6 @@ impl PredifiContract {
             .set(&last_prediction_key, &env.ledger().timestamp());
         Self::extend_persistent(&env, &last_prediction_key);
 
-        // Update outcome stake (INV-1) - using optimized batch storage
-        let _stakes =
-            Self::update_outcome_stake(&env, pool_id, outcome, amount, pool.options_count);
-
         // --- INTERACTIONS ---
 
         // Validate token transfer safety before executing
@@ -460,6 +436,115 @@ impl PredifiContract {
         Ok(())
     }
 
+    /// Withdraw `amount` of `user`'s stake on `outcome` from a pool that is
+    /// still open, less the pool's exit fee ([`Self::get_exit_fee_bps`]).
+    /// Returns the amount refunded.
+    ///
+    /// The exit fee is not refunded: it stays in `pool.total_stake` as house
+    /// money for the remaining participants, while the user's outcome stake
+    /// drops by the full `amount` (INV-1). If the pool is later canceled, the
+    /// retained fees are credited to the accrued fees instead. A user who withdraws every stake
+    /// they hold in the pool leaves its participants and their prediction
+    /// index.
+    /// Volume credited to the user's referrer, and the user's own lifetime
+    /// volume, shrink by `amount`.
+    ///
+    /// # Errors
+    /// - `PoolNotFound` – `pool_id` does not exist.
+    /// - `InvalidPoolState` – pool is not an `Active` parimutuel pool.
+    /// - `TimeConstraintError` – the pool's `end_time` has passed.
+    /// - `InvalidAmount` – `amount` is not positive.
+    /// - `InsufficientBalance` – `user` holds less than `amount` on `outcome`.
+    pub fn withdraw_prediction(
+        env: Env,
+        user: Address,
+        pool_id: u64,
+        outcome: u32,
+        amount: i128,
+    ) -> Result<i128, PredifiError> {
+        Self::require_not_paused(&env)?;
+        user.require_auth();
+        if amount <= 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .ok_or(PredifiError::PoolNotFound)?;
+        if !Self::is_pool_active(&pool) || pool.pricing_mode != PricingMode::Parimutuel {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if env.ledger().timestamp() >= pool.end_time {
+            return Err(PredifiError::TimeConstraintError);
+        }
+
+        let mut position = Self::load_position(&env, &user, pool_id);
+        Self::take_stake(&mut position, outcome, amount)?;
+
+        let exit_fee_bps = Self::get_exit_fee_bps(env.clone(), pool_id);
+        let fee = calculate_protocol_fee(amount, exit_fee_bps as i128)
+            .map_err(|_| PredifiError::ArithmeticError)?;
+        let refund = amount - fee;
+
+        Self::store_position(&env, &user, pool_id, &position);
+        if position.is_empty() {
+            Self::remove_from_user_index(&env, &user, pool_id);
+            pool.participants_count = pool.participants_count.saturating_sub(1);
+        }
+        Self::record_user_volume(&env, &user, -amount)?;
+
+        let referrer_key = DataKey::Referrer(user.clone(), pool_id);
+        if let Some(referrer) = env.storage().persistent().get::<_, Address>(&referrer_key) {
+            let vol_key = DataKey::ReferredVolume(referrer, pool_id);
+            let vol: i128 = env.storage().persistent().get(&vol_key).unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&vol_key, &(vol - amount).max(0));
+            Self::extend_persistent(&env, &vol_key);
+        }
+
+        // Only the refund leaves the pool (INV-1).
+        pool.total_stake = pool
+            .total_stake
+            .checked_sub(refund)
+            .ok_or(PredifiError::ArithmeticError)?;
+        env.storage().persistent().set(&pool_key, &pool);
+        Self::bump_ttl(&env, &pool_key);
+        Self::update_outcome_stake(&env, pool_id, outcome, -amount, pool.options_count);
+
+        if refund > 0 {
+            Self::validate_token_transfer(
+                &env,
+                &pool.token,
+                &env.current_contract_address(),
+                &user,
+                refund,
+            )?;
+            Self::enter_reentrancy_guard(&env);
+            token::Client::new(&env, &pool.token).transfer(
+                &env.current_contract_address(),
+                &user,
+                &refund,
+            );
+            Self::exit_reentrancy_guard(&env);
+        }
+
+        PredictionWithdrawnEvent {
+            pool_id,
+            user,
+            outcome,
+            amount,
+            fee,
+            refund,
+        }
+        .publish(&env);
+
+        Ok(refund)
+    }
+
     /// Claim winnings from a resolved pool. Returns the amount paid out (0 for losers).
     /// PRE: pool.state ≠ Active
     /// POST: HasClaimed(user, pool) = true (INV-3), payout ≤ pool.total_stake (INV-4)
@@ -483,6 +568,10 @@ impl PredifiContract {
                 return Err(PredifiError::PoolNotResolved);
             }
 
+            // Disputed pools and pools still inside their challenge window
+            // must not pay out — the outcome may yet change.
+            Self::require_challenge_window_elapsed(env, pool_id, &pool)?;
+
             let claimed_key = DataKey::Claimed(user.clone(), pool_id);
             if env.storage().persistent().has(&claimed_key) {
                 SuspiciousDoubleClaimEvent {
@@ -494,49 +583,44 @@ impl PredifiContract {
                 return Err(PredifiError::AlreadyClaimed);
             }
 
-            let pred_key = DataKey::Pred(user.clone(), pool_id);
-            // Single get — avoid redundant has() storage read on the hot claim path
-            let prediction: Option<Prediction> = env.storage().persistent().get(&pred_key);
-            let prediction = match prediction {
-                Some(p) => {
-                    Self::extend_persistent(env, &pred_key);
-                    p
-                }
-                None => return Ok(0),
-            };
+            let position = Self::load_position(env, user, pool_id);
+            if position.is_empty() {
+                return Ok(0);
+            }
 
             env.storage().persistent().set(&claimed_key, &true);
             Self::bump_ttl(env, &claimed_key);
 
             if pool.state == MarketState::Canceled {
+                let staked = Self::position_total(&position)?;
                 // Validate token transfer before sending refund
                 Self::validate_token_transfer(
                     env,
                     &pool.token,
                     &env.current_contract_address(),
                     user,
-                    prediction.amount,
+                    staked,
                 )?;
 
                 let token_client = token::Client::new(env, &pool.token);
-                token_client.transfer(&env.current_contract_address(), user, &prediction.amount);
+                token_client.transfer(&env.current_contract_address(), user, &staked);
 
                 WinningsClaimedEvent {
                     pool_id,
                     user: user.clone(),
-                    amount: prediction.amount,
+                    amount: staked,
                 }
                 .publish(env);
 
                 RewardClaimedEvent {
                     pool_id,
                     user: user.clone(),
-                    amount: prediction.amount,
+                    amount: staked,
                     claim_type: String::from_str(env, "winnings"),
                 }
                 .publish(env);
 
-                return Ok(prediction.amount);
+                return Ok(staked);
             }
 
             // Check if pool is properly resolved
@@ -557,74 +641,62 @@ impl PredifiContract {
                 }
             }
 
-            if prediction.outcome != pool.outcome {
-                return Ok(0);
+            // Each outcome held is paid out on its own; stakes on losing
+            // outcomes are owed nothing.
+            let mut paid_stake: i128 = 0;
+            let mut winnings: i128 = 0;
+            let mut realized_fee: i128 = 0;
+            let mut protocol_fee_total: i128 = 0;
+            for prediction in position.iter() {
+                let Some((breakdown, fee_share)) =
+                    Self::resolved_claim(env, pool_id, &pool, &prediction)?
+                else {
+                    continue;
+                };
+                paid_stake = paid_stake
+                    .checked_add(prediction.amount)
+                    .ok_or(PredifiError::ArithmeticError)?;
+                winnings = winnings
+                    .checked_add(breakdown.winnings)
+                    .ok_or(PredifiError::ArithmeticError)?;
+                realized_fee = realized_fee
+                    .checked_add(fee_share)
+                    .ok_or(PredifiError::ArithmeticError)?;
+                protocol_fee_total = breakdown.protocol_fee;
             }
-
-            let winning_stake = Self::get_outcome_stake(env.clone(), pool_id, pool.outcome);
-
-            if winning_stake == 0 {
+            if paid_stake == 0 {
                 return Ok(0);
             }
 
-            let fee_bps_i = if pool.fee_bps > 0 || pool.state == MarketState::Resolved {
-                pool.fee_bps as i128
-            } else {
-                let config = Self::get_config(env);
-                config.fee_bps as i128
-            };
-
-            // Payout math lives in `payouts` — keeps lib.rs focused on orchestration
-            let breakdown = calculate_claim_payout(&PayoutInput {
-                pool_total_stake: pool.total_stake,
-                fee_bps: fee_bps_i,
-                user_stake: prediction.amount,
-                winning_stake,
-            })
-            .map_err(|_| PredifiError::InvalidAmount)?;
-            let protocol_fee_total = breakdown.protocol_fee;
-            let winnings = breakdown.winnings;
-
             assert!(winnings <= pool.total_stake, "Winnings exceed total stake");
 
             let token_client = token::Client::new(env, &pool.token);
 
-            let referrer_key = DataKey::Referrer(user.clone(), pool_id);
-            if let Some(referrer) = env.storage().persistent().get::<_, Address>(&referrer_key) {
-                Self::extend_persistent(env, &referrer_key);
-                if protocol_fee_total > 0 && pool.total_stake > 0 {
-                    let referral_cut_bps = Self::read_referral_cut_bps(env) as i128;
-                    let referral_amount = calculate_referral_amount(
-                        prediction.amount,
-                        pool.total_stake,
-                        protocol_fee_total,
-                        referral_cut_bps,
-                    )
-                    .map_err(|_| PredifiError::InvalidAmount)?;
-                    if referral_amount > 0 {
-                        // Validate referral token transfer before execution
-                        Self::validate_token_transfer(
-                            env,
-                            &pool.token,
-                            &env.current_contract_address(),
-                            &referrer,
-                            referral_amount,
-                        )?;
-
-                        token_client.transfer(
-                            &env.current_contract_address(),
-                            &referrer,
-                            &referral_amount,
-                        );
-                        ReferralPaidEvent {
-                            pool_id,
-                            referrer: referrer.clone(),
-                            referred_user: user.clone(),
-                            amount: referral_amount,
-                        }
-                        .publish(env);
-                    }
-                }
+            let referral_paid = Self::pay_referrals(
+                env,
+                pool_id,
+                &pool,
+                user,
+                paid_stake,
+                protocol_fee_total,
+                realized_fee,
+            )?;
+            realized_fee = (realized_fee - referral_paid).max(0);
+
+            // Volume discount: rebate part of the fee the treasury would
+            // otherwise keep from this claim.
+            let discount_bps = Self::read_user_stats(env, user).discount_bps;
+            if discount_bps > 0 && realized_fee > 0 {
+                let rebate = SafeMath::percentage(
+                    realized_fee,
+                    discount_bps as i128,
+                    RoundingMode::ProtocolFavor,
+                )
+                .map_err(|_| PredifiError::ArithmeticError)?;
+                winnings = winnings
+                    .checked_add(rebate)
+                    .ok_or(PredifiError::ArithmeticError)?;
+                realized_fee -= rebate;
             }
 
             if winnings > 0 {
@@ -639,6 +711,7 @@ impl PredifiContract {
 
                 token_client.transfer(&env.current_contract_address(), user, &winnings);
             }
+            Self::credit_accrued_fees(env, &pool.token, realized_fee)?;
 
             WinningsClaimedEvent {
                 pool_id,
@@ -668,7 +741,11 @@ impl PredifiContract {
     /// - Payout is calculated proportionally based on the caller's stake relative to total winning stakes:
     ///   `user_payout = (user_stake / winning_stake) * (total_pool_stake - protocol_fee)`
     /// - Protocol fee is deducted based on the pool's configured basis points (`fee_bps`).
-    /// - If a referrer is associated with the user, a portion of the fee is transferred to the referrer.
+    /// - If a referrer is associated with the user, a portion of the fee is transferred to the referrer,
+    ///   and the second-level cut to the referrer's own referrer, at campaign rates if the referral
+    ///   was made under a campaign with budget left.
+    /// - Users in a volume tier ([`Self::get_user_fee_tier`]) get its discount of the fee the
+    ///   treasury would keep from the claim added to their payout.
     ///
     /// # Claim Window Enforcement
     /// - If `claim_window_seconds` is configured, claims are strictly rejected with `InvalidTimestamp`
@@ -734,7 +811,7 @@ impl PredifiContract {
     /// Claim a refund from a canceled prediction pool.
     ///
     /// # Refund Calculation & Economics
-    /// - Returns 100% of the user's original staked principal amount (`prediction.amount`).
+    /// - Returns 100% of the user's staked principal, summed over every outcome they staked on.
     /// - No protocol fees or penalties are deducted when a market pool is canceled.
     ///
     /// # Double-Claim Prevention & Security
@@ -797,23 +874,12 @@ impl PredifiContract {
                 return Err(PredifiError::AlreadyClaimed);
             }
 
-            // Get user's prediction
-            let pred_key = DataKey::Pred(user.clone(), pool_id);
-            let prediction: Option<Prediction> = env.storage().persistent().get(&pred_key);
-
-            if env.storage().persistent().has(&pred_key) {
-                Self::extend_persistent(&env, &pred_key);
-            }
-
-            let prediction = match prediction {
-                Some(p) => p,
-                None => {
-                    return Err(PredifiError::InsufficientBalance);
-                }
-            };
+            // Every outcome the user staked on is refunded in full
+            let position = Self::load_position(&env, &user, pool_id);
+            let refund_amount = Self::position_total(&position)?;
 
             // Verify user has a non-zero stake
-            if prediction.amount <= 0 {
+            if refund_amount <= 0 {
                 return Err(PredifiError::InsufficientBalance);
             }
 
@@ -823,8 +889,6 @@ impl PredifiContract {
             env.storage().persistent().set(&claimed_key, &true);
             Self::bump_ttl(&env, &claimed_key);
 
-            let refund_amount = prediction.amount;
-
             // --- INTERACTIONS ---
 
             // Validate token transfer before sending refund
@@ -863,6 +927,9 @@ impl PredifiContract {
 
     /// Get a paginated list of a user's predictions.
     ///
+    /// `offset` and `limit` count pools. A pool in which the user holds stakes
+    /// on several outcomes contributes one entry per outcome.
+    ///
     /// # Errors
     /// Returns `PredifiError::InvalidPagination` if `offset + limit` overflows `u32`.
     pub fn get_user_predictions(
@@ -899,13 +966,7 @@ impl PredifiContract {
                 .expect("index not found");
             Self::extend_persistent(&env, &index_key);
 
-            let pred_key = DataKey::Pred(user.clone(), pool_id);
-            let prediction: Prediction = env
-                .storage()
-                .persistent()
-                .get(&pred_key)
-                .expect("prediction not found");
-            Self::extend_persistent(&env, &pred_key);
+            let position = Self::load_position(&env, &user, pool_id);
 
             let pool_key = DataKey::Pool(pool_id);
             let pool: Pool = env
@@ -915,16 +976,25 @@ impl PredifiContract {
                 .expect("pool not found");
             Self::extend_persistent(&env, &pool_key);
 
-            results.push_back(UserPredictionDetail {
-                pool_id,
-                amount: prediction.amount,
-                user_outcome: prediction.outcome,
-                pool_end_time: pool.end_time,
-                pool_state: pool.state,
-                pool_outcome: pool.outcome,
-            });
+            for prediction in position.iter() {
+                results.push_back(UserPredictionDetail {
+                    pool_id,
+                    amount: prediction.amount,
+                    user_outcome: prediction.outcome,
+                    pool_end_time: pool.end_time,
+                    pool_state: pool.state,
+                    pool_outcome: pool.outcome,
+                });
+            }
         }
 
         Ok(results)
     }
+
+    /// Return `user`'s position in `pool_id`: one [`Prediction`] per outcome
+    /// they hold a stake on, in the order those outcomes were first staked.
+    /// Empty if the user has no stake in the pool.
+    pub fn get_user_position(env: Env, pool_id: u64, user: Address) -> Vec<Prediction> {
+        Self::load_position(&env, &user, pool_id)
+    }
 }
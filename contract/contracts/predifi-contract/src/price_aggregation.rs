@@ -0,0 +1,145 @@
+//! # Price aggregation module
+//!
+//! Pure math for resolving a pool on several price observations instead of
+//! the single latest push. Like [`crate::payouts`], everything here is
+//! `no_std`-safe and independent of contract storage; the contract copies
+//! observations out of its ring buffer into fixed-size arrays first.
+//!
+//! - [`time_weighted_average`]: each observation's price is held until the
+//!   next one, and the average is weighted by how long each price was held
+//!   inside the window.
+//! - [`median_without_outliers`]: the median of one price per oracle, after
+//!   dropping prices too far from the raw median.
+
+use predifi_errors::PrediFiError;
+
+/// Time-weighted average of `points` over `[start, end]`.
+///
+/// `points` are `(timestamp, price)` pairs sorted by timestamp, all at or
+/// before `end`. A point before `start` only contributes the part of the
+/// window until the next point. Fails with `OracleError` if no point falls
+/// inside the window, since the average would just repeat a stale price.
+pub fn time_weighted_average(
+    points: &[(u64, i128)],
+    start: u64,
+    end: u64,
+) -> Result<i128, PrediFiError> {
+    if start >= end {
+        return Err(PrediFiError::InvalidAmount);
+    }
+    match points.last() {
+        Some(&(last, _)) if last >= start && last <= end => {}
+        _ => return Err(PrediFiError::OracleError),
+    }
+
+    let mut weighted = 0i128;
+    let mut total = 0i128;
+    for (i, &(timestamp, price)) in points.iter().enumerate() {
+        let from = timestamp.max(start);
+        let to = points.get(i + 1).map_or(end, |&(next, _)| next.min(end));
+        if to <= from {
+            continue;
+        }
+        let held = (to - from) as i128;
+        weighted = price
+            .checked_mul(held)
+            .and_then(|w| weighted.checked_add(w))
+            .ok_or(PrediFiError::ArithmeticError)?;
+        total += held;
+    }
+
+    if total == 0 {
+        return Err(PrediFiError::OracleError);
+    }
+    Ok(weighted / total)
+}
+
+/// Median of `values` (sorted in place). An even count averages the two
+/// middle values, rounding down.
+pub fn median(values: &mut [i128]) -> Result<i128, PrediFiError> {
+    if values.is_empty() {
+        return Err(PrediFiError::OracleError);
+    }
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    if values.len() % 2 == 1 {
+        Ok(values[mid])
+    } else {
+        let (a, b) = (values[mid - 1], values[mid]);
+        Ok(a + (b - a) / 2)
+    }
+}
+
+/// Median of `values` after dropping every value that deviates from the raw
+/// median by more than `max_deviation_bps`.
+///
+/// Returns the filtered median and how many values survived, so the caller
+/// can enforce a quorum.
+pub fn median_without_outliers(
+    values: &mut [i128],
+    max_deviation_bps: u32,
+) -> Result<(i128, usize), PrediFiError> {
+    let raw = median(values)?;
+    let limit = raw
+        .checked_mul(max_deviation_bps as i128)
+        .ok_or(PrediFiError::ArithmeticError)?
+        / 10_000;
+
+    // `values` is sorted, so the survivors form one contiguous run.
+    let first = values
+        .iter()
+        .position(|v| raw - *v <= limit)
+        .unwrap_or(values.len());
+    let last = values
+        .iter()
+        .rposition(|v| *v - raw <= limit)
+        .map_or(first, |i| i + 1);
+    let kept = &mut values[first..last];
+    Ok((median(kept)?, kept.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn twap_weights_by_time_held() {
+        // 100 for 30s, then 200 for 10s.
+        let points = [(60, 100), (90, 200)];
+        assert_eq!(time_weighted_average(&points, 60, 100).unwrap(), 125);
+    }
+
+    #[test]
+    fn twap_carries_earlier_price_into_window() {
+        // 100 was set before the window and held for its first half.
+        let points = [(0, 100), (50, 300)];
+        assert_eq!(time_weighted_average(&points, 40, 60).unwrap(), 200);
+    }
+
+    #[test]
+    fn twap_rejects_window_without_observations() {
+        let points = [(10, 100)];
+        assert_eq!(
+            time_weighted_average(&points, 20, 60),
+            Err(PrediFiError::OracleError)
+        );
+        assert_eq!(
+            time_weighted_average(&[], 20, 60),
+            Err(PrediFiError::OracleError)
+        );
+    }
+
+    #[test]
+    fn median_of_odd_and_even_counts() {
+        assert_eq!(median(&mut [3, 1, 2]).unwrap(), 2);
+        assert_eq!(median(&mut [4, 1, 3, 2]).unwrap(), 2);
+    }
+
+    #[test]
+    fn outliers_are_dropped_before_median() {
+        let mut values = [1_000, 1_010, 990, 5_000, 1_005];
+        let (price, kept) = median_without_outliers(&mut values, 500).unwrap();
+        assert_eq!(kept, 4);
+        assert_eq!(price, 1_002);
+    }
+}
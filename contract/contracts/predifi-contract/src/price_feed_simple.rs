@@ -1,7 +1,12 @@
-use crate::{DataKey, PredifiError, MAX_TOLERANCE};
+use crate::price_source::{
+    load_price_feed, price_condition_outcome, remove_expired_feeds, store_price_feed,
+    validate_price_condition, OracleSettings, PriceReading, PriceSource,
+};
+use crate::{DataKey, PredifiError};
 use soroban_sdk::{contracttype, Address, Env, Symbol, Vec as SorobanVec};
 
-/// Oracle configuration stored under `DataKey::OracleConfig`.
+/// Latest pushed price of a feed pair, as returned by
+/// [`PriceFeedAdapter::get_price_feed`].
 #[contracttype]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SimplePriceFeed {
@@ -17,7 +22,7 @@ pub struct SimplePriceFeed {
     pub expires_at: u64,
 }
 
-/// Oracle configuration stored under `DataKey::OracleConfig`.
+/// Oracle configuration, as returned by [`PriceFeedAdapter::get_oracle_config`].
 #[contracttype]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SimpleOracleConfig {
@@ -29,14 +34,26 @@ pub struct SimpleOracleConfig {
     pub min_confidence_ratio: u32,
 }
 
-/// Price feed adapter for external oracle integration (simplified version).
+/// Price feed adapter for pushed oracle prices.
 ///
-/// Uses `DataKey::OracleConfig` for oracle configuration,
-/// `DataKey::PriceFeed(feed_pair)` for price data, and
-/// `DataKey::PriceCondition(pool_id)` for per-pool price conditions —
-/// all defined in the canonical `DataKey` enum in `lib.rs`.
+/// This is the [`PriceSource`] behind pools resolving on the latest
+/// `update_price_feed` push. It reads and writes the same
+/// `DataKey::OracleConfig`, `DataKey::PriceFeed(feed_pair)` and
+/// `DataKey::PriceCondition(pool_id)` entries as the contract entry points,
+/// through the shared code in [`crate::price_source`].
 pub struct PriceFeedAdapter;
 
+impl PriceSource for PriceFeedAdapter {
+    fn read(
+        env: &Env,
+        _pool_id: u64,
+        feed_pair: &Symbol,
+        _settings: &OracleSettings,
+    ) -> Result<PriceReading, PredifiError> {
+        load_price_feed(env, feed_pair).ok_or(PredifiError::PriceFeedNotFound)
+    }
+}
+
 impl PriceFeedAdapter {
     /// Initialize oracle configuration.
     pub fn init_oracle(
@@ -48,34 +65,25 @@ impl PriceFeedAdapter {
     ) -> Result<(), PredifiError> {
         admin.require_auth();
 
-        if max_price_age == 0 {
-            return Err(PredifiError::InvalidData);
-        }
-        if min_confidence_ratio > 10_000 {
-            return Err(PredifiError::InvalidFeeBps);
-        }
-
-        let config = SimpleOracleConfig {
+        OracleSettings {
             pyth_contract,
             max_price_age,
             min_confidence_ratio,
-        };
-
-        env.storage()
-            .persistent()
-            .set(&DataKey::OracleConfig, &config);
-
-        Ok(())
+        }
+        .store(env)
     }
 
     /// Get oracle configuration.
     pub fn get_oracle_config(env: &Env) -> Option<SimpleOracleConfig> {
-        env.storage().persistent().get(&DataKey::OracleConfig)
+        OracleSettings::load(env).map(|settings| SimpleOracleConfig {
+            pyth_contract: settings.pyth_contract,
+            max_price_age: settings.max_price_age,
+            min_confidence_ratio: settings.min_confidence_ratio,
+        })
     }
 
     /// Update price feed data (called by oracle keeper or by contract admin).
     ///
-    /// Requires the caller to be either a whitelisted oracle or an admin.
     /// Price updates that deviate too far from the previous price are rejected
     /// to prevent flash loan manipulation.
     pub fn update_price_feed(
@@ -89,94 +97,51 @@ impl PriceFeedAdapter {
     ) -> Result<(), PredifiError> {
         oracle.require_auth();
 
-        if price <= 0 || confidence < 0 {
-            return Err(PredifiError::InvalidAmount);
-        }
-
-        if timestamp > env.ledger().timestamp() || expires_at <= timestamp {
-            return Err(PredifiError::InvalidPoolState);
-        }
-
-        let feed_key = DataKey::PriceFeed(feed_pair.clone());
-
-        // Price deviation protection: prevent flash loan manipulation by rejecting
-        // price updates that deviate more than 5x from the previous price.
-        const MAX_DEVIATION_MULTIPLIER: i128 = 5;
-        if let Some(prev_feed) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, SimplePriceFeed>(&feed_key)
-        {
-            if prev_feed.price > 0 && price > 0 {
-                let (lower, upper) = (
-                    prev_feed
-                        .price
-                        .checked_div(MAX_DEVIATION_MULTIPLIER)
-                        .unwrap_or(i128::MIN),
-                    prev_feed
-                        .price
-                        .checked_mul(MAX_DEVIATION_MULTIPLIER)
-                        .unwrap_or(i128::MAX),
-                );
-                if price < lower || price > upper {
-                    return Err(PredifiError::InvalidData);
-                }
-            }
-        }
-
-        let feed = SimplePriceFeed {
-            pair: feed_pair.clone(),
-            price,
-            confidence,
-            timestamp,
-            expires_at,
-        };
-
-        env.storage()
-            .persistent()
-            .set(&feed_key, &feed);
-
-        // Track this feed pair in the global list for cleanup
-        let mut list: SorobanVec<Symbol> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::PriceFeedList)
-            .unwrap_or_else(|| SorobanVec::new(env));
-        if !list.contains(feed_pair.clone()) {
-            list.push_back(feed_pair);
-            env.storage()
-                .persistent()
-                .set(&DataKey::PriceFeedList, &list);
-        }
-
-        Ok(())
+        store_price_feed(
+            env,
+            oracle,
+            &feed_pair,
+            &PriceReading {
+                price,
+                confidence,
+                timestamp,
+                expires_at,
+            },
+        )
     }
 
     /// Get current price feed data for a given pair.
     pub fn get_price_feed(env: &Env, feed_pair: &Symbol) -> Option<SimplePriceFeed> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::PriceFeed(feed_pair.clone()))
+        load_price_feed(env, feed_pair).map(|reading| SimplePriceFeed {
+            pair: feed_pair.clone(),
+            price: reading.price,
+            confidence: reading.confidence,
+            timestamp: reading.timestamp,
+            expires_at: reading.expires_at,
+        })
     }
 
-    /// Check if price data is valid and fresh.
+    /// Check if price data is valid and fresh under the stored oracle
+    /// configuration, with `max_age` in place of its `max_price_age`.
     pub fn is_price_valid(env: &Env, feed: &SimplePriceFeed, max_age: u64) -> bool {
-        let current_time = env.ledger().timestamp();
-
-        if current_time > feed.expires_at {
-            return false;
-        }
-
-        if current_time > feed.timestamp + max_age {
-            return false;
-        }
-
-        // Basic confidence check: confidence must be <= 1% of price
-        if feed.confidence > feed.price / 100 {
+        let Some(settings) = OracleSettings::load(env) else {
             return false;
-        }
-
-        true
+        };
+        let reading = PriceReading {
+            price: feed.price,
+            confidence: feed.confidence,
+            timestamp: feed.timestamp,
+            expires_at: feed.expires_at,
+        };
+        reading
+            .check(
+                env,
+                &OracleSettings {
+                    max_price_age: max_age,
+                    ..settings
+                },
+            )
+            .is_ok()
     }
 
     /// Set price condition for a pool.
@@ -191,6 +156,11 @@ impl PriceFeedAdapter {
         operator: u32,
         tolerance_bps: u32,
     ) -> Result<(), PredifiError> {
+        if target_price <= 0 {
+            return Err(PredifiError::InvalidTargetPrice);
+        }
+        validate_price_condition(operator, tolerance_bps)?;
+
         env.storage().persistent().set(
             &DataKey::PriceCondition(pool_id),
             &(feed_pair, target_price, operator, tolerance_bps),
@@ -220,19 +190,9 @@ impl PriceFeedAdapter {
             return Err(PredifiError::PriceDataInvalid);
         }
 
-        let tolerance_amount = (target_price * *tolerance_bps as i128) / MAX_TOLERANCE as i128;
-
-        let result = match operator_type {
-            0 => {
-                feed.price >= target_price - tolerance_amount
-                    && feed.price <= target_price + tolerance_amount
-            }
-            1 => feed.price > target_price + tolerance_amount,
-            2 => feed.price < target_price - tolerance_amount,
-            _ => return Err(PredifiError::InvalidPoolState),
-        };
-
-        Ok(result)
+        let outcome =
+            price_condition_outcome(feed.price, *target_price, *operator_type, *tolerance_bps)?;
+        Ok(outcome == 1)
     }
 
     /// Resolve pool based on price condition.
@@ -257,17 +217,17 @@ impl PriceFeedAdapter {
     ) -> Result<(), PredifiError> {
         oracle.require_auth();
 
-        for i in 0..updates.len() {
-            let (feed_pair, price, confidence, timestamp, expires_at) = updates.get(i).unwrap();
-
-            Self::update_price_feed(
+        for (feed_pair, price, confidence, timestamp, expires_at) in updates.iter() {
+            store_price_feed(
                 env,
                 oracle,
-                feed_pair.clone(),
-                price,
-                confidence,
-                timestamp,
-                expires_at,
+                &feed_pair,
+                &PriceReading {
+                    price,
+                    confidence,
+                    timestamp,
+                    expires_at,
+                },
             )?;
         }
 
@@ -279,38 +239,6 @@ impl PriceFeedAdapter {
     /// Iterates the tracked feed list, removes entries whose `expires_at` is in
     /// the past, and returns the number of feeds removed.
     pub fn cleanup_expired_feeds(env: &Env) -> u32 {
-        let current_time = env.ledger().timestamp();
-
-        let list: SorobanVec<Symbol> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::PriceFeedList)
-            .unwrap_or_else(|| SorobanVec::new(env));
-
-        let mut remaining: SorobanVec<Symbol> = SorobanVec::new(env);
-        let mut removed: u32 = 0;
-
-        for i in 0..list.len() {
-            let pair = list.get(i).unwrap();
-            let expired = env
-                .storage()
-                .persistent()
-                .get::<DataKey, SimplePriceFeed>(&DataKey::PriceFeed(pair.clone()))
-                .map(|feed| feed.expires_at < current_time)
-                .unwrap_or(true); // missing entry counts as expired
-
-            if expired {
-                env.storage().persistent().remove(&DataKey::PriceFeed(pair));
-                removed += 1;
-            } else {
-                remaining.push_back(pair);
-            }
-        }
-
-        env.storage()
-            .persistent()
-            .set(&DataKey::PriceFeedList, &remaining);
-
-        removed
+        remove_expired_feeds(env)
     }
 }
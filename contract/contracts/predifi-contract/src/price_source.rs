@@ -0,0 +1,502 @@
+//! # Price sources
+//!
+//! Every way a pool can learn its resolution price implements [`PriceSource`]:
+//!
+//! - [`PriceFeedAdapter`]: the latest price a whitelisted oracle pushed
+//!   through `update_price_feed`.
+//! - [`PythSource`]: a price pulled from the configured Pyth contract at
+//!   resolution time.
+//! - [`AggregatedSource`]: a TWAP or median over the pushed observations in
+//!   the pool's window.
+//!
+//! What the sources have in common lives here once: the storage format of
+//! `DataKey::OracleConfig` and `DataKey::PriceFeed`, validation of pushed
+//! prices, the freshness and confidence checks applied to spot readings, and
+//! price condition evaluation. Each pool picks its source with
+//! `set_price_source`; [`resolution_price`] dispatches on that choice.
+//!
+//! Prices stay in the feed's native decimals. Nothing is rescaled, so target
+//! prices, ranges and brackets must use the precision of the feed they read.
+
+use soroban_sdk::{Address, BytesN, Env, IntoVal, Symbol, Vec};
+
+use crate::price_aggregation::{median_without_outliers, time_weighted_average};
+use crate::{
+    AggregationMode, DataKey, OracleKey, Pool, PredifiContract, PredifiError, PriceAggregation,
+    PriceFeedAdapter, PriceObservation, PriceSourceKind, PythPrice,
+    MAX_PRICE_CONDITION_MATCH_STEPS, MAX_PRICE_OBSERVATIONS, MAX_TOLERANCE,
+};
+
+/// Largest move a push may make from the previous price of its pair, as a
+/// multiplier in either direction. Catches flash-loan style manipulation
+/// while leaving room for volatile assets.
+const MAX_PRICE_DEVIATION_MULTIPLIER: i128 = 5;
+
+/// Oracle configuration, stored under `DataKey::OracleConfig` as
+/// `(pyth_contract, max_price_age, min_confidence_ratio)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OracleSettings {
+    pub pyth_contract: Address,
+    /// Seconds after its timestamp a spot reading stops being usable.
+    pub max_price_age: u64,
+    /// Widest accepted `confidence / price`, in basis points.
+    pub min_confidence_ratio: u32,
+}
+
+impl OracleSettings {
+    pub fn load(env: &Env) -> Option<Self> {
+        env.storage()
+            .persistent()
+            .get::<DataKey, (Address, u64, u32)>(&DataKey::OracleConfig)
+            .map(
+                |(pyth_contract, max_price_age, min_confidence_ratio)| Self {
+                    pyth_contract,
+                    max_price_age,
+                    min_confidence_ratio,
+                },
+            )
+    }
+
+    /// Validate and store the settings.
+    ///
+    /// # Errors
+    /// - `InvalidData`   – `max_price_age` is 0 (every feed would be immediately stale).
+    /// - `InvalidFeeBps` – `min_confidence_ratio` exceeds 10 000 bps (100 %).
+    pub fn store(&self, env: &Env) -> Result<(), PredifiError> {
+        if self.max_price_age == 0 {
+            return Err(PredifiError::InvalidData);
+        }
+        if self.min_confidence_ratio > 10_000 {
+            return Err(PredifiError::InvalidFeeBps);
+        }
+
+        env.storage().persistent().set(
+            &DataKey::OracleConfig,
+            &(
+                self.pyth_contract.clone(),
+                self.max_price_age,
+                self.min_confidence_ratio,
+            ),
+        );
+        PredifiContract::extend_persistent(env, &DataKey::OracleConfig);
+        Ok(())
+    }
+}
+
+/// A price read from a source.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PriceReading {
+    pub price: i128,
+    /// ± uncertainty of `price`, in the same decimals.
+    pub confidence: i128,
+    /// When the price was observed.
+    pub timestamp: u64,
+    /// When the source stops vouching for the price.
+    pub expires_at: u64,
+}
+
+impl PriceReading {
+    /// Reject a spot reading that is not positive, dated in the future,
+    /// expired, older than `max_price_age`, or whose confidence interval is
+    /// wider than `min_confidence_ratio` of the price.
+    pub fn check(&self, env: &Env, settings: &OracleSettings) -> Result<(), PredifiError> {
+        let current_time = env.ledger().timestamp();
+        if self.price <= 0
+            || self.timestamp > current_time
+            || current_time > self.expires_at
+            || current_time > self.timestamp.saturating_add(settings.max_price_age)
+        {
+            return Err(PredifiError::PriceDataInvalid);
+        }
+
+        let width = self
+            .confidence
+            .checked_mul(10_000)
+            .ok_or(PredifiError::ArithmeticError)?;
+        let limit = self
+            .price
+            .checked_mul(settings.min_confidence_ratio as i128)
+            .ok_or(PredifiError::ArithmeticError)?;
+        if width > limit {
+            return Err(PredifiError::PriceDataInvalid);
+        }
+        Ok(())
+    }
+}
+
+/// A place a pool's resolution price can come from.
+pub trait PriceSource {
+    /// Whether readings are single quotes subject to [`PriceReading::check`].
+    /// Aggregates are bounded by their own window instead.
+    const SPOT: bool = true;
+
+    /// The unchecked price `pool_id` would settle on from `feed_pair`.
+    fn read(
+        env: &Env,
+        pool_id: u64,
+        feed_pair: &Symbol,
+        settings: &OracleSettings,
+    ) -> Result<PriceReading, PredifiError>;
+
+    /// Read the price and, for spot sources, check it against the oracle
+    /// settings.
+    fn resolution_price(env: &Env, pool_id: u64, feed_pair: &Symbol) -> Result<i128, PredifiError> {
+        let settings = OracleSettings::load(env).ok_or(PredifiError::OracleNotInitialized)?;
+        let reading = Self::read(env, pool_id, feed_pair, &settings)?;
+        if Self::SPOT {
+            reading.check(env, &settings)?;
+        }
+        Ok(reading.price)
+    }
+}
+
+/// The configured Pyth contract, read through the pair's bound price id.
+pub struct PythSource;
+
+impl PriceSource for PythSource {
+    fn read(
+        env: &Env,
+        _pool_id: u64,
+        feed_pair: &Symbol,
+        settings: &OracleSettings,
+    ) -> Result<PriceReading, PredifiError> {
+        let price_id: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PythPriceId(feed_pair.clone()))
+            .ok_or(PredifiError::PriceFeedNotFound)?;
+
+        // try_invoke_contract returns Result<Result<T, ConversionError>, InvokeError>;
+        // a reverted or malformed answer is treated like stale data.
+        let pyth: PythPrice = env
+            .try_invoke_contract::<PythPrice, PredifiError>(
+                &settings.pyth_contract,
+                &Symbol::new(env, "get_price_no_older_than"),
+                soroban_sdk::vec![
+                    env,
+                    price_id.into_val(env),
+                    settings.max_price_age.into_val(env)
+                ],
+            )
+            .map_err(|_| PredifiError::PriceDataInvalid)
+            .and_then(|inner| inner.map_err(|_| PredifiError::PriceDataInvalid))?;
+
+        // Pyth answers carry no expiry of their own beyond the age limit.
+        Ok(PriceReading {
+            price: pyth.price as i128,
+            confidence: pyth.conf as i128,
+            timestamp: pyth.publish_time,
+            expires_at: pyth.publish_time.saturating_add(settings.max_price_age),
+        })
+    }
+}
+
+/// TWAP or median of the pair's observations in
+/// `[end_time - window, end_time]`, per the pool's `PriceAggregation`.
+pub struct AggregatedSource;
+
+impl PriceSource for AggregatedSource {
+    const SPOT: bool = false;
+
+    fn read(
+        env: &Env,
+        pool_id: u64,
+        feed_pair: &Symbol,
+        _settings: &OracleSettings,
+    ) -> Result<PriceReading, PredifiError> {
+        let aggregation: PriceAggregation = env
+            .storage()
+            .persistent()
+            .get(&OracleKey::PriceAggregation(pool_id))
+            .ok_or(PredifiError::PriceFeedNotFound)?;
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        let end = pool.end_time;
+        let start = end.saturating_sub(aggregation.window);
+
+        let observations: Vec<PriceObservation> = env
+            .storage()
+            .persistent()
+            .get(&OracleKey::PriceObservations(feed_pair.clone()))
+            .unwrap_or_else(|| Vec::new(env));
+
+        let price = match aggregation.mode {
+            AggregationMode::Twap => {
+                let mut points = [(0u64, 0i128); MAX_PRICE_OBSERVATIONS as usize];
+                let mut count = 0usize;
+                for observation in observations.iter() {
+                    if observation.timestamp <= end {
+                        points[count] = (observation.timestamp, observation.price);
+                        count += 1;
+                    }
+                }
+                time_weighted_average(&points[..count], start, end)
+                    .map_err(|_| PredifiError::PriceDataInvalid)?
+            }
+            AggregationMode::Median => {
+                // Latest price per oracle inside the window; the buffer is
+                // sorted, so later entries overwrite earlier ones.
+                let mut oracles: Vec<Address> = Vec::new(env);
+                let mut prices = [0i128; MAX_PRICE_OBSERVATIONS as usize];
+                for observation in observations.iter() {
+                    if observation.timestamp < start || observation.timestamp > end {
+                        continue;
+                    }
+                    match oracles.first_index_of(&observation.oracle) {
+                        Some(i) => prices[i as usize] = observation.price,
+                        None => {
+                            prices[oracles.len() as usize] = observation.price;
+                            oracles.push_back(observation.oracle);
+                        }
+                    }
+                }
+
+                let (price, sources) = median_without_outliers(
+                    &mut prices[..oracles.len() as usize],
+                    aggregation.max_deviation_bps,
+                )
+                .map_err(|_| PredifiError::PriceDataInvalid)?;
+                if (sources as u32) < aggregation.min_sources {
+                    return Err(PredifiError::PriceDataInvalid);
+                }
+                price
+            }
+        };
+
+        Ok(PriceReading {
+            price,
+            confidence: 0,
+            timestamp: end,
+            expires_at: u64::MAX,
+        })
+    }
+}
+
+/// The source `pool_id` resolves `feed_pair` on, with `Auto` resolved to a
+/// concrete source.
+pub fn selected_source(env: &Env, pool_id: u64, feed_pair: &Symbol) -> PriceSourceKind {
+    let storage = env.storage().persistent();
+    match storage.get(&OracleKey::SourceKind(pool_id)) {
+        Some(kind) if kind != PriceSourceKind::Auto => kind,
+        _ if storage.has(&DataKey::PythPriceId(feed_pair.clone())) => PriceSourceKind::Pyth,
+        _ if storage.has(&OracleKey::PriceAggregation(pool_id)) => PriceSourceKind::Aggregated,
+        _ => PriceSourceKind::Pushed,
+    }
+}
+
+/// Price `pool_id` settles on, read from its selected source.
+pub fn resolution_price(env: &Env, pool_id: u64, feed_pair: &Symbol) -> Result<i128, PredifiError> {
+    match selected_source(env, pool_id, feed_pair) {
+        PriceSourceKind::Pyth => PythSource::resolution_price(env, pool_id, feed_pair),
+        PriceSourceKind::Aggregated => AggregatedSource::resolution_price(env, pool_id, feed_pair),
+        PriceSourceKind::Pushed | PriceSourceKind::Auto => {
+            PriceFeedAdapter::resolution_price(env, pool_id, feed_pair)
+        }
+    }
+}
+
+/// Latest pushed reading of `feed_pair`, stored under `DataKey::PriceFeed` as
+/// `(price, confidence, timestamp, expires_at)`.
+pub fn load_price_feed(env: &Env, feed_pair: &Symbol) -> Option<PriceReading> {
+    env.storage()
+        .persistent()
+        .get::<DataKey, (i128, i128, u64, u64)>(&DataKey::PriceFeed(feed_pair.clone()))
+        .map(|(price, confidence, timestamp, expires_at)| PriceReading {
+            price,
+            confidence,
+            timestamp,
+            expires_at,
+        })
+}
+
+/// Validate a pushed price, store it as the latest reading of `feed_pair`
+/// and record it in the pair's observation buffer. Authorising `oracle` is
+/// left to the caller.
+///
+/// # Errors
+/// - `InvalidData`   – the timestamp is not strictly in the past, the expiry
+///   is not after it, or the price moved more than 5x from the last push.
+/// - `InvalidAmount` – the price is not positive or the confidence negative.
+pub fn store_price_feed(
+    env: &Env,
+    oracle: &Address,
+    feed_pair: &Symbol,
+    reading: &PriceReading,
+) -> Result<(), PredifiError> {
+    // Reject pre-dated and same-ledger prices, which could be used to inject
+    // a price in the same transaction that resolves on it.
+    if reading.timestamp >= env.ledger().timestamp() || reading.expires_at <= reading.timestamp {
+        return Err(PredifiError::InvalidData);
+    }
+    if reading.price <= 0 || reading.confidence < 0 {
+        return Err(PredifiError::InvalidAmount);
+    }
+
+    if let Some(previous) = load_price_feed(env, feed_pair) {
+        let lower = previous.price / MAX_PRICE_DEVIATION_MULTIPLIER;
+        let upper = previous
+            .price
+            .checked_mul(MAX_PRICE_DEVIATION_MULTIPLIER)
+            .ok_or(PredifiError::ArithmeticError)?;
+        if reading.price < lower || reading.price > upper {
+            return Err(PredifiError::InvalidData);
+        }
+    }
+
+    let feed_key = DataKey::PriceFeed(feed_pair.clone());
+    env.storage().persistent().set(
+        &feed_key,
+        &(
+            reading.price,
+            reading.confidence,
+            reading.timestamp,
+            reading.expires_at,
+        ),
+    );
+    PredifiContract::extend_persistent(env, &feed_key);
+    record_price_observation(env, feed_pair, oracle, reading.price, reading.timestamp);
+
+    // Track the pair for `remove_expired_feeds`.
+    let mut list: Vec<Symbol> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::PriceFeedList)
+        .unwrap_or_else(|| Vec::new(env));
+    if !list.contains(feed_pair.clone()) {
+        list.push_back(feed_pair.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::PriceFeedList, &list);
+    }
+
+    Ok(())
+}
+
+/// Append a pushed price to the pair's observation buffer, keeping it sorted
+/// by timestamp and dropping the oldest entry once full.
+fn record_price_observation(
+    env: &Env,
+    feed_pair: &Symbol,
+    oracle: &Address,
+    price: i128,
+    timestamp: u64,
+) {
+    let key = OracleKey::PriceObservations(feed_pair.clone());
+    let mut observations: Vec<PriceObservation> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env));
+
+    // Oracles push independently, so a late push may predate the newest
+    // entry; walk back from the end to find its slot.
+    let mut index = observations.len();
+    while index > 0 && observations.get_unchecked(index - 1).timestamp > timestamp {
+        index -= 1;
+    }
+    observations.insert(
+        index,
+        PriceObservation {
+            oracle: oracle.clone(),
+            price,
+            timestamp,
+        },
+    );
+    if observations.len() > MAX_PRICE_OBSERVATIONS {
+        observations.pop_front();
+    }
+
+    env.storage().persistent().set(&key, &observations);
+    PredifiContract::extend_persistent(env, &key);
+}
+
+/// Remove every tracked feed whose `expires_at` has passed, and any tracked
+/// pair with no feed left. Returns the number of feeds removed.
+pub fn remove_expired_feeds(env: &Env) -> u32 {
+    let current_time = env.ledger().timestamp();
+    let list: Vec<Symbol> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::PriceFeedList)
+        .unwrap_or_else(|| Vec::new(env));
+
+    let mut remaining: Vec<Symbol> = Vec::new(env);
+    let mut removed: u32 = 0;
+    for pair in list.iter() {
+        let expired = load_price_feed(env, &pair).is_none_or(|feed| feed.expires_at < current_time);
+        if expired {
+            env.storage().persistent().remove(&DataKey::PriceFeed(pair));
+            removed += 1;
+        } else {
+            remaining.push_back(pair);
+        }
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::PriceFeedList, &remaining);
+    removed
+}
+
+/// Operators: 0=Equal, 1=Greater, 2=Less; tolerance up to `MAX_TOLERANCE`.
+pub fn validate_price_condition(
+    comparison_op: u32,
+    tolerance_bps: u32,
+) -> Result<(), PredifiError> {
+    if comparison_op > 2 || tolerance_bps > MAX_TOLERANCE {
+        return Err(PredifiError::InvalidData);
+    }
+    Ok(())
+}
+
+/// Match a price condition in a fixed, explicitly bounded number of checks.
+///
+/// Operators: 0=Equal (within `target_price ± tolerance`), 1=Greater (above
+/// `target_price + tolerance`), 2=Less (below `target_price - tolerance`).
+/// Outcome: 0=No, 1=Yes.
+pub fn price_condition_outcome(
+    price: i128,
+    target_price: i128,
+    comparison_op: u32,
+    tolerance_bps: u32,
+) -> Result<u32, PredifiError> {
+    validate_price_condition(comparison_op, tolerance_bps)?;
+
+    let tolerance_amount = target_price
+        .checked_mul(tolerance_bps as i128)
+        .and_then(|amount| amount.checked_div(MAX_TOLERANCE as i128))
+        .ok_or(PredifiError::ArithmeticError)?;
+    let lower_bound = target_price
+        .checked_sub(tolerance_amount)
+        .ok_or(PredifiError::ArithmeticError)?;
+    let upper_bound = target_price
+        .checked_add(tolerance_amount)
+        .ok_or(PredifiError::ArithmeticError)?;
+
+    let mut steps = 0u32;
+    let condition_met = match comparison_op {
+        0 => {
+            steps += 2;
+            price >= lower_bound && price <= upper_bound
+        }
+        1 => {
+            steps += 1;
+            price > upper_bound
+        }
+        2 => {
+            steps += 1;
+            price < lower_bound
+        }
+        _ => return Err(PredifiError::InvalidData),
+    };
+
+    steps += 2;
+    if steps > MAX_PRICE_CONDITION_MATCH_STEPS {
+        return Err(PredifiError::RateLimitOrSuspiciousActivity);
+    }
+
+    Ok(if condition_met { 1 } else { 0 })
+}
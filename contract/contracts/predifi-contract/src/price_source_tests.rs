@@ -0,0 +1,142 @@
+//! Price sources: per-pool source selection, the shared freshness and
+//! confidence checks, and `PriceFeedAdapter` sharing the contract's storage.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{AggregationMode, PredifiError, PriceAggregation, PriceFeedAdapter, PriceSourceKind};
+use soroban_sdk::{testutils::Ledger, Address, Env, Symbol};
+
+const END_TIME: u64 = 10_000;
+
+fn feed(env: &Env) -> Symbol {
+    Symbol::new(env, "ETH_USD")
+}
+
+/// A pool where Yes wins above 3000, priced by an oracle held to a 1%
+/// confidence limit. Returns `(pool_id, oracle)`.
+fn source_pool(ctx: &TestContext) -> (u64, Address) {
+    let env = &ctx.client.env;
+    let oracle = ctx.add_price_oracle(3_600, 100);
+    let pool_id = ctx.create_pool(END_TIME, &default_pool_config(env));
+    ctx.client
+        .set_price_condition(&ctx.operator, &pool_id, &feed(env), &3_000, &1, &0);
+    (pool_id, oracle)
+}
+
+fn push_at_close(ctx: &TestContext, oracle: &Address, price: i128, confidence: i128) {
+    let env = &ctx.client.env;
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 10);
+    ctx.client.update_price_feed(
+        oracle,
+        &feed(env),
+        &price,
+        &confidence,
+        &(END_TIME + 5),
+        &(END_TIME + 100),
+    );
+}
+
+/// Pushed prices go through the same confidence check as Pyth answers.
+#[test]
+fn test_pushed_price_must_meet_confidence_ratio() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, oracle) = source_pool(&ctx);
+
+    push_at_close(&ctx, &oracle, 3_200, 40);
+    let uncertain = ctx.client.try_resolve_pool_from_price(&pool_id);
+    assert_eq!(uncertain, Err(Ok(PredifiError::PriceDataInvalid)));
+
+    push_at_close(&ctx, &oracle, 3_200, 32);
+    ctx.client.resolve_pool_from_price(&pool_id);
+    assert_eq!(ctx.client.get_pool(&pool_id).outcome, 1);
+}
+
+/// An explicit source overrides `Auto`, and a source without the settings it
+/// needs cannot resolve.
+#[test]
+fn test_price_source_selection_per_pool() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, oracle) = source_pool(&ctx);
+    assert_eq!(ctx.client.get_price_source(&pool_id), PriceSourceKind::Auto);
+
+    // Under `Auto` the pool would take a TWAP with no observations in its
+    // window; `Pushed` makes it read the latest push instead.
+    ctx.client.set_price_aggregation(
+        &ctx.operator,
+        &pool_id,
+        &Some(PriceAggregation {
+            mode: AggregationMode::Twap,
+            window: 100,
+            max_deviation_bps: 0,
+            min_sources: 0,
+        }),
+    );
+
+    ctx.client
+        .set_price_source(&ctx.operator, &pool_id, &PriceSourceKind::Pyth);
+    push_at_close(&ctx, &oracle, 3_500, 1);
+    let unbound = ctx.client.try_resolve_pool_from_price(&pool_id);
+    assert_eq!(unbound, Err(Ok(PredifiError::PriceFeedNotFound)));
+
+    ctx.client
+        .set_price_source(&ctx.operator, &pool_id, &PriceSourceKind::Pushed);
+    assert_eq!(
+        ctx.client.get_price_source(&pool_id),
+        PriceSourceKind::Pushed
+    );
+    ctx.client.resolve_pool_from_price(&pool_id);
+    assert_eq!(ctx.client.get_pool(&pool_id).outcome, 1);
+
+    let not_operator = ctx
+        .client
+        .try_set_price_source(&oracle, &pool_id, &PriceSourceKind::Auto);
+    assert_eq!(not_operator, Err(Ok(PredifiError::Unauthorized)));
+}
+
+/// `PriceFeedAdapter` and the contract entry points share one storage
+/// format, so either can read what the other wrote.
+#[test]
+fn test_adapter_shares_contract_storage() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, oracle) = source_pool(&ctx);
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 10);
+
+    env.as_contract(&ctx.client.address, || {
+        let config = PriceFeedAdapter::get_oracle_config(&env).unwrap();
+        assert_eq!(config.max_price_age, 3_600);
+        assert_eq!(config.min_confidence_ratio, 100);
+
+        PriceFeedAdapter::update_price_feed(
+            &env,
+            &oracle,
+            feed(&env),
+            2_800,
+            1,
+            END_TIME + 5,
+            END_TIME + 100,
+        )
+        .unwrap();
+        assert_eq!(
+            PriceFeedAdapter::resolve_pool_from_price(&env, pool_id, 3_600),
+            Ok(0)
+        );
+    });
+
+    ctx.client.resolve_pool_from_price(&pool_id);
+    assert_eq!(ctx.client.get_pool(&pool_id).outcome, 0);
+
+    // The same 5x deviation guard applies on both paths.
+    let spike = ctx.client.try_update_price_feed(
+        &oracle,
+        &feed(&env),
+        &20_000,
+        &1,
+        &(END_TIME + 6),
+        &(END_TIME + 100),
+    );
+    assert_eq!(spike, Err(Ok(PredifiError::InvalidData)));
+}
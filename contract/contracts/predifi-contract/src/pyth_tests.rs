@@ -0,0 +1,164 @@
+//! Pyth pull oracle: pools resolved from a price read off a mock Pyth
+//! contract at resolution time.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{PredifiError, PythPrice};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, Symbol,
+};
+
+mod mock_pyth {
+    use crate::PythPrice;
+    use soroban_sdk::{contract, contractimpl, BytesN, Env};
+
+    /// Minimal Pyth-compatible price contract: serves whatever price was
+    /// last stored for an id and panics for unknown ids.
+    #[contract]
+    pub struct MockPyth;
+
+    #[contractimpl]
+    impl MockPyth {
+        pub fn set_price(env: Env, price_id: BytesN<32>, price: PythPrice) {
+            env.storage().instance().set(&price_id, &price);
+        }
+
+        pub fn get_price_no_older_than(env: Env, price_id: BytesN<32>, _age: u64) -> PythPrice {
+            env.storage()
+                .instance()
+                .get(&price_id)
+                .expect("price feed not found")
+        }
+    }
+}
+
+const END_TIME: u64 = 10_000;
+const MAX_PRICE_AGE: u64 = 60;
+
+fn feed(env: &Env) -> Symbol {
+    Symbol::new(env, "ETH_USD")
+}
+
+fn price_id(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[7u8; 32])
+}
+
+/// An ended pool where Yes wins above 3000, with the oracle settings pointed
+/// at a mock Pyth contract. Returns `(pool_id, pyth, oracle)`.
+fn pyth_pool<'a>(ctx: &TestContext<'a>) -> (u64, mock_pyth::MockPythClient<'a>, Address) {
+    let env = &ctx.client.env;
+    let pyth_id = env.register(mock_pyth::MockPyth, ());
+    let pyth = mock_pyth::MockPythClient::new(env, &pyth_id);
+    let oracle = Address::generate(env);
+    ctx.client
+        .init_oracle(&ctx.admin, &pyth_id, &MAX_PRICE_AGE, &100u32);
+    ctx.client.add_oracle(&ctx.admin, &oracle);
+
+    let pool_id = ctx.create_pool(END_TIME, &default_pool_config(env));
+    ctx.client
+        .set_price_condition(&ctx.operator, &pool_id, &feed(env), &3_000, &1, &0);
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 100);
+    (pool_id, pyth, oracle)
+}
+
+fn publish(pyth: &mock_pyth::MockPythClient, price: i64, conf: u64, age: u64) {
+    let env = &pyth.env;
+    pyth.set_price(
+        &price_id(env),
+        &PythPrice {
+            price,
+            conf,
+            expo: -8,
+            publish_time: env.ledger().timestamp() - age,
+        },
+    );
+}
+
+/// With a Pyth price id bound, resolution reads the price from the Pyth
+/// contract; no pushed update is needed.
+#[test]
+fn test_resolve_pool_pulls_price_from_pyth() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, pyth, _oracle) = pyth_pool(&ctx);
+    ctx.client
+        .set_pyth_price_id(&ctx.admin, &feed(&env), &price_id(&env));
+    assert_eq!(
+        ctx.client.get_pyth_price_id(&feed(&env)),
+        Some(price_id(&env))
+    );
+
+    publish(&pyth, 3_200, 10, 5);
+    ctx.client.resolve_pool_from_price(&pool_id);
+
+    assert_eq!(ctx.client.get_pool(&pool_id).outcome, 1);
+}
+
+/// Answers older than `max_price_age`, with too wide a confidence interval,
+/// or from a Pyth contract that reverts, leave the pool unresolved.
+#[test]
+fn test_rejects_stale_or_uncertain_pyth_prices() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, pyth, _oracle) = pyth_pool(&ctx);
+    ctx.client
+        .set_pyth_price_id(&ctx.admin, &feed(&env), &price_id(&env));
+
+    let missing = ctx.client.try_resolve_pool_from_price(&pool_id);
+    assert_eq!(missing, Err(Ok(PredifiError::PriceDataInvalid)));
+
+    publish(&pyth, 3_200, 10, MAX_PRICE_AGE + 1);
+    let stale = ctx.client.try_resolve_pool_from_price(&pool_id);
+    assert_eq!(stale, Err(Ok(PredifiError::PriceDataInvalid)));
+
+    // 1% is the configured limit; 50 / 3200 is above it.
+    publish(&pyth, 3_200, 50, 5);
+    let uncertain = ctx.client.try_resolve_pool_from_price(&pool_id);
+    assert_eq!(uncertain, Err(Ok(PredifiError::PriceDataInvalid)));
+
+    publish(&pyth, 2_900, 29, 5);
+    ctx.client.resolve_pool_from_price(&pool_id);
+    assert_eq!(ctx.client.get_pool(&pool_id).outcome, 0);
+}
+
+/// Removing the Pyth binding returns the pair to pushed updates.
+#[test]
+fn test_remove_pyth_price_id_falls_back_to_pushed_feed() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, pyth, oracle) = pyth_pool(&ctx);
+    ctx.client
+        .set_pyth_price_id(&ctx.admin, &feed(&env), &price_id(&env));
+    publish(&pyth, 2_000, 1, 5);
+
+    ctx.client.remove_pyth_price_id(&ctx.admin, &feed(&env));
+    assert_eq!(ctx.client.get_pyth_price_id(&feed(&env)), None);
+
+    let now = env.ledger().timestamp();
+    ctx.client
+        .update_price_feed(&oracle, &feed(&env), &3_500, &1, &(now - 1), &(now + 60));
+    ctx.client.resolve_pool_from_price(&pool_id);
+    assert_eq!(ctx.client.get_pool(&pool_id).outcome, 1);
+}
+
+/// Only admins can bind a pair, and only once the oracle is configured.
+#[test]
+fn test_set_pyth_price_id_requires_admin() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    pyth_pool(&ctx);
+
+    let not_admin = ctx
+        .client
+        .try_set_pyth_price_id(&ctx.operator, &feed(&env), &price_id(&env));
+    assert_eq!(not_admin, Err(Ok(PredifiError::Unauthorized)));
+
+    let fresh = TestContext::new(&env);
+    let uninitialized =
+        fresh
+            .client
+            .try_set_pyth_price_id(&fresh.admin, &feed(&env), &price_id(&env));
+    assert_eq!(uninitialized, Err(Ok(PredifiError::OracleNotInitialized)));
+}
@@ -1,11 +1,14 @@
-//! Referral domain: referrer tracking, referred-volume accounting and the
-//! referral reward configuration.
+//! Referral domain: referrer tracking, referred-volume accounting, the
+//! referral reward configuration, second-level referrals and referral
+//! campaigns.
 
-use soroban_sdk::{contractimpl, Address, Env};
+use soroban_sdk::{contractimpl, Address, Env, Vec};
 
 use crate::{
     DataKey, PredifiContract, PredifiContractArgs, PredifiContractClient, PredifiError,
-    ReferralThresholdUpdatedEvent, ReferrerUpdatedEvent,
+    ReferralCampaign, ReferralCampaignConfig, ReferralCampaignCreatedEvent,
+    ReferralCampaignEndedEvent, ReferralKey, ReferralThresholdUpdatedEvent, ReferrerUpdatedEvent,
+    SecondLevelReferralUpdatedEvent, MAX_OPEN_REFERRAL_CAMPAIGNS,
 };
 
 #[contractimpl]
@@ -168,4 +171,202 @@ impl PredifiContract {
             .get(&DataKey::ReferralMinVolumeBps)
             .unwrap_or(0i128)
     }
+
+    // ── Second-level referrals and campaigns ──────────────────────────────────
+
+    /// Set the cut paid to a referrer's own referrer (their upline) on
+    /// claims by users they referred, in basis points of the referred
+    /// user's protocol fee share. `0` disables second-level rewards.
+    /// Campaigns set their own second-level cut.
+    ///
+    /// # Errors
+    /// * `Unauthorized`  — caller does not hold the Admin role.
+    /// * `InvalidFeeBps` — `second_level_cut_bps` exceeds 10_000.
+    pub fn set_second_level_referral_bps(
+        env: Env,
+        admin: Address,
+        second_level_cut_bps: u32,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        admin.require_auth();
+        Self::require_admin_role(&env, &admin, "set_second_level_referral_bps")?;
+        if second_level_cut_bps > 10_000 {
+            return Err(PredifiError::InvalidFeeBps);
+        }
+
+        env.storage()
+            .instance()
+            .set(&ReferralKey::SecondLevelCutBps, &second_level_cut_bps);
+        Self::extend_instance(&env);
+
+        SecondLevelReferralUpdatedEvent {
+            admin,
+            second_level_cut_bps,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Return the global second-level referral cut (0 if unset).
+    pub fn get_second_level_referral_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&ReferralKey::SecondLevelCutBps)
+            .unwrap_or(0)
+    }
+
+    /// Return the first referrer `user` ever staked under, who earns the
+    /// second-level cut on referrals `user` makes.
+    pub fn get_upline(env: Env, user: Address) -> Option<Address> {
+        env.storage().persistent().get(&ReferralKey::Upline(user))
+    }
+
+    /// Open a referral campaign and return its id.
+    ///
+    /// While the campaign is open, a referred user's first prediction in a
+    /// covered pool is attributed to it (to the earliest open campaign if
+    /// several cover the pool), and the rewards on that referral use the
+    /// campaign's cuts until its budget is spent.
+    ///
+    /// # Errors
+    /// * `Unauthorized`        — caller does not hold the Admin role.
+    /// * `InvalidFeeBps`       — a cut exceeds 10_000.
+    /// * `TimeConstraintError` — `end_time` is not after both `start_time` and now.
+    /// * `InvalidAmount`       — `budget` is not positive.
+    /// * `InvalidData`         — [`MAX_OPEN_REFERRAL_CAMPAIGNS`] campaigns are already open.
+    pub fn create_referral_campaign(
+        env: Env,
+        admin: Address,
+        config: ReferralCampaignConfig,
+    ) -> Result<u32, PredifiError> {
+        Self::require_not_paused(&env)?;
+        admin.require_auth();
+        Self::require_admin_role(&env, &admin, "create_referral_campaign")?;
+        if config.cut_bps > 10_000 || config.second_level_cut_bps > 10_000 {
+            return Err(PredifiError::InvalidFeeBps);
+        }
+        let now = env.ledger().timestamp();
+        if config.end_time <= config.start_time || config.end_time <= now {
+            return Err(PredifiError::TimeConstraintError);
+        }
+        if config.budget <= 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+
+        // Campaigns past their end no longer take referrals.
+        let mut open = Vec::new(&env);
+        for campaign_id in Self::get_open_referral_campaigns(env.clone()).iter() {
+            let ended = env
+                .storage()
+                .persistent()
+                .get::<_, ReferralCampaign>(&ReferralKey::Campaign(campaign_id))
+                .is_none_or(|campaign| campaign.config.end_time <= now);
+            if !ended {
+                open.push_back(campaign_id);
+            }
+        }
+        if open.len() >= MAX_OPEN_REFERRAL_CAMPAIGNS {
+            return Err(PredifiError::InvalidData);
+        }
+
+        let campaign_id: u32 = env
+            .storage()
+            .instance()
+            .get(&ReferralKey::CampaignCount)
+            .unwrap_or(0);
+        let key = ReferralKey::Campaign(campaign_id);
+        env.storage().persistent().set(
+            &key,
+            &ReferralCampaign {
+                config: config.clone(),
+                spent: 0,
+            },
+        );
+        Self::extend_persistent(&env, &key);
+        open.push_back(campaign_id);
+        env.storage()
+            .instance()
+            .set(&ReferralKey::OpenCampaigns, &open);
+        env.storage()
+            .instance()
+            .set(&ReferralKey::CampaignCount, &(campaign_id + 1));
+        Self::extend_instance(&env);
+
+        ReferralCampaignCreatedEvent {
+            admin,
+            campaign_id,
+            token: config.token,
+            cut_bps: config.cut_bps,
+            second_level_cut_bps: config.second_level_cut_bps,
+            start_time: config.start_time,
+            end_time: config.end_time,
+            budget: config.budget,
+        }
+        .publish(&env);
+        Ok(campaign_id)
+    }
+
+    /// Stop a campaign taking new referrals by moving its `end_time` to now.
+    /// Referrals already attributed keep the campaign's cuts until its
+    /// budget is spent.
+    ///
+    /// # Errors
+    /// * `Unauthorized` — caller does not hold the Admin role.
+    /// * `InvalidData`  — no campaign has id `campaign_id`.
+    pub fn end_referral_campaign(
+        env: Env,
+        admin: Address,
+        campaign_id: u32,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        admin.require_auth();
+        Self::require_admin_role(&env, &admin, "end_referral_campaign")?;
+
+        let key = ReferralKey::Campaign(campaign_id);
+        let mut campaign: ReferralCampaign = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(PredifiError::InvalidData)?;
+        let now = env.ledger().timestamp();
+        campaign.config.end_time = campaign.config.end_time.min(now);
+        env.storage().persistent().set(&key, &campaign);
+        Self::extend_persistent(&env, &key);
+
+        let mut open = Self::get_open_referral_campaigns(env.clone());
+        if let Some(index) = open.first_index_of(campaign_id) {
+            open.remove(index);
+            env.storage()
+                .instance()
+                .set(&ReferralKey::OpenCampaigns, &open);
+            Self::extend_instance(&env);
+        }
+
+        ReferralCampaignEndedEvent { admin, campaign_id }.publish(&env);
+        Ok(())
+    }
+
+    /// Return campaign `campaign_id`, or `None` if it does not exist.
+    pub fn get_referral_campaign(env: Env, campaign_id: u32) -> Option<ReferralCampaign> {
+        env.storage()
+            .persistent()
+            .get(&ReferralKey::Campaign(campaign_id))
+    }
+
+    /// Return the ids of campaigns taking referrals now or from a future
+    /// `start_time`. May include campaigns that ended since the last
+    /// `create_referral_campaign`.
+    pub fn get_open_referral_campaigns(env: Env) -> Vec<u32> {
+        env.storage()
+            .instance()
+            .get(&ReferralKey::OpenCampaigns)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Return the campaign `user`'s referral in `pool_id` was made under.
+    pub fn get_referral_attribution(env: Env, user: Address, pool_id: u64) -> Option<u32> {
+        env.storage()
+            .persistent()
+            .get(&ReferralKey::Attribution(user, pool_id))
+    }
 }
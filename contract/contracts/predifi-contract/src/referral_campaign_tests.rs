@@ -0,0 +1,217 @@
+//! Referral campaigns and second-level referrals: attribution to open
+//! campaigns, cuts and budget caps at claim time, and the referrer's upline.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext, START_TIME};
+use crate::{FeeTier, PoolConfig, PredifiError, ReferralCampaignConfig};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, String, Symbol, Vec,
+};
+
+const END_TIME: u64 = 10_000;
+
+/// A 10% protocol fee and no campaigns yet.
+fn set_protocol_fee(ctx: &TestContext) {
+    let tier = FeeTier {
+        stake_threshold: 0,
+        fee_bps: 1_000,
+    };
+    ctx.client
+        .set_fee_tiers(&ctx.admin, &vec![&ctx.client.env, tier]);
+}
+
+fn create_pool(ctx: &TestContext, category: Symbol) -> u64 {
+    let env = &ctx.client.env;
+    let config = PoolConfig {
+        description: String::from_str(env, "Match winner"),
+        metadata_url: String::from_str(env, "ipfs://campaign"),
+        outcome_descriptions: vec![
+            env,
+            String::from_str(env, "Home"),
+            String::from_str(env, "Away"),
+        ],
+        ..default_pool_config(env)
+    };
+    ctx.client.create_pool(
+        &ctx.creator,
+        &END_TIME,
+        &ctx.token_address,
+        &2u32,
+        &category,
+        &config,
+    )
+}
+
+/// A 50% / 20% campaign on any pool in the setup token, open until
+/// `END_TIME`.
+fn campaign(ctx: &TestContext, budget: i128) -> ReferralCampaignConfig {
+    ReferralCampaignConfig {
+        token: ctx.token_address.clone(),
+        cut_bps: 5_000,
+        second_level_cut_bps: 2_000,
+        start_time: START_TIME,
+        end_time: END_TIME,
+        pool_ids: Vec::new(&ctx.client.env),
+        categories: Vec::new(&ctx.client.env),
+        budget,
+    }
+}
+
+fn stake(ctx: &TestContext, user: &Address, pool_id: u64, outcome: u32, referrer: &Address) {
+    ctx.token_admin_client.mint(user, &1_000);
+    ctx.client.place_prediction(
+        user,
+        &pool_id,
+        &1_000,
+        &outcome,
+        &Some(referrer.clone()),
+        &None,
+    );
+}
+
+/// `referrer` (referred by `upline`) backs Away; `winner` (referred by
+/// `referrer`) backs Home, which wins the 200 fee pool. Returns
+/// `(pool_id, upline, referrer, winner)`.
+fn referred_pool(ctx: &TestContext) -> (u64, Address, Address, Address) {
+    let env = &ctx.client.env;
+    let pool_id = create_pool(ctx, symbol_short!("Sports"));
+    let upline = Address::generate(env);
+    let referrer = Address::generate(env);
+    let winner = Address::generate(env);
+    stake(ctx, &referrer, pool_id, 1, &upline);
+    stake(ctx, &winner, pool_id, 0, &referrer);
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &0u32);
+    (pool_id, upline, referrer, winner)
+}
+
+/// Both levels are paid at campaign rates and charged to its budget; the
+/// treasury keeps the rest of the fee.
+#[test]
+fn test_campaign_pays_both_levels() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    set_protocol_fee(&ctx);
+    let campaign_id = ctx
+        .client
+        .create_referral_campaign(&ctx.admin, &campaign(&ctx, 1_000));
+    let (pool_id, upline, referrer, winner) = referred_pool(&ctx);
+
+    assert_eq!(
+        ctx.client.get_referral_attribution(&winner, &pool_id),
+        Some(campaign_id)
+    );
+    assert_eq!(ctx.client.get_upline(&referrer), Some(upline.clone()));
+
+    assert_eq!(ctx.client.claim_winnings(&winner, &pool_id), 1_800);
+    assert_eq!(ctx.token.balance(&referrer), 50);
+    assert_eq!(ctx.token.balance(&upline), 20);
+    assert_eq!(ctx.client.get_accrued_fees(&ctx.token_address), 130);
+    let stored = ctx.client.get_referral_campaign(&campaign_id).unwrap();
+    assert_eq!(stored.spent, 70);
+}
+
+/// Rewards stop at the campaign budget, and a spent campaign takes no new
+/// referrals.
+#[test]
+fn test_campaign_budget_caps_rewards() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    set_protocol_fee(&ctx);
+    let campaign_id = ctx
+        .client
+        .create_referral_campaign(&ctx.admin, &campaign(&ctx, 60));
+    let (pool_id, upline, referrer, winner) = referred_pool(&ctx);
+
+    ctx.client.claim_winnings(&winner, &pool_id);
+    assert_eq!(ctx.token.balance(&referrer), 50);
+    assert_eq!(ctx.token.balance(&upline), 10);
+    let stored = ctx.client.get_referral_campaign(&campaign_id).unwrap();
+    assert_eq!(stored.spent, 60);
+
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    let next_pool = create_pool(&ctx, symbol_short!("Sports"));
+    let late = Address::generate(&env);
+    stake(&ctx, &late, next_pool, 0, &referrer);
+    assert_eq!(ctx.client.get_referral_attribution(&late, &next_pool), None);
+}
+
+/// Campaigns only take referrals in their token, pools, categories and
+/// time window; outside one, the global second-level cut applies.
+#[test]
+fn test_campaign_coverage_and_end() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    set_protocol_fee(&ctx);
+    let mut crypto_only = campaign(&ctx, 1_000);
+    crypto_only.categories = vec![&env, symbol_short!("Crypto")];
+    let crypto_id = ctx
+        .client
+        .create_referral_campaign(&ctx.admin, &crypto_only);
+    let mut later = campaign(&ctx, 1_000);
+    later.start_time = 5_000;
+    let later_id = ctx.client.create_referral_campaign(&ctx.admin, &later);
+    let mut other_token = campaign(&ctx, 1_000);
+    other_token.token = Address::generate(&env);
+    ctx.client
+        .create_referral_campaign(&ctx.admin, &other_token);
+    ctx.client
+        .set_second_level_referral_bps(&ctx.admin, &1_000u32);
+
+    let crypto_pool = create_pool(&ctx, symbol_short!("Crypto"));
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    ctx.client.end_referral_campaign(&ctx.admin, &crypto_id);
+    let user = Address::generate(&env);
+    stake(&ctx, &user, crypto_pool, 0, &ctx.creator);
+    assert_eq!(
+        ctx.client.get_referral_attribution(&user, &crypto_pool),
+        None
+    );
+    let ended = ctx.client.get_referral_campaign(&crypto_id).unwrap();
+    assert_eq!(ended.config.end_time, 2_000);
+    let open = ctx.client.get_open_referral_campaigns();
+    assert!(!open.contains(crypto_id) && open.contains(later_id));
+
+    let (pool_id, upline, _, winner) = referred_pool(&ctx);
+    assert_eq!(ctx.client.get_referral_attribution(&winner, &pool_id), None);
+    ctx.client.claim_winnings(&winner, &pool_id);
+    assert_eq!(ctx.token.balance(&upline), 10);
+}
+
+/// Cuts are capped at 100%, the window must end in the future, budgets
+/// must be positive, and campaigns are admin-only.
+#[test]
+fn test_campaign_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    set_protocol_fee(&ctx);
+
+    let mut too_high = campaign(&ctx, 1_000);
+    too_high.second_level_cut_bps = 10_001;
+    let result = ctx
+        .client
+        .try_create_referral_campaign(&ctx.admin, &too_high);
+    assert_eq!(result, Err(Ok(PredifiError::InvalidFeeBps)));
+
+    let mut over = campaign(&ctx, 1_000);
+    over.end_time = 1_000;
+    let result = ctx.client.try_create_referral_campaign(&ctx.admin, &over);
+    assert_eq!(result, Err(Ok(PredifiError::TimeConstraintError)));
+
+    let result = ctx
+        .client
+        .try_create_referral_campaign(&ctx.admin, &campaign(&ctx, 0));
+    assert_eq!(result, Err(Ok(PredifiError::InvalidAmount)));
+
+    let result = ctx
+        .client
+        .try_create_referral_campaign(&ctx.operator, &campaign(&ctx, 1_000));
+    assert_eq!(result, Err(Ok(PredifiError::Unauthorized)));
+
+    let result = ctx.client.try_end_referral_campaign(&ctx.admin, &7u32);
+    assert_eq!(result, Err(Ok(PredifiError::InvalidData)));
+}
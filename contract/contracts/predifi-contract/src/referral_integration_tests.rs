@@ -6,7 +6,7 @@
 #![cfg(test)]
 
 use crate::test::ROLE_ADMIN;
-use crate::PoolConfig;
+use crate::{PoolConfig, PricingMode};
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Ledger},
@@ -43,6 +43,10 @@ fn make_pool(
                 String::from_str(env, "No"),
                 String::from_str(env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     )
 }
@@ -96,7 +100,9 @@ fn test_referral_cut_paid_on_claim() {
     let client = crate::PredifiContractClient::new(&env, &contract_id);
 
     let token_admin_addr = Address::generate(&env);
-    let token_contract = env.register_stellar_asset_contract(token_admin_addr.clone());
+    let token_contract = env
+        .register_stellar_asset_contract_v2(token_admin_addr.clone())
+        .address();
     let token = token::Client::new(&env, &token_contract);
     let token_admin_client = token::StellarAssetClient::new(&env, &token_contract);
     let token_address = token_contract;
@@ -133,6 +139,8 @@ fn test_referral_cut_paid_on_claim() {
     assert_eq!(winnings, 980);
     assert_eq!(token.balance(&referred), 980);
     assert_eq!(token.balance(&referrer), 10);
+    // Only the fee left after the referral cut is withdrawable.
+    assert_eq!(client.get_accrued_fees(&token_address), 10);
 }
 
 /// A user must not be able to refer themselves.
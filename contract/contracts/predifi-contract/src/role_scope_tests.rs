@@ -0,0 +1,80 @@
+//! Operator grants scoped to one pool or one category: they resolve the
+//! pools they cover and confer no other operator powers.
+
+#![cfg(test)]
+
+use crate::test::ROLE_OPERATOR;
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{PoolConfig, PredifiError};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, String, Symbol,
+};
+
+const END_TIME: u64 = 10_000;
+
+fn create_pool(ctx: &TestContext, category: Symbol) -> u64 {
+    let env = &ctx.client.env;
+    let config = PoolConfig {
+        description: String::from_str(env, "Match winner"),
+        metadata_url: String::from_str(env, "ipfs://scoped"),
+        outcome_descriptions: vec![
+            env,
+            String::from_str(env, "Home"),
+            String::from_str(env, "Away"),
+        ],
+        ..default_pool_config(env)
+    };
+    ctx.client.create_pool(
+        &ctx.creator,
+        &END_TIME,
+        &ctx.token_address,
+        &2u32,
+        &category,
+        &config,
+    )
+}
+
+/// A Sports desk resolves Sports pools and the one pool it was handed, but
+/// nothing else.
+#[test]
+fn test_scoped_operator_resolves_covered_pools_only() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let sports = create_pool(&ctx, symbol_short!("Sports"));
+    let crypto = create_pool(&ctx, symbol_short!("Crypto"));
+    let handed = create_pool(&ctx, symbol_short!("Politics"));
+
+    let desk = Address::generate(&env);
+    ctx.ac_client
+        .grant_category_role(&desk, &ROLE_OPERATOR, &symbol_short!("Sports"));
+    ctx.ac_client
+        .grant_pool_role(&desk, &ROLE_OPERATOR, &handed);
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.resolve_pool(&desk, &sports, &0u32);
+    ctx.client.resolve_pool(&desk, &handed, &1u32);
+    assert_eq!(ctx.client.get_pool(&sports).outcome, 0);
+    assert_eq!(ctx.client.get_pool(&handed).outcome, 1);
+
+    let denied = ctx.client.try_resolve_pool(&desk, &crypto, &0u32);
+    assert_eq!(denied, Err(Ok(PredifiError::Unauthorized)));
+}
+
+/// A scoped grant covers resolution only; other operator actions still need
+/// the global role.
+#[test]
+fn test_scoped_operator_has_no_other_operator_powers() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = create_pool(&ctx, symbol_short!("Sports"));
+    let desk = Address::generate(&env);
+    ctx.ac_client
+        .grant_pool_role(&desk, &ROLE_OPERATOR, &pool_id);
+
+    let limits = ctx.client.try_set_stake_limits(&desk, &pool_id, &10, &100);
+    assert_eq!(limits, Err(Ok(PredifiError::Unauthorized)));
+    let exit_fee = ctx.client.try_set_exit_fee_bps(&desk, &pool_id, &0u32);
+    assert_eq!(exit_fee, Err(Ok(PredifiError::Unauthorized)));
+}
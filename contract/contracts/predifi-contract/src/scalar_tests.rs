@@ -0,0 +1,156 @@
+//! Scalar pools: a price range settled linearly through
+//! `resolve_pool_from_price`, clamped at the bounds.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{PoolConfig, PredifiError};
+use soroban_sdk::{testutils::Ledger, vec, Address, Env, String, Symbol};
+
+const END_TIME: u64 = 10_000;
+const LOWER: i128 = 2_000;
+const UPPER: i128 = 4_000;
+
+fn scalar_config(env: &Env, outcomes: u32) -> PoolConfig {
+    let mut outcome_descriptions = vec![
+        env,
+        String::from_str(env, "Short"),
+        String::from_str(env, "Long"),
+    ];
+    for _ in 2..outcomes {
+        outcome_descriptions.push_back(String::from_str(env, "Other"));
+    }
+    PoolConfig {
+        outcome_descriptions,
+        ..default_pool_config(env)
+    }
+}
+
+fn feed(env: &Env) -> Symbol {
+    Symbol::new(env, "ETH_USD")
+}
+
+/// A binary pool ranged over `LOWER..UPPER` on `feed`, and the oracle that
+/// reports it. Returns `(pool_id, oracle)`.
+fn scalar_pool(ctx: &TestContext) -> (u64, Address) {
+    let env = &ctx.client.env;
+    let oracle = ctx.add_price_oracle(3_600, 500);
+    let pool_id = ctx.create_pool(END_TIME, &scalar_config(env, 2));
+    ctx.client
+        .set_scalar_range(&ctx.operator, &pool_id, &feed(env), &LOWER, &UPPER);
+    (pool_id, oracle)
+}
+
+fn settle_at(ctx: &TestContext, oracle: &Address, pool_id: u64, price: i128) {
+    let env = &ctx.client.env;
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 10);
+    ctx.client.update_price_feed(
+        oracle,
+        &feed(env),
+        &price,
+        &1,
+        &(END_TIME + 5),
+        &(END_TIME + 100),
+    );
+    ctx.client.resolve_pool_from_price(&pool_id);
+}
+
+/// A price a quarter of the way into the range pays the long side a quarter
+/// of the pool and the short side the rest.
+#[test]
+fn test_scalar_pool_splits_payout_linearly() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, oracle) = scalar_pool(&ctx);
+    let long_a = ctx.staker(pool_id, 200, 1);
+    let long_b = ctx.staker(pool_id, 100, 1);
+    let short = ctx.staker(pool_id, 100, 0);
+
+    settle_at(&ctx, &oracle, pool_id, 2_500);
+
+    let range = ctx.client.get_scalar_range(&pool_id).unwrap();
+    assert_eq!(range.settlement_bps, Some(2_500));
+    assert_eq!(ctx.client.get_pool(&pool_id).outcome, 0);
+
+    assert_eq!(ctx.client.claim_winnings(&long_a, &pool_id), 66);
+    assert_eq!(ctx.client.claim_winnings(&long_b, &pool_id), 33);
+    assert_eq!(ctx.client.claim_winnings(&short, &pool_id), 300);
+    assert_eq!(ctx.token.balance(&short), 300);
+}
+
+/// Prices outside the range clamp to the nearest bound.
+#[test]
+fn test_scalar_price_outside_range_clamps() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, oracle) = scalar_pool(&ctx);
+    let long = ctx.staker(pool_id, 100, 1);
+    let short = ctx.staker(pool_id, 300, 0);
+
+    settle_at(&ctx, &oracle, pool_id, 5_000);
+
+    let range = ctx.client.get_scalar_range(&pool_id).unwrap();
+    assert_eq!(range.settlement_bps, Some(10_000));
+    assert_eq!(ctx.client.get_pool(&pool_id).outcome, 1);
+    assert_eq!(ctx.client.claim_winnings(&long, &pool_id), 400);
+    assert_eq!(ctx.client.claim_winnings(&short, &pool_id), 0);
+}
+
+/// With nobody on the other side, stakers get their stake back wherever the
+/// price lands.
+#[test]
+fn test_scalar_one_sided_pool_returns_stake() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, oracle) = scalar_pool(&ctx);
+    let long = ctx.staker(pool_id, 100, 1);
+
+    settle_at(&ctx, &oracle, pool_id, LOWER);
+
+    assert_eq!(
+        ctx.client
+            .get_scalar_range(&pool_id)
+            .unwrap()
+            .settlement_bps,
+        Some(0)
+    );
+    assert_eq!(ctx.client.claim_winnings(&long, &pool_id), 100);
+}
+
+/// Ranges need positive, ordered bounds on an active binary pool; a price
+/// condition replaces the range.
+#[test]
+fn test_set_scalar_range_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, _oracle) = scalar_pool(&ctx);
+
+    let zero_lower =
+        ctx.client
+            .try_set_scalar_range(&ctx.operator, &pool_id, &feed(&env), &0, &UPPER);
+    assert_eq!(zero_lower, Err(Ok(PredifiError::InvalidTargetPrice)));
+
+    let inverted =
+        ctx.client
+            .try_set_scalar_range(&ctx.operator, &pool_id, &feed(&env), &UPPER, &LOWER);
+    assert_eq!(inverted, Err(Ok(PredifiError::InvalidData)));
+
+    let not_operator =
+        ctx.client
+            .try_set_scalar_range(&ctx.creator, &pool_id, &feed(&env), &LOWER, &UPPER);
+    assert_eq!(not_operator, Err(Ok(PredifiError::Unauthorized)));
+
+    let three_way = ctx.create_pool(END_TIME, &scalar_config(&env, 3));
+    let multi =
+        ctx.client
+            .try_set_scalar_range(&ctx.operator, &three_way, &feed(&env), &LOWER, &UPPER);
+    assert_eq!(multi, Err(Ok(PredifiError::InvalidOutcome)));
+
+    let range = ctx.client.get_scalar_range(&pool_id).unwrap();
+    assert_eq!((range.lower, range.upper), (LOWER, UPPER));
+    assert_eq!(range.settlement_bps, None);
+
+    ctx.client
+        .set_price_condition(&ctx.operator, &pool_id, &feed(&env), &3_000, &1, &0);
+    assert_eq!(ctx.client.get_scalar_range(&pool_id), None);
+}
@@ -0,0 +1,295 @@
+//! Outcome shares domain: stakes exposed as fungible shares (one share per
+//! staked unit) with SEP-41 style allowances, and a factory deploying one
+//! `outcome-token` contract per pool outcome so wallets and DEXes can hold
+//! and move positions as ordinary tokens.
+//!
+//! `DataKey::Pred` / `PoolKey::Hedges` stay the share ledger: staking mints
+//! shares, `transfer_prediction` moves them and claiming burns every share the
+//! user holds in the pool.
+
+use soroban_sdk::{contractimpl, token, Address, BytesN, Env, String, Vec};
+
+use crate::{
+    DataKey, Invite, OutcomeTokenWasmSetEvent, OutcomeTokensDeployedEvent, Pool,
+    PredictionTransferredEvent, PredifiContract, PredifiContractArgs, PredifiContractClient,
+    PredifiError, ShareAllowance, ShareKey, SharesApprovedEvent,
+};
+
+#[contractimpl]
+impl PredifiContract {
+    /// Shares `owner` holds on `outcome` in `pool_id`: their stake on that
+    /// outcome, or 0 once they have claimed from the pool.
+    pub fn share_balance(env: Env, pool_id: u64, outcome: u32, owner: Address) -> i128 {
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Claimed(owner.clone(), pool_id))
+        {
+            return 0;
+        }
+        Self::load_position(&env, &owner, pool_id)
+            .iter()
+            .find(|stake| stake.outcome == outcome)
+            .map_or(0, |stake| stake.amount)
+    }
+
+    /// Allow `spender` to move up to `amount` of `owner`'s shares on
+    /// `outcome` in `pool_id` until `expiration_ledger`, replacing any
+    /// previous allowance. An `amount` of 0 revokes it.
+    ///
+    /// # Errors
+    /// - `InvalidAmount` – `amount` is negative.
+    /// - `TimeConstraintError` – `amount` is positive and `expiration_ledger`
+    ///   is before the current ledger.
+    pub fn approve_shares(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        pool_id: u64,
+        outcome: u32,
+        amount: i128,
+        expiration_ledger: u32,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        owner.require_auth();
+        if amount < 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+        let ledger = env.ledger().sequence();
+        if amount > 0 && expiration_ledger < ledger {
+            return Err(PredifiError::TimeConstraintError);
+        }
+
+        let key = ShareKey::Allowance(owner.clone(), spender.clone(), pool_id, outcome);
+        if amount == 0 {
+            env.storage().temporary().remove(&key);
+        } else {
+            let allowance = ShareAllowance {
+                amount,
+                expiration_ledger,
+            };
+            env.storage().temporary().set(&key, &allowance);
+            let live_for = expiration_ledger - ledger;
+            env.storage()
+                .temporary()
+                .extend_ttl(&key, live_for, live_for);
+        }
+
+        SharesApprovedEvent {
+            pool_id,
+            owner,
+            spender,
+            outcome,
+            amount,
+            expiration_ledger,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Shares on `outcome` in `pool_id` that `spender` may still move for
+    /// `owner`; 0 once the allowance has expired.
+    pub fn share_allowance(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        pool_id: u64,
+        outcome: u32,
+    ) -> i128 {
+        Self::read_share_allowance(&env, &owner, &spender, pool_id, outcome).amount
+    }
+
+    /// Move `amount` of `from`'s shares on `outcome` in `pool_id` to `to`,
+    /// spending `spender`'s allowance. Otherwise identical to
+    /// [`Self::transfer_prediction`].
+    ///
+    /// # Errors
+    /// - `Unauthorized` – the allowance is below `amount`, or the pool is
+    ///   private and `to` has no access.
+    /// - Any error of [`Self::transfer_prediction`].
+    pub fn transfer_shares_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        pool_id: u64,
+        outcome: u32,
+        amount: i128,
+        invite: Option<Invite>,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        spender.require_auth();
+
+        let (pool_key, mut pool) = Self::load_tradable_pool(&env, pool_id)?;
+        let mut allowance = Self::read_share_allowance(&env, &from, &spender, pool_id, outcome);
+        if amount > allowance.amount {
+            return Err(PredifiError::Unauthorized);
+        }
+        Self::move_position(
+            &env, &mut pool, pool_id, &from, &to, outcome, amount, &invite,
+        )?;
+
+        allowance.amount -= amount;
+        let allowance_key = ShareKey::Allowance(from.clone(), spender, pool_id, outcome);
+        env.storage().temporary().set(&allowance_key, &allowance);
+        env.storage().persistent().set(&pool_key, &pool);
+        Self::bump_ttl(&env, &pool_key);
+
+        PredictionTransferredEvent {
+            pool_id,
+            from,
+            to,
+            outcome,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Set the `outcome-token` wasm deployed by
+    /// [`Self::deploy_outcome_tokens`]. Tokens already deployed are not
+    /// affected. Caller must have Admin role (0).
+    ///
+    /// # Errors
+    /// - `Unauthorized` – caller lacks Admin role (0).
+    pub fn set_outcome_token_wasm(
+        env: Env,
+        admin: Address,
+        wasm_hash: BytesN<32>,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        admin.require_auth();
+        Self::require_admin_role(&env, &admin, "set_outcome_token_wasm")?;
+
+        env.storage()
+            .instance()
+            .set(&ShareKey::TokenWasm, &wasm_hash);
+        Self::extend_instance(&env);
+
+        OutcomeTokenWasmSetEvent { admin, wasm_hash }.publish(&env);
+        Ok(())
+    }
+
+    /// Deploy one SEP-41 token per outcome of `pool_id`, each serving the
+    /// pool's shares on that outcome. Callable by anyone, once per pool.
+    ///
+    /// Tokens are deployed at addresses derived from this contract, `pool_id`
+    /// and the outcome, take the pool token's decimals, are named after the
+    /// outcome description and use the symbol `P<pool_id>O<outcome>`.
+    ///
+    /// # Errors
+    /// - `PoolNotFound` – `pool_id` does not exist.
+    /// - `AlreadyInitializedOrConfigNotSet` – no wasm is set, or the pool's
+    ///   tokens are already deployed.
+    pub fn deploy_outcome_tokens(env: Env, pool_id: u64) -> Result<Vec<Address>, PredifiError> {
+        Self::require_not_paused(&env)?;
+
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        let wasm_hash: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&ShareKey::TokenWasm)
+            .ok_or(PredifiError::AlreadyInitializedOrConfigNotSet)?;
+        if env.storage().persistent().has(&ShareKey::Token(pool_id, 0)) {
+            return Err(PredifiError::AlreadyInitializedOrConfigNotSet);
+        }
+
+        let decimals = token::Client::new(&env, &pool.token).decimals();
+        let mut tokens = Vec::new(&env);
+        for outcome in 0..pool.options_count {
+            let name = pool
+                .outcome_descriptions
+                .get(outcome)
+                .unwrap_or_else(|| Self::share_symbol(&env, pool_id, outcome));
+            let address = env
+                .deployer()
+                .with_current_contract(Self::share_token_salt(&env, pool_id, outcome))
+                .deploy_v2(
+                    wasm_hash.clone(),
+                    (
+                        env.current_contract_address(),
+                        pool_id,
+                        outcome,
+                        decimals,
+                        name,
+                        Self::share_symbol(&env, pool_id, outcome),
+                    ),
+                );
+
+            let key = ShareKey::Token(pool_id, outcome);
+            env.storage().persistent().set(&key, &address);
+            Self::extend_persistent(&env, &key);
+            tokens.push_back(address);
+        }
+
+        OutcomeTokensDeployedEvent {
+            pool_id,
+            tokens: tokens.clone(),
+        }
+        .publish(&env);
+
+        Ok(tokens)
+    }
+
+    /// Token contract serving the shares on `outcome` in `pool_id`, if
+    /// deployed.
+    pub fn get_outcome_token(env: Env, pool_id: u64, outcome: u32) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&ShareKey::Token(pool_id, outcome))
+    }
+
+    /// The live allowance, or a zero one if unset or expired.
+    fn read_share_allowance(
+        env: &Env,
+        owner: &Address,
+        spender: &Address,
+        pool_id: u64,
+        outcome: u32,
+    ) -> ShareAllowance {
+        let key = ShareKey::Allowance(owner.clone(), spender.clone(), pool_id, outcome);
+        match env.storage().temporary().get::<_, ShareAllowance>(&key) {
+            Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => allowance,
+            _ => ShareAllowance {
+                amount: 0,
+                expiration_ledger: 0,
+            },
+        }
+    }
+
+    /// `pool_id` (big-endian) followed by `outcome` (big-endian), zero padded.
+    fn share_token_salt(env: &Env, pool_id: u64, outcome: u32) -> BytesN<32> {
+        let mut salt = [0u8; 32];
+        salt[..8].copy_from_slice(&pool_id.to_be_bytes());
+        salt[8..12].copy_from_slice(&outcome.to_be_bytes());
+        BytesN::from_array(env, &salt)
+    }
+
+    /// `P<pool_id>O<outcome>` in decimal, e.g. `P12O1`.
+    fn share_symbol(env: &Env, pool_id: u64, outcome: u32) -> String {
+        let mut buf = [0u8; 32];
+        let mut len = 0;
+        for (prefix, value) in [(b'P', pool_id), (b'O', outcome as u64)] {
+            buf[len] = prefix;
+            len += 1;
+            let start = len;
+            let mut rest = value;
+            loop {
+                buf[len] = b'0' + (rest % 10) as u8;
+                len += 1;
+                rest /= 10;
+                if rest == 0 {
+                    break;
+                }
+            }
+            buf[start..len].reverse();
+        }
+        String::from_bytes(env, &buf[..len])
+    }
+}
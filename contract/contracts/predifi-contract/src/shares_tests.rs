@@ -0,0 +1,148 @@
+//! Outcome shares: `share_balance` following a position, allowances spent
+//! by `transfer_shares_from`, and the outcome token factory.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{PoolConfig, PredifiError};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env, String,
+};
+
+const END_TIME: u64 = 10_000;
+
+/// A two-outcome pool open until `END_TIME`. Returns `pool_id`.
+fn share_pool(ctx: &TestContext) -> u64 {
+    let env = &ctx.client.env;
+    let config = PoolConfig {
+        description: String::from_str(env, "Match winner"),
+        metadata_url: String::from_str(env, "ipfs://shares"),
+        outcome_descriptions: vec![
+            env,
+            String::from_str(env, "Home"),
+            String::from_str(env, "Away"),
+        ],
+        ..default_pool_config(env)
+    };
+    ctx.create_pool(END_TIME, &config)
+}
+
+/// Staking mints shares, transfers move them and claiming burns them.
+#[test]
+fn test_share_balance_follows_position() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = share_pool(&ctx);
+    let alice = ctx.staker(pool_id, 1_000, 0);
+    let bob = Address::generate(&env);
+
+    assert_eq!(ctx.client.share_balance(&pool_id, &0u32, &alice), 1_000);
+    assert_eq!(ctx.client.share_balance(&pool_id, &1u32, &alice), 0);
+
+    ctx.client
+        .transfer_prediction(&alice, &bob, &pool_id, &0u32, &400, &None);
+    assert_eq!(ctx.client.share_balance(&pool_id, &0u32, &alice), 600);
+    assert_eq!(ctx.client.share_balance(&pool_id, &0u32, &bob), 400);
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &0u32);
+    assert_eq!(ctx.client.claim_winnings(&bob, &pool_id), 400);
+    assert_eq!(ctx.client.share_balance(&pool_id, &0u32, &bob), 0);
+    assert_eq!(ctx.client.share_balance(&pool_id, &0u32, &alice), 600);
+}
+
+/// `transfer_shares_from` spends the allowance, which lapses after its
+/// expiration ledger.
+#[test]
+fn test_transfer_shares_from_spends_allowance() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = share_pool(&ctx);
+    let owner = ctx.staker(pool_id, 1_000, 1);
+    let spender = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let expiration = env.ledger().sequence() + 100;
+
+    ctx.client
+        .approve_shares(&owner, &spender, &pool_id, &1u32, &500, &expiration);
+    ctx.client
+        .transfer_shares_from(&spender, &owner, &buyer, &pool_id, &1u32, &300, &None);
+    assert_eq!(
+        ctx.client
+            .share_allowance(&owner, &spender, &pool_id, &1u32),
+        200
+    );
+    assert_eq!(ctx.client.share_balance(&pool_id, &1u32, &owner), 700);
+    assert_eq!(ctx.client.share_balance(&pool_id, &1u32, &buyer), 300);
+    assert_eq!(ctx.client.get_pool(&pool_id).participants_count, 2);
+
+    let over = ctx
+        .client
+        .try_transfer_shares_from(&spender, &owner, &buyer, &pool_id, &1u32, &201, &None);
+    assert_eq!(over, Err(Ok(PredifiError::Unauthorized)));
+    let other_outcome = ctx
+        .client
+        .try_transfer_shares_from(&spender, &owner, &buyer, &pool_id, &0u32, &1, &None);
+    assert_eq!(other_outcome, Err(Ok(PredifiError::Unauthorized)));
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number = expiration + 1);
+    assert_eq!(
+        ctx.client
+            .share_allowance(&owner, &spender, &pool_id, &1u32),
+        0
+    );
+}
+
+/// Allowances cannot be negative or already expired, and 0 revokes them.
+#[test]
+fn test_approve_shares_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = share_pool(&ctx);
+    let owner = ctx.staker(pool_id, 1_000, 0);
+    let spender = Address::generate(&env);
+    env.ledger().with_mut(|li| li.sequence_number = 50);
+
+    let negative = ctx
+        .client
+        .try_approve_shares(&owner, &spender, &pool_id, &0u32, &-1, &100);
+    assert_eq!(negative, Err(Ok(PredifiError::InvalidAmount)));
+    let expired = ctx
+        .client
+        .try_approve_shares(&owner, &spender, &pool_id, &0u32, &10, &49);
+    assert_eq!(expired, Err(Ok(PredifiError::TimeConstraintError)));
+
+    ctx.client
+        .approve_shares(&owner, &spender, &pool_id, &0u32, &10, &100);
+    ctx.client
+        .approve_shares(&owner, &spender, &pool_id, &0u32, &0, &0);
+    assert_eq!(
+        ctx.client
+            .share_allowance(&owner, &spender, &pool_id, &0u32),
+        0
+    );
+}
+
+/// Outcome tokens can only be deployed once an admin has set their wasm.
+#[test]
+fn test_deploy_outcome_tokens_requires_wasm() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let pool_id = share_pool(&ctx);
+    let unset = ctx.client.try_deploy_outcome_tokens(&pool_id);
+    assert_eq!(
+        unset,
+        Err(Ok(PredifiError::AlreadyInitializedOrConfigNotSet))
+    );
+    let missing = ctx.client.try_deploy_outcome_tokens(&(pool_id + 1));
+    assert_eq!(missing, Err(Ok(PredifiError::PoolNotFound)));
+
+    let wasm_hash = BytesN::from_array(&env, &[7; 32]);
+    let stranger = Address::generate(&env);
+    let denied = ctx.client.try_set_outcome_token_wasm(&stranger, &wasm_hash);
+    assert_eq!(denied, Err(Ok(PredifiError::Unauthorized)));
+    ctx.client.set_outcome_token_wasm(&ctx.admin, &wasm_hash);
+    assert_eq!(ctx.client.get_outcome_token(&pool_id, &0u32), None);
+}
@@ -14,7 +14,7 @@
 mod tests {
     use crate::{
         price_feed_simple::{PriceFeedAdapter, SimpleOracleConfig, SimplePriceFeed},
-        DataKey, PoolConfig, PredifiContract, PredifiContractClient,
+        DataKey, PoolConfig, PredifiContract, PredifiContractClient, PricingMode,
     };
     use soroban_sdk::{
         symbol_short,
@@ -163,6 +163,8 @@ mod tests {
         let oracle = Address::generate(&env);
         let pair = symbol_short!("ETHUSD");
         let ts = env.ledger().timestamp();
+        // Pushed prices must be timestamped strictly before the ledger.
+        env.ledger().with_mut(|l| l.timestamp = ts + 1);
 
         env.as_contract(&contract_id, || {
             PriceFeedAdapter::update_price_feed(&env, &oracle, pair.clone(), 3000, 10, ts, ts + 60)
@@ -234,8 +236,7 @@ mod tests {
         });
 
         env.as_contract(&contract_id, || {
-            let cfg: Option<SimpleOracleConfig> =
-                env.storage().persistent().get(&DataKey::OracleConfig);
+            let cfg: Option<SimpleOracleConfig> = PriceFeedAdapter::get_oracle_config(&env);
             let cfg = cfg.expect("OracleConfig must be present");
             assert_eq!(cfg.pyth_contract, pyth);
             assert_eq!(cfg.max_price_age, 300);
@@ -253,6 +254,8 @@ mod tests {
         let oracle = Address::generate(&env);
         let pair = symbol_short!("ETHUSD");
         let ts = env.ledger().timestamp();
+        // Pushed prices must be timestamped strictly before the ledger.
+        env.ledger().with_mut(|l| l.timestamp = ts + 1);
 
         env.as_contract(&contract_id, || {
             PriceFeedAdapter::update_price_feed(&env, &oracle, pair.clone(), 3000, 10, ts, ts + 60)
@@ -260,10 +263,7 @@ mod tests {
         });
 
         env.as_contract(&contract_id, || {
-            let feed: Option<SimplePriceFeed> = env
-                .storage()
-                .persistent()
-                .get(&DataKey::PriceFeed(pair.clone()));
+            let feed: Option<SimplePriceFeed> = PriceFeedAdapter::get_price_feed(&env, &pair);
             let feed = feed.expect("PriceFeed must be present");
             assert_eq!(feed.pair, pair);
             assert_eq!(feed.price, 3000);
@@ -447,6 +447,8 @@ mod tests {
         let eth = symbol_short!("ETHUSD");
         let btc = symbol_short!("BTCUSD");
         let ts = env.ledger().timestamp();
+        // Pushed prices must be timestamped strictly before the ledger.
+        env.ledger().with_mut(|l| l.timestamp = ts + 1);
 
         env.as_contract(&contract_id, || {
             PriceFeedAdapter::update_price_feed(&env, &oracle1, eth.clone(), 3000, 5, ts, ts + 60)
@@ -464,16 +466,8 @@ mod tests {
         });
 
         env.as_contract(&contract_id, || {
-            let eth_feed: SimplePriceFeed = env
-                .storage()
-                .persistent()
-                .get(&DataKey::PriceFeed(eth.clone()))
-                .unwrap();
-            let btc_feed: SimplePriceFeed = env
-                .storage()
-                .persistent()
-                .get(&DataKey::PriceFeed(btc.clone()))
-                .unwrap();
+            let eth_feed: SimplePriceFeed = PriceFeedAdapter::get_price_feed(&env, &eth).unwrap();
+            let btc_feed: SimplePriceFeed = PriceFeedAdapter::get_price_feed(&env, &btc).unwrap();
 
             assert_eq!(eth_feed.price, 3000);
             assert_eq!(btc_feed.price, 60000);
@@ -493,7 +487,7 @@ mod tests {
         let oracle = Address::generate(&env);
         let pair = symbol_short!("ETHUSD");
         let ts = 1000u64;
-        env.ledger().with_mut(|l| l.timestamp = ts);
+        env.ledger().with_mut(|l| l.timestamp = ts + 1);
 
         env.as_contract(&contract_id, || {
             PriceFeedAdapter::update_price_feed(&env, &oracle, pair.clone(), 3000, 10, ts, ts + 30)
@@ -504,11 +498,7 @@ mod tests {
         env.ledger().with_mut(|l| l.timestamp = ts + 60);
 
         env.as_contract(&contract_id, || {
-            let feed: SimplePriceFeed = env
-                .storage()
-                .persistent()
-                .get(&DataKey::PriceFeed(pair.clone()))
-                .unwrap();
+            let feed: SimplePriceFeed = PriceFeedAdapter::get_price_feed(&env, &pair).unwrap();
             assert!(
                 !PriceFeedAdapter::is_price_valid(&env, &feed, 300),
                 "Expired feed must be invalid"
@@ -521,12 +511,16 @@ mod tests {
     fn test_price_validity_fresh() {
         let env = Env::default();
         env.mock_all_auths();
-        let (_, contract_id, _) = setup(&env);
+        let (_, contract_id, admin) = setup(&env);
 
         let oracle = Address::generate(&env);
         let pair = symbol_short!("ETHUSD");
         let ts = 1000u64;
-        env.ledger().with_mut(|l| l.timestamp = ts);
+        env.ledger().with_mut(|l| l.timestamp = ts + 1);
+
+        env.as_contract(&contract_id, || {
+            PriceFeedAdapter::init_oracle(&env, &admin, Address::generate(&env), 300, 500).unwrap();
+        });
 
         env.as_contract(&contract_id, || {
             PriceFeedAdapter::update_price_feed(
@@ -542,11 +536,7 @@ mod tests {
         });
 
         env.as_contract(&contract_id, || {
-            let feed: SimplePriceFeed = env
-                .storage()
-                .persistent()
-                .get(&DataKey::PriceFeed(pair.clone()))
-                .unwrap();
+            let feed: SimplePriceFeed = PriceFeedAdapter::get_price_feed(&env, &pair).unwrap();
             assert!(
                 PriceFeedAdapter::is_price_valid(&env, &feed, 300),
                 "Fresh feed must be valid"
@@ -591,6 +581,10 @@ mod tests {
                         String::from_str(&env, "Yes"),
                         String::from_str(&env, "No"),
                     ],
+                    optimistic_liveness: 0,
+                    optimistic_bond: 0,
+                    pricing_mode: PricingMode::Parimutuel,
+                    creator_fee_bps: 0,
                 },
             )
         };
@@ -663,9 +657,12 @@ mod tests {
                         String::from_str(&env, "Yes"),
                         String::from_str(&env, "No"),
                     ],
+                    optimistic_liveness: 0,
+                    optimistic_bond: 0,
+                    pricing_mode: PricingMode::Parimutuel,
+                    creator_fee_bps: 0,
                 },
-            )
-            .unwrap();
+            );
 
         // Verify that participants_count in Pool struct is the source of truth
         let pool = client.get_pool(&pool_id);
@@ -685,8 +682,7 @@ mod tests {
         // Verify participants_count incremented in Pool struct
         let pool = client.get_pool(&pool_id);
         assert_eq!(
-            pool.participants_count,
-            1,
+            pool.participants_count, 1,
             "participants_count should be 1 after first prediction"
         );
 
@@ -698,16 +694,14 @@ mod tests {
         // Verify participants_count incremented again
         let pool = client.get_pool(&pool_id);
         assert_eq!(
-            pool.participants_count,
-            2,
+            pool.participants_count, 2,
             "participants_count should be 2 after second unique user"
         );
 
         // Verify get_pool_participants_count returns the same value
         let count = client.get_pool_participants_count(&pool_id);
         assert_eq!(
-            count,
-            2,
+            count, 2,
             "get_pool_participants_count should match pool.participants_count"
         );
     }
@@ -749,9 +743,12 @@ mod tests {
                         String::from_str(&env, "Yes"),
                         String::from_str(&env, "No"),
                     ],
+                    optimistic_liveness: 0,
+                    optimistic_bond: 0,
+                    pricing_mode: PricingMode::Parimutuel,
+                    creator_fee_bps: 0,
                 },
-            )
-            .unwrap();
+            );
 
         let contract_id = client.address;
         env.as_contract(&contract_id, || {
@@ -1,4 +1,4 @@
-use crate::{MarketState, PoolConfig, PredifiContract, PredifiContractClient};
+use crate::{MarketState, PoolConfig, PredifiContract, PredifiContractClient, PricingMode};
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Ledger},
@@ -125,6 +125,10 @@ fn test_high_volume_predictions_single_pool() {
                 String::from_str(&env, "Outcome 0"),
                 String::from_str(&env, "Outcome 1"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 
@@ -176,6 +180,10 @@ fn test_bulk_claim_winnings() {
                 String::from_str(&env, "Outcome 0"),
                 String::from_str(&env, "Outcome 1"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 
@@ -232,6 +240,10 @@ fn test_sequential_pool_creation_stress() {
                     String::from_str(&env, "Outcome 0"),
                     String::from_str(&env, "Outcome 1"),
                 ],
+                optimistic_liveness: 0,
+                optimistic_bond: 0,
+                pricing_mode: PricingMode::Parimutuel,
+                creator_fee_bps: 0,
             },
         );
         assert_eq!(pool_id, i as u64);
@@ -283,6 +295,10 @@ fn test_max_outcomes_high_volume() {
                 String::from_str(&env, "Outcome 14"),
                 String::from_str(&env, "Outcome 15"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 
@@ -326,6 +342,10 @@ fn test_prediction_throughput_measurement() {
                 String::from_str(&env, "Outcome 0"),
                 String::from_str(&env, "Outcome 1"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 
@@ -377,6 +397,10 @@ fn test_resolution_under_load() {
                     String::from_str(&env, "Outcome 0"),
                     String::from_str(&env, "Outcome 1"),
                 ],
+                optimistic_liveness: 0,
+                optimistic_bond: 0,
+                pricing_mode: PricingMode::Parimutuel,
+                creator_fee_bps: 0,
             },
         );
         pool_ids.push(pid);
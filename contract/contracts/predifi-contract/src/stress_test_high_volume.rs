@@ -16,230 +16,137 @@ mod high_volume_stress_tests {
     extern crate std;
     use std::collections::HashMap;
     use std::time::Instant;
+    use std::{format, println, vec, vec::Vec};
 
-    use crate::{
-        calculate_claim_payout, PayoutInput, PoolConfig, PredifiContract, PredifiContractClient,
-    };
-    use soroban_sdk::{
-        symbol_short,
-        testutils::{Address as _, Ledger},
-        token, Address, Env, String, Vec,
-    };
-
-    mod dummy_access_control {
-        use soroban_sdk::{contract, contractimpl, Address, Env, Symbol};
-
-        #[contract]
-        pub struct DummyAccessControl;
-
-        #[contractimpl]
-        impl DummyAccessControl {
-            pub fn grant_role(env: Env, user: Address, role: u32) {
-                let key = (Symbol::new(&env, "role"), user, role);
-                env.storage().instance().set(&key, &true);
-            }
+    use crate::test_utils::{default_pool_config, TestContext, START_TIME};
+    use crate::{calculate_claim_payout, PayoutInput, PoolConfig};
+    use soroban_sdk::{testutils::Ledger, Address, Env, String};
 
-            pub fn has_role(env: Env, user: Address, role: u32) -> bool {
-                let key = (Symbol::new(&env, "role"), user, role);
-                env.storage().instance().get(&key).unwrap_or(false)
-            }
+    /// Pool end time used by every test; resolution happens right after it.
+    const END_TIME: u64 = START_TIME + 4_000;
+
+    /// Create a pool whose outcomes are named `Outcome 0..options_count`.
+    fn create_stress_pool(ctx: &TestContext, options_count: u32, description: &str) -> u64 {
+        let env = &ctx.client.env;
+        let mut outcome_descriptions = soroban_sdk::Vec::new(env);
+        for i in 0..options_count {
+            outcome_descriptions.push_back(String::from_str(env, &format!("Outcome {}", i)));
         }
-    }
 
-    const ROLE_ADMIN: u32 = 0;
-    const ROLE_OPERATOR: u32 = 1;
+        ctx.create_pool(
+            END_TIME,
+            &PoolConfig {
+                description: String::from_str(env, description),
+                min_stake: 1_000,
+                max_stake: 100_000,
+                max_total_stake: 1_000_000_000,
+                min_total_stake: 1,
+                outcome_descriptions,
+                ..default_pool_config(env)
+            },
+        )
+    }
 
-    fn stress_setup(
+    /// Place a 1_000 stake for `user` and return the CPU instructions it cost.
+    fn measured_prediction(
         env: &Env,
-    ) -> (
-        PredifiContractClient<'_>,
-        Address,
-        token::Client<'_>,
-        token::StellarAssetClient<'_>,
-    ) {
-        env.mock_all_auths();
-
-        env.ledger().with_mut(|li| {
-            li.protocol_version = 23;
-            li.timestamp = 1000;
-        });
-
-        let ac_id = env.register(dummy_access_control::DummyAccessControl, ());
-        let ac_client = dummy_access_control::DummyAccessControlClient::new(env, &ac_id);
-
-        let contract_id = env.register(PredifiContract, ());
-        let client = PredifiContractClient::new(env, &contract_id);
-
-        let admin = Address::generate(env);
-        let treasury = Address::generate(env);
-
-        ac_client.grant_role(&admin, &ROLE_ADMIN);
-        ac_client.grant_role(&admin, &ROLE_OPERATOR);
-
-        client.init(&ac_id, &treasury, &500, &3600, &3600u64, &0u32);
-
-        let token_admin = Address::generate(env);
-        let token_contract = env.register_stellar_asset_contract_v2(token_admin.clone());
-        let token_id = token_contract.address();
-        let token_client = token::Client::new(env, &token_id);
-        let token_admin_client = token::StellarAssetClient::new(env, &token_id);
-
-        client.add_token_to_whitelist(&admin, &token_id);
+        ctx: &TestContext,
+        user: &Address,
+        pool_id: u64,
+        outcome: u32,
+    ) -> u64 {
+        env.cost_estimate().budget().reset_default();
+        ctx.client
+            .place_prediction(user, &pool_id, &1_000i128, &outcome, &None, &None);
+        env.cost_estimate().budget().cpu_instruction_cost()
+    }
 
-        (client, admin, token_client, token_admin_client)
+    fn resolve_after_end(env: &Env, ctx: &TestContext, pool_id: u64, outcome: u32) {
+        env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+        ctx.client.resolve_pool(&ctx.operator, &pool_id, &outcome);
     }
 
     /// Test: 1000 concurrent predictions on a single 2-outcome pool
     /// Measures: Gas scaling, transaction throughput, payout accuracy
     #[test]
     fn test_1000_concurrent_predictions_binary_pool() {
-        let env = Env::new();
-        let (client, admin, token_client, token_admin_client) = stress_setup(&env);
-
-        // Setup token
-        let token_id = token_client.address();
-        let mut users = Vec::new();
-        for i in 0..1000u32 {
-            let user = Address::generate(&env);
-            token_admin_client.mint(&user, &1_000_000_000i128);
-            users.push(user);
-        }
-
-        // Create pool
-        let creator = Address::generate(&env);
-        token_admin_client.mint(&creator, &10_000_000i128);
-
-        let config = PoolConfig {
-            start_time: 1000u64,
-            description: String::from_slice(&env, "1000-user stress test"),
-            metadata_url: String::from_slice(&env, "https://example.com"),
-            min_stake: 1_000i128,
-            max_stake: 100_000i128,
-            min_total_stake: 1_000_000i128,
-            max_total_stake: 1_000_000_000i128,
-            initial_liquidity: 10_000i128,
-            required_resolutions: 1u32,
-            private: false,
-            whitelist_key: None,
-            outcome_descriptions: Vec::new(&env),
-        };
-
-        let pool_id = client.create_pool(
-            &creator,
-            &5000u64,
-            &token_id,
-            &2u32,
-            &symbol_short!("TEST"),
-            &config,
-        );
+        let env = Env::default();
+        let ctx = TestContext::new(&env);
 
-        println!("[stress] Pool created: {}", pool_id);
+        let users: Vec<Address> = (0..1000).map(|_| ctx.funded_user(1_000)).collect();
+        let pool_id = create_stress_pool(&ctx, 2, "1000-user stress test");
 
         // Phase 1: Place 500 predictions on outcome 0
         let start = Instant::now();
-        let mut gas_costs_outcome_0 = Vec::new();
-
-        for i in 0..500u32 {
-            let user = &users[i as usize];
-            let gas_before = env.budget().cpu_instruction_cost();
-            
-            client.place_prediction(&user, &pool_id, &1_000i128, &0i32, &None, &None);
-            
-            let gas_after = env.budget().cpu_instruction_cost();
-            let gas_used = gas_after.saturating_sub(gas_before);
-            gas_costs_outcome_0.push(gas_used);
-        }
-
-        let elapsed_0 = start.elapsed();
-        println!("[stress] 500 predictions on outcome 0: {:.2}s", elapsed_0.as_secs_f64());
-        println!("[gas] avg gas (outcome 0): {}", average(&gas_costs_outcome_0));
-        println!("[gas] min gas (outcome 0): {}", min_or_zero(&gas_costs_outcome_0));
-        println!("[gas] max gas (outcome 0): {}", max_or_zero(&gas_costs_outcome_0));
+        let gas_costs_outcome_0: Vec<u64> = users[..500]
+            .iter()
+            .map(|user| measured_prediction(&env, &ctx, user, pool_id, 0))
+            .collect();
+
+        println!(
+            "[stress] 500 predictions on outcome 0: {:.2}s",
+            start.elapsed().as_secs_f64()
+        );
+        println!(
+            "[gas] outcome 0: avg={}, min={}, max={}",
+            average(&gas_costs_outcome_0),
+            min_or_zero(&gas_costs_outcome_0),
+            max_or_zero(&gas_costs_outcome_0)
+        );
 
         // Phase 2: Place 500 predictions on outcome 1
         let start = Instant::now();
-        let mut gas_costs_outcome_1 = Vec::new();
-
-        for i in 500..1000u32 {
-            let user = &users[i as usize];
-            let gas_before = env.budget().cpu_instruction_cost();
-            
-            client.place_prediction(&user, &pool_id, &1_000i128, &1i32, &None, &None);
-            
-            let gas_after = env.budget().cpu_instruction_cost();
-            let gas_used = gas_after.saturating_sub(gas_before);
-            gas_costs_outcome_1.push(gas_used);
-        }
-
-        let elapsed_1 = start.elapsed();
-        println!("[stress] 500 predictions on outcome 1: {:.2}s", elapsed_1.as_secs_f64());
-        println!("[gas] avg gas (outcome 1): {}", average(&gas_costs_outcome_1));
-        println!("[gas] min gas (outcome 1): {}", min_or_zero(&gas_costs_outcome_1));
-        println!("[gas] max gas (outcome 1): {}", max_or_zero(&gas_costs_outcome_1));
+        let gas_costs_outcome_1: Vec<u64> = users[500..]
+            .iter()
+            .map(|user| measured_prediction(&env, &ctx, user, pool_id, 1))
+            .collect();
+
+        println!(
+            "[stress] 500 predictions on outcome 1: {:.2}s",
+            start.elapsed().as_secs_f64()
+        );
+        println!(
+            "[gas] outcome 1: avg={}, min={}, max={}",
+            average(&gas_costs_outcome_1),
+            min_or_zero(&gas_costs_outcome_1),
+            max_or_zero(&gas_costs_outcome_1)
+        );
 
         // Verify pool total stake is correct
-        let pool = client.get_pool(&pool_id);
-        let expected_total_stake = 500_000i128 + 500_000i128; // 500 × 1000 + 500 × 1000
+        let expected_total_stake = 1_000_000i128; // 1000 × 1000
+        let pool = ctx.client.get_pool(&pool_id);
         assert_eq!(pool.total_stake, expected_total_stake);
-        println!("[stress] ✅ Pool total stake correct: {}", pool.total_stake);
+        assert_eq!(ctx.client.get_pool_participants_count(&pool_id), 1000);
 
         // Phase 3: Resolve pool to outcome 0
-        let operator = Address::generate(&env);
-        ac_client.grant_role(&operator, &ROLE_OPERATOR);
-        
-        let ac_id = client.get_ac_id();
-        let ac_client = dummy_access_control::DummyAccessControlClient::new(&env, &ac_id);
-        ac_client.grant_role(&operator, &ROLE_OPERATOR);
-
-        env.ledger().with_mut(|li| {
-            li.timestamp = 5001;
-        });
-
-        client.resolve_pool(&operator, &pool_id, &0i32);
-        println!("[stress] Pool resolved to outcome 0");
-
-        // Phase 4: Verify payout accuracy for all 500 winners
-        let winning_stake = 500_000i128; // Total stake on outcome 0
-        let protocol_fee_bps = 500i128; // From config
-        
-        println!("[stress] Verifying payouts for 500 winners...");
-        for i in 0..500u32 {
-            let user = &users[i as usize];
-            let user_stake = 1_000i128;
-            
-            let payout_input = PayoutInput {
-                pool_total_stake: expected_total_stake,
-                fee_bps: protocol_fee_bps,
-                user_stake,
-                winning_stake,
-            };
-
-            let payout = calculate_claim_payout(&payout_input).unwrap();
-            
-            // Verify invariant: winnings never exceed total stake
-            assert!(payout.winnings <= expected_total_stake);
-            
-            // Verify proportional split
-            let expected_payout = (user_stake as f64 / winning_stake as f64) * payout.payout_pool as f64;
-            let actual_payout = payout.winnings as f64;
-            let diff_pct = ((actual_payout - expected_payout).abs() / expected_payout) * 100.0;
-            
-            // Allow 1% rounding error
-            assert!(diff_pct < 1.0, "Payout mismatch for user {}: {:.2}% off", i, diff_pct);
+        resolve_after_end(&env, &ctx, pool_id, 0);
+
+        // Phase 4: Every winner's claim matches the payout formula
+        let payout = calculate_claim_payout(&PayoutInput {
+            pool_total_stake: expected_total_stake,
+            fee_bps: 0,
+            user_stake: 1_000,
+            winning_stake: 500_000,
+        })
+        .unwrap();
+        assert_eq!(payout.winnings, 2_000);
+
+        for user in users[..500].iter() {
+            assert_eq!(ctx.client.claim_winnings(user, &pool_id), payout.winnings);
         }
-        println!("[stress] ✅ All 500 payouts verified (±1% tolerance)");
-
-        // Algorithmic Complexity Analysis
-        println!("\n[analysis] Algorithmic Complexity:");
-        println!("[analysis] Gas increase (outcome 0 → 1): {:.1}%", 
-            ((average(&gas_costs_outcome_1) as f64 / average(&gas_costs_outcome_0) as f64 - 1.0) * 100.0));
-        
-        // If gas is linear in prediction count, should be flat. If quadratic, should increase.
-        let gas_ratio = average(&gas_costs_outcome_1) as f64 / average(&gas_costs_outcome_0) as f64;
+        assert_eq!(ctx.token.balance(&ctx.client.address), 0);
+        println!("[stress] ✅ All 500 payouts verified");
+
+        // If per-prediction cost does not depend on prior predictions, the ratio stays flat.
+        // Wall-clock and budget figures are reported rather than asserted.
+        let gas_ratio =
+            average(&gas_costs_outcome_1) as f64 / average(&gas_costs_outcome_0).max(1) as f64;
+        println!("[analysis] Gas ratio (outcome 0 → 1): {:.2}x", gas_ratio);
         if gas_ratio > 1.1 {
-            println!("[warn] ⚠️  Gas scaling detected: {:.2}x (potential O(n) or worse)", gas_ratio);
-        } else {
-            println!("[stress] ✅ Gas scaling is linear/constant (good)");
+            println!(
+                "[warn] ⚠️  Gas scaling detected: {:.2}x (potential O(n) or worse)",
+                gas_ratio
+            );
         }
     }
 
@@ -247,103 +154,47 @@ mod high_volume_stress_tests {
     /// Measures: Gas scaling with outcome count, outcome stake updates
     #[test]
     fn test_1000_predictions_16_outcomes() {
-        let env = Env::new();
-        let (client, admin, token_client, token_admin_client) = stress_setup(&env);
-
-        let token_id = token_client.address();
-        let mut users = Vec::new();
-        for i in 0..1000u32 {
-            let user = Address::generate(&env);
-            token_admin_client.mint(&user, &1_000_000_000i128);
-            users.push(user);
-        }
+        let env = Env::default();
+        let ctx = TestContext::new(&env);
 
-        let creator = Address::generate(&env);
-        token_admin_client.mint(&creator, &10_000_000i128);
-
-        let config = PoolConfig {
-            start_time: 1000u64,
-            description: String::from_slice(&env, "16-outcome stress test"),
-            metadata_url: String::from_slice(&env, "https://example.com"),
-            min_stake: 1_000i128,
-            max_stake: 100_000i128,
-            min_total_stake: 1_000_000i128,
-            max_total_stake: 1_000_000_000i128,
-            initial_liquidity: 10_000i128,
-            required_resolutions: 1u32,
-            private: false,
-            whitelist_key: None,
-            outcome_descriptions: Vec::new(&env),
-        };
-
-        let pool_id = client.create_pool(
-            &creator,
-            &5000u64,
-            &token_id,
-            &16u32, // 16 outcomes
-            &symbol_short!("TEST"),
-            &config,
-        );
-
-        println!("[stress] 16-outcome pool created: {}", pool_id);
+        let users: Vec<Address> = (0..1000).map(|_| ctx.funded_user(1_000)).collect();
+        let pool_id = create_stress_pool(&ctx, 16, "16-outcome stress test");
 
-        // Place predictions on each outcome (62-63 per outcome, total 1000)
-        let mut gas_costs = Vec::new();
+        // Spread users over the 16 outcomes (62 each, the remainder on outcome 15)
         let predictions_per_outcome = 1000 / 16;
+        let mut gas_by_outcome: HashMap<u32, Vec<u64>> = HashMap::new();
 
-        for outcome in 0..16i32 {
-            let start_idx = (outcome as usize) * predictions_per_outcome;
-            let end_idx = if outcome == 15 {
-                1000
-            } else {
-                start_idx + predictions_per_outcome
-            };
-
-            for i in start_idx..end_idx {
-                let user = &users[i];
-                let gas_before = env.budget().cpu_instruction_cost();
-                
-                client.place_prediction(&user, &pool_id, &1_000i128, &outcome, &None, &None);
-                
-                let gas_after = env.budget().cpu_instruction_cost();
-                let gas_used = gas_after.saturating_sub(gas_before);
-                gas_costs.push((outcome, gas_used));
-            }
+        for (i, user) in users.iter().enumerate() {
+            let outcome = core::cmp::min(i / predictions_per_outcome, 15) as u32;
+            let gas = measured_prediction(&env, &ctx, user, pool_id, outcome);
+            gas_by_outcome.entry(outcome).or_default().push(gas);
         }
 
-        println!("[stress] 1000 predictions on 16 outcomes completed");
-        
-        // Analyze gas per outcome
-        let mut gas_by_outcome: HashMap<i32, Vec<u64>> = HashMap::new();
-        for (outcome, gas) in gas_costs.iter() {
-            gas_by_outcome.entry(*outcome).or_insert_with(Vec::new).push(*gas);
+        for outcome in 0..16u32 {
+            let costs = &gas_by_outcome[&outcome];
+            println!(
+                "[gas] outcome {}: avg={}, min={}, max={}",
+                outcome,
+                average(costs),
+                min_or_zero(costs),
+                max_or_zero(costs)
+            );
         }
 
-        for outcome in 0..16i32 {
-            if let Some(costs) = gas_by_outcome.get(&outcome) {
-                println!("[gas] outcome {}: avg={}, min={}, max={}", 
-                    outcome,
-                    average_slice(costs),
-                    min_or_zero_slice(costs),
-                    max_or_zero_slice(costs));
-            }
-        }
-
-        // Verify outcome stakes
-        let pool = client.get_pool(&pool_id);
+        let pool = ctx.client.get_pool(&pool_id);
         assert_eq!(pool.total_stake, 1_000_000i128);
-        println!("[stress] ✅ Pool total stake: {} (expected 1,000,000)", pool.total_stake);
 
         // Check for quadratic behavior
-        let first_pred_avg = gas_by_outcome.get(&0).map(|v| average_slice(v)).unwrap_or(0);
-        let last_pred_avg = gas_by_outcome.get(&15).map(|v| average_slice(v)).unwrap_or(0);
-        let ratio = last_pred_avg as f64 / first_pred_avg as f64;
+        let first_avg = average(&gas_by_outcome[&0]);
+        let last_avg = average(&gas_by_outcome[&15]);
+        let ratio = last_avg as f64 / first_avg.max(1) as f64;
 
         println!("[analysis] Gas scaling (outcome 0 → 15): {:.2}x", ratio);
         if ratio > 1.5 {
-            println!("[warn] ⚠️  Significant gas increase detected: {:.2}x", ratio);
-        } else {
-            println!("[stress] ✅ No quadratic complexity detected");
+            println!(
+                "[warn] ⚠️  Significant gas increase detected: {:.2}x",
+                ratio
+            );
         }
     }
 
@@ -351,44 +202,38 @@ mod high_volume_stress_tests {
     /// Measures: Payout correctness under load, rounding error accumulation
     #[test]
     fn test_payout_accuracy_scaling_winners() {
-        println!("\n[stress] Testing payout accuracy with varying winner counts...");
-
         let winner_counts = vec![10, 50, 100, 500, 1000];
         let pool_total_stake = 1_000_000i128;
         let fee_bps = 500i128; // 5%
         let protocol_fee = (pool_total_stake * fee_bps) / 10_000;
         let payout_pool = pool_total_stake - protocol_fee;
 
-        for winner_count in winner_counts.iter() {
+        for winner_count in winner_counts {
             let winning_stake = pool_total_stake / 2; // 50% on winning side
-            let user_stake = winning_stake / (*winner_count as i128);
-
-            let mut payout_sum = 0i128;
-            let mut max_error = 0i128;
-
-            for _ in 0..*winner_count {
-                let payout_input = PayoutInput {
-                    pool_total_stake,
-                    fee_bps,
-                    user_stake,
-                    winning_stake,
-                };
-
-                let payout = calculate_claim_payout(&payout_input).unwrap();
-                payout_sum += payout.winnings;
-                
-                let expected_payout = (user_stake as f64 / winning_stake as f64) * payout_pool as f64;
-                let actual_payout = payout.winnings as f64;
-                let error = (actual_payout - expected_payout).abs() as i128;
-                max_error = max_error.max(error);
-            }
+            let user_stake = winning_stake / (winner_count as i128);
 
-            let error_pct = (max_error as f64 / payout_pool as f64) * 100.0;
-            println!("[payout] {} winners: sum_payouts={}, max_error={} ({:.4}%)",
-                winner_count, payout_sum, max_error, error_pct);
-
-            // Ensure payout sum doesn't exceed pool
-            assert!(payout_sum <= pool_total_stake);
+            let payout = calculate_claim_payout(&PayoutInput {
+                pool_total_stake,
+                fee_bps,
+                user_stake,
+                winning_stake,
+            })
+            .unwrap();
+            assert_eq!(payout.protocol_fee, protocol_fee);
+
+            let expected_payout = payout_pool * user_stake / winning_stake;
+            let payout_sum = payout.winnings * winner_count as i128;
+
+            println!(
+                "[payout] {} winners: sum_payouts={}, per-winner error={}",
+                winner_count,
+                payout_sum,
+                (payout.winnings - expected_payout).abs()
+            );
+
+            // Each share rounds down by less than one unit and never overpays.
+            assert!((payout.winnings - expected_payout).abs() <= 1);
+            assert!(payout_sum <= payout_pool);
         }
 
         println!("[stress] ✅ Payout accuracy verified across winner scales");
@@ -398,124 +243,61 @@ mod high_volume_stress_tests {
     /// Measures: Claim latency scaling with winner count
     #[test]
     fn test_claim_processing_complexity() {
-        let env = Env::new();
-        let (client, admin, token_client, token_admin_client) = stress_setup(&env);
-
-        let token_id = token_client.address();
-        let mut users = Vec::new();
-        for i in 0..200u32 {
-            let user = Address::generate(&env);
-            token_admin_client.mint(&user, &1_000_000_000i128);
-            users.push(user);
-        }
+        let env = Env::default();
+        let ctx = TestContext::new(&env);
 
-        let creator = Address::generate(&env);
-        token_admin_client.mint(&creator, &10_000_000i128);
-
-        let config = PoolConfig {
-            start_time: 1000u64,
-            description: String::from_slice(&env, "Claim complexity test"),
-            metadata_url: String::from_slice(&env, "https://example.com"),
-            min_stake: 1_000i128,
-            max_stake: 100_000i128,
-            min_total_stake: 1_000_000i128,
-            max_total_stake: 1_000_000_000i128,
-            initial_liquidity: 10_000i128,
-            required_resolutions: 1u32,
-            private: false,
-            whitelist_key: None,
-            outcome_descriptions: Vec::new(&env),
-        };
-
-        let pool_id = client.create_pool(
-            &creator,
-            &5000u64,
-            &token_id,
-            &2u32,
-            &symbol_short!("TEST"),
-            &config,
-        );
-
-        // Place 200 predictions on outcome 0
-        for user in users.iter() {
-            client.place_prediction(&user, &pool_id, &1_000i128, &0i32, &None, &None);
-        }
-
-        // Resolve to outcome 0
-        let operator = Address::generate(&env);
-        
-        let ac_id = env.register(dummy_access_control::DummyAccessControl, ());
-        let ac_client = dummy_access_control::DummyAccessControlClient::new(&env, &ac_id);
-        ac_client.grant_role(&operator, &ROLE_OPERATOR);
+        let pool_id = create_stress_pool(&ctx, 2, "Claim complexity test");
+        let users: Vec<Address> = (0..360).map(|_| ctx.staker(pool_id, 1_000, 0)).collect();
 
-        env.ledger().with_mut(|li| {
-            li.timestamp = 5001;
-        });
+        resolve_after_end(&env, &ctx, pool_id, 0);
 
-        client.resolve_pool(&operator, &pool_id, &0i32);
+        // Claim in successively larger, disjoint batches of winners
+        let batch_sizes = vec![10usize, 50, 100, 200];
+        let mut claim_cpu: Vec<(usize, u64)> = Vec::new();
+        let mut next = 0usize;
 
-        // Measure claim processing time for increasing winner counts
-        let winner_counts = vec![10, 50, 100, 200];
-        let mut claim_times = Vec::new();
-
-        for count in winner_counts.iter() {
+        for size in batch_sizes {
             let start = Instant::now();
-
-            for i in 0..*count {
-                let user = &users[i];
-                client.claim_winnings(&user, &pool_id);
+            let mut cpu = 0u64;
+            for user in users[next..next + size].iter() {
+                env.cost_estimate().budget().reset_default();
+                assert_eq!(ctx.client.claim_winnings(user, &pool_id), 1_000);
+                cpu += env.cost_estimate().budget().cpu_instruction_cost();
             }
-
-            let elapsed = start.elapsed();
-            claim_times.push((*count, elapsed.as_secs_f64()));
-            println!("[claim] {} winners: {:.3}s", count, elapsed.as_secs_f64());
+            next += size;
+
+            claim_cpu.push((size, cpu));
+            println!(
+                "[claim] {} winners: {:.3}s, {} cpu",
+                size,
+                start.elapsed().as_secs_f64(),
+                cpu
+            );
         }
 
-        // Analyze complexity
-        for i in 1..claim_times.len() {
-            let (count1, time1) = claim_times[i - 1];
-            let (count2, time2) = claim_times[i];
-
-            let count_ratio = count2 as f64 / count1 as f64;
-            let time_ratio = time2 / time1;
-
-            let complexity = time_ratio.log2() / count_ratio.log2();
-            println!("[analysis] Scaling: {:.2}x winners → {:.2}x time (complexity: ~O(n^{:.2}))", 
-                count_ratio, time_ratio, complexity);
-
-            // If complexity > 1.5, likely O(n²)
-            if complexity > 1.5 {
-                println!("[warn] ⚠️  Potential O(n²) behavior detected!");
-            }
-        }
-
-        println!("[stress] ✅ Claim processing complexity analysis complete");
+        // Per-claim cost must not grow with the number of prior claims.
+        let per_claim: Vec<u64> = claim_cpu
+            .iter()
+            .map(|(size, cpu)| cpu / *size as u64)
+            .collect();
+        let ratio = max_or_zero(&per_claim) as f64 / min_or_zero(&per_claim).max(1) as f64;
+        println!("[analysis] Per-claim cpu spread: {:.2}x", ratio);
+        assert!(ratio < 1.5, "Potential O(n²) claim behavior: {:.2}x", ratio);
     }
 
     // Helper functions
-    fn average(values: &Vec<u64>) -> u64 {
-        if values.is_empty() { return 0; }
-        values.iter().sum::<u64>() / values.len() as u64
-    }
-
-    fn average_slice(values: &[u64]) -> u64 {
-        if values.is_empty() { return 0; }
+    fn average(values: &[u64]) -> u64 {
+        if values.is_empty() {
+            return 0;
+        }
         values.iter().sum::<u64>() / values.len() as u64
     }
 
-    fn min_or_zero(values: &Vec<u64>) -> u64 {
-        values.iter().copied().min().unwrap_or(0)
-    }
-
-    fn min_or_zero_slice(values: &[u64]) -> u64 {
+    fn min_or_zero(values: &[u64]) -> u64 {
         values.iter().copied().min().unwrap_or(0)
     }
 
-    fn max_or_zero(values: &Vec<u64>) -> u64 {
-        values.iter().copied().max().unwrap_or(0)
-    }
-
-    fn max_or_zero_slice(values: &[u64]) -> u64 {
+    fn max_or_zero(values: &[u64]) -> u64 {
         values.iter().copied().max().unwrap_or(0)
     }
 }
@@ -0,0 +1,175 @@
+//! Unclaimed winnings sweeps: what winners left after the claim window goes
+//! to the unclaimed recipient, in resumable batches, and what nobody was
+//! owed goes to the accrued fees.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext, START_TIME};
+use crate::{PredifiError, FEE_CHANGE_TIMELOCK_SECONDS};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+/// Default claim window of 30 days.
+const CLAIM_WINDOW: u64 = 2_592_000;
+
+/// A resolved 10% fee pool where `early` and `late` stake 300 each on the
+/// winning outcome and a loser stakes 400: each winner is owed 450.
+/// Returns `(pool_id, resolved_at, early, late)`.
+fn resolved_pool(ctx: &TestContext) -> (u64, u64, Address, Address) {
+    let env = &ctx.client.env;
+    ctx.client.set_fee_bps(&ctx.admin, &1_000u32);
+    env.ledger()
+        .with_mut(|li| li.timestamp = START_TIME + FEE_CHANGE_TIMELOCK_SECONDS);
+    ctx.client.apply_fee_bps(&ctx.admin);
+
+    let end_time = env.ledger().timestamp() + 3_600;
+    let pool_id = ctx.create_pool(end_time, &default_pool_config(env));
+    let early = ctx.staker(pool_id, 300, 0);
+    let late = ctx.staker(pool_id, 300, 0);
+    ctx.staker(pool_id, 400, 1);
+
+    let resolved_at = end_time + 1;
+    env.ledger().with_mut(|li| li.timestamp = resolved_at);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &0u32);
+    (pool_id, resolved_at, early, late)
+}
+
+/// Only what was left unclaimed is swept, and only once the window closed.
+#[test]
+fn test_sweep_moves_unclaimed_winnings_to_treasury() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, resolved_at, early, late) = resolved_pool(&ctx);
+    assert_eq!(ctx.client.claim_winnings(&early, &pool_id), 450);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = resolved_at + CLAIM_WINDOW);
+    let open = ctx.client.try_sweep_expired_pool(&pool_id, &10);
+    assert_eq!(open, Err(Ok(PredifiError::TimeConstraintError)));
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = resolved_at + CLAIM_WINDOW + 1);
+    let expired = ctx.client.try_claim_winnings(&late, &pool_id);
+    assert_eq!(expired, Err(Ok(PredifiError::InvalidTimestamp)));
+
+    assert_eq!(ctx.client.sweep_expired_pool(&pool_id, &10), 450);
+    assert_eq!(ctx.token.balance(&ctx.treasury), 450);
+    // The whole 100 fee is realized: 50 by the claim, 50 by the sweep.
+    assert_eq!(ctx.client.get_accrued_fees(&ctx.token_address), 100);
+    assert_eq!(ctx.token.balance(&ctx.client.address), 100);
+
+    // Per-user storage is archived; the pool itself stays readable.
+    assert_eq!(ctx.client.get_user_predictions(&late, &0, &10).len(), 0);
+    assert_eq!(ctx.client.get_pool(&pool_id).outcome, 0);
+
+    let again = ctx.client.try_sweep_expired_pool(&pool_id, &10);
+    assert_eq!(again, Err(Ok(PredifiError::AlreadyClaimed)));
+}
+
+/// A sweep capped at one participant per call resumes where it stopped and
+/// only completes after the last one.
+#[test]
+fn test_sweep_resumes_in_batches() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, resolved_at, early, _late) = resolved_pool(&ctx);
+    assert_eq!(ctx.client.claim_winnings(&early, &pool_id), 450);
+    env.ledger()
+        .with_mut(|li| li.timestamp = resolved_at + CLAIM_WINDOW + 1);
+
+    let empty = ctx.client.try_sweep_expired_pool(&pool_id, &0);
+    assert_eq!(empty, Err(Ok(PredifiError::InvalidData)));
+
+    // Participants are visited in order: early (claimed), late, the loser.
+    assert_eq!(ctx.client.sweep_expired_pool(&pool_id, &1), 0);
+    assert_eq!(ctx.client.sweep_expired_pool(&pool_id, &1), 450);
+    assert_eq!(ctx.token.balance(&ctx.treasury), 450);
+    assert_eq!(ctx.client.sweep_expired_pool(&pool_id, &1), 0);
+    assert_eq!(ctx.client.get_accrued_fees(&ctx.token_address), 100);
+
+    let again = ctx.client.try_sweep_expired_pool(&pool_id, &1);
+    assert_eq!(again, Err(Ok(PredifiError::AlreadyClaimed)));
+}
+
+/// Sweeps go to the configured recipient, which only an admin can set.
+#[test]
+fn test_sweep_to_configured_recipient() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, resolved_at, early, _late) = resolved_pool(&ctx);
+    assert_eq!(ctx.client.get_unclaimed_recipient(), ctx.treasury);
+
+    let not_admin = ctx
+        .client
+        .try_set_unclaimed_recipient(&early, &Some(early.clone()));
+    assert_eq!(not_admin, Err(Ok(PredifiError::Unauthorized)));
+
+    let recipient = Address::generate(&env);
+    ctx.client
+        .set_unclaimed_recipient(&ctx.admin, &Some(recipient.clone()));
+    env.ledger()
+        .with_mut(|li| li.timestamp = resolved_at + CLAIM_WINDOW + 1);
+
+    assert_eq!(ctx.client.sweep_expired_pool(&pool_id, &10), 900);
+    assert_eq!(ctx.token.balance(&recipient), 900);
+    assert_eq!(ctx.token.balance(&ctx.treasury), 0);
+}
+
+/// A pool nobody backed the winning outcome of owes its whole stake to no
+/// one: the sweep accrues it, and the treasury can withdraw it.
+#[test]
+fn test_sweep_accrues_pool_without_winners() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let end_time = START_TIME + 3_600;
+    let pool_id = ctx.create_pool(end_time, &default_pool_config(&env));
+    let loser = ctx.staker(pool_id, 300, 1);
+    ctx.staker(pool_id, 400, 1);
+
+    let resolved_at = end_time + 1;
+    env.ledger().with_mut(|li| li.timestamp = resolved_at);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &0u32);
+    assert_eq!(ctx.client.claim_winnings(&loser, &pool_id), 0);
+    assert_eq!(ctx.client.get_accrued_fees(&ctx.token_address), 0);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = resolved_at + CLAIM_WINDOW + 1);
+    assert_eq!(ctx.client.sweep_expired_pool(&pool_id, &10), 0);
+    assert_eq!(ctx.client.get_accrued_fees(&ctx.token_address), 700);
+
+    ctx.client
+        .withdraw_treasury(&ctx.admin, &ctx.token_address, &700, &ctx.treasury);
+    assert_eq!(ctx.token.balance(&ctx.treasury), 700);
+    assert_eq!(ctx.token.balance(&ctx.client.address), 0);
+    assert_eq!(ctx.client.get_accrued_fees(&ctx.token_address), 0);
+}
+
+/// Payout rounding dust is accrued once the last participant is swept.
+#[test]
+fn test_sweep_accrues_rounding_dust() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let end_time = START_TIME + 3_600;
+    let pool_id = ctx.create_pool(end_time, &default_pool_config(&env));
+    let winners = [
+        ctx.staker(pool_id, 100, 0),
+        ctx.staker(pool_id, 100, 0),
+        ctx.staker(pool_id, 100, 0),
+    ];
+    ctx.staker(pool_id, 100, 1);
+
+    let resolved_at = end_time + 1;
+    env.ledger().with_mut(|li| li.timestamp = resolved_at);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &0u32);
+    for winner in winners.iter() {
+        assert_eq!(ctx.client.claim_winnings(winner, &pool_id), 133);
+    }
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = resolved_at + CLAIM_WINDOW + 1);
+    assert_eq!(ctx.client.sweep_expired_pool(&pool_id, &10), 0);
+    assert_eq!(ctx.client.get_accrued_fees(&ctx.token_address), 1);
+    assert_eq!(ctx.token.balance(&ctx.client.address), 1);
+}
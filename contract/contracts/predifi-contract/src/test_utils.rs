@@ -1,4 +1,14 @@
-use soroban_sdk::{token, Address, Env};
+#![allow(dead_code)]
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    token, vec, Address, Env, String,
+};
+
+use crate::test::dummy_access_control::DummyAccessControlClient;
+use crate::test::ROLE_ADMIN;
+use crate::PredifiContractClient;
 
 pub struct TokenTestContext {
     pub token_address: Address,
@@ -25,7 +35,137 @@ impl TokenTestContext {
     }
 }
 
-use crate::{DataKey, MarketState, Pool};
+use crate::{DataKey, MarketState, Pool, PoolConfig, PricingMode};
+
+/// Ledger timestamp [`TestContext::new`] starts at.
+pub const START_TIME: u64 = 1_000;
+
+/// The contract deployed by [`crate::test::setup`] plus a dedicated Admin,
+/// with auths mocked and the ledger at [`START_TIME`].
+pub struct TestContext<'a> {
+    pub ac_client: DummyAccessControlClient<'a>,
+    pub client: PredifiContractClient<'a>,
+    pub token_address: Address,
+    pub token: token::Client<'a>,
+    pub token_admin_client: token::StellarAssetClient<'a>,
+    pub treasury: Address,
+    pub operator: Address,
+    pub creator: Address,
+    pub admin: Address,
+}
+
+impl<'a> TestContext<'a> {
+    pub fn new(env: &'a Env) -> Self {
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| li.timestamp = START_TIME);
+
+        let (
+            ac_client,
+            client,
+            token_address,
+            token,
+            token_admin_client,
+            treasury,
+            operator,
+            creator,
+        ) = crate::test::setup(env);
+        let admin = Address::generate(env);
+        ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+        Self {
+            ac_client,
+            client,
+            token_address,
+            token,
+            token_admin_client,
+            treasury,
+            operator,
+            creator,
+            admin,
+        }
+    }
+
+    /// A fresh address holding `role`.
+    pub fn grant(&self, role: u32) -> Address {
+        let who = Address::generate(&self.client.env);
+        self.ac_client.grant_role(&who, &role);
+        who
+    }
+
+    /// A fresh address holding `amount` of the test token.
+    pub fn funded_user(&self, amount: i128) -> Address {
+        let who = Address::generate(&self.client.env);
+        self.token_admin_client.mint(&who, &amount);
+        who
+    }
+
+    /// Configure the oracle settings with a placeholder Pyth contract and
+    /// register a fresh address as an oracle, which is returned.
+    pub fn add_price_oracle(&self, max_price_age: u64, min_confidence_ratio: u32) -> Address {
+        let env = &self.client.env;
+        self.client.init_oracle(
+            &self.admin,
+            &Address::generate(env),
+            &max_price_age,
+            &min_confidence_ratio,
+        );
+        let oracle = Address::generate(env);
+        self.client.add_oracle(&self.admin, &oracle);
+        oracle
+    }
+
+    /// A fresh user staking `amount` on `outcome` in `pool_id`.
+    pub fn staker(&self, pool_id: u64, amount: i128, outcome: u32) -> Address {
+        let who = self.funded_user(amount);
+        self.client
+            .place_prediction(&who, &pool_id, &amount, &outcome, &None, &None);
+        who
+    }
+
+    /// Create a pool from `creator` in the test token, with one outcome per
+    /// entry of `config.outcome_descriptions`.
+    pub fn create_pool(&self, end_time: u64, config: &PoolConfig) -> u64 {
+        self.client.create_pool(
+            &self.creator,
+            &end_time,
+            &self.token_address,
+            &config.outcome_descriptions.len(),
+            &symbol_short!("Tech"),
+            config,
+        )
+    }
+}
+
+/// A public, two-outcome (`No`/`Yes`) parimutuel pool config with no stake
+/// caps, opening immediately.
+///
+/// Tests override only the fields they exercise, e.g.
+/// `PoolConfig { private: true, ..default_pool_config(&env) }`, so adding a
+/// `PoolConfig` field means updating this one literal.
+pub fn default_pool_config(env: &Env) -> PoolConfig {
+    PoolConfig {
+        start_time: 0,
+        description: String::from_str(env, "Test pool"),
+        metadata_url: String::from_str(env, "ipfs://test"),
+        min_stake: 1,
+        max_stake: 0,
+        max_total_stake: 0,
+        min_total_stake: 1,
+        initial_liquidity: 0,
+        required_resolutions: 1,
+        private: false,
+        whitelist_key: None,
+        outcome_descriptions: vec![
+            env,
+            String::from_str(env, "No"),
+            String::from_str(env, "Yes"),
+        ],
+        optimistic_liveness: 0,
+        optimistic_bond: 0,
+        pricing_mode: PricingMode::Parimutuel,
+        creator_fee_bps: 0,
+    }
+}
 
 /// Transition an existing pool to Disputed state for testing.
 ///
@@ -1,11 +1,16 @@
-//! Treasury domain: treasury configuration, fee withdrawal and emergency
-//! fund recovery.
+//! Treasury domain: treasury configuration, fee withdrawal, creator fees,
+//! volume discounts, sweeps of unclaimed winnings and emergency fund
+//! recovery.
 
-use soroban_sdk::{contractimpl, token, Address, Env};
+use soroban_sdk::{contractimpl, token, Address, Env, Vec};
 
 use crate::{
-    DataKey, EmergencyWithdrawEvent, PredifiContract, PredifiContractArgs, PredifiContractClient,
-    PredifiError, TreasuryUpdateEvent, TreasuryWithdrawnEvent, MIN_WITHDRAWAL_AMOUNT,
+    CreatorFeesClaimedEvent, DataKey, EmergencyWithdrawEvent, MarketState,
+    MaxCreatorFeeUpdatedEvent, Pool, PoolKey, PredifiContract, PredifiContractArgs,
+    PredifiContractClient, PredifiError, PricingMode, ReferralKey, SensitiveAction, SweepProgress,
+    TreasuryKey, TreasuryUpdateEvent, TreasuryWithdrawnEvent, UnclaimedRecipientSetEvent,
+    UnclaimedSweptEvent, UserStats, VolumeTier, VolumeTiersUpdateEvent,
+    DEFAULT_MAX_CREATOR_FEE_BPS, MIN_WITHDRAWAL_AMOUNT,
 };
 
 #[contractimpl]
@@ -24,9 +29,12 @@ impl PredifiContract {
         Ok(())
     }
 
-    /// Withdraw accumulated protocol fees or unused liquidity from the contract.
+    /// Withdraw accrued protocol fees from the contract.
     /// Only callable by Admin (role 0).
     ///
+    /// Withdrawals are capped to [`Self::get_accrued_fees`], so stakes and
+    /// unclaimed winnings held for live pools can never leave through here.
+    ///
     /// # Arguments
     /// * `admin` - Address with Admin role (must provide auth)
     /// * `token` - The token contract address to withdraw
@@ -40,6 +48,7 @@ impl PredifiContract {
     /// - Requires Admin role (0)
     /// - Emits TreasuryWithdrawnEvent for audit trail
     /// - Validates amount >= MIN_WITHDRAWAL_AMOUNT
+    /// - Checks `amount` does not exceed the accrued fees for `token`
     /// - Checks contract has sufficient balance
     pub fn withdraw_treasury(
         env: Env,
@@ -59,6 +68,12 @@ impl PredifiContract {
             return Err(PredifiError::InvalidAmount);
         }
 
+        // Only realized protocol fees may leave; stakes and winnings stay put.
+        let accrued = Self::get_accrued_fees(env.clone(), token.clone());
+        if accrued < amount {
+            return Err(PredifiError::InsufficientBalance);
+        }
+
         // Get token client and check the contract's available balance first.
         let token_client = token::Client::new(&env, &token);
         let available_balance = token_client.balance(&env.current_contract_address());
@@ -68,6 +83,12 @@ impl PredifiContract {
             return Err(PredifiError::InsufficientBalance);
         }
 
+        let fees_key = TreasuryKey::AccruedFees(token.clone());
+        env.storage()
+            .persistent()
+            .set(&fees_key, &(accrued - amount));
+        Self::extend_persistent(&env, &fees_key);
+
         Self::enter_reentrancy_guard(&env);
 
         // Validate token transfer before withdrawal
@@ -101,11 +122,403 @@ impl PredifiContract {
         Ok(())
     }
 
+    /// Protocol fees realized in `token` and not yet withdrawn.
+    ///
+    /// Credited as winners claim, net of referral cuts and the creator fee
+    /// paid out of the protocol fee.
+    pub fn get_accrued_fees(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&TreasuryKey::AccruedFees(token))
+            .unwrap_or(0)
+    }
+
+    /// Set where [`Self::sweep_expired_pool`] sends unclaimed winnings;
+    /// `None` sends them to the treasury. Caller must have Admin role (0).
+    pub fn set_unclaimed_recipient(
+        env: Env,
+        admin: Address,
+        recipient: Option<Address>,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        admin.require_auth();
+        Self::require_admin_role(&env, &admin, "set_unclaimed_recipient")?;
+
+        let key = TreasuryKey::UnclaimedRecipient;
+        match &recipient {
+            Some(recipient) => env.storage().instance().set(&key, recipient),
+            None => env.storage().instance().remove(&key),
+        }
+        Self::extend_instance(&env);
+
+        UnclaimedRecipientSetEvent { admin, recipient }.publish(&env);
+        Ok(())
+    }
+
+    /// Recipient of swept unclaimed winnings: the configured address, or the
+    /// treasury.
+    pub fn get_unclaimed_recipient(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&TreasuryKey::UnclaimedRecipient)
+            .unwrap_or_else(|| Self::get_config(&env).treasury)
+    }
+
+    /// Set the cap on the creator fee new pools may set, in basis points of
+    /// the pool's total stake. Existing pools keep their fee. Caller must
+    /// have Admin role (0).
+    ///
+    /// # Errors
+    /// - `Unauthorized` – caller lacks Admin role (0).
+    /// - `InvalidFeeBps` – `max_creator_fee_bps` exceeds 10_000.
+    pub fn set_max_creator_fee_bps(
+        env: Env,
+        admin: Address,
+        max_creator_fee_bps: u32,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        admin.require_auth();
+        Self::require_admin_role(&env, &admin, "set_max_creator_fee_bps")?;
+        if max_creator_fee_bps > 10_000 {
+            return Err(PredifiError::InvalidFeeBps);
+        }
+
+        env.storage()
+            .instance()
+            .set(&TreasuryKey::MaxCreatorFeeBps, &max_creator_fee_bps);
+        Self::extend_instance(&env);
+
+        MaxCreatorFeeUpdatedEvent {
+            admin,
+            max_creator_fee_bps,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Cap on `PoolConfig::creator_fee_bps`, defaulting to
+    /// [`DEFAULT_MAX_CREATOR_FEE_BPS`].
+    pub fn get_max_creator_fee_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&TreasuryKey::MaxCreatorFeeBps)
+            .unwrap_or(DEFAULT_MAX_CREATOR_FEE_BPS)
+    }
+
+    /// Creator fee set for `pool_id` at creation, in basis points of its
+    /// total stake (0 if none).
+    pub fn get_creator_fee_bps(env: Env, pool_id: u64) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&PoolKey::CreatorFeeBps(pool_id))
+            .unwrap_or(0)
+    }
+
+    /// Set the volume discount schedule. Caller must have Admin role (0).
+    ///
+    /// Users whose lifetime staked volume reaches a tier's `volume_threshold`
+    /// are rebated its `discount_bps` of their share of the protocol fee when
+    /// they claim winnings. Thresholds must be strictly ascending; an empty
+    /// schedule disables discounts. Applies to claims made from now on.
+    ///
+    /// # Errors
+    /// - `Unauthorized` – caller lacks Admin role (0).
+    /// - `InvalidFeeBps` – a `discount_bps` exceeds 10_000, or thresholds
+    ///   are not strictly ascending.
+    pub fn set_volume_tiers(
+        env: Env,
+        admin: Address,
+        tiers: Vec<VolumeTier>,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env)?;
+        admin.require_auth();
+        Self::require_admin_role(&env, &admin, "set_volume_tiers")?;
+
+        let mut prev_threshold: Option<i128> = None;
+        for tier in tiers.iter() {
+            if tier.discount_bps > 10_000
+                || prev_threshold.is_some_and(|prev| tier.volume_threshold <= prev)
+            {
+                return Err(PredifiError::InvalidFeeBps);
+            }
+            prev_threshold = Some(tier.volume_threshold);
+        }
+
+        env.storage()
+            .instance()
+            .set(&TreasuryKey::VolumeTiers, &tiers);
+        Self::extend_instance(&env);
+
+        VolumeTiersUpdateEvent {
+            admin,
+            tiers_count: tiers.len(),
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// The volume discount schedule, empty if none is set.
+    pub fn get_volume_tiers(env: Env) -> Vec<VolumeTier> {
+        env.storage()
+            .instance()
+            .get(&TreasuryKey::VolumeTiers)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// `user`'s lifetime staked volume and the volume tier and discount it
+    /// earns under the current schedule.
+    pub fn get_user_fee_tier(env: Env, user: Address) -> UserStats {
+        Self::read_user_stats(&env, &user)
+    }
+
+    /// Pay the pool creator their fee once the pool has resolved. Returns the
+    /// amount paid.
+    ///
+    /// The fee is `creator_fee_bps` of the pool's total stake, capped at and
+    /// carved out of the protocol fee: winners are paid exactly as without
+    /// it, and the treasury accrues correspondingly less as they claim.
+    ///
+    /// # Errors
+    /// - `PoolNotFound` – `pool_id` does not exist.
+    /// - `PoolNotResolved` – the pool is not resolved, or its challenge
+    ///   window is still open.
+    /// - `AlreadyClaimed` – the creator fee was already paid.
+    /// - `InsufficientBalance` – nothing is owed: the pool has no creator
+    ///   fee, no protocol fee, or no winning stake.
+    pub fn claim_creator_fees(env: Env, pool_id: u64) -> Result<i128, PredifiError> {
+        Self::require_not_paused(&env)?;
+
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        pool.creator.require_auth();
+        if pool.state != MarketState::Resolved || !Self::is_pool_resolved(&pool) {
+            return Err(PredifiError::PoolNotResolved);
+        }
+        Self::require_challenge_window_elapsed(&env, pool_id, &pool)?;
+
+        let paid_key = PoolKey::CreatorFeesPaid(pool_id);
+        if env.storage().persistent().has(&paid_key) {
+            return Err(PredifiError::AlreadyClaimed);
+        }
+        let amount = Self::creator_fee_owed(&env, pool_id, &pool)?;
+        if amount <= 0 {
+            return Err(PredifiError::InsufficientBalance);
+        }
+
+        env.storage().persistent().set(&paid_key, &amount);
+        Self::extend_persistent(&env, &paid_key);
+
+        Self::validate_token_transfer(
+            &env,
+            &pool.token,
+            &env.current_contract_address(),
+            &pool.creator,
+            amount,
+        )?;
+        Self::enter_reentrancy_guard(&env);
+        token::Client::new(&env, &pool.token).transfer(
+            &env.current_contract_address(),
+            &pool.creator,
+            &amount,
+        );
+        Self::exit_reentrancy_guard(&env);
+
+        CreatorFeesClaimedEvent {
+            pool_id,
+            creator: pool.creator,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(amount)
+    }
+
+    /// Sweep the winnings left unclaimed once a resolved pool's claim window
+    /// has closed. Permissionless — callable by any address.
+    ///
+    /// Walks up to `max_participants` of the pool's participants, resuming
+    /// where the previous call stopped, and sums what each unclaimed
+    /// prediction would have been paid. That amount goes to
+    /// [`Self::get_unclaimed_recipient`], and the protocol fee those claims
+    /// would have realized is credited to the accrued fees. Every visited
+    /// participant's position, claim marker and referrer entry is then
+    /// removed; the `Pool` record and its outcome stakes are kept. Call again
+    /// until the event reports no participants remaining. The last call also
+    /// credits the accrued fees with the stake no prediction was owed: all of
+    /// it if the pool resolved with no winning stake, otherwise the rounding
+    /// dust of its payouts. Returns the amount swept by this call.
+    ///
+    /// # Errors
+    /// - `InvalidData` – `max_participants` is 0.
+    /// - `InvalidPoolState` – not a parimutuel pool (AMM payouts never expire).
+    /// - `PoolNotResolved` – the pool is not resolved.
+    /// - `TimeConstraintError` – the claim window is still open, or the pool
+    ///   has no resolution timestamp and so no claim deadline.
+    /// - `AlreadyClaimed` – the pool was already swept.
+    pub fn sweep_expired_pool(
+        env: Env,
+        pool_id: u64,
+        max_participants: u32,
+    ) -> Result<i128, PredifiError> {
+        Self::require_not_paused(&env)?;
+        if max_participants == 0 {
+            return Err(PredifiError::InvalidData);
+        }
+
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.pricing_mode != PricingMode::Parimutuel {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if pool.state != MarketState::Resolved || !Self::is_pool_resolved(&pool) {
+            return Err(PredifiError::PoolNotResolved);
+        }
+        let swept_key = PoolKey::Swept(pool_id);
+        if env.storage().persistent().has(&swept_key) {
+            return Err(PredifiError::AlreadyClaimed);
+        }
+
+        let resolved_at = pool
+            .resolution_timestamp
+            .ok_or(PredifiError::TimeConstraintError)?;
+        let claim_deadline = resolved_at
+            .checked_add(Self::get_config(&env).claim_window_seconds)
+            .ok_or(PredifiError::InvalidTimestamp)?;
+        if env.ledger().timestamp() <= claim_deadline {
+            return Err(PredifiError::TimeConstraintError);
+        }
+
+        let progress_key = PoolKey::SweepProgress(pool_id);
+        let mut progress: SweepProgress = env
+            .storage()
+            .persistent()
+            .get(&progress_key)
+            .unwrap_or_default();
+        let count_key = PoolKey::ParticipantCt(pool_id);
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let end = progress.next.saturating_add(max_participants).min(count);
+
+        let mut amount: i128 = 0;
+        let mut fees: i128 = 0;
+        for i in progress.next..end {
+            let index_key = PoolKey::Participant(pool_id, i);
+            let Some(user) = env.storage().persistent().get::<_, Address>(&index_key) else {
+                continue;
+            };
+            env.storage().persistent().remove(&index_key);
+
+            // A user who left and re-entered the pool is indexed twice; the
+            // first visit removes the position, so the second finds none.
+            let position = Self::load_position(&env, &user, pool_id);
+            if position.is_empty() {
+                continue;
+            }
+            let claimed_key = DataKey::Claimed(user.clone(), pool_id);
+            let claimed = env.storage().persistent().has(&claimed_key);
+            for prediction in position.iter() {
+                let Some((breakdown, fee_share)) =
+                    Self::resolved_claim(&env, pool_id, &pool, &prediction)?
+                else {
+                    continue;
+                };
+                progress.owed = progress
+                    .owed
+                    .checked_add(breakdown.winnings)
+                    .and_then(|owed| owed.checked_add(fee_share))
+                    .ok_or(PredifiError::ArithmeticError)?;
+                if !claimed {
+                    amount = amount
+                        .checked_add(breakdown.winnings)
+                        .ok_or(PredifiError::ArithmeticError)?;
+                    fees = fees
+                        .checked_add(fee_share)
+                        .ok_or(PredifiError::ArithmeticError)?;
+                }
+            }
+
+            Self::store_position(&env, &user, pool_id, &Vec::new(&env));
+            env.storage().persistent().remove(&claimed_key);
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Referrer(user.clone(), pool_id));
+            env.storage()
+                .persistent()
+                .remove(&ReferralKey::Attribution(user.clone(), pool_id));
+            Self::remove_from_user_index(&env, &user, pool_id);
+        }
+
+        progress.next = end;
+        progress.amount = progress
+            .amount
+            .checked_add(amount)
+            .ok_or(PredifiError::ArithmeticError)?;
+        let remaining = count - end;
+        if remaining == 0 {
+            // What no prediction was owed stays with the protocol: the whole
+            // pool when nobody backed the winning outcome, rounding dust
+            // otherwise. The creator fee is left for `claim_creator_fees`.
+            let creator_fee = Self::creator_fee_owed(&env, pool_id, &pool)?;
+            let unowed = pool
+                .total_stake
+                .checked_sub(progress.owed)
+                .and_then(|rest| rest.checked_sub(creator_fee))
+                .ok_or(PredifiError::ArithmeticError)?;
+            fees = fees
+                .checked_add(unowed.max(0))
+                .ok_or(PredifiError::ArithmeticError)?;
+            env.storage().persistent().remove(&count_key);
+            env.storage().persistent().remove(&progress_key);
+            env.storage().persistent().set(&swept_key, &progress.amount);
+            Self::extend_persistent(&env, &swept_key);
+        } else {
+            env.storage().persistent().set(&progress_key, &progress);
+            Self::extend_persistent(&env, &progress_key);
+        }
+        Self::credit_accrued_fees(&env, &pool.token, fees)?;
+
+        let recipient = Self::get_unclaimed_recipient(env.clone());
+        if amount > 0 {
+            Self::enter_reentrancy_guard(&env);
+            token::Client::new(&env, &pool.token).transfer(
+                &env.current_contract_address(),
+                &recipient,
+                &amount,
+            );
+            Self::exit_reentrancy_guard(&env);
+        }
+
+        UnclaimedSweptEvent {
+            pool_id,
+            recipient,
+            amount,
+            fees,
+            remaining,
+        }
+        .publish(&env);
+
+        Ok(amount)
+    }
+
     /// Emergency escape hatch: transfers any token balance held by this contract
     /// to a destination address. Restricted to the admin role.
     ///
     /// Intended for use when the protocol or oracle has failed and funds must be
-    /// rescued. Emits an `EmergencyWithdraw` event for on-chain auditability.
+    /// rescued. Each call records the caller's approval of this exact
+    /// `(token, destination, amount)`; the transfer happens once the
+    /// `SensitiveActionKind::EmergencyWithdraw` approval policy's threshold
+    /// is met (two admins by default). Emits an `EmergencyWithdraw` event for
+    /// on-chain auditability.
+    ///
+    /// # Errors
+    /// - [`PredifiError::Unauthorized`] – caller lacks Admin role (0).
+    /// - [`PredifiError::EmergencyCancelAlreadyApproved`] – caller already
+    ///   approved this withdrawal.
     pub fn emergency_withdraw(
         env: Env,
         admin: Address,
@@ -125,6 +538,11 @@ impl PredifiContract {
             amount,
         )?;
 
+        let action = SensitiveAction::EmergencyWithdraw(token.clone(), destination.clone(), amount);
+        if Self::record_approval(&env, &admin, &action)?.is_some() {
+            return Ok(());
+        }
+
         let token_client = token::Client::new(&env, &token);
 
         Self::enter_reentrancy_guard(&env);
@@ -0,0 +1,207 @@
+//! Upgrade timelock: queued upgrades, N-of-M admin approvals, the policy
+//! delay and cancellation.
+
+#![cfg(test)]
+
+use crate::test::ROLE_ADMIN;
+use crate::test_utils::{TestContext, START_TIME};
+use crate::{
+    PredifiError, UpgradePolicy, DEFAULT_UPGRADE_APPROVALS, DEFAULT_UPGRADE_TIMELOCK_SECONDS,
+    MIN_UPGRADE_TIMELOCK_SECONDS,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env,
+};
+
+/// Three admins under a 2-of-3 policy with the minimum delay, and the hash
+/// they upgrade to. Returns `(admins, hash)`.
+fn upgrade_admins(ctx: &TestContext) -> ([Address; 3], BytesN<32>) {
+    let admins = [
+        ctx.admin.clone(),
+        ctx.grant(ROLE_ADMIN),
+        ctx.grant(ROLE_ADMIN),
+    ];
+    ctx.client
+        .set_upgrade_policy(&admins[0], &MIN_UPGRADE_TIMELOCK_SECONDS, &2u32);
+    (admins, BytesN::from_array(&ctx.client.env, &[7u8; 32]))
+}
+
+/// An upgrade needs both its approvals and its delay; only then does it
+/// reach the WASM swap, which fails here since no WASM was uploaded.
+#[test]
+fn test_upgrade_waits_for_approvals_and_delay() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (admins, hash) = upgrade_admins(&ctx);
+    let [a, b, _] = &admins;
+
+    ctx.client.propose_upgrade(a, &hash);
+    let pending = ctx.client.get_pending_upgrade().unwrap();
+    assert_eq!(
+        pending.executable_at,
+        START_TIME + MIN_UPGRADE_TIMELOCK_SECONDS
+    );
+    assert_eq!(pending.approvals.len(), 1);
+
+    let again = ctx.client.try_propose_upgrade(b, &hash);
+    assert_eq!(again, Err(Ok(PredifiError::FeeChangePending)));
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = pending.executable_at);
+    let one_of_three = ctx.client.try_upgrade_contract(a, &hash);
+    assert_eq!(one_of_three, Err(Ok(PredifiError::EmergencyCancelPending)));
+
+    let twice = ctx.client.try_approve_upgrade(a);
+    assert_eq!(twice, Err(Ok(PredifiError::EmergencyCancelAlreadyApproved)));
+    ctx.client.approve_upgrade(b);
+
+    let other_hash = BytesN::from_array(&env, &[8u8; 32]);
+    let swapped = ctx.client.try_upgrade_contract(a, &other_hash);
+    assert_eq!(swapped, Err(Ok(PredifiError::InvalidData)));
+
+    // Every check passed; the host rejects the unknown hash.
+    assert!(matches!(
+        ctx.client.try_upgrade_contract(a, &hash),
+        Err(Err(_))
+    ));
+}
+
+/// The delay runs from the proposal, not from the last approval.
+#[test]
+fn test_upgrade_timelock() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (admins, hash) = upgrade_admins(&ctx);
+    let [a, b, _] = &admins;
+
+    ctx.client.propose_upgrade(a, &hash);
+    ctx.client.approve_upgrade(b);
+    env.ledger()
+        .with_mut(|li| li.timestamp = START_TIME + MIN_UPGRADE_TIMELOCK_SECONDS - 1);
+    let early = ctx.client.try_upgrade_contract(a, &hash);
+    assert_eq!(early, Err(Ok(PredifiError::TimelockNotExpired)));
+}
+
+/// An approver who loses the Admin role no longer counts.
+#[test]
+fn test_revoked_approver_does_not_count() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (admins, hash) = upgrade_admins(&ctx);
+    let [a, b, _] = &admins;
+
+    ctx.client.propose_upgrade(a, &hash);
+    ctx.client.approve_upgrade(b);
+    ctx.ac_client.revoke_role(b, &ROLE_ADMIN);
+    env.ledger()
+        .with_mut(|li| li.timestamp = START_TIME + MIN_UPGRADE_TIMELOCK_SECONDS);
+
+    let result = ctx.client.try_upgrade_contract(a, &hash);
+    assert_eq!(result, Err(Ok(PredifiError::EmergencyCancelPending)));
+}
+
+/// Any admin can cancel; a new proposal can follow.
+#[test]
+fn test_cancel_upgrade() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (admins, hash) = upgrade_admins(&ctx);
+    let [a, b, c] = &admins;
+
+    let none = ctx.client.try_cancel_upgrade(c);
+    assert_eq!(none, Err(Ok(PredifiError::NoFeeChangePending)));
+
+    ctx.client.propose_upgrade(a, &hash);
+    ctx.client.approve_upgrade(b);
+    ctx.client.cancel_upgrade(c);
+    assert_eq!(ctx.client.get_pending_upgrade(), None);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = START_TIME + MIN_UPGRADE_TIMELOCK_SECONDS);
+    let canceled = ctx.client.try_upgrade_contract(a, &hash);
+    assert_eq!(canceled, Err(Ok(PredifiError::NoFeeChangePending)));
+
+    ctx.client.propose_upgrade(c, &hash);
+}
+
+/// Lowering the threshold takes the current threshold of admins agreeing on
+/// the same policy.
+#[test]
+fn test_upgrade_policy_change_needs_approvals() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (admins, _hash) = upgrade_admins(&ctx);
+    let [a, b, c] = &admins;
+    let two_of_three = ctx.client.get_upgrade_policy();
+
+    ctx.client
+        .set_upgrade_policy(a, &MIN_UPGRADE_TIMELOCK_SECONDS, &1u32);
+    assert_eq!(ctx.client.get_upgrade_policy(), two_of_three);
+    let twice = ctx
+        .client
+        .try_set_upgrade_policy(a, &MIN_UPGRADE_TIMELOCK_SECONDS, &1u32);
+    assert_eq!(twice, Err(Ok(PredifiError::EmergencyCancelAlreadyApproved)));
+
+    // A different policy is a separate proposal.
+    ctx.client
+        .set_upgrade_policy(b, &(MIN_UPGRADE_TIMELOCK_SECONDS + 1), &1u32);
+    assert_eq!(ctx.client.get_upgrade_policy(), two_of_three);
+
+    ctx.client
+        .set_upgrade_policy(c, &MIN_UPGRADE_TIMELOCK_SECONDS, &1u32);
+    assert_eq!(
+        ctx.client.get_upgrade_policy(),
+        UpgradePolicy {
+            delay: MIN_UPGRADE_TIMELOCK_SECONDS,
+            required_approvals: 1,
+        }
+    );
+}
+
+/// The policy keeps a minimum delay, cannot change under a queued upgrade,
+/// and is admin-only.
+#[test]
+fn test_upgrade_policy_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (admins, hash) = upgrade_admins(&ctx);
+    let [a, _, _] = &admins;
+
+    let short = ctx
+        .client
+        .try_set_upgrade_policy(a, &(MIN_UPGRADE_TIMELOCK_SECONDS - 1), &2u32);
+    assert_eq!(short, Err(Ok(PredifiError::InvalidData)));
+    let no_approvals = ctx
+        .client
+        .try_set_upgrade_policy(a, &MIN_UPGRADE_TIMELOCK_SECONDS, &0u32);
+    assert_eq!(no_approvals, Err(Ok(PredifiError::InvalidData)));
+
+    let stranger = Address::generate(&env);
+    let not_admin =
+        ctx.client
+            .try_set_upgrade_policy(&stranger, &MIN_UPGRADE_TIMELOCK_SECONDS, &1u32);
+    assert_eq!(not_admin, Err(Ok(PredifiError::Unauthorized)));
+    let not_admin = ctx.client.try_propose_upgrade(&stranger, &hash);
+    assert_eq!(not_admin, Err(Ok(PredifiError::Unauthorized)));
+
+    ctx.client.propose_upgrade(a, &hash);
+    let queued = ctx
+        .client
+        .try_set_upgrade_policy(a, &MIN_UPGRADE_TIMELOCK_SECONDS, &1u32);
+    assert_eq!(queued, Err(Ok(PredifiError::FeeChangePending)));
+}
+
+/// Without a stored policy the defaults apply.
+#[test]
+fn test_default_upgrade_policy() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    assert_eq!(
+        ctx.client.get_upgrade_policy(),
+        UpgradePolicy {
+            delay: DEFAULT_UPGRADE_TIMELOCK_SECONDS,
+            required_approvals: DEFAULT_UPGRADE_APPROVALS,
+        }
+    );
+}
@@ -0,0 +1,154 @@
+//! Fee discounts by lifetime staking volume: volume tracked through stakes,
+//! withdrawals and transfers, and the discount rebated at claim time.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{FeeTier, PoolConfig, PredifiError, TreasuryKey, UserStats, VolumeTier};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, String,
+};
+
+const END_TIME: u64 = 10_000;
+
+/// A 5% protocol fee, a 50% discount from 1_000 volume, and a pool with
+/// 1_000 staked on each outcome. Returns `(pool_id, home, away)`.
+fn discounted_pool(ctx: &TestContext) -> (u64, Address, Address) {
+    let env = &ctx.client.env;
+    let fee_tier = FeeTier {
+        stake_threshold: 0,
+        fee_bps: 500,
+    };
+    ctx.client.set_fee_tiers(&ctx.admin, &vec![env, fee_tier]);
+    let volume_tier = VolumeTier {
+        volume_threshold: 1_000,
+        discount_bps: 5_000,
+    };
+    ctx.client
+        .set_volume_tiers(&ctx.admin, &vec![env, volume_tier]);
+
+    let config = PoolConfig {
+        description: String::from_str(env, "Match winner"),
+        metadata_url: String::from_str(env, "ipfs://volume"),
+        outcome_descriptions: vec![
+            env,
+            String::from_str(env, "Home"),
+            String::from_str(env, "Away"),
+        ],
+        ..default_pool_config(env)
+    };
+    let pool_id = ctx.create_pool(END_TIME, &config);
+    let home = ctx.staker(pool_id, 1_000, 0);
+    let away = ctx.staker(pool_id, 1_000, 1);
+    (pool_id, home, away)
+}
+
+/// Half of the winner's 100 fee share comes back with the payout; the
+/// treasury keeps the rest.
+#[test]
+fn test_volume_discount_rebates_fee_share() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, home, _away) = discounted_pool(&ctx);
+    assert_eq!(
+        ctx.client.get_user_fee_tier(&home),
+        UserStats {
+            volume: 1_000,
+            tier: 1,
+            discount_bps: 5_000,
+        }
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &0u32);
+
+    assert_eq!(ctx.client.claim_winnings(&home, &pool_id), 1_950);
+    assert_eq!(ctx.client.get_accrued_fees(&ctx.token_address), 50);
+}
+
+/// Withdrawn stakes leave the user's volume, and tiers follow the current
+/// schedule.
+#[test]
+fn test_volume_tracks_withdrawals_and_schedule() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, home, away) = discounted_pool(&ctx);
+
+    ctx.client.withdraw_prediction(&away, &pool_id, &1u32, &400);
+    let stats = ctx.client.get_user_fee_tier(&away);
+    assert_eq!((stats.volume, stats.tier, stats.discount_bps), (600, 0, 0));
+
+    let tiers = vec![
+        &env,
+        VolumeTier {
+            volume_threshold: 500,
+            discount_bps: 1_000,
+        },
+        VolumeTier {
+            volume_threshold: 1_000,
+            discount_bps: 2_500,
+        },
+    ];
+    ctx.client.set_volume_tiers(&ctx.admin, &tiers);
+    let stats = ctx.client.get_user_fee_tier(&away);
+    assert_eq!((stats.tier, stats.discount_bps), (1, 1_000));
+    assert_eq!(ctx.client.get_user_fee_tier(&home).tier, 2);
+}
+
+/// A transferred stake moves its volume to the recipient, and a volume that
+/// would overflow fails the stake instead of trapping.
+#[test]
+fn test_volume_follows_transfers() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, home, away) = discounted_pool(&ctx);
+    let buyer = Address::generate(&env);
+
+    ctx.client
+        .transfer_prediction(&home, &buyer, &pool_id, &0u32, &400, &None);
+    assert_eq!(ctx.client.get_user_fee_tier(&home).volume, 600);
+    assert_eq!(ctx.client.get_user_fee_tier(&buyer).volume, 400);
+
+    env.as_contract(&ctx.client.address, || {
+        env.storage().persistent().set(
+            &TreasuryKey::UserStats(away.clone()),
+            &UserStats {
+                volume: i128::MAX,
+                tier: 1,
+                discount_bps: 5_000,
+            },
+        );
+    });
+    let overflow = ctx
+        .client
+        .try_transfer_prediction(&home, &away, &pool_id, &0u32, &1, &None);
+    assert_eq!(overflow, Err(Ok(PredifiError::ArithmeticError)));
+}
+
+/// Discounts are capped at 100%, thresholds must ascend, and the schedule
+/// is admin-only.
+#[test]
+fn test_volume_tiers_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    discounted_pool(&ctx);
+    let tier = |volume_threshold: i128, discount_bps: u32| VolumeTier {
+        volume_threshold,
+        discount_bps,
+    };
+
+    let too_high = ctx
+        .client
+        .try_set_volume_tiers(&ctx.admin, &vec![&env, tier(0, 10_001)]);
+    assert_eq!(too_high, Err(Ok(PredifiError::InvalidFeeBps)));
+    let unordered = vec![&env, tier(1_000, 100), tier(1_000, 200)];
+    let unordered = ctx.client.try_set_volume_tiers(&ctx.admin, &unordered);
+    assert_eq!(unordered, Err(Ok(PredifiError::InvalidFeeBps)));
+    let not_admin = ctx
+        .client
+        .try_set_volume_tiers(&ctx.operator, &vec![&env, tier(0, 100)]);
+    assert_eq!(not_admin, Err(Ok(PredifiError::Unauthorized)));
+
+    assert_eq!(ctx.client.get_volume_tiers().len(), 1);
+}
@@ -0,0 +1,133 @@
+//! Leaving a pool early with `withdraw_prediction`, and the per-pool exit
+//! fee that stays behind for the winners.
+
+#![cfg(test)]
+
+use crate::test_utils::{default_pool_config, TestContext};
+use crate::{PoolConfig, PredifiError, DEFAULT_EXIT_FEE_BPS, MAX_EXIT_FEE_BPS};
+use soroban_sdk::{testutils::Ledger, vec, Address, Env, String};
+
+const END_TIME: u64 = 10_000;
+
+/// A Home/Away pool with 1_000 staked on each side. Returns
+/// `(pool_id, home, away)`.
+fn staked_pool(ctx: &TestContext) -> (u64, Address, Address) {
+    let env = &ctx.client.env;
+    let config = PoolConfig {
+        description: String::from_str(env, "Match winner"),
+        metadata_url: String::from_str(env, "ipfs://withdraw"),
+        outcome_descriptions: vec![
+            env,
+            String::from_str(env, "Home"),
+            String::from_str(env, "Away"),
+        ],
+        ..default_pool_config(env)
+    };
+    let pool_id = ctx.create_pool(END_TIME, &config);
+    let home = ctx.staker(pool_id, 1_000, 0);
+    let away = ctx.staker(pool_id, 1_000, 1);
+    (pool_id, home, away)
+}
+
+/// The default 2% fee stays in the pool and ends up with the winners.
+#[test]
+fn test_withdraw_keeps_exit_fee_in_pool() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, home, _away) = staked_pool(&ctx);
+    assert_eq!(ctx.client.get_exit_fee_bps(&pool_id), DEFAULT_EXIT_FEE_BPS);
+
+    assert_eq!(
+        ctx.client.withdraw_prediction(&home, &pool_id, &0u32, &500),
+        490
+    );
+    assert_eq!(ctx.token.balance(&home), 490);
+
+    // INV-1: outcome stakes plus the retained fee make up the total.
+    let pool = ctx.client.get_pool(&pool_id);
+    assert_eq!(pool.total_stake, 1_510);
+    let home_stake = ctx.client.get_outcome_stake(&pool_id, &0u32);
+    let away_stake = ctx.client.get_outcome_stake(&pool_id, &1u32);
+    assert_eq!((home_stake, away_stake), (500, 1_000));
+    assert_eq!(pool.total_stake, home_stake + away_stake + 10);
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME + 1);
+    ctx.client.resolve_pool(&ctx.operator, &pool_id, &0u32);
+    assert_eq!(ctx.client.claim_winnings(&home, &pool_id), 1_510);
+}
+
+/// Withdrawing a whole position leaves the pool; nothing is left to withdraw.
+#[test]
+fn test_full_withdraw_removes_position() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, home, away) = staked_pool(&ctx);
+    ctx.client.set_exit_fee_bps(&ctx.operator, &pool_id, &0u32);
+
+    assert_eq!(
+        ctx.client
+            .withdraw_prediction(&away, &pool_id, &1u32, &1_000),
+        1_000
+    );
+    assert_eq!(ctx.token.balance(&away), 1_000);
+    assert_eq!(ctx.client.get_pool(&pool_id).participants_count, 1);
+    assert_eq!(ctx.client.get_user_predictions(&away, &0, &10).len(), 0);
+
+    let empty = ctx
+        .client
+        .try_withdraw_prediction(&away, &pool_id, &1u32, &1);
+    assert_eq!(empty, Err(Ok(PredifiError::InsufficientBalance)));
+    let too_much = ctx
+        .client
+        .try_withdraw_prediction(&home, &pool_id, &0u32, &1_001);
+    assert_eq!(too_much, Err(Ok(PredifiError::InsufficientBalance)));
+    let zero = ctx
+        .client
+        .try_withdraw_prediction(&home, &pool_id, &0u32, &0);
+    assert_eq!(zero, Err(Ok(PredifiError::InvalidAmount)));
+}
+
+/// Withdrawals close with betting; exit fees are capped and operator-only.
+#[test]
+fn test_withdraw_window_and_exit_fee_validation() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, home, _away) = staked_pool(&ctx);
+
+    let too_high =
+        ctx.client
+            .try_set_exit_fee_bps(&ctx.operator, &pool_id, &(MAX_EXIT_FEE_BPS + 1));
+    assert_eq!(too_high, Err(Ok(PredifiError::InvalidFeeBps)));
+    let not_operator = ctx.client.try_set_exit_fee_bps(&home, &pool_id, &0u32);
+    assert_eq!(not_operator, Err(Ok(PredifiError::Unauthorized)));
+
+    env.ledger().with_mut(|li| li.timestamp = END_TIME);
+    let closed = ctx
+        .client
+        .try_withdraw_prediction(&home, &pool_id, &0u32, &100);
+    assert_eq!(closed, Err(Ok(PredifiError::TimeConstraintError)));
+}
+
+/// Exit fees left in a pool that is then canceled are owed to nobody, so
+/// they accrue to the protocol while refunds return what is still staked.
+#[test]
+fn test_canceled_pool_accrues_exit_fees() {
+    let env = Env::default();
+    let ctx = TestContext::new(&env);
+    let (pool_id, home, away) = staked_pool(&ctx);
+    assert_eq!(
+        ctx.client.withdraw_prediction(&home, &pool_id, &0u32, &500),
+        490
+    );
+
+    ctx.client.cancel_pool(
+        &ctx.operator,
+        &pool_id,
+        &String::from_str(&env, "Match abandoned"),
+    );
+    assert_eq!(ctx.client.get_accrued_fees(&ctx.token_address), 10);
+
+    assert_eq!(ctx.client.claim_refund(&home, &pool_id), 500);
+    assert_eq!(ctx.client.claim_refund(&away, &pool_id), 1_000);
+    assert_eq!(ctx.token.balance(&ctx.client.address), 10);
+}
@@ -1,6 +1,8 @@
 #![cfg(test)]
 
-use predifi_contract::{MarketState, PoolConfig, PredifiContract, PredifiContractClient};
+use predifi_contract::{
+    MarketState, PoolConfig, PredifiContract, PredifiContractClient, PricingMode,
+};
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Ledger},
@@ -98,6 +100,10 @@ fn test_price_based_pool_mock_resolution() {
                 String::from_str(&env, "No"),
                 String::from_str(&env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 
@@ -196,6 +202,10 @@ fn test_resolve_pool_rejects_stale_price_feed() {
                 String::from_str(&env, "No"),
                 String::from_str(&env, "Yes"),
             ],
+            optimistic_liveness: 0,
+            optimistic_bond: 0,
+            pricing_mode: PricingMode::Parimutuel,
+            creator_fee_bps: 0,
         },
     );
 